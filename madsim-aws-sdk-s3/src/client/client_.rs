@@ -97,6 +97,69 @@ impl Client {
             inner: Default::default(),
         }
     }
+
+    pub fn get_bucket_location(&self) -> fluent_builders::GetBucketLocation {
+        fluent_builders::GetBucketLocation {
+            config: self.config.clone(),
+            inner: Default::default(),
+        }
+    }
+
+    pub fn get_bucket_versioning(&self) -> fluent_builders::GetBucketVersioning {
+        fluent_builders::GetBucketVersioning {
+            config: self.config.clone(),
+            inner: Default::default(),
+        }
+    }
+
+    pub fn get_object_attributes(&self) -> fluent_builders::GetObjectAttributes {
+        fluent_builders::GetObjectAttributes {
+            config: self.config.clone(),
+            inner: Default::default(),
+        }
+    }
+
+    pub fn put_object_lock_configuration(&self) -> fluent_builders::PutObjectLockConfiguration {
+        fluent_builders::PutObjectLockConfiguration {
+            config: self.config.clone(),
+            inner: Default::default(),
+        }
+    }
+
+    pub fn get_object_lock_configuration(&self) -> fluent_builders::GetObjectLockConfiguration {
+        fluent_builders::GetObjectLockConfiguration {
+            config: self.config.clone(),
+            inner: Default::default(),
+        }
+    }
+
+    pub fn put_object_retention(&self) -> fluent_builders::PutObjectRetention {
+        fluent_builders::PutObjectRetention {
+            config: self.config.clone(),
+            inner: Default::default(),
+        }
+    }
+
+    pub fn put_object_legal_hold(&self) -> fluent_builders::PutObjectLegalHold {
+        fluent_builders::PutObjectLegalHold {
+            config: self.config.clone(),
+            inner: Default::default(),
+        }
+    }
+
+    pub fn restore_object(&self) -> fluent_builders::RestoreObject {
+        fluent_builders::RestoreObject {
+            config: self.config.clone(),
+            inner: Default::default(),
+        }
+    }
+
+    pub fn head_bucket(&self) -> fluent_builders::HeadBucket {
+        fluent_builders::HeadBucket {
+            config: self.config.clone(),
+            inner: Default::default(),
+        }
+    }
 }
 
 pub mod fluent_builders {
@@ -104,24 +167,37 @@ pub mod fluent_builders {
     use crate::server::service::Request;
     use crate::{error::*, input::*, output::*, types::ByteStream};
     use aws_sdk_s3::types::SdkError;
-    use madsim::net::Endpoint;
-    use std::net::SocketAddr;
+    use bytes::Bytes;
+    use futures_core::Stream;
+    use madsim::net::{Endpoint, Receiver, Sender};
+    use std::{future::poll_fn, net::SocketAddr, pin::Pin};
 
     async fn send_aux<O: 'static, E: 'static>(
         cfg: &Config,
         req: Request,
     ) -> Result<O, SdkError<E>> {
-        let addr = cfg
-            .endpoint
-            .uri()
-            .authority()
-            .expect("invalid URI")
-            .as_str()
-            .parse::<SocketAddr>()
-            .unwrap();
-        let ep = Endpoint::connect(addr).await.map_err(io_err)?;
-        let (tx, mut rx) = ep.connect1(addr).await.map_err(io_err)?;
-        tx.send(Box::new(req)).await.map_err(io_err)?;
+        send_streaming(cfg, req, None).await
+    }
+
+    /// Send a request followed by its body.
+    ///
+    /// The body is read from the stream chunk by chunk as it is sent. If the stream fails,
+    /// the request fails with a dispatch error and the server stores nothing.
+    async fn send_streaming<O: 'static, E: 'static>(
+        cfg: &Config,
+        req: Request,
+        body: Option<ByteStream>,
+    ) -> Result<O, SdkError<E>> {
+        let (tx, mut rx) = connect(cfg, req).await.map_err(io_err)?;
+        if let Some(mut body) = body {
+            // stream the body in chunks, terminated by `None`
+            while let Some(chunk) = poll_fn(|cx| Pin::new(&mut body).poll_next(cx)).await {
+                // close the connection in the middle of the body, so the server discards it
+                let chunk = chunk.map_err(|e| io_err(e.into()))?;
+                tx.send(Box::new(Some(chunk))).await.map_err(io_err)?;
+            }
+            tx.send(Box::new(None::<Bytes>)).await.map_err(io_err)?;
+        }
         let resp = rx.recv().await.map_err(io_err)?;
         let resp = *resp.downcast::<Result<O, E>>().expect("failed to downcast");
         resp.map_err(|e| SdkError::ServiceError { err: e, raw: raw() })
@@ -134,9 +210,9 @@ pub mod fluent_builders {
     impl UploadPart {
         pub async fn send(self) -> Result<UploadPartOutput, SdkError<UploadPartError>> {
             let mut input = self.inner.build().map_err(build_err)?;
-            input.collect_body().await.map_err(build_err)?;
+            let body = input.take_body();
             let req = Request::UploadPart(input);
-            send_aux(&self.config, req).await
+            send_streaming(&self.config, req, Some(body)).await
         }
 
         pub fn body(mut self, input: ByteStream) -> Self {
@@ -154,6 +230,16 @@ pub mod fluent_builders {
             self
         }
 
+        pub fn content_md5(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.content_md5(input.into());
+            self
+        }
+
+        pub fn set_content_md5(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_content_md5(input);
+            self
+        }
+
         pub fn key(mut self, input: impl Into<String>) -> Self {
             self.inner = self.inner.key(input.into());
             self
@@ -168,6 +254,16 @@ pub mod fluent_builders {
             self.inner = self.inner.upload_id(input.into());
             self
         }
+
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.expected_bucket_owner(input.into());
+            self
+        }
+
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_expected_bucket_owner(input);
+            self
+        }
     }
 
     #[derive(Clone)]
@@ -202,6 +298,16 @@ pub mod fluent_builders {
             self.inner = self.inner.upload_id(input.into());
             self
         }
+
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.expected_bucket_owner(input.into());
+            self
+        }
+
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_expected_bucket_owner(input);
+            self
+        }
     }
 
     #[derive(Clone)]
@@ -233,6 +339,16 @@ pub mod fluent_builders {
             self.inner = self.inner.upload_id(input.into());
             self
         }
+
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.expected_bucket_owner(input.into());
+            self
+        }
+
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_expected_bucket_owner(input);
+            self
+        }
     }
 
     pub struct GetObject {
@@ -243,7 +359,7 @@ pub mod fluent_builders {
         pub async fn send(self) -> Result<GetObjectOutput, SdkError<GetObjectError>> {
             let input = self.inner.build().map_err(build_err)?;
             let req = Request::GetObject(input);
-            send_aux(&self.config, req).await
+            send_download(&self.config, req).await
         }
 
         pub fn bucket(mut self, input: impl Into<String>) -> Self {
@@ -265,6 +381,40 @@ pub mod fluent_builders {
             self.inner = self.inner.range(input.into());
             self
         }
+
+        /// Returns the object only if its ETag matches the specified one, including the
+        /// `-N` suffix of multipart objects, otherwise fails with `PreconditionFailed`.
+        pub fn if_match(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.if_match(input.into());
+            self
+        }
+
+        pub fn set_if_match(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_if_match(input);
+            self
+        }
+
+        /// Returns the object only if it has been modified since the specified time,
+        /// otherwise fails with `NotModified`.
+        pub fn if_modified_since(mut self, input: aws_smithy_types::DateTime) -> Self {
+            self.inner = self.inner.if_modified_since(input);
+            self
+        }
+
+        pub fn set_if_modified_since(mut self, input: Option<aws_smithy_types::DateTime>) -> Self {
+            self.inner = self.inner.set_if_modified_since(input);
+            self
+        }
+
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.expected_bucket_owner(input.into());
+            self
+        }
+
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_expected_bucket_owner(input);
+            self
+        }
     }
 
     pub struct PutObject {
@@ -274,9 +424,9 @@ pub mod fluent_builders {
     impl PutObject {
         pub async fn send(self) -> Result<PutObjectOutput, SdkError<PutObjectError>> {
             let mut input = self.inner.build().map_err(build_err)?;
-            input.collect_body().await.map_err(build_err)?;
+            let body = input.take_body();
             let req = Request::PutObject(input);
-            send_aux(&self.config, req).await
+            send_streaming(&self.config, req, Some(body)).await
         }
 
         pub fn body(mut self, input: ByteStream) -> Self {
@@ -303,6 +453,100 @@ pub mod fluent_builders {
             self.inner = self.inner.set_content_length(input);
             self
         }
+
+        pub fn content_md5(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.content_md5(input.into());
+            self
+        }
+
+        pub fn set_content_md5(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_content_md5(input);
+            self
+        }
+
+        /// Uploads the object only if the key does not exist. Only `*` is supported.
+        pub fn if_none_match(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.if_none_match(input.into());
+            self
+        }
+
+        pub fn set_if_none_match(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_if_none_match(input);
+            self
+        }
+
+        pub fn cache_control(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.cache_control(input.into());
+            self
+        }
+
+        pub fn set_cache_control(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_cache_control(input);
+            self
+        }
+
+        pub fn content_encoding(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.content_encoding(input.into());
+            self
+        }
+
+        pub fn set_content_encoding(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_content_encoding(input);
+            self
+        }
+
+        pub fn ssekms_key_id(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.ssekms_key_id(input.into());
+            self
+        }
+
+        pub fn set_ssekms_key_id(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_ssekms_key_id(input);
+            self
+        }
+
+        pub fn storage_class(mut self, input: crate::model::StorageClass) -> Self {
+            self.inner = self.inner.storage_class(input);
+            self
+        }
+
+        pub fn set_storage_class(mut self, input: Option<crate::model::StorageClass>) -> Self {
+            self.inner = self.inner.set_storage_class(input);
+            self
+        }
+
+        pub fn server_side_encryption(mut self, input: crate::model::ServerSideEncryption) -> Self {
+            self.inner = self.inner.server_side_encryption(input);
+            self
+        }
+
+        pub fn set_server_side_encryption(
+            mut self,
+            input: Option<crate::model::ServerSideEncryption>,
+        ) -> Self {
+            self.inner = self.inner.set_server_side_encryption(input);
+            self
+        }
+
+        pub fn expires(mut self, input: aws_smithy_types::DateTime) -> Self {
+            self.inner = self.inner.expires(input);
+            self
+        }
+
+        pub fn set_expires(mut self, input: Option<aws_smithy_types::DateTime>) -> Self {
+            self.inner = self.inner.set_expires(input);
+            self
+        }
+
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.expected_bucket_owner(input.into());
+            self
+        }
+
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_expected_bucket_owner(input);
+            self
+        }
     }
 
     pub struct DeleteObject {
@@ -325,6 +569,16 @@ pub mod fluent_builders {
             self.inner = self.inner.key(input.into());
             self
         }
+
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.expected_bucket_owner(input.into());
+            self
+        }
+
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_expected_bucket_owner(input);
+            self
+        }
     }
 
     #[derive(Clone)]
@@ -348,6 +602,16 @@ pub mod fluent_builders {
             self.inner = self.inner.delete(input);
             self
         }
+
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.expected_bucket_owner(input.into());
+            self
+        }
+
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_expected_bucket_owner(input);
+            self
+        }
     }
 
     #[derive(Clone)]
@@ -373,6 +637,39 @@ pub mod fluent_builders {
             self.inner = self.inner.key(input.into());
             self
         }
+
+        pub fn server_side_encryption(mut self, input: crate::model::ServerSideEncryption) -> Self {
+            self.inner = self.inner.server_side_encryption(input);
+            self
+        }
+
+        pub fn set_server_side_encryption(
+            mut self,
+            input: Option<crate::model::ServerSideEncryption>,
+        ) -> Self {
+            self.inner = self.inner.set_server_side_encryption(input);
+            self
+        }
+
+        pub fn ssekms_key_id(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.ssekms_key_id(input.into());
+            self
+        }
+
+        pub fn set_ssekms_key_id(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_ssekms_key_id(input);
+            self
+        }
+
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.expected_bucket_owner(input.into());
+            self
+        }
+
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_expected_bucket_owner(input);
+            self
+        }
     }
 
     #[derive(Clone)]
@@ -396,6 +693,16 @@ pub mod fluent_builders {
             self.inner = self.inner.key(input.into());
             self
         }
+
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.expected_bucket_owner(input.into());
+            self
+        }
+
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_expected_bucket_owner(input);
+            self
+        }
     }
 
     #[derive(Clone)]
@@ -424,6 +731,38 @@ pub mod fluent_builders {
             self.inner = self.inner.continuation_token(input.into());
             self
         }
+
+        /// Lists only the keys after this key. Ignored if a continuation token is given.
+        pub fn start_after(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.start_after(input.into());
+            self
+        }
+
+        pub fn set_start_after(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_start_after(input);
+            self
+        }
+
+        /// Sets the maximum number of keys returned. The default is 1000.
+        pub fn max_keys(mut self, input: i32) -> Self {
+            self.inner = self.inner.max_keys(input);
+            self
+        }
+
+        pub fn set_max_keys(mut self, input: Option<i32>) -> Self {
+            self.inner = self.inner.set_max_keys(input);
+            self
+        }
+
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.expected_bucket_owner(input.into());
+            self
+        }
+
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_expected_bucket_owner(input);
+            self
+        }
     }
 
     #[derive(Clone, Debug)]
@@ -518,6 +857,505 @@ pub mod fluent_builders {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct GetBucketLocation {
+        pub(super) config: Arc<Config>,
+        pub(super) inner: get_bucket_location_input::Builder,
+    }
+    impl GetBucketLocation {
+        pub async fn send(
+            self,
+        ) -> Result<GetBucketLocationOutput, SdkError<GetBucketLocationError>> {
+            let input = self.inner.build().map_err(build_err)?;
+            let req = Request::GetBucketLocation(input);
+            send_aux(&self.config, req).await
+        }
+
+        pub fn bucket(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.bucket(input.into());
+            self
+        }
+
+        pub fn set_bucket(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_bucket(input);
+            self
+        }
+
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.expected_bucket_owner(input.into());
+            self
+        }
+
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_expected_bucket_owner(input);
+            self
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct GetBucketVersioning {
+        pub(super) config: Arc<Config>,
+        pub(super) inner: get_bucket_versioning_input::Builder,
+    }
+    impl GetBucketVersioning {
+        pub async fn send(
+            self,
+        ) -> Result<GetBucketVersioningOutput, SdkError<GetBucketVersioningError>> {
+            let input = self.inner.build().map_err(build_err)?;
+            let req = Request::GetBucketVersioning(input);
+            send_aux(&self.config, req).await
+        }
+
+        pub fn bucket(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.bucket(input.into());
+            self
+        }
+
+        pub fn set_bucket(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_bucket(input);
+            self
+        }
+
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.expected_bucket_owner(input.into());
+            self
+        }
+
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_expected_bucket_owner(input);
+            self
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct GetObjectAttributes {
+        pub(super) config: Arc<Config>,
+        pub(super) inner: get_object_attributes_input::Builder,
+    }
+    impl GetObjectAttributes {
+        pub async fn send(
+            self,
+        ) -> Result<GetObjectAttributesOutput, SdkError<GetObjectAttributesError>> {
+            let input = self.inner.build().map_err(build_err)?;
+            let req = Request::GetObjectAttributes(input);
+            send_aux(&self.config, req).await
+        }
+
+        pub fn bucket(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.bucket(input.into());
+            self
+        }
+
+        pub fn set_bucket(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_bucket(input);
+            self
+        }
+
+        pub fn key(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.key(input.into());
+            self
+        }
+
+        pub fn set_key(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_key(input);
+            self
+        }
+
+        pub fn max_parts(mut self, input: i32) -> Self {
+            self.inner = self.inner.max_parts(input);
+            self
+        }
+
+        pub fn set_max_parts(mut self, input: Option<i32>) -> Self {
+            self.inner = self.inner.set_max_parts(input);
+            self
+        }
+
+        pub fn part_number_marker(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.part_number_marker(input.into());
+            self
+        }
+
+        pub fn set_part_number_marker(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_part_number_marker(input);
+            self
+        }
+
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.expected_bucket_owner(input.into());
+            self
+        }
+
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_expected_bucket_owner(input);
+            self
+        }
+
+        pub fn object_attributes(mut self, input: crate::model::ObjectAttributes) -> Self {
+            self.inner = self.inner.object_attributes(input);
+            self
+        }
+
+        pub fn set_object_attributes(
+            mut self,
+            input: Option<Vec<crate::model::ObjectAttributes>>,
+        ) -> Self {
+            self.inner = self.inner.set_object_attributes(input);
+            self
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct PutObjectLockConfiguration {
+        pub(super) config: Arc<Config>,
+        pub(super) inner: put_object_lock_configuration_input::Builder,
+    }
+    impl PutObjectLockConfiguration {
+        pub async fn send(
+            self,
+        ) -> Result<PutObjectLockConfigurationOutput, SdkError<PutObjectLockConfigurationError>>
+        {
+            let input = self.inner.build().map_err(build_err)?;
+            let req = Request::PutObjectLockConfiguration(input);
+            send_aux(&self.config, req).await
+        }
+
+        pub fn bucket(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.bucket(input.into());
+            self
+        }
+
+        pub fn set_bucket(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_bucket(input);
+            self
+        }
+
+        pub fn object_lock_configuration(
+            mut self,
+            input: crate::model::ObjectLockConfiguration,
+        ) -> Self {
+            self.inner = self.inner.object_lock_configuration(input);
+            self
+        }
+
+        pub fn set_object_lock_configuration(
+            mut self,
+            input: Option<crate::model::ObjectLockConfiguration>,
+        ) -> Self {
+            self.inner = self.inner.set_object_lock_configuration(input);
+            self
+        }
+
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.expected_bucket_owner(input.into());
+            self
+        }
+
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_expected_bucket_owner(input);
+            self
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct GetObjectLockConfiguration {
+        pub(super) config: Arc<Config>,
+        pub(super) inner: get_object_lock_configuration_input::Builder,
+    }
+    impl GetObjectLockConfiguration {
+        pub async fn send(
+            self,
+        ) -> Result<GetObjectLockConfigurationOutput, SdkError<GetObjectLockConfigurationError>>
+        {
+            let input = self.inner.build().map_err(build_err)?;
+            let req = Request::GetObjectLockConfiguration(input);
+            send_aux(&self.config, req).await
+        }
+
+        pub fn bucket(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.bucket(input.into());
+            self
+        }
+
+        pub fn set_bucket(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_bucket(input);
+            self
+        }
+
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.expected_bucket_owner(input.into());
+            self
+        }
+
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_expected_bucket_owner(input);
+            self
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct PutObjectRetention {
+        pub(super) config: Arc<Config>,
+        pub(super) inner: put_object_retention_input::Builder,
+    }
+    impl PutObjectRetention {
+        pub async fn send(
+            self,
+        ) -> Result<PutObjectRetentionOutput, SdkError<PutObjectRetentionError>> {
+            let input = self.inner.build().map_err(build_err)?;
+            let req = Request::PutObjectRetention(input);
+            send_aux(&self.config, req).await
+        }
+
+        pub fn bucket(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.bucket(input.into());
+            self
+        }
+
+        pub fn set_bucket(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_bucket(input);
+            self
+        }
+
+        pub fn key(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.key(input.into());
+            self
+        }
+
+        pub fn set_key(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_key(input);
+            self
+        }
+
+        pub fn retention(mut self, input: crate::model::ObjectLockRetention) -> Self {
+            self.inner = self.inner.retention(input);
+            self
+        }
+
+        pub fn set_retention(mut self, input: Option<crate::model::ObjectLockRetention>) -> Self {
+            self.inner = self.inner.set_retention(input);
+            self
+        }
+
+        pub fn bypass_governance_retention(mut self, input: bool) -> Self {
+            self.inner = self.inner.bypass_governance_retention(input);
+            self
+        }
+
+        pub fn set_bypass_governance_retention(mut self, input: Option<bool>) -> Self {
+            self.inner = self.inner.set_bypass_governance_retention(input);
+            self
+        }
+
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.expected_bucket_owner(input.into());
+            self
+        }
+
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_expected_bucket_owner(input);
+            self
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct PutObjectLegalHold {
+        pub(super) config: Arc<Config>,
+        pub(super) inner: put_object_legal_hold_input::Builder,
+    }
+    impl PutObjectLegalHold {
+        pub async fn send(
+            self,
+        ) -> Result<PutObjectLegalHoldOutput, SdkError<PutObjectLegalHoldError>> {
+            let input = self.inner.build().map_err(build_err)?;
+            let req = Request::PutObjectLegalHold(input);
+            send_aux(&self.config, req).await
+        }
+
+        pub fn bucket(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.bucket(input.into());
+            self
+        }
+
+        pub fn set_bucket(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_bucket(input);
+            self
+        }
+
+        pub fn key(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.key(input.into());
+            self
+        }
+
+        pub fn set_key(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_key(input);
+            self
+        }
+
+        pub fn legal_hold(mut self, input: crate::model::ObjectLockLegalHold) -> Self {
+            self.inner = self.inner.legal_hold(input);
+            self
+        }
+
+        pub fn set_legal_hold(mut self, input: Option<crate::model::ObjectLockLegalHold>) -> Self {
+            self.inner = self.inner.set_legal_hold(input);
+            self
+        }
+
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.expected_bucket_owner(input.into());
+            self
+        }
+
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_expected_bucket_owner(input);
+            self
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct RestoreObject {
+        pub(super) config: Arc<Config>,
+        pub(super) inner: restore_object_input::Builder,
+    }
+    impl RestoreObject {
+        pub async fn send(self) -> Result<RestoreObjectOutput, SdkError<RestoreObjectError>> {
+            let input = self.inner.build().map_err(build_err)?;
+            let req = Request::RestoreObject(input);
+            send_aux(&self.config, req).await
+        }
+
+        pub fn bucket(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.bucket(input.into());
+            self
+        }
+
+        pub fn set_bucket(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_bucket(input);
+            self
+        }
+
+        pub fn key(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.key(input.into());
+            self
+        }
+
+        pub fn set_key(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_key(input);
+            self
+        }
+
+        pub fn restore_request(mut self, input: crate::model::RestoreRequest) -> Self {
+            self.inner = self.inner.restore_request(input);
+            self
+        }
+
+        pub fn set_restore_request(mut self, input: Option<crate::model::RestoreRequest>) -> Self {
+            self.inner = self.inner.set_restore_request(input);
+            self
+        }
+
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.expected_bucket_owner(input.into());
+            self
+        }
+
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_expected_bucket_owner(input);
+            self
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct HeadBucket {
+        pub(super) config: Arc<Config>,
+        pub(super) inner: head_bucket_input::Builder,
+    }
+    impl HeadBucket {
+        pub async fn send(self) -> Result<HeadBucketOutput, SdkError<HeadBucketError>> {
+            let input = self.inner.build().map_err(build_err)?;
+            let req = Request::HeadBucket(input);
+            send_aux(&self.config, req).await
+        }
+
+        pub fn bucket(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.bucket(input.into());
+            self
+        }
+
+        pub fn set_bucket(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_bucket(input);
+            self
+        }
+
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.inner = self.inner.expected_bucket_owner(input.into());
+            self
+        }
+
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.inner = self.inner.set_expected_bucket_owner(input);
+            self
+        }
+    }
+
+    /// Send a `GetObject` request.
+    ///
+    /// The body of the output is requested from the server chunk by chunk as it is read,
+    /// so dropping the body stops the transfer.
+    async fn send_download(
+        cfg: &Config,
+        req: Request,
+    ) -> Result<GetObjectOutput, SdkError<GetObjectError>> {
+        let (tx, mut rx) = connect(cfg, req).await.map_err(io_err)?;
+        let resp = rx.recv().await.map_err(io_err)?;
+        let resp = *(resp.downcast::<Result<GetObjectOutput, GetObjectError>>())
+            .expect("failed to downcast");
+        let mut output = resp.map_err(|e| SdkError::ServiceError { err: e, raw: raw() })?;
+        let (mut sender, body) = hyper::Body::channel();
+        madsim::task::spawn(async move {
+            // wait until the previous chunk is read before requesting the next one
+            while poll_fn(|cx| sender.poll_ready(cx)).await.is_ok() {
+                let chunk = async {
+                    tx.send(Box::new(())).await?;
+                    rx.recv().await
+                };
+                match chunk.await {
+                    Ok(chunk) => match *chunk.downcast::<Option<Bytes>>().unwrap() {
+                        Some(chunk) => {
+                            if sender.send_data(chunk).await.is_err() {
+                                return;
+                            }
+                        }
+                        None => return,
+                    },
+                    // the body fails if the connection is lost
+                    Err(_) => return sender.abort(),
+                }
+            }
+        });
+        output.body = ByteStream::from(body);
+        Ok(output)
+    }
+
+    /// Connect to the server and send the request.
+    async fn connect(cfg: &Config, req: Request) -> std::io::Result<(Sender, Receiver)> {
+        let addr = cfg
+            .endpoint
+            .uri()
+            .authority()
+            .expect("invalid URI")
+            .as_str()
+            .parse::<SocketAddr>()
+            .unwrap();
+        let ep = Endpoint::connect(addr).await?;
+        let (tx, rx) = ep.connect1(addr).await?;
+        // requests are tagged with the region of the client
+        let region = cfg.region.as_ref().map(|region| region.to_string());
+        tx.send(Box::new((region, req))).await?;
+        Ok((tx, rx))
+    }
+
     /// Returns an empty raw response.
     fn raw() -> aws_smithy_http::operation::Response {
         aws_smithy_http::operation::Response::new(http::response::Response::new(
@@ -8,6 +8,7 @@ use aws_types::SdkConfig;
 #[derive(Debug)]
 pub struct Config {
     pub(crate) endpoint: Endpoint,
+    pub(crate) region: Option<Region>,
 }
 
 impl Config {
@@ -50,6 +51,7 @@ impl Builder {
     pub fn build(self) -> Config {
         Config {
             endpoint: self.endpoint.expect("endpoint must be set"),
+            region: self.region,
         }
     }
 }
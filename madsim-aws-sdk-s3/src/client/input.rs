@@ -11,9 +11,11 @@ pub mod upload_part_input {
         pub(crate) body: Option<crate::types::ByteStream>,
         pub(crate) bucket: Option<String>,
         pub(crate) content_length: Option<i64>,
+        pub(crate) content_md5: Option<String>,
         pub(crate) key: Option<String>,
         pub(crate) part_number: Option<i32>,
         pub(crate) upload_id: Option<String>,
+        pub(crate) expected_bucket_owner: Option<String>,
     }
     impl Builder {
         pub fn body(mut self, input: crate::types::ByteStream) -> Self {
@@ -40,6 +42,14 @@ pub mod upload_part_input {
             self.content_length = input;
             self
         }
+        pub fn content_md5(mut self, input: impl Into<String>) -> Self {
+            self.content_md5 = Some(input.into());
+            self
+        }
+        pub fn set_content_md5(mut self, input: Option<String>) -> Self {
+            self.content_md5 = input;
+            self
+        }
         pub fn key(mut self, input: impl Into<String>) -> Self {
             self.key = Some(input.into());
             self
@@ -64,15 +74,25 @@ pub mod upload_part_input {
             self.upload_id = input;
             self
         }
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.expected_bucket_owner = Some(input.into());
+            self
+        }
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.expected_bucket_owner = input;
+            self
+        }
         pub fn build(self) -> Result<UploadPartInput, BuildError> {
             Ok(UploadPartInput {
                 body: self.body.unwrap_or_default(),
                 body0: Default::default(),
                 bucket: self.bucket.ok_or(super::missing_field("bucket"))?,
-                content_length: self.content_length.unwrap_or_default(),
+                content_length: self.content_length,
+                content_md5: self.content_md5,
                 key: self.key.ok_or(super::missing_field("key"))?,
                 part_number: self.part_number.unwrap_or_default(),
                 upload_id: self.upload_id.ok_or(super::missing_field("upload_id"))?,
+                expected_bucket_owner: self.expected_bucket_owner,
             })
         }
     }
@@ -90,6 +110,7 @@ pub mod complete_multipart_upload_input {
         pub(crate) key: Option<String>,
         pub(crate) multipart_upload: Option<crate::model::CompletedMultipartUpload>,
         pub(crate) upload_id: Option<String>,
+        pub(crate) expected_bucket_owner: Option<String>,
     }
     impl Builder {
         pub fn bucket(mut self, input: impl Into<String>) -> Self {
@@ -127,6 +148,14 @@ pub mod complete_multipart_upload_input {
             self.upload_id = input;
             self
         }
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.expected_bucket_owner = Some(input.into());
+            self
+        }
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.expected_bucket_owner = input;
+            self
+        }
         pub fn build(
             self,
         ) -> Result<
@@ -140,6 +169,7 @@ pub mod complete_multipart_upload_input {
                     .multipart_upload
                     .ok_or(super::missing_field("multipart_upload"))?,
                 upload_id: self.upload_id.ok_or(super::missing_field("upload_id"))?,
+                expected_bucket_owner: self.expected_bucket_owner,
             })
         }
     }
@@ -158,6 +188,7 @@ pub mod abort_multipart_upload_input {
         pub(crate) bucket: Option<String>,
         pub(crate) key: Option<String>,
         pub(crate) upload_id: Option<String>,
+        pub(crate) expected_bucket_owner: Option<String>,
     }
     impl Builder {
         pub fn bucket(mut self, input: impl Into<String>) -> Self {
@@ -185,11 +216,22 @@ pub mod abort_multipart_upload_input {
             self
         }
 
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.expected_bucket_owner = Some(input.into());
+            self
+        }
+
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.expected_bucket_owner = input;
+            self
+        }
+
         pub fn build(self) -> Result<crate::input::AbortMultipartUploadInput, BuildError> {
             Ok(crate::input::AbortMultipartUploadInput {
                 bucket: self.bucket.ok_or(super::missing_field("bucket"))?,
                 key: self.key.ok_or(super::missing_field("key"))?,
                 upload_id: self.upload_id.ok_or(super::missing_field("upload_id"))?,
+                expected_bucket_owner: self.expected_bucket_owner,
             })
         }
     }
@@ -204,12 +246,15 @@ impl AbortMultipartUploadInput {
 pub mod get_object_input {
     use aws_smithy_http::operation::BuildError;
 
-    #[derive(Default, Clone, PartialEq, Eq, Debug)]
+    #[derive(Default, Clone, PartialEq, Debug)]
     pub struct Builder {
         pub(crate) bucket: Option<String>,
         pub(crate) key: Option<String>,
         pub(crate) range: Option<String>,
         pub(crate) part_number: Option<i32>,
+        pub(crate) if_match: Option<String>,
+        pub(crate) if_modified_since: Option<crate::types::DateTime>,
+        pub(crate) expected_bucket_owner: Option<String>,
     }
     impl Builder {
         pub fn bucket(mut self, input: impl Into<String>) -> Self {
@@ -228,6 +273,22 @@ pub mod get_object_input {
             self.key = input;
             self
         }
+        pub fn if_match(mut self, input: impl Into<String>) -> Self {
+            self.if_match = Some(input.into());
+            self
+        }
+        pub fn set_if_match(mut self, input: Option<String>) -> Self {
+            self.if_match = input;
+            self
+        }
+        pub fn if_modified_since(mut self, input: crate::types::DateTime) -> Self {
+            self.if_modified_since = Some(input);
+            self
+        }
+        pub fn set_if_modified_since(mut self, input: Option<crate::types::DateTime>) -> Self {
+            self.if_modified_since = input;
+            self
+        }
         pub fn range(mut self, input: impl Into<String>) -> Self {
             self.range = Some(input.into());
             self
@@ -244,12 +305,23 @@ pub mod get_object_input {
             self.part_number = input;
             self
         }
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.expected_bucket_owner = Some(input.into());
+            self
+        }
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.expected_bucket_owner = input;
+            self
+        }
         pub fn build(self) -> Result<crate::input::GetObjectInput, BuildError> {
             Ok(crate::input::GetObjectInput {
                 bucket: self.bucket.ok_or(super::missing_field("bucket"))?,
                 key: self.key.ok_or(super::missing_field("key"))?,
                 range: self.range,
                 part_number: self.part_number,
+                if_match: self.if_match,
+                if_modified_since: self.if_modified_since,
+                expected_bucket_owner: self.expected_bucket_owner,
             })
         }
     }
@@ -270,6 +342,15 @@ pub mod put_object_input {
         pub(crate) bucket: Option<String>,
         pub(crate) key: Option<String>,
         pub(crate) content_length: Option<i64>,
+        pub(crate) content_md5: Option<String>,
+        pub(crate) if_none_match: Option<String>,
+        pub(crate) cache_control: Option<String>,
+        pub(crate) content_encoding: Option<String>,
+        pub(crate) ssekms_key_id: Option<String>,
+        pub(crate) storage_class: Option<crate::model::StorageClass>,
+        pub(crate) server_side_encryption: Option<crate::model::ServerSideEncryption>,
+        pub(crate) expires: Option<crate::types::DateTime>,
+        pub(crate) expected_bucket_owner: Option<String>,
     }
     impl Builder {
         pub fn body(mut self, input: crate::types::ByteStream) -> Self {
@@ -305,6 +386,83 @@ pub mod put_object_input {
             self.content_length = input;
             self
         }
+        pub fn content_md5(mut self, input: impl Into<String>) -> Self {
+            self.content_md5 = Some(input.into());
+            self
+        }
+        pub fn set_content_md5(mut self, input: Option<String>) -> Self {
+            self.content_md5 = input;
+            self
+        }
+        pub fn if_none_match(mut self, input: impl Into<String>) -> Self {
+            self.if_none_match = Some(input.into());
+            self
+        }
+        pub fn set_if_none_match(mut self, input: Option<String>) -> Self {
+            self.if_none_match = input;
+            self
+        }
+        pub fn cache_control(mut self, input: impl Into<String>) -> Self {
+            self.cache_control = Some(input.into());
+            self
+        }
+        pub fn set_cache_control(mut self, input: Option<String>) -> Self {
+            self.cache_control = input;
+            self
+        }
+        pub fn content_encoding(mut self, input: impl Into<String>) -> Self {
+            self.content_encoding = Some(input.into());
+            self
+        }
+        pub fn set_content_encoding(mut self, input: Option<String>) -> Self {
+            self.content_encoding = input;
+            self
+        }
+        pub fn ssekms_key_id(mut self, input: impl Into<String>) -> Self {
+            self.ssekms_key_id = Some(input.into());
+            self
+        }
+        pub fn set_ssekms_key_id(mut self, input: Option<String>) -> Self {
+            self.ssekms_key_id = input;
+            self
+        }
+        pub fn storage_class(mut self, input: crate::model::StorageClass) -> Self {
+            self.storage_class = Some(input);
+            self
+        }
+        pub fn set_storage_class(mut self, input: Option<crate::model::StorageClass>) -> Self {
+            self.storage_class = input;
+            self
+        }
+        pub fn server_side_encryption(mut self, input: crate::model::ServerSideEncryption) -> Self {
+            self.server_side_encryption = Some(input);
+            self
+        }
+        pub fn set_server_side_encryption(
+            mut self,
+            input: Option<crate::model::ServerSideEncryption>,
+        ) -> Self {
+            self.server_side_encryption = input;
+            self
+        }
+        pub fn expires(mut self, input: crate::types::DateTime) -> Self {
+            self.expires = Some(input);
+            self
+        }
+        pub fn set_expires(mut self, input: Option<crate::types::DateTime>) -> Self {
+            self.expires = input;
+            self
+        }
+
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.expected_bucket_owner = Some(input.into());
+            self
+        }
+
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.expected_bucket_owner = input;
+            self
+        }
 
         pub fn build(self) -> Result<crate::input::PutObjectInput, BuildError> {
             Ok(crate::input::PutObjectInput {
@@ -312,6 +470,16 @@ pub mod put_object_input {
                 body0: Default::default(),
                 bucket: self.bucket.ok_or(super::missing_field("bucket"))?,
                 key: self.key.ok_or(super::missing_field("key"))?,
+                content_length: self.content_length,
+                content_md5: self.content_md5,
+                if_none_match: self.if_none_match,
+                cache_control: self.cache_control,
+                content_encoding: self.content_encoding,
+                ssekms_key_id: self.ssekms_key_id,
+                storage_class: self.storage_class,
+                server_side_encryption: self.server_side_encryption,
+                expires: self.expires,
+                expected_bucket_owner: self.expected_bucket_owner,
             })
         }
     }
@@ -330,6 +498,7 @@ pub mod delete_object_input {
     pub struct Builder {
         pub(crate) bucket: Option<String>,
         pub(crate) key: Option<String>,
+        pub(crate) expected_bucket_owner: Option<String>,
     }
     impl Builder {
         pub fn bucket(mut self, input: impl Into<String>) -> Self {
@@ -349,10 +518,21 @@ pub mod delete_object_input {
             self
         }
 
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.expected_bucket_owner = Some(input.into());
+            self
+        }
+
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.expected_bucket_owner = input;
+            self
+        }
+
         pub fn build(self) -> Result<crate::input::DeleteObjectInput, BuildError> {
             Ok(crate::input::DeleteObjectInput {
                 bucket: self.bucket.ok_or(super::missing_field("bucket"))?,
                 key: self.key.ok_or(super::missing_field("key"))?,
+                expected_bucket_owner: self.expected_bucket_owner,
             })
         }
     }
@@ -371,6 +551,7 @@ pub mod delete_objects_input {
     pub struct Builder {
         pub(crate) bucket: Option<String>,
         pub(crate) delete: Option<crate::model::Delete>,
+        pub(crate) expected_bucket_owner: Option<String>,
     }
     impl Builder {
         pub fn bucket(mut self, input: impl Into<String>) -> Self {
@@ -389,10 +570,19 @@ pub mod delete_objects_input {
             self.delete = input;
             self
         }
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.expected_bucket_owner = Some(input.into());
+            self
+        }
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.expected_bucket_owner = input;
+            self
+        }
         pub fn build(self) -> Result<crate::input::DeleteObjectsInput, BuildError> {
             Ok(crate::input::DeleteObjectsInput {
                 bucket: self.bucket.ok_or(super::missing_field("bucket"))?,
                 delete: self.delete.ok_or(super::missing_field("delete"))?,
+                expected_bucket_owner: self.expected_bucket_owner,
             })
         }
     }
@@ -410,6 +600,9 @@ pub mod create_multipart_upload_input {
     pub struct Builder {
         pub(crate) bucket: Option<String>,
         pub(crate) key: Option<String>,
+        pub(crate) server_side_encryption: Option<crate::model::ServerSideEncryption>,
+        pub(crate) ssekms_key_id: Option<String>,
+        pub(crate) expected_bucket_owner: Option<String>,
     }
     impl Builder {
         pub fn bucket(mut self, input: impl Into<String>) -> Self {
@@ -428,10 +621,40 @@ pub mod create_multipart_upload_input {
             self.key = input;
             self
         }
+        pub fn server_side_encryption(mut self, input: crate::model::ServerSideEncryption) -> Self {
+            self.server_side_encryption = Some(input);
+            self
+        }
+        pub fn set_server_side_encryption(
+            mut self,
+            input: Option<crate::model::ServerSideEncryption>,
+        ) -> Self {
+            self.server_side_encryption = input;
+            self
+        }
+        pub fn ssekms_key_id(mut self, input: impl Into<String>) -> Self {
+            self.ssekms_key_id = Some(input.into());
+            self
+        }
+        pub fn set_ssekms_key_id(mut self, input: Option<String>) -> Self {
+            self.ssekms_key_id = input;
+            self
+        }
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.expected_bucket_owner = Some(input.into());
+            self
+        }
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.expected_bucket_owner = input;
+            self
+        }
         pub fn build(self) -> Result<crate::input::CreateMultipartUploadInput, BuildError> {
             Ok(crate::input::CreateMultipartUploadInput {
                 bucket: self.bucket.ok_or(super::missing_field("bucket"))?,
                 key: self.key.ok_or(super::missing_field("key"))?,
+                server_side_encryption: self.server_side_encryption,
+                ssekms_key_id: self.ssekms_key_id,
+                expected_bucket_owner: self.expected_bucket_owner,
             })
         }
     }
@@ -449,6 +672,7 @@ pub mod head_object_input {
     pub struct Builder {
         pub(crate) bucket: Option<String>,
         pub(crate) key: Option<String>,
+        pub(crate) expected_bucket_owner: Option<String>,
     }
     impl Builder {
         pub fn bucket(mut self, input: impl Into<String>) -> Self {
@@ -467,10 +691,19 @@ pub mod head_object_input {
             self.key = input;
             self
         }
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.expected_bucket_owner = Some(input.into());
+            self
+        }
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.expected_bucket_owner = input;
+            self
+        }
         pub fn build(self) -> Result<crate::input::HeadObjectInput, BuildError> {
             Ok(crate::input::HeadObjectInput {
                 bucket: self.bucket.ok_or(super::missing_field("bucket"))?,
                 key: self.key.ok_or(super::missing_field("key"))?,
+                expected_bucket_owner: self.expected_bucket_owner,
             })
         }
     }
@@ -489,6 +722,9 @@ pub mod list_objects_v2_input {
         pub(crate) bucket: Option<String>,
         pub(crate) prefix: Option<String>,
         pub(crate) continuation_token: Option<String>,
+        pub(crate) start_after: Option<String>,
+        pub(crate) max_keys: Option<i32>,
+        pub(crate) expected_bucket_owner: Option<String>,
     }
     impl Builder {
         pub fn bucket(mut self, input: impl Into<String>) -> Self {
@@ -516,12 +752,41 @@ pub mod list_objects_v2_input {
             self.continuation_token = input;
             self
         }
+        pub fn start_after(mut self, input: impl Into<String>) -> Self {
+            self.start_after = Some(input.into());
+            self
+        }
+        pub fn set_start_after(mut self, input: Option<String>) -> Self {
+            self.start_after = input;
+            self
+        }
+        pub fn max_keys(mut self, input: i32) -> Self {
+            self.max_keys = Some(input);
+            self
+        }
+        pub fn set_max_keys(mut self, input: Option<i32>) -> Self {
+            self.max_keys = input;
+            self
+        }
+
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.expected_bucket_owner = Some(input.into());
+            self
+        }
+
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.expected_bucket_owner = input;
+            self
+        }
 
         pub fn build(self) -> Result<crate::input::ListObjectsV2Input, BuildError> {
             Ok(crate::input::ListObjectsV2Input {
                 bucket: self.bucket.ok_or(super::missing_field("bucket"))?,
                 prefix: self.prefix,
                 continuation_token: self.continuation_token,
+                start_after: self.start_after,
+                max_keys: self.max_keys.unwrap_or_default(),
+                expected_bucket_owner: self.expected_bucket_owner,
             })
         }
     }
@@ -538,10 +803,12 @@ pub struct UploadPartInput {
     pub body: crate::types::ByteStream,
     pub(crate) body0: Bytes,
     pub(crate) bucket: String,
-    pub(crate) content_length: i64,
+    pub(crate) content_length: Option<i64>,
+    pub(crate) content_md5: Option<String>,
     pub(crate) key: String,
     pub(crate) part_number: i32,
     pub(crate) upload_id: String,
+    pub(crate) expected_bucket_owner: Option<String>,
 }
 impl UploadPartInput {
     pub fn body(&self) -> &crate::types::ByteStream {
@@ -551,7 +818,10 @@ impl UploadPartInput {
         Some(&self.bucket)
     }
     pub fn content_length(&self) -> i64 {
-        self.content_length
+        self.content_length.unwrap_or_default()
+    }
+    pub fn content_md5(&self) -> Option<&str> {
+        self.content_md5.as_deref()
     }
     pub fn key(&self) -> Option<&str> {
         Some(&self.key)
@@ -562,12 +832,13 @@ impl UploadPartInput {
     pub fn upload_id(&self) -> Option<&str> {
         Some(&self.upload_id)
     }
-    /// Read all the data from the ByteStream `body` into Bytes `body0`.
-    /// This will be done on the client before sending the request.
-    pub(crate) async fn collect_body(&mut self) -> Result<(), aws_smithy_http::byte_stream::Error> {
-        let mut body = std::mem::take(&mut self.body);
-        self.body0 = body.collect().await?.into_bytes();
-        Ok(())
+    pub fn expected_bucket_owner(&self) -> Option<&str> {
+        self.expected_bucket_owner.as_deref()
+    }
+    /// Take the ByteStream `body` to be streamed after the request.
+    /// The server collects the chunks into Bytes `body0`.
+    pub(crate) fn take_body(&mut self) -> crate::types::ByteStream {
+        std::mem::take(&mut self.body)
     }
 }
 
@@ -578,6 +849,7 @@ pub struct CompleteMultipartUploadInput {
     pub(crate) key: String,
     pub(crate) multipart_upload: crate::model::CompletedMultipartUpload,
     pub(crate) upload_id: String,
+    pub(crate) expected_bucket_owner: Option<String>,
 }
 impl CompleteMultipartUploadInput {
     pub fn bucket(&self) -> Option<&str> {
@@ -592,6 +864,9 @@ impl CompleteMultipartUploadInput {
     pub fn upload_id(&self) -> Option<&str> {
         Some(&self.upload_id)
     }
+    pub fn expected_bucket_owner(&self) -> Option<&str> {
+        self.expected_bucket_owner.as_deref()
+    }
 }
 
 #[non_exhaustive]
@@ -600,6 +875,7 @@ pub struct AbortMultipartUploadInput {
     pub(crate) bucket: String,
     pub(crate) key: String,
     pub(crate) upload_id: String,
+    pub(crate) expected_bucket_owner: Option<String>,
 }
 impl AbortMultipartUploadInput {
     pub fn bucket(&self) -> Option<&str> {
@@ -611,15 +887,21 @@ impl AbortMultipartUploadInput {
     pub fn upload_id(&self) -> Option<&str> {
         Some(&self.upload_id)
     }
+    pub fn expected_bucket_owner(&self) -> Option<&str> {
+        self.expected_bucket_owner.as_deref()
+    }
 }
 
 #[non_exhaustive]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct GetObjectInput {
     pub(crate) bucket: String,
     pub(crate) key: String,
     pub(crate) range: Option<String>,
     pub(crate) part_number: Option<i32>,
+    pub(crate) if_match: Option<String>,
+    pub(crate) if_modified_since: Option<crate::types::DateTime>,
+    pub(crate) expected_bucket_owner: Option<String>,
 }
 impl GetObjectInput {
     pub fn bucket(&self) -> Option<&str> {
@@ -634,6 +916,15 @@ impl GetObjectInput {
     pub fn part_number(&self) -> Option<i32> {
         self.part_number
     }
+    pub fn if_match(&self) -> Option<&str> {
+        self.if_match.as_deref()
+    }
+    pub fn if_modified_since(&self) -> Option<&crate::types::DateTime> {
+        self.if_modified_since.as_ref()
+    }
+    pub fn expected_bucket_owner(&self) -> Option<&str> {
+        self.expected_bucket_owner.as_deref()
+    }
 }
 
 #[non_exhaustive]
@@ -643,6 +934,16 @@ pub struct PutObjectInput {
     pub(crate) body0: Bytes,
     pub(crate) bucket: String,
     pub(crate) key: String,
+    pub(crate) content_length: Option<i64>,
+    pub(crate) content_md5: Option<String>,
+    pub(crate) if_none_match: Option<String>,
+    pub(crate) cache_control: Option<String>,
+    pub(crate) content_encoding: Option<String>,
+    pub(crate) ssekms_key_id: Option<String>,
+    pub(crate) storage_class: Option<crate::model::StorageClass>,
+    pub(crate) server_side_encryption: Option<crate::model::ServerSideEncryption>,
+    pub(crate) expires: Option<crate::types::DateTime>,
+    pub(crate) expected_bucket_owner: Option<String>,
 }
 impl PutObjectInput {
     pub fn body(&self) -> &crate::types::ByteStream {
@@ -654,12 +955,40 @@ impl PutObjectInput {
     pub fn key(&self) -> Option<&str> {
         Some(&self.key)
     }
-    /// Read all the data from the ByteStream `body` into Bytes `body0`.
-    /// This will be done on the client before sending the request.
-    pub(crate) async fn collect_body(&mut self) -> Result<(), aws_smithy_http::byte_stream::Error> {
-        let mut body = std::mem::take(&mut self.body);
-        self.body0 = body.collect().await?.into_bytes();
-        Ok(())
+    pub fn content_length(&self) -> i64 {
+        self.content_length.unwrap_or_default()
+    }
+    pub fn content_md5(&self) -> Option<&str> {
+        self.content_md5.as_deref()
+    }
+    pub fn if_none_match(&self) -> Option<&str> {
+        self.if_none_match.as_deref()
+    }
+    pub fn cache_control(&self) -> Option<&str> {
+        self.cache_control.as_deref()
+    }
+    pub fn content_encoding(&self) -> Option<&str> {
+        self.content_encoding.as_deref()
+    }
+    pub fn ssekms_key_id(&self) -> Option<&str> {
+        self.ssekms_key_id.as_deref()
+    }
+    pub fn storage_class(&self) -> Option<&crate::model::StorageClass> {
+        self.storage_class.as_ref()
+    }
+    pub fn server_side_encryption(&self) -> Option<&crate::model::ServerSideEncryption> {
+        self.server_side_encryption.as_ref()
+    }
+    pub fn expires(&self) -> Option<&crate::types::DateTime> {
+        self.expires.as_ref()
+    }
+    pub fn expected_bucket_owner(&self) -> Option<&str> {
+        self.expected_bucket_owner.as_deref()
+    }
+    /// Take the ByteStream `body` to be streamed after the request.
+    /// The server collects the chunks into Bytes `body0`.
+    pub(crate) fn take_body(&mut self) -> crate::types::ByteStream {
+        std::mem::take(&mut self.body)
     }
 }
 
@@ -668,6 +997,7 @@ impl PutObjectInput {
 pub struct DeleteObjectInput {
     pub(crate) bucket: String,
     pub(crate) key: String,
+    pub(crate) expected_bucket_owner: Option<String>,
 }
 impl DeleteObjectInput {
     pub fn bucket(&self) -> Option<&str> {
@@ -676,6 +1006,9 @@ impl DeleteObjectInput {
     pub fn key(&self) -> Option<&str> {
         Some(&self.key)
     }
+    pub fn expected_bucket_owner(&self) -> Option<&str> {
+        self.expected_bucket_owner.as_deref()
+    }
 }
 
 #[non_exhaustive]
@@ -683,6 +1016,7 @@ impl DeleteObjectInput {
 pub struct DeleteObjectsInput {
     pub(crate) bucket: String,
     pub(crate) delete: crate::model::Delete,
+    pub(crate) expected_bucket_owner: Option<String>,
 }
 impl DeleteObjectsInput {
     pub fn bucket(&self) -> Option<&str> {
@@ -691,6 +1025,9 @@ impl DeleteObjectsInput {
     pub fn delete(&self) -> Option<&crate::model::Delete> {
         Some(&self.delete)
     }
+    pub fn expected_bucket_owner(&self) -> Option<&str> {
+        self.expected_bucket_owner.as_deref()
+    }
 }
 
 #[non_exhaustive]
@@ -698,6 +1035,9 @@ impl DeleteObjectsInput {
 pub struct CreateMultipartUploadInput {
     pub(crate) bucket: String,
     pub(crate) key: String,
+    pub(crate) server_side_encryption: Option<crate::model::ServerSideEncryption>,
+    pub(crate) ssekms_key_id: Option<String>,
+    pub(crate) expected_bucket_owner: Option<String>,
 }
 impl CreateMultipartUploadInput {
     pub fn bucket(&self) -> Option<&str> {
@@ -706,6 +1046,15 @@ impl CreateMultipartUploadInput {
     pub fn key(&self) -> Option<&str> {
         Some(&self.key)
     }
+    pub fn server_side_encryption(&self) -> Option<&crate::model::ServerSideEncryption> {
+        self.server_side_encryption.as_ref()
+    }
+    pub fn ssekms_key_id(&self) -> Option<&str> {
+        self.ssekms_key_id.as_deref()
+    }
+    pub fn expected_bucket_owner(&self) -> Option<&str> {
+        self.expected_bucket_owner.as_deref()
+    }
 }
 
 #[non_exhaustive]
@@ -713,6 +1062,7 @@ impl CreateMultipartUploadInput {
 pub struct HeadObjectInput {
     pub(crate) bucket: String,
     pub(crate) key: String,
+    pub(crate) expected_bucket_owner: Option<String>,
 }
 impl HeadObjectInput {
     pub fn bucket(&self) -> Option<&str> {
@@ -721,6 +1071,9 @@ impl HeadObjectInput {
     pub fn key(&self) -> Option<&str> {
         Some(&self.key)
     }
+    pub fn expected_bucket_owner(&self) -> Option<&str> {
+        self.expected_bucket_owner.as_deref()
+    }
 }
 
 #[non_exhaustive]
@@ -729,6 +1082,9 @@ pub struct ListObjectsV2Input {
     pub(crate) bucket: String,
     pub(crate) prefix: Option<String>,
     pub(crate) continuation_token: Option<String>,
+    pub(crate) start_after: Option<String>,
+    pub(crate) max_keys: i32,
+    pub(crate) expected_bucket_owner: Option<String>,
 }
 impl ListObjectsV2Input {
     pub fn bucket(&self) -> Option<&str> {
@@ -740,6 +1096,15 @@ impl ListObjectsV2Input {
     pub fn continuation_token(&self) -> Option<&str> {
         self.continuation_token.as_deref()
     }
+    pub fn start_after(&self) -> Option<&str> {
+        self.start_after.as_deref()
+    }
+    pub fn max_keys(&self) -> i32 {
+        self.max_keys
+    }
+    pub fn expected_bucket_owner(&self) -> Option<&str> {
+        self.expected_bucket_owner.as_deref()
+    }
 }
 
 #[non_exhaustive]
@@ -875,6 +1240,735 @@ impl GetBucketLifecycleConfigurationInput {
     }
 }
 
+pub mod get_bucket_location_input {
+    #[derive(Default, Clone, PartialEq, Eq, Debug)]
+    pub struct Builder {
+        pub(crate) bucket: Option<String>,
+        pub(crate) expected_bucket_owner: Option<String>,
+    }
+    impl Builder {
+        pub fn bucket(mut self, input: impl Into<String>) -> Self {
+            self.bucket = Some(input.into());
+            self
+        }
+        pub fn set_bucket(mut self, input: Option<String>) -> Self {
+            self.bucket = input;
+            self
+        }
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.expected_bucket_owner = Some(input.into());
+            self
+        }
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.expected_bucket_owner = input;
+            self
+        }
+        pub fn build(
+            self,
+        ) -> Result<crate::input::GetBucketLocationInput, aws_smithy_http::operation::BuildError>
+        {
+            Ok(crate::input::GetBucketLocationInput {
+                bucket: self.bucket.ok_or(super::missing_field("bucket"))?,
+                expected_bucket_owner: self.expected_bucket_owner,
+            })
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetBucketLocationInput {
+    pub(crate) bucket: String,
+    pub(crate) expected_bucket_owner: Option<String>,
+}
+impl GetBucketLocationInput {
+    pub fn bucket(&self) -> Option<&str> {
+        Some(&self.bucket)
+    }
+    pub fn expected_bucket_owner(&self) -> Option<&str> {
+        self.expected_bucket_owner.as_deref()
+    }
+}
+impl GetBucketLocationInput {
+    pub fn builder() -> crate::input::get_bucket_location_input::Builder {
+        crate::input::get_bucket_location_input::Builder::default()
+    }
+}
+
+pub mod get_bucket_versioning_input {
+    #[derive(Default, Clone, PartialEq, Eq, Debug)]
+    pub struct Builder {
+        pub(crate) bucket: Option<String>,
+        pub(crate) expected_bucket_owner: Option<String>,
+    }
+    impl Builder {
+        pub fn bucket(mut self, input: impl Into<String>) -> Self {
+            self.bucket = Some(input.into());
+            self
+        }
+        pub fn set_bucket(mut self, input: Option<String>) -> Self {
+            self.bucket = input;
+            self
+        }
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.expected_bucket_owner = Some(input.into());
+            self
+        }
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.expected_bucket_owner = input;
+            self
+        }
+        pub fn build(
+            self,
+        ) -> Result<crate::input::GetBucketVersioningInput, aws_smithy_http::operation::BuildError>
+        {
+            Ok(crate::input::GetBucketVersioningInput {
+                bucket: self.bucket.ok_or(super::missing_field("bucket"))?,
+                expected_bucket_owner: self.expected_bucket_owner,
+            })
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetBucketVersioningInput {
+    pub(crate) bucket: String,
+    pub(crate) expected_bucket_owner: Option<String>,
+}
+impl GetBucketVersioningInput {
+    pub fn bucket(&self) -> Option<&str> {
+        Some(&self.bucket)
+    }
+    pub fn expected_bucket_owner(&self) -> Option<&str> {
+        self.expected_bucket_owner.as_deref()
+    }
+}
+impl GetBucketVersioningInput {
+    pub fn builder() -> crate::input::get_bucket_versioning_input::Builder {
+        crate::input::get_bucket_versioning_input::Builder::default()
+    }
+}
+
+pub mod get_object_attributes_input {
+    #[derive(Default, Clone, PartialEq, Eq, Debug)]
+    pub struct Builder {
+        pub(crate) bucket: Option<String>,
+        pub(crate) key: Option<String>,
+        pub(crate) max_parts: Option<i32>,
+        pub(crate) part_number_marker: Option<String>,
+        pub(crate) expected_bucket_owner: Option<String>,
+        pub(crate) object_attributes: Option<Vec<crate::model::ObjectAttributes>>,
+    }
+    impl Builder {
+        pub fn bucket(mut self, input: impl Into<String>) -> Self {
+            self.bucket = Some(input.into());
+            self
+        }
+        pub fn set_bucket(mut self, input: Option<String>) -> Self {
+            self.bucket = input;
+            self
+        }
+        pub fn key(mut self, input: impl Into<String>) -> Self {
+            self.key = Some(input.into());
+            self
+        }
+        pub fn set_key(mut self, input: Option<String>) -> Self {
+            self.key = input;
+            self
+        }
+        pub fn max_parts(mut self, input: i32) -> Self {
+            self.max_parts = Some(input);
+            self
+        }
+        pub fn set_max_parts(mut self, input: Option<i32>) -> Self {
+            self.max_parts = input;
+            self
+        }
+        pub fn part_number_marker(mut self, input: impl Into<String>) -> Self {
+            self.part_number_marker = Some(input.into());
+            self
+        }
+        pub fn set_part_number_marker(mut self, input: Option<String>) -> Self {
+            self.part_number_marker = input;
+            self
+        }
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.expected_bucket_owner = Some(input.into());
+            self
+        }
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.expected_bucket_owner = input;
+            self
+        }
+        pub fn object_attributes(mut self, input: crate::model::ObjectAttributes) -> Self {
+            let mut v = self.object_attributes.unwrap_or_default();
+            v.push(input);
+            self.object_attributes = Some(v);
+            self
+        }
+        pub fn set_object_attributes(
+            mut self,
+            input: Option<Vec<crate::model::ObjectAttributes>>,
+        ) -> Self {
+            self.object_attributes = input;
+            self
+        }
+        pub fn build(
+            self,
+        ) -> Result<crate::input::GetObjectAttributesInput, aws_smithy_http::operation::BuildError>
+        {
+            Ok(crate::input::GetObjectAttributesInput {
+                bucket: self.bucket.ok_or(super::missing_field("bucket"))?,
+                key: self.key.ok_or(super::missing_field("key"))?,
+                max_parts: self.max_parts.unwrap_or_default(),
+                part_number_marker: self.part_number_marker,
+                expected_bucket_owner: self.expected_bucket_owner,
+                object_attributes: self.object_attributes,
+            })
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetObjectAttributesInput {
+    pub(crate) bucket: String,
+    pub(crate) key: String,
+    pub(crate) max_parts: i32,
+    pub(crate) part_number_marker: Option<String>,
+    pub(crate) expected_bucket_owner: Option<String>,
+    pub(crate) object_attributes: Option<Vec<crate::model::ObjectAttributes>>,
+}
+impl GetObjectAttributesInput {
+    pub fn bucket(&self) -> Option<&str> {
+        Some(&self.bucket)
+    }
+    pub fn key(&self) -> Option<&str> {
+        Some(&self.key)
+    }
+    pub fn max_parts(&self) -> i32 {
+        self.max_parts
+    }
+    pub fn part_number_marker(&self) -> Option<&str> {
+        self.part_number_marker.as_deref()
+    }
+    pub fn expected_bucket_owner(&self) -> Option<&str> {
+        self.expected_bucket_owner.as_deref()
+    }
+    pub fn object_attributes(&self) -> Option<&[crate::model::ObjectAttributes]> {
+        self.object_attributes.as_deref()
+    }
+}
+impl GetObjectAttributesInput {
+    pub fn builder() -> crate::input::get_object_attributes_input::Builder {
+        crate::input::get_object_attributes_input::Builder::default()
+    }
+}
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
+pub struct PutObjectLockConfigurationInput {
+    pub(crate) bucket: String,
+    pub(crate) object_lock_configuration: Option<crate::model::ObjectLockConfiguration>,
+    pub(crate) expected_bucket_owner: Option<String>,
+}
+impl PutObjectLockConfigurationInput {
+    pub fn bucket(&self) -> Option<&str> {
+        Some(&self.bucket)
+    }
+    pub fn object_lock_configuration(&self) -> Option<&crate::model::ObjectLockConfiguration> {
+        self.object_lock_configuration.as_ref()
+    }
+    pub fn expected_bucket_owner(&self) -> Option<&str> {
+        self.expected_bucket_owner.as_deref()
+    }
+}
+impl Debug for PutObjectLockConfigurationInput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut formatter = f.debug_struct("PutObjectLockConfigurationInput");
+        formatter.field("bucket", &self.bucket);
+        formatter.field("object_lock_configuration", &self.object_lock_configuration);
+        formatter.field("expected_bucket_owner", &self.expected_bucket_owner);
+        formatter.finish()
+    }
+}
+pub mod put_object_lock_configuration_input {
+    #[derive(Default, Clone, PartialEq, Eq, Debug)]
+    pub struct Builder {
+        pub(crate) bucket: Option<String>,
+        pub(crate) object_lock_configuration: Option<crate::model::ObjectLockConfiguration>,
+        pub(crate) expected_bucket_owner: Option<String>,
+    }
+    impl Builder {
+        pub fn bucket(mut self, input: impl Into<String>) -> Self {
+            self.bucket = Some(input.into());
+            self
+        }
+        pub fn set_bucket(mut self, input: Option<String>) -> Self {
+            self.bucket = input;
+            self
+        }
+        pub fn object_lock_configuration(
+            mut self,
+            input: crate::model::ObjectLockConfiguration,
+        ) -> Self {
+            self.object_lock_configuration = Some(input);
+            self
+        }
+        pub fn set_object_lock_configuration(
+            mut self,
+            input: Option<crate::model::ObjectLockConfiguration>,
+        ) -> Self {
+            self.object_lock_configuration = input;
+            self
+        }
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.expected_bucket_owner = Some(input.into());
+            self
+        }
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.expected_bucket_owner = input;
+            self
+        }
+        pub fn build(
+            self,
+        ) -> Result<
+            crate::input::PutObjectLockConfigurationInput,
+            aws_smithy_http::operation::BuildError,
+        > {
+            Ok(crate::input::PutObjectLockConfigurationInput {
+                bucket: self.bucket.ok_or(super::missing_field("bucket"))?,
+                object_lock_configuration: self.object_lock_configuration,
+                expected_bucket_owner: self.expected_bucket_owner,
+            })
+        }
+    }
+}
+impl PutObjectLockConfigurationInput {
+    pub fn builder() -> crate::input::put_object_lock_configuration_input::Builder {
+        crate::input::put_object_lock_configuration_input::Builder::default()
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
+pub struct GetObjectLockConfigurationInput {
+    pub(crate) bucket: String,
+    pub(crate) expected_bucket_owner: Option<String>,
+}
+impl GetObjectLockConfigurationInput {
+    pub fn bucket(&self) -> Option<&str> {
+        Some(&self.bucket)
+    }
+    pub fn expected_bucket_owner(&self) -> Option<&str> {
+        self.expected_bucket_owner.as_deref()
+    }
+}
+impl Debug for GetObjectLockConfigurationInput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut formatter = f.debug_struct("GetObjectLockConfigurationInput");
+        formatter.field("bucket", &self.bucket);
+        formatter.field("expected_bucket_owner", &self.expected_bucket_owner);
+        formatter.finish()
+    }
+}
+pub mod get_object_lock_configuration_input {
+    #[derive(Default, Clone, PartialEq, Eq, Debug)]
+    pub struct Builder {
+        pub(crate) bucket: Option<String>,
+        pub(crate) expected_bucket_owner: Option<String>,
+    }
+    impl Builder {
+        pub fn bucket(mut self, input: impl Into<String>) -> Self {
+            self.bucket = Some(input.into());
+            self
+        }
+        pub fn set_bucket(mut self, input: Option<String>) -> Self {
+            self.bucket = input;
+            self
+        }
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.expected_bucket_owner = Some(input.into());
+            self
+        }
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.expected_bucket_owner = input;
+            self
+        }
+        pub fn build(
+            self,
+        ) -> Result<
+            crate::input::GetObjectLockConfigurationInput,
+            aws_smithy_http::operation::BuildError,
+        > {
+            Ok(crate::input::GetObjectLockConfigurationInput {
+                bucket: self.bucket.ok_or(super::missing_field("bucket"))?,
+                expected_bucket_owner: self.expected_bucket_owner,
+            })
+        }
+    }
+}
+impl GetObjectLockConfigurationInput {
+    pub fn builder() -> crate::input::get_object_lock_configuration_input::Builder {
+        crate::input::get_object_lock_configuration_input::Builder::default()
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, PartialEq)]
+pub struct PutObjectRetentionInput {
+    pub(crate) bucket: String,
+    pub(crate) key: String,
+    pub(crate) retention: Option<crate::model::ObjectLockRetention>,
+    pub(crate) bypass_governance_retention: bool,
+    pub(crate) expected_bucket_owner: Option<String>,
+}
+impl PutObjectRetentionInput {
+    pub fn bucket(&self) -> Option<&str> {
+        Some(&self.bucket)
+    }
+    pub fn key(&self) -> Option<&str> {
+        Some(&self.key)
+    }
+    pub fn retention(&self) -> Option<&crate::model::ObjectLockRetention> {
+        self.retention.as_ref()
+    }
+    pub fn bypass_governance_retention(&self) -> bool {
+        self.bypass_governance_retention
+    }
+    pub fn expected_bucket_owner(&self) -> Option<&str> {
+        self.expected_bucket_owner.as_deref()
+    }
+}
+impl Debug for PutObjectRetentionInput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut formatter = f.debug_struct("PutObjectRetentionInput");
+        formatter.field("bucket", &self.bucket);
+        formatter.field("key", &self.key);
+        formatter.field("retention", &self.retention);
+        formatter.field(
+            "bypass_governance_retention",
+            &self.bypass_governance_retention,
+        );
+        formatter.field("expected_bucket_owner", &self.expected_bucket_owner);
+        formatter.finish()
+    }
+}
+pub mod put_object_retention_input {
+    #[derive(Default, Clone, PartialEq, Debug)]
+    pub struct Builder {
+        pub(crate) bucket: Option<String>,
+        pub(crate) key: Option<String>,
+        pub(crate) retention: Option<crate::model::ObjectLockRetention>,
+        pub(crate) bypass_governance_retention: Option<bool>,
+        pub(crate) expected_bucket_owner: Option<String>,
+    }
+    impl Builder {
+        pub fn bucket(mut self, input: impl Into<String>) -> Self {
+            self.bucket = Some(input.into());
+            self
+        }
+        pub fn set_bucket(mut self, input: Option<String>) -> Self {
+            self.bucket = input;
+            self
+        }
+        pub fn key(mut self, input: impl Into<String>) -> Self {
+            self.key = Some(input.into());
+            self
+        }
+        pub fn set_key(mut self, input: Option<String>) -> Self {
+            self.key = input;
+            self
+        }
+        pub fn retention(mut self, input: crate::model::ObjectLockRetention) -> Self {
+            self.retention = Some(input);
+            self
+        }
+        pub fn set_retention(mut self, input: Option<crate::model::ObjectLockRetention>) -> Self {
+            self.retention = input;
+            self
+        }
+        pub fn bypass_governance_retention(mut self, input: bool) -> Self {
+            self.bypass_governance_retention = Some(input);
+            self
+        }
+        pub fn set_bypass_governance_retention(mut self, input: Option<bool>) -> Self {
+            self.bypass_governance_retention = input;
+            self
+        }
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.expected_bucket_owner = Some(input.into());
+            self
+        }
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.expected_bucket_owner = input;
+            self
+        }
+        pub fn build(
+            self,
+        ) -> Result<crate::input::PutObjectRetentionInput, aws_smithy_http::operation::BuildError>
+        {
+            Ok(crate::input::PutObjectRetentionInput {
+                bucket: self.bucket.ok_or(super::missing_field("bucket"))?,
+                key: self.key.ok_or(super::missing_field("key"))?,
+                retention: self.retention,
+                bypass_governance_retention: self.bypass_governance_retention.unwrap_or_default(),
+                expected_bucket_owner: self.expected_bucket_owner,
+            })
+        }
+    }
+}
+impl PutObjectRetentionInput {
+    pub fn builder() -> crate::input::put_object_retention_input::Builder {
+        crate::input::put_object_retention_input::Builder::default()
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
+pub struct PutObjectLegalHoldInput {
+    pub(crate) bucket: String,
+    pub(crate) key: String,
+    pub(crate) legal_hold: Option<crate::model::ObjectLockLegalHold>,
+    pub(crate) expected_bucket_owner: Option<String>,
+}
+impl PutObjectLegalHoldInput {
+    pub fn bucket(&self) -> Option<&str> {
+        Some(&self.bucket)
+    }
+    pub fn key(&self) -> Option<&str> {
+        Some(&self.key)
+    }
+    pub fn legal_hold(&self) -> Option<&crate::model::ObjectLockLegalHold> {
+        self.legal_hold.as_ref()
+    }
+    pub fn expected_bucket_owner(&self) -> Option<&str> {
+        self.expected_bucket_owner.as_deref()
+    }
+}
+impl Debug for PutObjectLegalHoldInput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut formatter = f.debug_struct("PutObjectLegalHoldInput");
+        formatter.field("bucket", &self.bucket);
+        formatter.field("key", &self.key);
+        formatter.field("legal_hold", &self.legal_hold);
+        formatter.field("expected_bucket_owner", &self.expected_bucket_owner);
+        formatter.finish()
+    }
+}
+pub mod put_object_legal_hold_input {
+    #[derive(Default, Clone, PartialEq, Eq, Debug)]
+    pub struct Builder {
+        pub(crate) bucket: Option<String>,
+        pub(crate) key: Option<String>,
+        pub(crate) legal_hold: Option<crate::model::ObjectLockLegalHold>,
+        pub(crate) expected_bucket_owner: Option<String>,
+    }
+    impl Builder {
+        pub fn bucket(mut self, input: impl Into<String>) -> Self {
+            self.bucket = Some(input.into());
+            self
+        }
+        pub fn set_bucket(mut self, input: Option<String>) -> Self {
+            self.bucket = input;
+            self
+        }
+        pub fn key(mut self, input: impl Into<String>) -> Self {
+            self.key = Some(input.into());
+            self
+        }
+        pub fn set_key(mut self, input: Option<String>) -> Self {
+            self.key = input;
+            self
+        }
+        pub fn legal_hold(mut self, input: crate::model::ObjectLockLegalHold) -> Self {
+            self.legal_hold = Some(input);
+            self
+        }
+        pub fn set_legal_hold(mut self, input: Option<crate::model::ObjectLockLegalHold>) -> Self {
+            self.legal_hold = input;
+            self
+        }
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.expected_bucket_owner = Some(input.into());
+            self
+        }
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.expected_bucket_owner = input;
+            self
+        }
+        pub fn build(
+            self,
+        ) -> Result<crate::input::PutObjectLegalHoldInput, aws_smithy_http::operation::BuildError>
+        {
+            Ok(crate::input::PutObjectLegalHoldInput {
+                bucket: self.bucket.ok_or(super::missing_field("bucket"))?,
+                key: self.key.ok_or(super::missing_field("key"))?,
+                legal_hold: self.legal_hold,
+                expected_bucket_owner: self.expected_bucket_owner,
+            })
+        }
+    }
+}
+impl PutObjectLegalHoldInput {
+    pub fn builder() -> crate::input::put_object_legal_hold_input::Builder {
+        crate::input::put_object_legal_hold_input::Builder::default()
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
+pub struct RestoreObjectInput {
+    pub(crate) bucket: String,
+    pub(crate) key: String,
+    pub(crate) restore_request: Option<crate::model::RestoreRequest>,
+    pub(crate) expected_bucket_owner: Option<String>,
+}
+impl RestoreObjectInput {
+    pub fn bucket(&self) -> Option<&str> {
+        Some(&self.bucket)
+    }
+    pub fn key(&self) -> Option<&str> {
+        Some(&self.key)
+    }
+    pub fn restore_request(&self) -> Option<&crate::model::RestoreRequest> {
+        self.restore_request.as_ref()
+    }
+    pub fn expected_bucket_owner(&self) -> Option<&str> {
+        self.expected_bucket_owner.as_deref()
+    }
+}
+impl Debug for RestoreObjectInput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut formatter = f.debug_struct("RestoreObjectInput");
+        formatter.field("bucket", &self.bucket);
+        formatter.field("key", &self.key);
+        formatter.field("restore_request", &self.restore_request);
+        formatter.field("expected_bucket_owner", &self.expected_bucket_owner);
+        formatter.finish()
+    }
+}
+pub mod restore_object_input {
+    #[derive(Default, Clone, PartialEq, Eq, Debug)]
+    pub struct Builder {
+        pub(crate) bucket: Option<String>,
+        pub(crate) key: Option<String>,
+        pub(crate) restore_request: Option<crate::model::RestoreRequest>,
+        pub(crate) expected_bucket_owner: Option<String>,
+    }
+    impl Builder {
+        pub fn bucket(mut self, input: impl Into<String>) -> Self {
+            self.bucket = Some(input.into());
+            self
+        }
+        pub fn set_bucket(mut self, input: Option<String>) -> Self {
+            self.bucket = input;
+            self
+        }
+        pub fn key(mut self, input: impl Into<String>) -> Self {
+            self.key = Some(input.into());
+            self
+        }
+        pub fn set_key(mut self, input: Option<String>) -> Self {
+            self.key = input;
+            self
+        }
+        pub fn restore_request(mut self, input: crate::model::RestoreRequest) -> Self {
+            self.restore_request = Some(input);
+            self
+        }
+        pub fn set_restore_request(mut self, input: Option<crate::model::RestoreRequest>) -> Self {
+            self.restore_request = input;
+            self
+        }
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.expected_bucket_owner = Some(input.into());
+            self
+        }
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.expected_bucket_owner = input;
+            self
+        }
+        pub fn build(
+            self,
+        ) -> Result<crate::input::RestoreObjectInput, aws_smithy_http::operation::BuildError>
+        {
+            Ok(crate::input::RestoreObjectInput {
+                bucket: self.bucket.ok_or(super::missing_field("bucket"))?,
+                key: self.key.ok_or(super::missing_field("key"))?,
+                restore_request: self.restore_request,
+                expected_bucket_owner: self.expected_bucket_owner,
+            })
+        }
+    }
+}
+impl RestoreObjectInput {
+    pub fn builder() -> crate::input::restore_object_input::Builder {
+        crate::input::restore_object_input::Builder::default()
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
+pub struct HeadBucketInput {
+    pub(crate) bucket: String,
+    pub(crate) expected_bucket_owner: Option<String>,
+}
+impl HeadBucketInput {
+    pub fn bucket(&self) -> Option<&str> {
+        Some(&self.bucket)
+    }
+    pub fn expected_bucket_owner(&self) -> Option<&str> {
+        self.expected_bucket_owner.as_deref()
+    }
+}
+impl Debug for HeadBucketInput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut formatter = f.debug_struct("HeadBucketInput");
+        formatter.field("bucket", &self.bucket);
+        formatter.field("expected_bucket_owner", &self.expected_bucket_owner);
+        formatter.finish()
+    }
+}
+pub mod head_bucket_input {
+    #[derive(Default, Clone, PartialEq, Eq, Debug)]
+    pub struct Builder {
+        pub(crate) bucket: Option<String>,
+        pub(crate) expected_bucket_owner: Option<String>,
+    }
+    impl Builder {
+        pub fn bucket(mut self, input: impl Into<String>) -> Self {
+            self.bucket = Some(input.into());
+            self
+        }
+        pub fn set_bucket(mut self, input: Option<String>) -> Self {
+            self.bucket = input;
+            self
+        }
+        pub fn expected_bucket_owner(mut self, input: impl Into<String>) -> Self {
+            self.expected_bucket_owner = Some(input.into());
+            self
+        }
+        pub fn set_expected_bucket_owner(mut self, input: Option<String>) -> Self {
+            self.expected_bucket_owner = input;
+            self
+        }
+        pub fn build(
+            self,
+        ) -> Result<crate::input::HeadBucketInput, aws_smithy_http::operation::BuildError> {
+            Ok(crate::input::HeadBucketInput {
+                bucket: self.bucket.ok_or(super::missing_field("bucket"))?,
+                expected_bucket_owner: self.expected_bucket_owner,
+            })
+        }
+    }
+}
+impl HeadBucketInput {
+    pub fn builder() -> crate::input::head_bucket_input::Builder {
+        crate::input::head_bucket_input::Builder::default()
+    }
+}
+
 const fn missing_field(field: &'static str) -> BuildError {
     BuildError::MissingField { field, details: "" }
 }
@@ -115,16 +115,21 @@ impl CompletedPart {
 #[derive(Clone, PartialEq, Eq)]
 pub struct Delete {
     pub objects: Option<Vec<crate::model::ObjectIdentifier>>,
+    pub quiet: bool,
 }
 impl Delete {
     pub fn objects(&self) -> Option<&[crate::model::ObjectIdentifier]> {
         self.objects.as_deref()
     }
+    pub fn quiet(&self) -> bool {
+        self.quiet
+    }
 }
 impl Debug for Delete {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         let mut formatter = f.debug_struct("Delete");
         formatter.field("objects", &self.objects);
+        formatter.field("quiet", &self.quiet);
         formatter.finish()
     }
 }
@@ -133,6 +138,7 @@ pub mod delete {
     #[derive(Default, Clone, PartialEq, Debug, Eq)]
     pub struct Builder {
         pub(crate) objects: Option<Vec<crate::model::ObjectIdentifier>>,
+        pub(crate) quiet: Option<bool>,
     }
     impl Builder {
         pub fn objects(mut self, input: crate::model::ObjectIdentifier) -> Self {
@@ -146,9 +152,20 @@ pub mod delete {
             self.objects = input;
             self
         }
+
+        pub fn quiet(mut self, input: bool) -> Self {
+            self.quiet = Some(input);
+            self
+        }
+
+        pub fn set_quiet(mut self, input: Option<bool>) -> Self {
+            self.quiet = input;
+            self
+        }
         pub fn build(self) -> crate::model::Delete {
             crate::model::Delete {
                 objects: self.objects,
+                quiet: self.quiet.unwrap_or_default(),
             }
         }
     }
@@ -1387,3 +1404,1239 @@ impl BucketLifecycleConfiguration {
         crate::model::bucket_lifecycle_configuration::Builder::default()
     }
 }
+
+#[allow(missing_docs)]
+#[non_exhaustive]
+#[derive(Clone, Eq, Ord, PartialEq, PartialOrd, Debug, Hash)]
+pub enum BucketLocationConstraint {
+    #[allow(missing_docs)]
+    Eu,
+    #[allow(missing_docs)]
+    AfSouth1,
+    #[allow(missing_docs)]
+    ApEast1,
+    #[allow(missing_docs)]
+    ApNortheast1,
+    #[allow(missing_docs)]
+    ApNortheast2,
+    #[allow(missing_docs)]
+    ApNortheast3,
+    #[allow(missing_docs)]
+    ApSouth1,
+    #[allow(missing_docs)]
+    ApSoutheast1,
+    #[allow(missing_docs)]
+    ApSoutheast2,
+    #[allow(missing_docs)]
+    CaCentral1,
+    #[allow(missing_docs)]
+    CnNorth1,
+    #[allow(missing_docs)]
+    CnNorthwest1,
+    #[allow(missing_docs)]
+    EuCentral1,
+    #[allow(missing_docs)]
+    EuNorth1,
+    #[allow(missing_docs)]
+    EuSouth1,
+    #[allow(missing_docs)]
+    EuWest1,
+    #[allow(missing_docs)]
+    EuWest2,
+    #[allow(missing_docs)]
+    EuWest3,
+    #[allow(missing_docs)]
+    MeSouth1,
+    #[allow(missing_docs)]
+    SaEast1,
+    #[allow(missing_docs)]
+    UsEast2,
+    #[allow(missing_docs)]
+    UsGovEast1,
+    #[allow(missing_docs)]
+    UsGovWest1,
+    #[allow(missing_docs)]
+    UsWest1,
+    #[allow(missing_docs)]
+    UsWest2,
+    Unknown(String),
+}
+impl From<&str> for BucketLocationConstraint {
+    fn from(s: &str) -> Self {
+        match s {
+            "EU" => BucketLocationConstraint::Eu,
+            "af-south-1" => BucketLocationConstraint::AfSouth1,
+            "ap-east-1" => BucketLocationConstraint::ApEast1,
+            "ap-northeast-1" => BucketLocationConstraint::ApNortheast1,
+            "ap-northeast-2" => BucketLocationConstraint::ApNortheast2,
+            "ap-northeast-3" => BucketLocationConstraint::ApNortheast3,
+            "ap-south-1" => BucketLocationConstraint::ApSouth1,
+            "ap-southeast-1" => BucketLocationConstraint::ApSoutheast1,
+            "ap-southeast-2" => BucketLocationConstraint::ApSoutheast2,
+            "ca-central-1" => BucketLocationConstraint::CaCentral1,
+            "cn-north-1" => BucketLocationConstraint::CnNorth1,
+            "cn-northwest-1" => BucketLocationConstraint::CnNorthwest1,
+            "eu-central-1" => BucketLocationConstraint::EuCentral1,
+            "eu-north-1" => BucketLocationConstraint::EuNorth1,
+            "eu-south-1" => BucketLocationConstraint::EuSouth1,
+            "eu-west-1" => BucketLocationConstraint::EuWest1,
+            "eu-west-2" => BucketLocationConstraint::EuWest2,
+            "eu-west-3" => BucketLocationConstraint::EuWest3,
+            "me-south-1" => BucketLocationConstraint::MeSouth1,
+            "sa-east-1" => BucketLocationConstraint::SaEast1,
+            "us-east-2" => BucketLocationConstraint::UsEast2,
+            "us-gov-east-1" => BucketLocationConstraint::UsGovEast1,
+            "us-gov-west-1" => BucketLocationConstraint::UsGovWest1,
+            "us-west-1" => BucketLocationConstraint::UsWest1,
+            "us-west-2" => BucketLocationConstraint::UsWest2,
+            other => BucketLocationConstraint::Unknown(other.to_owned()),
+        }
+    }
+}
+impl FromStr for BucketLocationConstraint {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(BucketLocationConstraint::from(s))
+    }
+}
+impl BucketLocationConstraint {
+    pub fn as_str(&self) -> &str {
+        match self {
+            BucketLocationConstraint::Eu => "EU",
+            BucketLocationConstraint::AfSouth1 => "af-south-1",
+            BucketLocationConstraint::ApEast1 => "ap-east-1",
+            BucketLocationConstraint::ApNortheast1 => "ap-northeast-1",
+            BucketLocationConstraint::ApNortheast2 => "ap-northeast-2",
+            BucketLocationConstraint::ApNortheast3 => "ap-northeast-3",
+            BucketLocationConstraint::ApSouth1 => "ap-south-1",
+            BucketLocationConstraint::ApSoutheast1 => "ap-southeast-1",
+            BucketLocationConstraint::ApSoutheast2 => "ap-southeast-2",
+            BucketLocationConstraint::CaCentral1 => "ca-central-1",
+            BucketLocationConstraint::CnNorth1 => "cn-north-1",
+            BucketLocationConstraint::CnNorthwest1 => "cn-northwest-1",
+            BucketLocationConstraint::EuCentral1 => "eu-central-1",
+            BucketLocationConstraint::EuNorth1 => "eu-north-1",
+            BucketLocationConstraint::EuSouth1 => "eu-south-1",
+            BucketLocationConstraint::EuWest1 => "eu-west-1",
+            BucketLocationConstraint::EuWest2 => "eu-west-2",
+            BucketLocationConstraint::EuWest3 => "eu-west-3",
+            BucketLocationConstraint::MeSouth1 => "me-south-1",
+            BucketLocationConstraint::SaEast1 => "sa-east-1",
+            BucketLocationConstraint::UsEast2 => "us-east-2",
+            BucketLocationConstraint::UsGovEast1 => "us-gov-east-1",
+            BucketLocationConstraint::UsGovWest1 => "us-gov-west-1",
+            BucketLocationConstraint::UsWest1 => "us-west-1",
+            BucketLocationConstraint::UsWest2 => "us-west-2",
+            BucketLocationConstraint::Unknown(s) => s.as_ref(),
+        }
+    }
+    pub fn values() -> &'static [&'static str] {
+        &[
+            "EU",
+            "af-south-1",
+            "ap-east-1",
+            "ap-northeast-1",
+            "ap-northeast-2",
+            "ap-northeast-3",
+            "ap-south-1",
+            "ap-southeast-1",
+            "ap-southeast-2",
+            "ca-central-1",
+            "cn-north-1",
+            "cn-northwest-1",
+            "eu-central-1",
+            "eu-north-1",
+            "eu-south-1",
+            "eu-west-1",
+            "eu-west-2",
+            "eu-west-3",
+            "me-south-1",
+            "sa-east-1",
+            "us-east-2",
+            "us-gov-east-1",
+            "us-gov-west-1",
+            "us-west-1",
+            "us-west-2",
+        ]
+    }
+}
+impl AsRef<str> for BucketLocationConstraint {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+#[allow(missing_docs)]
+#[non_exhaustive]
+#[derive(Clone, Eq, Ord, PartialEq, PartialOrd, Debug, Hash)]
+pub enum BucketVersioningStatus {
+    #[allow(missing_docs)]
+    Enabled,
+    #[allow(missing_docs)]
+    Suspended,
+    Unknown(String),
+}
+impl From<&str> for BucketVersioningStatus {
+    fn from(s: &str) -> Self {
+        match s {
+            "Enabled" => BucketVersioningStatus::Enabled,
+            "Suspended" => BucketVersioningStatus::Suspended,
+            other => BucketVersioningStatus::Unknown(other.to_owned()),
+        }
+    }
+}
+impl FromStr for BucketVersioningStatus {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(BucketVersioningStatus::from(s))
+    }
+}
+impl BucketVersioningStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            BucketVersioningStatus::Enabled => "Enabled",
+            BucketVersioningStatus::Suspended => "Suspended",
+            BucketVersioningStatus::Unknown(s) => s.as_ref(),
+        }
+    }
+    pub fn values() -> &'static [&'static str] {
+        &["Enabled", "Suspended"]
+    }
+}
+impl AsRef<str> for BucketVersioningStatus {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+#[allow(missing_docs)]
+#[non_exhaustive]
+#[derive(Clone, Eq, Ord, PartialEq, PartialOrd, Debug, Hash)]
+pub enum MfaDeleteStatus {
+    #[allow(missing_docs)]
+    Disabled,
+    #[allow(missing_docs)]
+    Enabled,
+    Unknown(String),
+}
+impl From<&str> for MfaDeleteStatus {
+    fn from(s: &str) -> Self {
+        match s {
+            "Disabled" => MfaDeleteStatus::Disabled,
+            "Enabled" => MfaDeleteStatus::Enabled,
+            other => MfaDeleteStatus::Unknown(other.to_owned()),
+        }
+    }
+}
+impl FromStr for MfaDeleteStatus {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(MfaDeleteStatus::from(s))
+    }
+}
+impl MfaDeleteStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            MfaDeleteStatus::Disabled => "Disabled",
+            MfaDeleteStatus::Enabled => "Enabled",
+            MfaDeleteStatus::Unknown(s) => s.as_ref(),
+        }
+    }
+    pub fn values() -> &'static [&'static str] {
+        &["Disabled", "Enabled"]
+    }
+}
+impl AsRef<str> for MfaDeleteStatus {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+#[allow(missing_docs)]
+#[non_exhaustive]
+#[derive(Clone, Eq, Ord, PartialEq, PartialOrd, Debug, Hash)]
+pub enum ObjectAttributes {
+    #[allow(missing_docs)]
+    Checksum,
+    #[allow(missing_docs)]
+    Etag,
+    #[allow(missing_docs)]
+    ObjectParts,
+    #[allow(missing_docs)]
+    ObjectSize,
+    #[allow(missing_docs)]
+    StorageClass,
+    Unknown(String),
+}
+impl From<&str> for ObjectAttributes {
+    fn from(s: &str) -> Self {
+        match s {
+            "Checksum" => ObjectAttributes::Checksum,
+            "ETag" => ObjectAttributes::Etag,
+            "ObjectParts" => ObjectAttributes::ObjectParts,
+            "ObjectSize" => ObjectAttributes::ObjectSize,
+            "StorageClass" => ObjectAttributes::StorageClass,
+            other => ObjectAttributes::Unknown(other.to_owned()),
+        }
+    }
+}
+impl FromStr for ObjectAttributes {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(ObjectAttributes::from(s))
+    }
+}
+impl ObjectAttributes {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ObjectAttributes::Checksum => "Checksum",
+            ObjectAttributes::Etag => "ETag",
+            ObjectAttributes::ObjectParts => "ObjectParts",
+            ObjectAttributes::ObjectSize => "ObjectSize",
+            ObjectAttributes::StorageClass => "StorageClass",
+            ObjectAttributes::Unknown(s) => s.as_ref(),
+        }
+    }
+    pub fn values() -> &'static [&'static str] {
+        &[
+            "Checksum",
+            "ETag",
+            "ObjectParts",
+            "ObjectSize",
+            "StorageClass",
+        ]
+    }
+}
+impl AsRef<str> for ObjectAttributes {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
+pub struct Checksum {
+    pub checksum_crc32: Option<String>,
+    pub checksum_crc32_c: Option<String>,
+    pub checksum_sha1: Option<String>,
+    pub checksum_sha256: Option<String>,
+}
+impl Checksum {
+    pub fn checksum_crc32(&self) -> Option<&str> {
+        self.checksum_crc32.as_deref()
+    }
+    pub fn checksum_crc32_c(&self) -> Option<&str> {
+        self.checksum_crc32_c.as_deref()
+    }
+    pub fn checksum_sha1(&self) -> Option<&str> {
+        self.checksum_sha1.as_deref()
+    }
+    pub fn checksum_sha256(&self) -> Option<&str> {
+        self.checksum_sha256.as_deref()
+    }
+}
+impl Debug for Checksum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut formatter = f.debug_struct("Checksum");
+        formatter.field("checksum_crc32", &self.checksum_crc32);
+        formatter.field("checksum_crc32_c", &self.checksum_crc32_c);
+        formatter.field("checksum_sha1", &self.checksum_sha1);
+        formatter.field("checksum_sha256", &self.checksum_sha256);
+        formatter.finish()
+    }
+}
+pub mod checksum {
+
+    #[derive(Default, Clone, PartialEq, Debug, Eq)]
+    pub struct Builder {
+        pub(crate) checksum_crc32: Option<String>,
+        pub(crate) checksum_crc32_c: Option<String>,
+        pub(crate) checksum_sha1: Option<String>,
+        pub(crate) checksum_sha256: Option<String>,
+    }
+    impl Builder {
+        pub fn checksum_crc32(mut self, input: impl Into<String>) -> Self {
+            self.checksum_crc32 = Some(input.into());
+            self
+        }
+        pub fn set_checksum_crc32(mut self, input: Option<String>) -> Self {
+            self.checksum_crc32 = input;
+            self
+        }
+        pub fn checksum_crc32_c(mut self, input: impl Into<String>) -> Self {
+            self.checksum_crc32_c = Some(input.into());
+            self
+        }
+        pub fn set_checksum_crc32_c(mut self, input: Option<String>) -> Self {
+            self.checksum_crc32_c = input;
+            self
+        }
+        pub fn checksum_sha1(mut self, input: impl Into<String>) -> Self {
+            self.checksum_sha1 = Some(input.into());
+            self
+        }
+        pub fn set_checksum_sha1(mut self, input: Option<String>) -> Self {
+            self.checksum_sha1 = input;
+            self
+        }
+        pub fn checksum_sha256(mut self, input: impl Into<String>) -> Self {
+            self.checksum_sha256 = Some(input.into());
+            self
+        }
+        pub fn set_checksum_sha256(mut self, input: Option<String>) -> Self {
+            self.checksum_sha256 = input;
+            self
+        }
+        pub fn build(self) -> crate::model::Checksum {
+            crate::model::Checksum {
+                checksum_crc32: self.checksum_crc32,
+                checksum_crc32_c: self.checksum_crc32_c,
+                checksum_sha1: self.checksum_sha1,
+                checksum_sha256: self.checksum_sha256,
+            }
+        }
+    }
+}
+impl Checksum {
+    pub fn builder() -> crate::model::checksum::Builder {
+        crate::model::checksum::Builder::default()
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
+pub struct GetObjectAttributesParts {
+    pub total_parts_count: i32,
+    pub part_number_marker: Option<String>,
+    pub next_part_number_marker: Option<String>,
+    pub max_parts: i32,
+    pub is_truncated: bool,
+    pub parts: Option<Vec<crate::model::ObjectPart>>,
+}
+impl GetObjectAttributesParts {
+    pub fn total_parts_count(&self) -> i32 {
+        self.total_parts_count
+    }
+    pub fn part_number_marker(&self) -> Option<&str> {
+        self.part_number_marker.as_deref()
+    }
+    pub fn next_part_number_marker(&self) -> Option<&str> {
+        self.next_part_number_marker.as_deref()
+    }
+    pub fn max_parts(&self) -> i32 {
+        self.max_parts
+    }
+    pub fn is_truncated(&self) -> bool {
+        self.is_truncated
+    }
+    pub fn parts(&self) -> Option<&[crate::model::ObjectPart]> {
+        self.parts.as_deref()
+    }
+}
+impl Debug for GetObjectAttributesParts {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut formatter = f.debug_struct("GetObjectAttributesParts");
+        formatter.field("total_parts_count", &self.total_parts_count);
+        formatter.field("part_number_marker", &self.part_number_marker);
+        formatter.field("next_part_number_marker", &self.next_part_number_marker);
+        formatter.field("max_parts", &self.max_parts);
+        formatter.field("is_truncated", &self.is_truncated);
+        formatter.field("parts", &self.parts);
+        formatter.finish()
+    }
+}
+pub mod get_object_attributes_parts {
+
+    #[derive(Default, Clone, PartialEq, Debug, Eq)]
+    pub struct Builder {
+        pub(crate) total_parts_count: Option<i32>,
+        pub(crate) part_number_marker: Option<String>,
+        pub(crate) next_part_number_marker: Option<String>,
+        pub(crate) max_parts: Option<i32>,
+        pub(crate) is_truncated: Option<bool>,
+        pub(crate) parts: Option<Vec<crate::model::ObjectPart>>,
+    }
+    impl Builder {
+        pub fn total_parts_count(mut self, input: i32) -> Self {
+            self.total_parts_count = Some(input);
+            self
+        }
+        pub fn set_total_parts_count(mut self, input: Option<i32>) -> Self {
+            self.total_parts_count = input;
+            self
+        }
+        pub fn part_number_marker(mut self, input: impl Into<String>) -> Self {
+            self.part_number_marker = Some(input.into());
+            self
+        }
+        pub fn set_part_number_marker(mut self, input: Option<String>) -> Self {
+            self.part_number_marker = input;
+            self
+        }
+        pub fn next_part_number_marker(mut self, input: impl Into<String>) -> Self {
+            self.next_part_number_marker = Some(input.into());
+            self
+        }
+        pub fn set_next_part_number_marker(mut self, input: Option<String>) -> Self {
+            self.next_part_number_marker = input;
+            self
+        }
+        pub fn max_parts(mut self, input: i32) -> Self {
+            self.max_parts = Some(input);
+            self
+        }
+        pub fn set_max_parts(mut self, input: Option<i32>) -> Self {
+            self.max_parts = input;
+            self
+        }
+        pub fn is_truncated(mut self, input: bool) -> Self {
+            self.is_truncated = Some(input);
+            self
+        }
+        pub fn set_is_truncated(mut self, input: Option<bool>) -> Self {
+            self.is_truncated = input;
+            self
+        }
+        pub fn parts(mut self, input: crate::model::ObjectPart) -> Self {
+            let mut v = self.parts.unwrap_or_default();
+            v.push(input);
+            self.parts = Some(v);
+            self
+        }
+        pub fn set_parts(mut self, input: Option<Vec<crate::model::ObjectPart>>) -> Self {
+            self.parts = input;
+            self
+        }
+        pub fn build(self) -> crate::model::GetObjectAttributesParts {
+            crate::model::GetObjectAttributesParts {
+                total_parts_count: self.total_parts_count.unwrap_or_default(),
+                part_number_marker: self.part_number_marker,
+                next_part_number_marker: self.next_part_number_marker,
+                max_parts: self.max_parts.unwrap_or_default(),
+                is_truncated: self.is_truncated.unwrap_or_default(),
+                parts: self.parts,
+            }
+        }
+    }
+}
+impl GetObjectAttributesParts {
+    pub fn builder() -> crate::model::get_object_attributes_parts::Builder {
+        crate::model::get_object_attributes_parts::Builder::default()
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
+pub struct ObjectPart {
+    pub part_number: i32,
+    pub size: i64,
+    pub checksum_crc32: Option<String>,
+    pub checksum_crc32_c: Option<String>,
+    pub checksum_sha1: Option<String>,
+    pub checksum_sha256: Option<String>,
+}
+impl ObjectPart {
+    pub fn part_number(&self) -> i32 {
+        self.part_number
+    }
+    pub fn size(&self) -> i64 {
+        self.size
+    }
+    pub fn checksum_crc32(&self) -> Option<&str> {
+        self.checksum_crc32.as_deref()
+    }
+    pub fn checksum_crc32_c(&self) -> Option<&str> {
+        self.checksum_crc32_c.as_deref()
+    }
+    pub fn checksum_sha1(&self) -> Option<&str> {
+        self.checksum_sha1.as_deref()
+    }
+    pub fn checksum_sha256(&self) -> Option<&str> {
+        self.checksum_sha256.as_deref()
+    }
+}
+impl Debug for ObjectPart {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut formatter = f.debug_struct("ObjectPart");
+        formatter.field("part_number", &self.part_number);
+        formatter.field("size", &self.size);
+        formatter.field("checksum_crc32", &self.checksum_crc32);
+        formatter.field("checksum_crc32_c", &self.checksum_crc32_c);
+        formatter.field("checksum_sha1", &self.checksum_sha1);
+        formatter.field("checksum_sha256", &self.checksum_sha256);
+        formatter.finish()
+    }
+}
+pub mod object_part {
+
+    #[derive(Default, Clone, PartialEq, Debug, Eq)]
+    pub struct Builder {
+        pub(crate) part_number: Option<i32>,
+        pub(crate) size: Option<i64>,
+        pub(crate) checksum_crc32: Option<String>,
+        pub(crate) checksum_crc32_c: Option<String>,
+        pub(crate) checksum_sha1: Option<String>,
+        pub(crate) checksum_sha256: Option<String>,
+    }
+    impl Builder {
+        pub fn part_number(mut self, input: i32) -> Self {
+            self.part_number = Some(input);
+            self
+        }
+        pub fn set_part_number(mut self, input: Option<i32>) -> Self {
+            self.part_number = input;
+            self
+        }
+        pub fn size(mut self, input: i64) -> Self {
+            self.size = Some(input);
+            self
+        }
+        pub fn set_size(mut self, input: Option<i64>) -> Self {
+            self.size = input;
+            self
+        }
+        pub fn checksum_crc32(mut self, input: impl Into<String>) -> Self {
+            self.checksum_crc32 = Some(input.into());
+            self
+        }
+        pub fn set_checksum_crc32(mut self, input: Option<String>) -> Self {
+            self.checksum_crc32 = input;
+            self
+        }
+        pub fn checksum_crc32_c(mut self, input: impl Into<String>) -> Self {
+            self.checksum_crc32_c = Some(input.into());
+            self
+        }
+        pub fn set_checksum_crc32_c(mut self, input: Option<String>) -> Self {
+            self.checksum_crc32_c = input;
+            self
+        }
+        pub fn checksum_sha1(mut self, input: impl Into<String>) -> Self {
+            self.checksum_sha1 = Some(input.into());
+            self
+        }
+        pub fn set_checksum_sha1(mut self, input: Option<String>) -> Self {
+            self.checksum_sha1 = input;
+            self
+        }
+        pub fn checksum_sha256(mut self, input: impl Into<String>) -> Self {
+            self.checksum_sha256 = Some(input.into());
+            self
+        }
+        pub fn set_checksum_sha256(mut self, input: Option<String>) -> Self {
+            self.checksum_sha256 = input;
+            self
+        }
+        pub fn build(self) -> crate::model::ObjectPart {
+            crate::model::ObjectPart {
+                part_number: self.part_number.unwrap_or_default(),
+                size: self.size.unwrap_or_default(),
+                checksum_crc32: self.checksum_crc32,
+                checksum_crc32_c: self.checksum_crc32_c,
+                checksum_sha1: self.checksum_sha1,
+                checksum_sha256: self.checksum_sha256,
+            }
+        }
+    }
+}
+impl ObjectPart {
+    pub fn builder() -> crate::model::object_part::Builder {
+        crate::model::object_part::Builder::default()
+    }
+}
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
+pub struct ObjectLockConfiguration {
+    pub object_lock_enabled: Option<crate::model::ObjectLockEnabled>,
+    pub rule: Option<crate::model::ObjectLockRule>,
+}
+impl ObjectLockConfiguration {
+    pub fn object_lock_enabled(&self) -> Option<&crate::model::ObjectLockEnabled> {
+        self.object_lock_enabled.as_ref()
+    }
+    pub fn rule(&self) -> Option<&crate::model::ObjectLockRule> {
+        self.rule.as_ref()
+    }
+}
+impl Debug for ObjectLockConfiguration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut formatter = f.debug_struct("ObjectLockConfiguration");
+        formatter.field("object_lock_enabled", &self.object_lock_enabled);
+        formatter.field("rule", &self.rule);
+        formatter.finish()
+    }
+}
+pub mod object_lock_configuration {
+
+    #[derive(Default, Clone, PartialEq, Eq, Debug)]
+    pub struct Builder {
+        pub(crate) object_lock_enabled: Option<crate::model::ObjectLockEnabled>,
+        pub(crate) rule: Option<crate::model::ObjectLockRule>,
+    }
+    impl Builder {
+        pub fn object_lock_enabled(mut self, input: crate::model::ObjectLockEnabled) -> Self {
+            self.object_lock_enabled = Some(input);
+            self
+        }
+        pub fn set_object_lock_enabled(
+            mut self,
+            input: Option<crate::model::ObjectLockEnabled>,
+        ) -> Self {
+            self.object_lock_enabled = input;
+            self
+        }
+        pub fn rule(mut self, input: crate::model::ObjectLockRule) -> Self {
+            self.rule = Some(input);
+            self
+        }
+        pub fn set_rule(mut self, input: Option<crate::model::ObjectLockRule>) -> Self {
+            self.rule = input;
+            self
+        }
+        pub fn build(self) -> crate::model::ObjectLockConfiguration {
+            crate::model::ObjectLockConfiguration {
+                object_lock_enabled: self.object_lock_enabled,
+                rule: self.rule,
+            }
+        }
+    }
+}
+impl ObjectLockConfiguration {
+    pub fn builder() -> crate::model::object_lock_configuration::Builder {
+        crate::model::object_lock_configuration::Builder::default()
+    }
+}
+
+#[allow(missing_docs)]
+#[non_exhaustive]
+#[derive(Clone, Eq, Ord, PartialEq, PartialOrd, Debug, Hash)]
+pub enum ObjectLockEnabled {
+    #[allow(missing_docs)]
+    Enabled,
+    Unknown(String),
+}
+impl From<&str> for ObjectLockEnabled {
+    fn from(s: &str) -> Self {
+        match s {
+            "Enabled" => ObjectLockEnabled::Enabled,
+            other => ObjectLockEnabled::Unknown(other.to_owned()),
+        }
+    }
+}
+impl FromStr for ObjectLockEnabled {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(ObjectLockEnabled::from(s))
+    }
+}
+impl ObjectLockEnabled {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ObjectLockEnabled::Enabled => "Enabled",
+            ObjectLockEnabled::Unknown(s) => s.as_ref(),
+        }
+    }
+    pub fn values() -> &'static [&'static str] {
+        &["Enabled"]
+    }
+}
+impl AsRef<str> for ObjectLockEnabled {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
+pub struct ObjectLockRule {
+    pub default_retention: Option<crate::model::DefaultRetention>,
+}
+impl ObjectLockRule {
+    pub fn default_retention(&self) -> Option<&crate::model::DefaultRetention> {
+        self.default_retention.as_ref()
+    }
+}
+impl Debug for ObjectLockRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut formatter = f.debug_struct("ObjectLockRule");
+        formatter.field("default_retention", &self.default_retention);
+        formatter.finish()
+    }
+}
+pub mod object_lock_rule {
+
+    #[derive(Default, Clone, PartialEq, Eq, Debug)]
+    pub struct Builder {
+        pub(crate) default_retention: Option<crate::model::DefaultRetention>,
+    }
+    impl Builder {
+        pub fn default_retention(mut self, input: crate::model::DefaultRetention) -> Self {
+            self.default_retention = Some(input);
+            self
+        }
+        pub fn set_default_retention(
+            mut self,
+            input: Option<crate::model::DefaultRetention>,
+        ) -> Self {
+            self.default_retention = input;
+            self
+        }
+        pub fn build(self) -> crate::model::ObjectLockRule {
+            crate::model::ObjectLockRule {
+                default_retention: self.default_retention,
+            }
+        }
+    }
+}
+impl ObjectLockRule {
+    pub fn builder() -> crate::model::object_lock_rule::Builder {
+        crate::model::object_lock_rule::Builder::default()
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
+pub struct DefaultRetention {
+    pub mode: Option<crate::model::ObjectLockRetentionMode>,
+    pub days: i32,
+    pub years: i32,
+}
+impl DefaultRetention {
+    pub fn mode(&self) -> Option<&crate::model::ObjectLockRetentionMode> {
+        self.mode.as_ref()
+    }
+    pub fn days(&self) -> i32 {
+        self.days
+    }
+    pub fn years(&self) -> i32 {
+        self.years
+    }
+}
+impl Debug for DefaultRetention {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut formatter = f.debug_struct("DefaultRetention");
+        formatter.field("mode", &self.mode);
+        formatter.field("days", &self.days);
+        formatter.field("years", &self.years);
+        formatter.finish()
+    }
+}
+pub mod default_retention {
+
+    #[derive(Default, Clone, PartialEq, Eq, Debug)]
+    pub struct Builder {
+        pub(crate) mode: Option<crate::model::ObjectLockRetentionMode>,
+        pub(crate) days: Option<i32>,
+        pub(crate) years: Option<i32>,
+    }
+    impl Builder {
+        pub fn mode(mut self, input: crate::model::ObjectLockRetentionMode) -> Self {
+            self.mode = Some(input);
+            self
+        }
+        pub fn set_mode(mut self, input: Option<crate::model::ObjectLockRetentionMode>) -> Self {
+            self.mode = input;
+            self
+        }
+        pub fn days(mut self, input: i32) -> Self {
+            self.days = Some(input);
+            self
+        }
+        pub fn set_days(mut self, input: Option<i32>) -> Self {
+            self.days = input;
+            self
+        }
+        pub fn years(mut self, input: i32) -> Self {
+            self.years = Some(input);
+            self
+        }
+        pub fn set_years(mut self, input: Option<i32>) -> Self {
+            self.years = input;
+            self
+        }
+        pub fn build(self) -> crate::model::DefaultRetention {
+            crate::model::DefaultRetention {
+                mode: self.mode,
+                days: self.days.unwrap_or_default(),
+                years: self.years.unwrap_or_default(),
+            }
+        }
+    }
+}
+impl DefaultRetention {
+    pub fn builder() -> crate::model::default_retention::Builder {
+        crate::model::default_retention::Builder::default()
+    }
+}
+
+#[allow(missing_docs)]
+#[non_exhaustive]
+#[derive(Clone, Eq, Ord, PartialEq, PartialOrd, Debug, Hash)]
+pub enum ObjectLockRetentionMode {
+    #[allow(missing_docs)]
+    Compliance,
+    #[allow(missing_docs)]
+    Governance,
+    Unknown(String),
+}
+impl From<&str> for ObjectLockRetentionMode {
+    fn from(s: &str) -> Self {
+        match s {
+            "COMPLIANCE" => ObjectLockRetentionMode::Compliance,
+            "GOVERNANCE" => ObjectLockRetentionMode::Governance,
+            other => ObjectLockRetentionMode::Unknown(other.to_owned()),
+        }
+    }
+}
+impl FromStr for ObjectLockRetentionMode {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(ObjectLockRetentionMode::from(s))
+    }
+}
+impl ObjectLockRetentionMode {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ObjectLockRetentionMode::Compliance => "COMPLIANCE",
+            ObjectLockRetentionMode::Governance => "GOVERNANCE",
+            ObjectLockRetentionMode::Unknown(s) => s.as_ref(),
+        }
+    }
+    pub fn values() -> &'static [&'static str] {
+        &["COMPLIANCE", "GOVERNANCE"]
+    }
+}
+impl AsRef<str> for ObjectLockRetentionMode {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, PartialEq)]
+pub struct ObjectLockRetention {
+    pub mode: Option<crate::model::ObjectLockRetentionMode>,
+    pub retain_until_date: Option<aws_smithy_types::DateTime>,
+}
+impl ObjectLockRetention {
+    pub fn mode(&self) -> Option<&crate::model::ObjectLockRetentionMode> {
+        self.mode.as_ref()
+    }
+    pub fn retain_until_date(&self) -> Option<&aws_smithy_types::DateTime> {
+        self.retain_until_date.as_ref()
+    }
+}
+impl Debug for ObjectLockRetention {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut formatter = f.debug_struct("ObjectLockRetention");
+        formatter.field("mode", &self.mode);
+        formatter.field("retain_until_date", &self.retain_until_date);
+        formatter.finish()
+    }
+}
+pub mod object_lock_retention {
+
+    #[derive(Default, Clone, PartialEq, Debug)]
+    pub struct Builder {
+        pub(crate) mode: Option<crate::model::ObjectLockRetentionMode>,
+        pub(crate) retain_until_date: Option<aws_smithy_types::DateTime>,
+    }
+    impl Builder {
+        pub fn mode(mut self, input: crate::model::ObjectLockRetentionMode) -> Self {
+            self.mode = Some(input);
+            self
+        }
+        pub fn set_mode(mut self, input: Option<crate::model::ObjectLockRetentionMode>) -> Self {
+            self.mode = input;
+            self
+        }
+        pub fn retain_until_date(mut self, input: aws_smithy_types::DateTime) -> Self {
+            self.retain_until_date = Some(input);
+            self
+        }
+        pub fn set_retain_until_date(mut self, input: Option<aws_smithy_types::DateTime>) -> Self {
+            self.retain_until_date = input;
+            self
+        }
+        pub fn build(self) -> crate::model::ObjectLockRetention {
+            crate::model::ObjectLockRetention {
+                mode: self.mode,
+                retain_until_date: self.retain_until_date,
+            }
+        }
+    }
+}
+impl ObjectLockRetention {
+    pub fn builder() -> crate::model::object_lock_retention::Builder {
+        crate::model::object_lock_retention::Builder::default()
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
+pub struct ObjectLockLegalHold {
+    pub status: Option<crate::model::ObjectLockLegalHoldStatus>,
+}
+impl ObjectLockLegalHold {
+    pub fn status(&self) -> Option<&crate::model::ObjectLockLegalHoldStatus> {
+        self.status.as_ref()
+    }
+}
+impl Debug for ObjectLockLegalHold {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut formatter = f.debug_struct("ObjectLockLegalHold");
+        formatter.field("status", &self.status);
+        formatter.finish()
+    }
+}
+pub mod object_lock_legal_hold {
+
+    #[derive(Default, Clone, PartialEq, Eq, Debug)]
+    pub struct Builder {
+        pub(crate) status: Option<crate::model::ObjectLockLegalHoldStatus>,
+    }
+    impl Builder {
+        pub fn status(mut self, input: crate::model::ObjectLockLegalHoldStatus) -> Self {
+            self.status = Some(input);
+            self
+        }
+        pub fn set_status(
+            mut self,
+            input: Option<crate::model::ObjectLockLegalHoldStatus>,
+        ) -> Self {
+            self.status = input;
+            self
+        }
+        pub fn build(self) -> crate::model::ObjectLockLegalHold {
+            crate::model::ObjectLockLegalHold {
+                status: self.status,
+            }
+        }
+    }
+}
+impl ObjectLockLegalHold {
+    pub fn builder() -> crate::model::object_lock_legal_hold::Builder {
+        crate::model::object_lock_legal_hold::Builder::default()
+    }
+}
+
+#[allow(missing_docs)]
+#[non_exhaustive]
+#[derive(Clone, Eq, Ord, PartialEq, PartialOrd, Debug, Hash)]
+pub enum ObjectLockLegalHoldStatus {
+    #[allow(missing_docs)]
+    Off,
+    #[allow(missing_docs)]
+    On,
+    Unknown(String),
+}
+impl From<&str> for ObjectLockLegalHoldStatus {
+    fn from(s: &str) -> Self {
+        match s {
+            "OFF" => ObjectLockLegalHoldStatus::Off,
+            "ON" => ObjectLockLegalHoldStatus::On,
+            other => ObjectLockLegalHoldStatus::Unknown(other.to_owned()),
+        }
+    }
+}
+impl FromStr for ObjectLockLegalHoldStatus {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(ObjectLockLegalHoldStatus::from(s))
+    }
+}
+impl ObjectLockLegalHoldStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ObjectLockLegalHoldStatus::Off => "OFF",
+            ObjectLockLegalHoldStatus::On => "ON",
+            ObjectLockLegalHoldStatus::Unknown(s) => s.as_ref(),
+        }
+    }
+    pub fn values() -> &'static [&'static str] {
+        &["OFF", "ON"]
+    }
+}
+impl AsRef<str> for ObjectLockLegalHoldStatus {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+#[allow(missing_docs)]
+#[non_exhaustive]
+#[derive(Clone, Eq, Ord, PartialEq, PartialOrd, Debug, Hash)]
+pub enum StorageClass {
+    #[allow(missing_docs)]
+    Standard,
+    #[allow(missing_docs)]
+    ReducedRedundancy,
+    #[allow(missing_docs)]
+    StandardIa,
+    #[allow(missing_docs)]
+    OnezoneIa,
+    #[allow(missing_docs)]
+    IntelligentTiering,
+    #[allow(missing_docs)]
+    Glacier,
+    #[allow(missing_docs)]
+    DeepArchive,
+    #[allow(missing_docs)]
+    Outposts,
+    #[allow(missing_docs)]
+    GlacierIr,
+    Unknown(String),
+}
+impl From<&str> for StorageClass {
+    fn from(s: &str) -> Self {
+        match s {
+            "STANDARD" => StorageClass::Standard,
+            "REDUCED_REDUNDANCY" => StorageClass::ReducedRedundancy,
+            "STANDARD_IA" => StorageClass::StandardIa,
+            "ONEZONE_IA" => StorageClass::OnezoneIa,
+            "INTELLIGENT_TIERING" => StorageClass::IntelligentTiering,
+            "GLACIER" => StorageClass::Glacier,
+            "DEEP_ARCHIVE" => StorageClass::DeepArchive,
+            "OUTPOSTS" => StorageClass::Outposts,
+            "GLACIER_IR" => StorageClass::GlacierIr,
+            other => StorageClass::Unknown(other.to_owned()),
+        }
+    }
+}
+impl FromStr for StorageClass {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(StorageClass::from(s))
+    }
+}
+impl StorageClass {
+    pub fn as_str(&self) -> &str {
+        match self {
+            StorageClass::Standard => "STANDARD",
+            StorageClass::ReducedRedundancy => "REDUCED_REDUNDANCY",
+            StorageClass::StandardIa => "STANDARD_IA",
+            StorageClass::OnezoneIa => "ONEZONE_IA",
+            StorageClass::IntelligentTiering => "INTELLIGENT_TIERING",
+            StorageClass::Glacier => "GLACIER",
+            StorageClass::DeepArchive => "DEEP_ARCHIVE",
+            StorageClass::Outposts => "OUTPOSTS",
+            StorageClass::GlacierIr => "GLACIER_IR",
+            StorageClass::Unknown(s) => s.as_ref(),
+        }
+    }
+    pub fn values() -> &'static [&'static str] {
+        &[
+            "STANDARD",
+            "REDUCED_REDUNDANCY",
+            "STANDARD_IA",
+            "ONEZONE_IA",
+            "INTELLIGENT_TIERING",
+            "GLACIER",
+            "DEEP_ARCHIVE",
+            "OUTPOSTS",
+            "GLACIER_IR",
+        ]
+    }
+}
+impl AsRef<str> for StorageClass {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
+pub struct RestoreRequest {
+    pub days: i32,
+}
+impl RestoreRequest {
+    pub fn days(&self) -> i32 {
+        self.days
+    }
+}
+impl Debug for RestoreRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut formatter = f.debug_struct("RestoreRequest");
+        formatter.field("days", &self.days);
+        formatter.finish()
+    }
+}
+pub mod restore_request {
+
+    #[derive(Default, Clone, PartialEq, Eq, Debug)]
+    pub struct Builder {
+        pub(crate) days: Option<i32>,
+    }
+    impl Builder {
+        pub fn days(mut self, input: i32) -> Self {
+            self.days = Some(input);
+            self
+        }
+        pub fn set_days(mut self, input: Option<i32>) -> Self {
+            self.days = input;
+            self
+        }
+        pub fn build(self) -> crate::model::RestoreRequest {
+            crate::model::RestoreRequest {
+                days: self.days.unwrap_or_default(),
+            }
+        }
+    }
+}
+impl RestoreRequest {
+    pub fn builder() -> crate::model::restore_request::Builder {
+        crate::model::restore_request::Builder::default()
+    }
+}
+
+#[allow(missing_docs)]
+#[non_exhaustive]
+#[derive(Clone, Eq, Ord, PartialEq, PartialOrd, Debug, Hash)]
+pub enum ServerSideEncryption {
+    #[allow(missing_docs)]
+    Aes256,
+    #[allow(missing_docs)]
+    AwsKms,
+    Unknown(String),
+}
+impl From<&str> for ServerSideEncryption {
+    fn from(s: &str) -> Self {
+        match s {
+            "AES256" => ServerSideEncryption::Aes256,
+            "aws:kms" => ServerSideEncryption::AwsKms,
+            other => ServerSideEncryption::Unknown(other.to_owned()),
+        }
+    }
+}
+impl FromStr for ServerSideEncryption {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(ServerSideEncryption::from(s))
+    }
+}
+impl ServerSideEncryption {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ServerSideEncryption::Aes256 => "AES256",
+            ServerSideEncryption::AwsKms => "aws:kms",
+            ServerSideEncryption::Unknown(s) => s.as_ref(),
+        }
+    }
+    pub fn values() -> &'static [&'static str] {
+        &["AES256", "aws:kms"]
+    }
+}
+impl AsRef<str> for ServerSideEncryption {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
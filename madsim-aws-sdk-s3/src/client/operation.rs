@@ -153,3 +153,120 @@ impl PutBucketLifecycleConfiguration {
         Self { _private: () }
     }
 }
+
+#[derive(Default, Clone, Debug)]
+pub struct GetBucketLocation {
+    _private: (),
+}
+impl GetBucketLocation {
+    pub fn builder() -> crate::input::get_bucket_location_input::Builder {
+        crate::input::get_bucket_location_input::Builder::default()
+    }
+    pub fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct GetBucketVersioning {
+    _private: (),
+}
+impl GetBucketVersioning {
+    pub fn builder() -> crate::input::get_bucket_versioning_input::Builder {
+        crate::input::get_bucket_versioning_input::Builder::default()
+    }
+    pub fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct GetObjectAttributes {
+    _private: (),
+}
+impl GetObjectAttributes {
+    pub fn builder() -> crate::input::get_object_attributes_input::Builder {
+        crate::input::get_object_attributes_input::Builder::default()
+    }
+    pub fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct PutObjectLockConfiguration {
+    _private: (),
+}
+impl PutObjectLockConfiguration {
+    pub fn builder() -> crate::input::put_object_lock_configuration_input::Builder {
+        crate::input::put_object_lock_configuration_input::Builder::default()
+    }
+    pub fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct GetObjectLockConfiguration {
+    _private: (),
+}
+impl GetObjectLockConfiguration {
+    pub fn builder() -> crate::input::get_object_lock_configuration_input::Builder {
+        crate::input::get_object_lock_configuration_input::Builder::default()
+    }
+    pub fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct PutObjectRetention {
+    _private: (),
+}
+impl PutObjectRetention {
+    pub fn builder() -> crate::input::put_object_retention_input::Builder {
+        crate::input::put_object_retention_input::Builder::default()
+    }
+    pub fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct PutObjectLegalHold {
+    _private: (),
+}
+impl PutObjectLegalHold {
+    pub fn builder() -> crate::input::put_object_legal_hold_input::Builder {
+        crate::input::put_object_legal_hold_input::Builder::default()
+    }
+    pub fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct RestoreObject {
+    _private: (),
+}
+impl RestoreObject {
+    pub fn builder() -> crate::input::restore_object_input::Builder {
+        crate::input::restore_object_input::Builder::default()
+    }
+    pub fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct HeadBucket {
+    _private: (),
+}
+impl HeadBucket {
+    pub fn builder() -> crate::input::head_bucket_input::Builder {
+        crate::input::head_bucket_input::Builder::default()
+    }
+    pub fn new() -> Self {
+        Self { _private: () }
+    }
+}
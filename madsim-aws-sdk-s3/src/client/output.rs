@@ -49,9 +49,17 @@ impl UploadPartOutput {
 #[non_exhaustive]
 #[derive(Clone, PartialEq, Eq)]
 pub struct CreateMultipartUploadOutput {
+    pub(crate) bucket: Option<String>,
+    pub(crate) key: Option<String>,
     pub(crate) upload_id: Option<String>,
 }
 impl CreateMultipartUploadOutput {
+    pub fn bucket(&self) -> Option<&str> {
+        self.bucket.as_deref()
+    }
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
     pub fn upload_id(&self) -> Option<&str> {
         self.upload_id.as_deref()
     }
@@ -59,6 +67,8 @@ impl CreateMultipartUploadOutput {
 impl Debug for CreateMultipartUploadOutput {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         let mut formatter = f.debug_struct("CreateMultipartUploadOutput");
+        formatter.field("bucket", &self.bucket);
+        formatter.field("key", &self.key);
         formatter.field("upload_id", &self.upload_id);
         formatter.finish()
     }
@@ -67,9 +77,31 @@ pub mod create_multipart_output {
 
     #[derive(Default, Clone, PartialEq, Debug, Eq)]
     pub struct Builder {
+        pub(crate) bucket: Option<String>,
+        pub(crate) key: Option<String>,
         pub(crate) upload_id: Option<String>,
     }
     impl Builder {
+        pub fn bucket(mut self, input: impl Into<String>) -> Self {
+            self.bucket = Some(input.into());
+            self
+        }
+
+        pub fn set_bucket(mut self, input: Option<String>) -> Self {
+            self.bucket = input;
+            self
+        }
+
+        pub fn key(mut self, input: impl Into<String>) -> Self {
+            self.key = Some(input.into());
+            self
+        }
+
+        pub fn set_key(mut self, input: Option<String>) -> Self {
+            self.key = input;
+            self
+        }
+
         pub fn upload_id(mut self, input: impl Into<String>) -> Self {
             self.upload_id = Some(input.into());
             self
@@ -82,6 +114,8 @@ pub mod create_multipart_output {
 
         pub fn build(self) -> crate::output::CreateMultipartUploadOutput {
             crate::output::CreateMultipartUploadOutput {
+                bucket: self.bucket,
+                key: self.key,
                 upload_id: self.upload_id,
             }
         }
@@ -96,16 +130,94 @@ impl CreateMultipartUploadOutput {
 #[non_exhaustive]
 pub struct GetObjectOutput {
     pub body: ByteStream,
+    pub(crate) accept_ranges: Option<String>,
+    pub(crate) content_length: i64,
+    pub(crate) content_range: Option<String>,
+    pub(crate) parts_count: i32,
+    pub(crate) cache_control: Option<String>,
+    pub(crate) content_encoding: Option<String>,
+    pub(crate) ssekms_key_id: Option<String>,
+    pub(crate) restore: Option<String>,
+    pub(crate) storage_class: Option<crate::model::StorageClass>,
+    pub(crate) server_side_encryption: Option<crate::model::ServerSideEncryption>,
+    pub(crate) expires: Option<crate::types::DateTime>,
+    pub(crate) last_modified: Option<crate::types::DateTime>,
+    pub(crate) e_tag: Option<String>,
 }
 impl GetObjectOutput {
     pub fn body(&self) -> &ByteStream {
         &self.body
     }
+
+    pub fn accept_ranges(&self) -> Option<&str> {
+        self.accept_ranges.as_deref()
+    }
+
+    pub fn content_length(&self) -> i64 {
+        self.content_length
+    }
+
+    pub fn content_range(&self) -> Option<&str> {
+        self.content_range.as_deref()
+    }
+
+    pub fn parts_count(&self) -> i32 {
+        self.parts_count
+    }
+
+    pub fn cache_control(&self) -> Option<&str> {
+        self.cache_control.as_deref()
+    }
+
+    pub fn content_encoding(&self) -> Option<&str> {
+        self.content_encoding.as_deref()
+    }
+
+    pub fn ssekms_key_id(&self) -> Option<&str> {
+        self.ssekms_key_id.as_deref()
+    }
+
+    pub fn restore(&self) -> Option<&str> {
+        self.restore.as_deref()
+    }
+
+    pub fn storage_class(&self) -> Option<&crate::model::StorageClass> {
+        self.storage_class.as_ref()
+    }
+
+    pub fn server_side_encryption(&self) -> Option<&crate::model::ServerSideEncryption> {
+        self.server_side_encryption.as_ref()
+    }
+
+    pub fn expires(&self) -> Option<&aws_smithy_types::DateTime> {
+        self.expires.as_ref()
+    }
+
+    pub fn last_modified(&self) -> Option<&aws_smithy_types::DateTime> {
+        self.last_modified.as_ref()
+    }
+
+    pub fn e_tag(&self) -> Option<&str> {
+        self.e_tag.as_deref()
+    }
 }
 impl Debug for GetObjectOutput {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         let mut formatter = f.debug_struct("GetObjectOutput");
         formatter.field("body", &self.body);
+        formatter.field("accept_ranges", &self.accept_ranges);
+        formatter.field("content_length", &self.content_length);
+        formatter.field("content_range", &self.content_range);
+        formatter.field("parts_count", &self.parts_count);
+        formatter.field("cache_control", &self.cache_control);
+        formatter.field("content_encoding", &self.content_encoding);
+        formatter.field("ssekms_key_id", &self.ssekms_key_id);
+        formatter.field("restore", &self.restore);
+        formatter.field("storage_class", &self.storage_class);
+        formatter.field("server_side_encryption", &self.server_side_encryption);
+        formatter.field("expires", &self.expires);
+        formatter.field("last_modified", &self.last_modified);
+        formatter.field("e_tag", &self.e_tag);
         formatter.finish()
     }
 }
@@ -114,6 +226,19 @@ pub mod get_object_output {
     #[derive(Default, Debug)]
     pub struct Builder {
         pub(crate) body: Option<crate::types::ByteStream>,
+        pub(crate) accept_ranges: Option<String>,
+        pub(crate) content_length: Option<i64>,
+        pub(crate) content_range: Option<String>,
+        pub(crate) parts_count: Option<i32>,
+        pub(crate) cache_control: Option<String>,
+        pub(crate) content_encoding: Option<String>,
+        pub(crate) ssekms_key_id: Option<String>,
+        pub(crate) restore: Option<String>,
+        pub(crate) storage_class: Option<crate::model::StorageClass>,
+        pub(crate) server_side_encryption: Option<crate::model::ServerSideEncryption>,
+        pub(crate) expires: Option<aws_smithy_types::DateTime>,
+        pub(crate) last_modified: Option<aws_smithy_types::DateTime>,
+        pub(crate) e_tag: Option<String>,
     }
     impl Builder {
         pub fn body(mut self, input: crate::types::ByteStream) -> Self {
@@ -126,9 +251,155 @@ pub mod get_object_output {
             self
         }
 
+        pub fn accept_ranges(mut self, input: impl Into<String>) -> Self {
+            self.accept_ranges = Some(input.into());
+            self
+        }
+
+        pub fn set_accept_ranges(mut self, input: Option<String>) -> Self {
+            self.accept_ranges = input;
+            self
+        }
+
+        pub fn content_length(mut self, input: i64) -> Self {
+            self.content_length = Some(input);
+            self
+        }
+
+        pub fn set_content_length(mut self, input: Option<i64>) -> Self {
+            self.content_length = input;
+            self
+        }
+
+        pub fn content_range(mut self, input: impl Into<String>) -> Self {
+            self.content_range = Some(input.into());
+            self
+        }
+
+        pub fn set_content_range(mut self, input: Option<String>) -> Self {
+            self.content_range = input;
+            self
+        }
+
+        pub fn parts_count(mut self, input: i32) -> Self {
+            self.parts_count = Some(input);
+            self
+        }
+
+        pub fn set_parts_count(mut self, input: Option<i32>) -> Self {
+            self.parts_count = input;
+            self
+        }
+
+        pub fn cache_control(mut self, input: impl Into<String>) -> Self {
+            self.cache_control = Some(input.into());
+            self
+        }
+
+        pub fn set_cache_control(mut self, input: Option<String>) -> Self {
+            self.cache_control = input;
+            self
+        }
+
+        pub fn content_encoding(mut self, input: impl Into<String>) -> Self {
+            self.content_encoding = Some(input.into());
+            self
+        }
+
+        pub fn set_content_encoding(mut self, input: Option<String>) -> Self {
+            self.content_encoding = input;
+            self
+        }
+
+        pub fn ssekms_key_id(mut self, input: impl Into<String>) -> Self {
+            self.ssekms_key_id = Some(input.into());
+            self
+        }
+
+        pub fn set_ssekms_key_id(mut self, input: Option<String>) -> Self {
+            self.ssekms_key_id = input;
+            self
+        }
+
+        pub fn restore(mut self, input: impl Into<String>) -> Self {
+            self.restore = Some(input.into());
+            self
+        }
+
+        pub fn set_restore(mut self, input: Option<String>) -> Self {
+            self.restore = input;
+            self
+        }
+
+        pub fn storage_class(mut self, input: crate::model::StorageClass) -> Self {
+            self.storage_class = Some(input);
+            self
+        }
+
+        pub fn set_storage_class(mut self, input: Option<crate::model::StorageClass>) -> Self {
+            self.storage_class = input;
+            self
+        }
+
+        pub fn server_side_encryption(mut self, input: crate::model::ServerSideEncryption) -> Self {
+            self.server_side_encryption = Some(input);
+            self
+        }
+
+        pub fn set_server_side_encryption(
+            mut self,
+            input: Option<crate::model::ServerSideEncryption>,
+        ) -> Self {
+            self.server_side_encryption = input;
+            self
+        }
+
+        pub fn expires(mut self, input: aws_smithy_types::DateTime) -> Self {
+            self.expires = Some(input);
+            self
+        }
+
+        pub fn set_expires(mut self, input: Option<aws_smithy_types::DateTime>) -> Self {
+            self.expires = input;
+            self
+        }
+
+        pub fn last_modified(mut self, input: aws_smithy_types::DateTime) -> Self {
+            self.last_modified = Some(input);
+            self
+        }
+
+        pub fn set_last_modified(mut self, input: Option<aws_smithy_types::DateTime>) -> Self {
+            self.last_modified = input;
+            self
+        }
+
+        pub fn e_tag(mut self, input: impl Into<String>) -> Self {
+            self.e_tag = Some(input.into());
+            self
+        }
+
+        pub fn set_e_tag(mut self, input: Option<String>) -> Self {
+            self.e_tag = input;
+            self
+        }
+
         pub fn build(self) -> crate::output::GetObjectOutput {
             crate::output::GetObjectOutput {
                 body: self.body.unwrap_or_default(),
+                accept_ranges: self.accept_ranges,
+                content_length: self.content_length.unwrap_or_default(),
+                content_range: self.content_range,
+                parts_count: self.parts_count.unwrap_or_default(),
+                cache_control: self.cache_control,
+                content_encoding: self.content_encoding,
+                ssekms_key_id: self.ssekms_key_id,
+                restore: self.restore,
+                storage_class: self.storage_class,
+                server_side_encryption: self.server_side_encryption,
+                expires: self.expires,
+                last_modified: self.last_modified,
+                e_tag: self.e_tag,
             }
         }
     }
@@ -144,6 +415,13 @@ impl GetObjectOutput {
 pub struct HeadObjectOutput {
     pub(crate) last_modified: Option<crate::types::DateTime>,
     pub(crate) content_length: i64,
+    pub(crate) cache_control: Option<String>,
+    pub(crate) content_encoding: Option<String>,
+    pub(crate) ssekms_key_id: Option<String>,
+    pub(crate) restore: Option<String>,
+    pub(crate) storage_class: Option<crate::model::StorageClass>,
+    pub(crate) server_side_encryption: Option<crate::model::ServerSideEncryption>,
+    pub(crate) expires: Option<crate::types::DateTime>,
 }
 impl HeadObjectOutput {
     pub fn last_modified(&self) -> Option<&aws_smithy_types::DateTime> {
@@ -153,12 +431,47 @@ impl HeadObjectOutput {
     pub fn content_length(&self) -> i64 {
         self.content_length
     }
+
+    pub fn cache_control(&self) -> Option<&str> {
+        self.cache_control.as_deref()
+    }
+
+    pub fn content_encoding(&self) -> Option<&str> {
+        self.content_encoding.as_deref()
+    }
+
+    pub fn ssekms_key_id(&self) -> Option<&str> {
+        self.ssekms_key_id.as_deref()
+    }
+
+    pub fn restore(&self) -> Option<&str> {
+        self.restore.as_deref()
+    }
+
+    pub fn storage_class(&self) -> Option<&crate::model::StorageClass> {
+        self.storage_class.as_ref()
+    }
+
+    pub fn server_side_encryption(&self) -> Option<&crate::model::ServerSideEncryption> {
+        self.server_side_encryption.as_ref()
+    }
+
+    pub fn expires(&self) -> Option<&aws_smithy_types::DateTime> {
+        self.expires.as_ref()
+    }
 }
 impl Debug for HeadObjectOutput {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         let mut formatter = f.debug_struct("HeadObjectOutput");
         formatter.field("last_modified", &self.last_modified);
         formatter.field("content_length", &self.content_length);
+        formatter.field("cache_control", &self.cache_control);
+        formatter.field("content_encoding", &self.content_encoding);
+        formatter.field("ssekms_key_id", &self.ssekms_key_id);
+        formatter.field("restore", &self.restore);
+        formatter.field("storage_class", &self.storage_class);
+        formatter.field("server_side_encryption", &self.server_side_encryption);
+        formatter.field("expires", &self.expires);
         formatter.finish()
     }
 }
@@ -168,6 +481,13 @@ pub mod head_object_output {
     pub struct Builder {
         pub(crate) content_length: Option<i64>,
         pub(crate) last_modified: Option<aws_smithy_types::DateTime>,
+        pub(crate) cache_control: Option<String>,
+        pub(crate) content_encoding: Option<String>,
+        pub(crate) ssekms_key_id: Option<String>,
+        pub(crate) restore: Option<String>,
+        pub(crate) storage_class: Option<crate::model::StorageClass>,
+        pub(crate) server_side_encryption: Option<crate::model::ServerSideEncryption>,
+        pub(crate) expires: Option<aws_smithy_types::DateTime>,
     }
     impl Builder {
         pub fn last_modified(mut self, input: aws_smithy_types::DateTime) -> Self {
@@ -190,10 +510,90 @@ pub mod head_object_output {
             self
         }
 
+        pub fn cache_control(mut self, input: impl Into<String>) -> Self {
+            self.cache_control = Some(input.into());
+            self
+        }
+
+        pub fn set_cache_control(mut self, input: Option<String>) -> Self {
+            self.cache_control = input;
+            self
+        }
+
+        pub fn content_encoding(mut self, input: impl Into<String>) -> Self {
+            self.content_encoding = Some(input.into());
+            self
+        }
+
+        pub fn set_content_encoding(mut self, input: Option<String>) -> Self {
+            self.content_encoding = input;
+            self
+        }
+
+        pub fn ssekms_key_id(mut self, input: impl Into<String>) -> Self {
+            self.ssekms_key_id = Some(input.into());
+            self
+        }
+
+        pub fn set_ssekms_key_id(mut self, input: Option<String>) -> Self {
+            self.ssekms_key_id = input;
+            self
+        }
+
+        pub fn restore(mut self, input: impl Into<String>) -> Self {
+            self.restore = Some(input.into());
+            self
+        }
+
+        pub fn set_restore(mut self, input: Option<String>) -> Self {
+            self.restore = input;
+            self
+        }
+
+        pub fn storage_class(mut self, input: crate::model::StorageClass) -> Self {
+            self.storage_class = Some(input);
+            self
+        }
+
+        pub fn set_storage_class(mut self, input: Option<crate::model::StorageClass>) -> Self {
+            self.storage_class = input;
+            self
+        }
+
+        pub fn server_side_encryption(mut self, input: crate::model::ServerSideEncryption) -> Self {
+            self.server_side_encryption = Some(input);
+            self
+        }
+
+        pub fn set_server_side_encryption(
+            mut self,
+            input: Option<crate::model::ServerSideEncryption>,
+        ) -> Self {
+            self.server_side_encryption = input;
+            self
+        }
+
+        pub fn expires(mut self, input: aws_smithy_types::DateTime) -> Self {
+            self.expires = Some(input);
+            self
+        }
+
+        pub fn set_expires(mut self, input: Option<aws_smithy_types::DateTime>) -> Self {
+            self.expires = input;
+            self
+        }
+
         pub fn build(self) -> crate::output::HeadObjectOutput {
             crate::output::HeadObjectOutput {
                 last_modified: self.last_modified,
                 content_length: self.content_length.unwrap_or_default(),
+                cache_control: self.cache_control,
+                content_encoding: self.content_encoding,
+                ssekms_key_id: self.ssekms_key_id,
+                restore: self.restore,
+                storage_class: self.storage_class,
+                server_side_encryption: self.server_side_encryption,
+                expires: self.expires,
             }
         }
     }
@@ -525,3 +925,398 @@ impl PutBucketLifecycleConfigurationOutput {
         crate::output::put_bucket_lifecycle_configuration_output::Builder::default()
     }
 }
+
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
+pub struct GetBucketLocationOutput {
+    pub(crate) location_constraint: Option<crate::model::BucketLocationConstraint>,
+}
+impl GetBucketLocationOutput {
+    pub fn location_constraint(&self) -> Option<&crate::model::BucketLocationConstraint> {
+        self.location_constraint.as_ref()
+    }
+}
+impl Debug for GetBucketLocationOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut formatter = f.debug_struct("GetBucketLocationOutput");
+        formatter.field("location_constraint", &self.location_constraint);
+        formatter.finish()
+    }
+}
+pub mod get_bucket_location_output {
+
+    #[derive(Default, Clone, PartialEq, Eq, Debug)]
+    pub struct Builder {
+        pub(crate) location_constraint: Option<crate::model::BucketLocationConstraint>,
+    }
+    impl Builder {
+        pub fn location_constraint(
+            mut self,
+            input: crate::model::BucketLocationConstraint,
+        ) -> Self {
+            self.location_constraint = Some(input);
+            self
+        }
+        pub fn set_location_constraint(
+            mut self,
+            input: Option<crate::model::BucketLocationConstraint>,
+        ) -> Self {
+            self.location_constraint = input;
+            self
+        }
+        pub fn build(self) -> crate::output::GetBucketLocationOutput {
+            crate::output::GetBucketLocationOutput {
+                location_constraint: self.location_constraint,
+            }
+        }
+    }
+}
+impl GetBucketLocationOutput {
+    pub fn builder() -> crate::output::get_bucket_location_output::Builder {
+        crate::output::get_bucket_location_output::Builder::default()
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
+pub struct GetBucketVersioningOutput {
+    pub(crate) status: Option<crate::model::BucketVersioningStatus>,
+    pub(crate) mfa_delete: Option<crate::model::MfaDeleteStatus>,
+}
+impl GetBucketVersioningOutput {
+    pub fn status(&self) -> Option<&crate::model::BucketVersioningStatus> {
+        self.status.as_ref()
+    }
+    pub fn mfa_delete(&self) -> Option<&crate::model::MfaDeleteStatus> {
+        self.mfa_delete.as_ref()
+    }
+}
+impl Debug for GetBucketVersioningOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut formatter = f.debug_struct("GetBucketVersioningOutput");
+        formatter.field("status", &self.status);
+        formatter.field("mfa_delete", &self.mfa_delete);
+        formatter.finish()
+    }
+}
+pub mod get_bucket_versioning_output {
+
+    #[derive(Default, Clone, PartialEq, Eq, Debug)]
+    pub struct Builder {
+        pub(crate) status: Option<crate::model::BucketVersioningStatus>,
+        pub(crate) mfa_delete: Option<crate::model::MfaDeleteStatus>,
+    }
+    impl Builder {
+        pub fn status(mut self, input: crate::model::BucketVersioningStatus) -> Self {
+            self.status = Some(input);
+            self
+        }
+        pub fn set_status(mut self, input: Option<crate::model::BucketVersioningStatus>) -> Self {
+            self.status = input;
+            self
+        }
+        pub fn mfa_delete(mut self, input: crate::model::MfaDeleteStatus) -> Self {
+            self.mfa_delete = Some(input);
+            self
+        }
+        pub fn set_mfa_delete(mut self, input: Option<crate::model::MfaDeleteStatus>) -> Self {
+            self.mfa_delete = input;
+            self
+        }
+        pub fn build(self) -> crate::output::GetBucketVersioningOutput {
+            crate::output::GetBucketVersioningOutput {
+                status: self.status,
+                mfa_delete: self.mfa_delete,
+            }
+        }
+    }
+}
+impl GetBucketVersioningOutput {
+    pub fn builder() -> crate::output::get_bucket_versioning_output::Builder {
+        crate::output::get_bucket_versioning_output::Builder::default()
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, PartialEq)]
+pub struct GetObjectAttributesOutput {
+    pub(crate) last_modified: Option<crate::types::DateTime>,
+    pub(crate) e_tag: Option<String>,
+    pub(crate) checksum: Option<crate::model::Checksum>,
+    pub(crate) object_parts: Option<crate::model::GetObjectAttributesParts>,
+    pub(crate) object_size: i64,
+}
+impl GetObjectAttributesOutput {
+    pub fn last_modified(&self) -> Option<&crate::types::DateTime> {
+        self.last_modified.as_ref()
+    }
+    pub fn e_tag(&self) -> Option<&str> {
+        self.e_tag.as_deref()
+    }
+    pub fn checksum(&self) -> Option<&crate::model::Checksum> {
+        self.checksum.as_ref()
+    }
+    pub fn object_parts(&self) -> Option<&crate::model::GetObjectAttributesParts> {
+        self.object_parts.as_ref()
+    }
+    pub fn object_size(&self) -> i64 {
+        self.object_size
+    }
+}
+impl Debug for GetObjectAttributesOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut formatter = f.debug_struct("GetObjectAttributesOutput");
+        formatter.field("last_modified", &self.last_modified);
+        formatter.field("e_tag", &self.e_tag);
+        formatter.field("checksum", &self.checksum);
+        formatter.field("object_parts", &self.object_parts);
+        formatter.field("object_size", &self.object_size);
+        formatter.finish()
+    }
+}
+pub mod get_object_attributes_output {
+
+    #[derive(Default, Clone, PartialEq, Debug)]
+    pub struct Builder {
+        pub(crate) last_modified: Option<crate::types::DateTime>,
+        pub(crate) e_tag: Option<String>,
+        pub(crate) checksum: Option<crate::model::Checksum>,
+        pub(crate) object_parts: Option<crate::model::GetObjectAttributesParts>,
+        pub(crate) object_size: Option<i64>,
+    }
+    impl Builder {
+        pub fn last_modified(mut self, input: crate::types::DateTime) -> Self {
+            self.last_modified = Some(input);
+            self
+        }
+        pub fn set_last_modified(mut self, input: Option<crate::types::DateTime>) -> Self {
+            self.last_modified = input;
+            self
+        }
+        pub fn e_tag(mut self, input: impl Into<String>) -> Self {
+            self.e_tag = Some(input.into());
+            self
+        }
+        pub fn set_e_tag(mut self, input: Option<String>) -> Self {
+            self.e_tag = input;
+            self
+        }
+        pub fn checksum(mut self, input: crate::model::Checksum) -> Self {
+            self.checksum = Some(input);
+            self
+        }
+        pub fn set_checksum(mut self, input: Option<crate::model::Checksum>) -> Self {
+            self.checksum = input;
+            self
+        }
+        pub fn object_parts(mut self, input: crate::model::GetObjectAttributesParts) -> Self {
+            self.object_parts = Some(input);
+            self
+        }
+        pub fn set_object_parts(
+            mut self,
+            input: Option<crate::model::GetObjectAttributesParts>,
+        ) -> Self {
+            self.object_parts = input;
+            self
+        }
+        pub fn object_size(mut self, input: i64) -> Self {
+            self.object_size = Some(input);
+            self
+        }
+        pub fn set_object_size(mut self, input: Option<i64>) -> Self {
+            self.object_size = input;
+            self
+        }
+        pub fn build(self) -> crate::output::GetObjectAttributesOutput {
+            crate::output::GetObjectAttributesOutput {
+                last_modified: self.last_modified,
+                e_tag: self.e_tag,
+                checksum: self.checksum,
+                object_parts: self.object_parts,
+                object_size: self.object_size.unwrap_or_default(),
+            }
+        }
+    }
+}
+impl GetObjectAttributesOutput {
+    pub fn builder() -> crate::output::get_object_attributes_output::Builder {
+        crate::output::get_object_attributes_output::Builder::default()
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
+pub struct PutObjectLockConfigurationOutput {}
+impl Debug for PutObjectLockConfigurationOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut formatter = f.debug_struct("PutObjectLockConfigurationOutput");
+        formatter.finish()
+    }
+}
+pub mod put_object_lock_configuration_output {
+
+    #[derive(Default, Clone, PartialEq, Eq, Debug)]
+    pub struct Builder {}
+    impl Builder {
+        pub fn build(self) -> crate::output::PutObjectLockConfigurationOutput {
+            crate::output::PutObjectLockConfigurationOutput {}
+        }
+    }
+}
+impl PutObjectLockConfigurationOutput {
+    pub fn builder() -> crate::output::put_object_lock_configuration_output::Builder {
+        crate::output::put_object_lock_configuration_output::Builder::default()
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
+pub struct GetObjectLockConfigurationOutput {
+    pub(crate) object_lock_configuration: Option<crate::model::ObjectLockConfiguration>,
+}
+impl GetObjectLockConfigurationOutput {
+    pub fn object_lock_configuration(&self) -> Option<&crate::model::ObjectLockConfiguration> {
+        self.object_lock_configuration.as_ref()
+    }
+}
+impl Debug for GetObjectLockConfigurationOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut formatter = f.debug_struct("GetObjectLockConfigurationOutput");
+        formatter.field("object_lock_configuration", &self.object_lock_configuration);
+        formatter.finish()
+    }
+}
+pub mod get_object_lock_configuration_output {
+
+    #[derive(Default, Clone, PartialEq, Eq, Debug)]
+    pub struct Builder {
+        pub(crate) object_lock_configuration: Option<crate::model::ObjectLockConfiguration>,
+    }
+    impl Builder {
+        pub fn object_lock_configuration(
+            mut self,
+            input: crate::model::ObjectLockConfiguration,
+        ) -> Self {
+            self.object_lock_configuration = Some(input);
+            self
+        }
+        pub fn set_object_lock_configuration(
+            mut self,
+            input: Option<crate::model::ObjectLockConfiguration>,
+        ) -> Self {
+            self.object_lock_configuration = input;
+            self
+        }
+        pub fn build(self) -> crate::output::GetObjectLockConfigurationOutput {
+            crate::output::GetObjectLockConfigurationOutput {
+                object_lock_configuration: self.object_lock_configuration,
+            }
+        }
+    }
+}
+impl GetObjectLockConfigurationOutput {
+    pub fn builder() -> crate::output::get_object_lock_configuration_output::Builder {
+        crate::output::get_object_lock_configuration_output::Builder::default()
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
+pub struct PutObjectRetentionOutput {}
+impl Debug for PutObjectRetentionOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut formatter = f.debug_struct("PutObjectRetentionOutput");
+        formatter.finish()
+    }
+}
+pub mod put_object_retention_output {
+
+    #[derive(Default, Clone, PartialEq, Eq, Debug)]
+    pub struct Builder {}
+    impl Builder {
+        pub fn build(self) -> crate::output::PutObjectRetentionOutput {
+            crate::output::PutObjectRetentionOutput {}
+        }
+    }
+}
+impl PutObjectRetentionOutput {
+    pub fn builder() -> crate::output::put_object_retention_output::Builder {
+        crate::output::put_object_retention_output::Builder::default()
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
+pub struct PutObjectLegalHoldOutput {}
+impl Debug for PutObjectLegalHoldOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut formatter = f.debug_struct("PutObjectLegalHoldOutput");
+        formatter.finish()
+    }
+}
+pub mod put_object_legal_hold_output {
+
+    #[derive(Default, Clone, PartialEq, Eq, Debug)]
+    pub struct Builder {}
+    impl Builder {
+        pub fn build(self) -> crate::output::PutObjectLegalHoldOutput {
+            crate::output::PutObjectLegalHoldOutput {}
+        }
+    }
+}
+impl PutObjectLegalHoldOutput {
+    pub fn builder() -> crate::output::put_object_legal_hold_output::Builder {
+        crate::output::put_object_legal_hold_output::Builder::default()
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
+pub struct RestoreObjectOutput {}
+impl Debug for RestoreObjectOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut formatter = f.debug_struct("RestoreObjectOutput");
+        formatter.finish()
+    }
+}
+pub mod restore_object_output {
+
+    #[derive(Default, Clone, PartialEq, Eq, Debug)]
+    pub struct Builder {}
+    impl Builder {
+        pub fn build(self) -> crate::output::RestoreObjectOutput {
+            crate::output::RestoreObjectOutput {}
+        }
+    }
+}
+impl RestoreObjectOutput {
+    pub fn builder() -> crate::output::restore_object_output::Builder {
+        crate::output::restore_object_output::Builder::default()
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
+pub struct HeadBucketOutput {}
+impl Debug for HeadBucketOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut formatter = f.debug_struct("HeadBucketOutput");
+        formatter.finish()
+    }
+}
+pub mod head_bucket_output {
+
+    #[derive(Default, Clone, PartialEq, Eq, Debug)]
+    pub struct Builder {}
+    impl Builder {
+        pub fn build(self) -> crate::output::HeadBucketOutput {
+            crate::output::HeadBucketOutput {}
+        }
+    }
+}
+impl HeadBucketOutput {
+    pub fn builder() -> crate::output::head_bucket_output::Builder {
+        crate::output::head_bucket_output::Builder::default()
+    }
+}
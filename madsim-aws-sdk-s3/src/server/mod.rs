@@ -1,4 +1,7 @@
 mod rpc_server;
 pub(crate) mod service;
+mod transcript;
 
 pub use rpc_server::*;
+pub use service::{IncompleteUpload, ObjectMetadata, RequestRecord, S3Service, SizeLimits};
+pub use transcript::{Transcript, TranscriptEntry};
@@ -1,12 +1,22 @@
-use madsim::net::{Endpoint, Payload};
+use bytes::{Bytes, BytesMut};
+use madsim::net::{Endpoint, Payload, Receiver, Sender};
 use std::{io::Result, net::SocketAddr, sync::Arc};
 
-use super::{service::Request, service::S3Service};
+use aws_smithy_types::retry::ProvideErrorKind;
+
+use super::{
+    service::ObjectMetadata, service::Request, service::RequestRecord, service::S3Service,
+};
+
+/// The size of chunks of bodies sent by `GetObject`.
+const DOWNLOAD_CHUNK_SIZE: usize = 64 * 1024;
 
 /// A simulated s3 server.
 #[derive(Default, Clone)]
 pub struct SimServer {
     bucket: Option<String>,
+    owner: Option<String>,
+    service: Arc<S3Service>,
 }
 
 impl SimServer {
@@ -19,37 +29,191 @@ impl SimServer {
         self
     }
 
+    /// Set the account ID that owns the bucket created by [`with_bucket`].
+    ///
+    /// Requests with a different `expected_bucket_owner` fail with `AccessDenied`.
+    ///
+    /// [`with_bucket`]: SimServer::with_bucket
+    pub fn with_bucket_owner(mut self, owner: impl Into<String>) -> Self {
+        self.owner = Some(owner.into());
+        self
+    }
+
+    /// Record the requests processed by the server.
+    ///
+    /// The log can be read from [`SimServer::service`].
+    pub fn with_request_log(self) -> Self {
+        self.service.enable_request_log();
+        self
+    }
+
+    /// Throttle requests to a bucket with `SlowDown` errors once more than `max_requests`
+    /// requests are accepted within `window` of simulated time.
+    pub fn with_throttle(self, max_requests: usize, window: std::time::Duration) -> Self {
+        self.service.set_throttle(max_requests, window);
+        self
+    }
+
+    /// Throttle requests to each bucket with a token bucket of `capacity` tokens, refilled
+    /// by `refill_rate` tokens per second of simulated time.
+    ///
+    /// See [`S3Service::set_token_capacity`].
+    pub fn with_token_bucket(self, capacity: u32, refill_rate: f64) -> Self {
+        self.service.set_token_capacity(capacity);
+        self.service.set_token_refill_rate(refill_rate);
+        self
+    }
+
+    /// Delay each request by a random duration in `latency` of simulated time.
+    pub fn with_latency(self, latency: std::ops::Range<std::time::Duration>) -> Self {
+        self.service.set_latency(latency);
+        self
+    }
+
+    /// Delay each request from clients configured with `region` by `latency` of simulated
+    /// time.
+    ///
+    /// See [`S3Service::set_region_latency`].
+    pub fn with_region_latency(
+        self,
+        region: impl Into<String>,
+        latency: std::time::Duration,
+    ) -> Self {
+        self.service.set_region_latency(region, latency);
+        self
+    }
+
+    /// Set the region of buckets. Defaults to `us-east-1`.
+    ///
+    /// Once set, requests from clients configured with another region fail with
+    /// `PermanentRedirect`.
+    pub fn with_region(self, region: impl Into<String>) -> Self {
+        self.service.set_region(region);
+        self
+    }
+
+    /// Hide written objects from reads for `delay` of simulated time after each write.
+    pub fn with_read_delay(self, delay: std::time::Duration) -> Self {
+        self.service.set_read_delay(delay);
+        self
+    }
+
+    /// Set the time it takes to restore an archived object. Defaults to zero.
+    pub fn with_restore_delay(self, delay: std::time::Duration) -> Self {
+        self.service.set_restore_delay(delay);
+        self
+    }
+
+    /// Set the size limits of objects and parts. No limits are enforced by default.
+    pub fn with_size_limits(self, limits: super::service::SizeLimits) -> Self {
+        self.service.set_size_limits(limits);
+        self
+    }
+
+    /// Set the capacity of the bucket in bytes. No capacity is enforced by default.
+    ///
+    /// See [`S3Service::set_bucket_quota`].
+    pub fn with_bucket_quota(self, bucket: impl Into<String>, capacity: u64) -> Self {
+        self.service.set_bucket_quota(bucket, capacity);
+        self
+    }
+
+    /// Returns the service behind this server.
+    pub fn service(&self) -> Arc<S3Service> {
+        self.service.clone()
+    }
+
     pub async fn serve(self, addr: SocketAddr) -> Result<()> {
         let ep = Endpoint::bind(addr).await?;
-        let mut service = S3Service::new();
+        let service = self.service;
         if let Some(bucket) = self.bucket {
             service.create_bucket(&bucket).await;
+            if let Some(owner) = self.owner {
+                service.set_bucket_owner(bucket, owner);
+            }
         }
-        let service = Arc::new(service);
         loop {
             let (tx, mut rx, _) = ep.accept1().await?;
             let service = service.clone();
             madsim::task::spawn(async move {
-                let request = *rx.recv().await?.downcast::<Request>().unwrap();
+                let (region, mut request) = *(rx.recv().await?)
+                    .downcast::<(Option<String>, Request)>()
+                    .unwrap();
+                if let Some(body) = request.body_mut() {
+                    // a body interrupted by a closed connection is discarded
+                    *body = recv_body(&mut rx).await?;
+                }
+                service.delay(region.as_deref()).await;
+                service.log_request(&request);
+                let record = request.record();
+                if let Err(e) = service.check_region(region.as_deref()) {
+                    service.transcribe(record, e.code());
+                    tx.send(request.error_response(e)).await?;
+                    return Ok(());
+                }
+                if let Err(e) = service.throttle(&request) {
+                    service.transcribe(record, e.code());
+                    tx.send(request.error_response(e)).await?;
+                    return Ok(());
+                }
+                if let Err(e) = service.check_bucket_owner(&request) {
+                    service.transcribe(record, e.code());
+                    tx.send(request.error_response(e)).await?;
+                    return Ok(());
+                }
+                if let Err(e) = service.take_fault(&record) {
+                    service.transcribe(record, e.code());
+                    tx.send(request.error_response(e)).await?;
+                    return Ok(());
+                }
 
                 use crate::input::*;
                 use Request::*;
 
+                // the body of `GetObject`, sent after the response
+                let mut download = None;
+
                 let response: Payload = match request {
-                    CreateMultipartUpload(CreateMultipartUploadInput { bucket, key }) => {
-                        Box::new(service.create_multipart_upload(bucket, key).await)
+                    CreateMultipartUpload(CreateMultipartUploadInput {
+                        bucket,
+                        key,
+                        server_side_encryption,
+                        ssekms_key_id,
+                        expected_bucket_owner: _,
+                    }) => {
+                        let metadata = ObjectMetadata {
+                            server_side_encryption,
+                            ssekms_key_id,
+                            ..Default::default()
+                        };
+                        respond(
+                            &service,
+                            record,
+                            service.create_multipart_upload(bucket, key, metadata).await,
+                        )
                     }
                     UploadPart(UploadPartInput {
                         body0,
                         bucket,
                         content_length,
+                        content_md5,
                         key,
                         part_number,
                         upload_id,
                         ..
-                    }) => Box::new(
+                    }) => respond(
+                        &service,
+                        record,
                         service
-                            .upload_part(bucket, key, body0, content_length, part_number, upload_id)
+                            .upload_part(
+                                bucket,
+                                key,
+                                body0,
+                                content_length,
+                                content_md5,
+                                part_number,
+                                upload_id,
+                            )
                             .await,
                     ),
                     CompletedMultipartUpload(CompleteMultipartUploadInput {
@@ -57,7 +221,10 @@ impl SimServer {
                         key,
                         multipart_upload,
                         upload_id,
-                    }) => Box::new(
+                        expected_bucket_owner: _,
+                    }) => respond(
+                        &service,
+                        record,
                         service
                             .complete_multipart_upload(bucket, key, multipart_upload, upload_id)
                             .await,
@@ -66,39 +233,126 @@ impl SimServer {
                         bucket,
                         key,
                         upload_id,
-                    }) => Box::new(service.abort_multipart_upload(bucket, key, upload_id).await),
+                        expected_bucket_owner: _,
+                    }) => respond(
+                        &service,
+                        record,
+                        service.abort_multipart_upload(bucket, key, upload_id).await,
+                    ),
                     GetObject(GetObjectInput {
                         bucket,
                         key,
                         range,
                         part_number,
-                    }) => Box::new(service.get_object(bucket, key, range, part_number).await),
-                    PutObject(PutObjectInput {
-                        body0, bucket, key, ..
-                    }) => Box::new(service.put_object(bucket, key, body0).await),
-                    DeleteObject(DeleteObjectInput { bucket, key }) => {
-                        Box::new(service.delete_object(bucket, key).await)
-                    }
-                    DeleteObjects(DeleteObjectsInput { bucket, delete }) => {
-                        Box::new(service.delete_objects(bucket, delete).await)
+                        if_match,
+                        if_modified_since,
+                        expected_bucket_owner: _,
+                    }) => {
+                        let mut result = service
+                            .get_object(
+                                bucket,
+                                key,
+                                range,
+                                part_number,
+                                if_match,
+                                if_modified_since,
+                            )
+                            .await;
+                        if let Ok(output) = &mut result {
+                            let body = std::mem::replace(
+                                &mut output.body,
+                                crate::types::ByteStream::from_static(b""),
+                            );
+                            let body = body.collect().await.expect("in-memory body");
+                            download = Some(body.into_bytes());
+                        }
+                        respond(&service, record, result)
                     }
-                    HeadObject(HeadObjectInput { bucket, key }) => {
-                        Box::new(service.head_object(bucket, key).await)
+                    PutObject(PutObjectInput {
+                        body0,
+                        bucket,
+                        key,
+                        content_length,
+                        content_md5,
+                        if_none_match,
+                        cache_control,
+                        content_encoding,
+                        expires,
+                        storage_class,
+                        server_side_encryption,
+                        ssekms_key_id,
+                        ..
+                    }) => {
+                        let metadata = ObjectMetadata {
+                            cache_control,
+                            content_encoding,
+                            expires,
+                            storage_class,
+                            server_side_encryption,
+                            ssekms_key_id,
+                        };
+                        respond(
+                            &service,
+                            record,
+                            service
+                                .put_object(
+                                    bucket,
+                                    key,
+                                    body0,
+                                    content_length,
+                                    content_md5,
+                                    if_none_match,
+                                    metadata,
+                                )
+                                .await,
+                        )
                     }
+                    DeleteObject(DeleteObjectInput {
+                        bucket,
+                        key,
+                        expected_bucket_owner: _,
+                    }) => respond(&service, record, service.delete_object(bucket, key).await),
+                    DeleteObjects(DeleteObjectsInput {
+                        bucket,
+                        delete,
+                        expected_bucket_owner: _,
+                    }) => respond(
+                        &service,
+                        record,
+                        service.delete_objects(bucket, delete).await,
+                    ),
+                    HeadObject(HeadObjectInput {
+                        bucket,
+                        key,
+                        expected_bucket_owner: _,
+                    }) => respond(&service, record, service.head_object(bucket, key).await),
                     ListObjectsV2(ListObjectsV2Input {
                         bucket,
                         prefix,
                         continuation_token,
-                    }) => Box::new(
+                        start_after,
+                        max_keys,
+                        expected_bucket_owner: _,
+                    }) => respond(
+                        &service,
+                        record,
                         service
-                            .list_objects_v2(bucket, prefix, continuation_token)
+                            .list_objects_v2(
+                                bucket,
+                                prefix,
+                                continuation_token,
+                                start_after,
+                                max_keys,
+                            )
                             .await,
                     ),
                     PutBucketLifecycleConfiguration(PutBucketLifecycleConfigurationInput {
                         bucket,
                         lifecycle_configuration,
                         expected_bucket_owner,
-                    }) => Box::new(
+                    }) => respond(
+                        &service,
+                        record,
                         service
                             .put_bucket_lifecycle_configuration(
                                 bucket,
@@ -110,15 +364,167 @@ impl SimServer {
                     GetBucketLifecycleConfiguration(GetBucketLifecycleConfigurationInput {
                         bucket,
                         expected_bucket_owner,
-                    }) => Box::new(
+                    }) => respond(
+                        &service,
+                        record,
                         service
                             .get_bucket_lifecycle_configuration(bucket, expected_bucket_owner)
                             .await,
                     ),
+                    GetBucketLocation(GetBucketLocationInput {
+                        bucket,
+                        expected_bucket_owner,
+                    }) => respond(
+                        &service,
+                        record,
+                        service
+                            .get_bucket_location(bucket, expected_bucket_owner)
+                            .await,
+                    ),
+                    GetBucketVersioning(GetBucketVersioningInput {
+                        bucket,
+                        expected_bucket_owner,
+                    }) => respond(
+                        &service,
+                        record,
+                        service
+                            .get_bucket_versioning(bucket, expected_bucket_owner)
+                            .await,
+                    ),
+                    GetObjectAttributes(GetObjectAttributesInput {
+                        bucket,
+                        key,
+                        max_parts,
+                        part_number_marker,
+                        expected_bucket_owner: _,
+                        object_attributes,
+                    }) => respond(
+                        &service,
+                        record,
+                        service
+                            .get_object_attributes(
+                                bucket,
+                                key,
+                                max_parts,
+                                part_number_marker,
+                                object_attributes.unwrap_or_default(),
+                            )
+                            .await,
+                    ),
+                    PutObjectLockConfiguration(PutObjectLockConfigurationInput {
+                        bucket,
+                        object_lock_configuration,
+                        expected_bucket_owner: _,
+                    }) => respond(
+                        &service,
+                        record,
+                        service
+                            .put_object_lock_configuration(bucket, object_lock_configuration)
+                            .await,
+                    ),
+                    GetObjectLockConfiguration(GetObjectLockConfigurationInput {
+                        bucket,
+                        expected_bucket_owner: _,
+                    }) => respond(
+                        &service,
+                        record,
+                        service.get_object_lock_configuration(bucket).await,
+                    ),
+                    PutObjectRetention(PutObjectRetentionInput {
+                        bucket,
+                        key,
+                        retention,
+                        bypass_governance_retention,
+                        expected_bucket_owner: _,
+                    }) => respond(
+                        &service,
+                        record,
+                        service
+                            .put_object_retention(
+                                bucket,
+                                key,
+                                retention,
+                                bypass_governance_retention,
+                            )
+                            .await,
+                    ),
+                    PutObjectLegalHold(PutObjectLegalHoldInput {
+                        bucket,
+                        key,
+                        legal_hold,
+                        expected_bucket_owner: _,
+                    }) => respond(
+                        &service,
+                        record,
+                        service.put_object_legal_hold(bucket, key, legal_hold).await,
+                    ),
+                    RestoreObject(RestoreObjectInput {
+                        bucket,
+                        key,
+                        restore_request,
+                        expected_bucket_owner: _,
+                    }) => respond(
+                        &service,
+                        record,
+                        service.restore_object(bucket, key, restore_request).await,
+                    ),
+                    HeadBucket(HeadBucketInput {
+                        bucket,
+                        expected_bucket_owner: _,
+                    }) => respond(&service, record, service.head_bucket(bucket).await),
                 };
                 tx.send(response).await?;
+                if let Some(body) = download {
+                    send_body(&service, &tx, &mut rx, body).await?;
+                }
                 Ok(()) as Result<()>
             });
         }
     }
 }
+
+/// Records the outcome of the response in the transcript of the service.
+fn respond<O, E>(
+    service: &S3Service,
+    record: RequestRecord,
+    result: std::result::Result<O, E>,
+) -> Payload
+where
+    O: Send + Sync + 'static,
+    E: ProvideErrorKind + Send + Sync + 'static,
+{
+    let error = (result.as_ref().err()).map(|e| e.code().unwrap_or("Unhandled"));
+    service.transcribe(record, error);
+    Box::new(result)
+}
+
+/// Sends a body in chunks, terminated by `None`.
+///
+/// Each chunk is sent only when the client asks for it, so the transfer stops once the
+/// client drops the body and closes the connection.
+async fn send_body(service: &S3Service, tx: &Sender, rx: &mut Receiver, body: Bytes) -> Result<()> {
+    let mut chunks = (0..body.len())
+        .step_by(DOWNLOAD_CHUNK_SIZE)
+        .map(|i| body.slice(i..body.len().min(i + DOWNLOAD_CHUNK_SIZE)));
+    loop {
+        rx.recv().await?;
+        let chunk = chunks.next();
+        let done = chunk.is_none();
+        if let Some(chunk) = &chunk {
+            service.add_downloaded_bytes(chunk.len());
+        }
+        tx.send(Box::new(chunk)).await?;
+        if done {
+            return Ok(());
+        }
+    }
+}
+
+/// Receives a body streamed in chunks, terminated by `None`.
+async fn recv_body(rx: &mut Receiver) -> Result<Bytes> {
+    let mut body = BytesMut::new();
+    while let Some(chunk) = *rx.recv().await?.downcast::<Option<Bytes>>().unwrap() {
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body.freeze())
+}
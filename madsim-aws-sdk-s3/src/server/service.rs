@@ -1,15 +1,26 @@
 use crate::input::*;
 use crate::model::*;
 use crate::output::*;
+use aws_smithy_types::date_time::Format;
 use bytes::Bytes;
-use madsim::rand::{thread_rng, Rng};
+use madsim::net::Payload;
+use madsim::rand::{thread_rng, Rng, SeedableRng};
+use madsim::time::TimeHandle;
+use md5::Digest;
+use rand::rngs::SmallRng;
 use spin::Mutex;
 use tracing::debug;
 
-use std::collections::{btree_map::Entry::*, BTreeMap, VecDeque};
+use std::collections::{
+    btree_map::Entry::*, btree_map::OccupiedEntry, BTreeMap, BTreeSet, HashMap, VecDeque,
+};
+use std::ops::{Bound, Range};
+use std::time::{Duration, Instant};
 
 use aws_sdk_s3::error::*;
 
+use super::transcript::{Transcript, TranscriptEntry, TranscriptState};
+
 /// A request to s3 server.
 #[derive(Debug)]
 pub(crate) enum Request {
@@ -25,17 +36,638 @@ pub(crate) enum Request {
     ListObjectsV2(ListObjectsV2Input),
     PutBucketLifecycleConfiguration(PutBucketLifecycleConfigurationInput),
     GetBucketLifecycleConfiguration(GetBucketLifecycleConfigurationInput),
+    GetBucketLocation(GetBucketLocationInput),
+    GetBucketVersioning(GetBucketVersioningInput),
+    GetObjectAttributes(GetObjectAttributesInput),
+    PutObjectLockConfiguration(PutObjectLockConfigurationInput),
+    GetObjectLockConfiguration(GetObjectLockConfigurationInput),
+    PutObjectRetention(PutObjectRetentionInput),
+    PutObjectLegalHold(PutObjectLegalHoldInput),
+    RestoreObject(RestoreObjectInput),
+    HeadBucket(HeadBucketInput),
+}
+
+impl Request {
+    /// The names of all operations.
+    pub(crate) const OPERATIONS: &'static [&'static str] = &[
+        "CreateMultipartUpload",
+        "UploadPart",
+        "CompleteMultipartUpload",
+        "AbortMultipartUpload",
+        "GetObject",
+        "PutObject",
+        "DeleteObject",
+        "DeleteObjects",
+        "HeadObject",
+        "ListObjectsV2",
+        "PutBucketLifecycleConfiguration",
+        "GetBucketLifecycleConfiguration",
+        "GetBucketLocation",
+        "GetBucketVersioning",
+        "GetObjectAttributes",
+        "PutObjectLockConfiguration",
+        "GetObjectLockConfiguration",
+        "PutObjectRetention",
+        "PutObjectLegalHold",
+        "RestoreObject",
+        "HeadBucket",
+    ];
+
+    /// Returns the record of this request in the request log.
+    pub(crate) fn record(&self) -> RequestRecord {
+        use Request::*;
+        let (operation, bucket, key) = match self {
+            CreateMultipartUpload(i) => ("CreateMultipartUpload", &i.bucket, Some(&i.key)),
+            UploadPart(i) => ("UploadPart", &i.bucket, Some(&i.key)),
+            CompletedMultipartUpload(i) => ("CompleteMultipartUpload", &i.bucket, Some(&i.key)),
+            AbortMultipartUpload(i) => ("AbortMultipartUpload", &i.bucket, Some(&i.key)),
+            GetObject(i) => ("GetObject", &i.bucket, Some(&i.key)),
+            PutObject(i) => ("PutObject", &i.bucket, Some(&i.key)),
+            DeleteObject(i) => ("DeleteObject", &i.bucket, Some(&i.key)),
+            DeleteObjects(i) => ("DeleteObjects", &i.bucket, None),
+            HeadObject(i) => ("HeadObject", &i.bucket, Some(&i.key)),
+            ListObjectsV2(i) => ("ListObjectsV2", &i.bucket, None),
+            PutBucketLifecycleConfiguration(i) => {
+                ("PutBucketLifecycleConfiguration", &i.bucket, None)
+            }
+            GetBucketLifecycleConfiguration(i) => {
+                ("GetBucketLifecycleConfiguration", &i.bucket, None)
+            }
+            GetBucketLocation(i) => ("GetBucketLocation", &i.bucket, None),
+            GetBucketVersioning(i) => ("GetBucketVersioning", &i.bucket, None),
+            GetObjectAttributes(i) => ("GetObjectAttributes", &i.bucket, Some(&i.key)),
+            PutObjectLockConfiguration(i) => ("PutObjectLockConfiguration", &i.bucket, None),
+            GetObjectLockConfiguration(i) => ("GetObjectLockConfiguration", &i.bucket, None),
+            PutObjectRetention(i) => ("PutObjectRetention", &i.bucket, Some(&i.key)),
+            PutObjectLegalHold(i) => ("PutObjectLegalHold", &i.bucket, Some(&i.key)),
+            RestoreObject(i) => ("RestoreObject", &i.bucket, Some(&i.key)),
+            HeadBucket(i) => ("HeadBucket", &i.bucket, None),
+        };
+        RequestRecord {
+            operation,
+            bucket: bucket.clone(),
+            key: key.cloned(),
+        }
+    }
+
+    /// Returns the body of this request to be received after it, if any.
+    pub(crate) fn body_mut(&mut self) -> Option<&mut Bytes> {
+        match self {
+            Request::PutObject(i) => Some(&mut i.body0),
+            Request::UploadPart(i) => Some(&mut i.body0),
+            _ => None,
+        }
+    }
+
+    /// Returns the `expected_bucket_owner` of this request.
+    fn expected_bucket_owner(&self) -> Option<&str> {
+        use Request::*;
+        match self {
+            CreateMultipartUpload(i) => i.expected_bucket_owner.as_deref(),
+            UploadPart(i) => i.expected_bucket_owner.as_deref(),
+            CompletedMultipartUpload(i) => i.expected_bucket_owner.as_deref(),
+            AbortMultipartUpload(i) => i.expected_bucket_owner.as_deref(),
+            GetObject(i) => i.expected_bucket_owner.as_deref(),
+            PutObject(i) => i.expected_bucket_owner.as_deref(),
+            DeleteObject(i) => i.expected_bucket_owner.as_deref(),
+            DeleteObjects(i) => i.expected_bucket_owner.as_deref(),
+            HeadObject(i) => i.expected_bucket_owner.as_deref(),
+            ListObjectsV2(i) => i.expected_bucket_owner.as_deref(),
+            PutBucketLifecycleConfiguration(i) => i.expected_bucket_owner.as_deref(),
+            GetBucketLifecycleConfiguration(i) => i.expected_bucket_owner.as_deref(),
+            GetBucketLocation(i) => i.expected_bucket_owner.as_deref(),
+            GetBucketVersioning(i) => i.expected_bucket_owner.as_deref(),
+            GetObjectAttributes(i) => i.expected_bucket_owner.as_deref(),
+            PutObjectLockConfiguration(i) => i.expected_bucket_owner.as_deref(),
+            GetObjectLockConfiguration(i) => i.expected_bucket_owner.as_deref(),
+            PutObjectRetention(i) => i.expected_bucket_owner.as_deref(),
+            PutObjectLegalHold(i) => i.expected_bucket_owner.as_deref(),
+            RestoreObject(i) => i.expected_bucket_owner.as_deref(),
+            HeadBucket(i) => i.expected_bucket_owner.as_deref(),
+        }
+    }
+
+    /// Returns the response of this request failed with the error.
+    pub(crate) fn error_response(&self, e: aws_smithy_types::error::Error) -> Payload {
+        fn err<O: Send + Sync + 'static, E: Send + Sync + 'static>(e: E) -> Payload {
+            Box::new(Err::<O, E>(e))
+        }
+        use Request::*;
+        match self {
+            CreateMultipartUpload(_) => {
+                err::<CreateMultipartUploadOutput, _>(CreateMultipartUploadError::generic(e))
+            }
+            UploadPart(_) => err::<UploadPartOutput, _>(UploadPartError::generic(e)),
+            CompletedMultipartUpload(_) => {
+                err::<CompleteMultipartUploadOutput, _>(CompleteMultipartUploadError::generic(e))
+            }
+            AbortMultipartUpload(_) => {
+                err::<AbortMultipartUploadOutput, _>(AbortMultipartUploadError::generic(e))
+            }
+            GetObject(_) => err::<GetObjectOutput, _>(GetObjectError::generic(e)),
+            PutObject(_) => err::<PutObjectOutput, _>(PutObjectError::generic(e)),
+            DeleteObject(_) => err::<DeleteObjectOutput, _>(DeleteObjectError::generic(e)),
+            DeleteObjects(_) => err::<DeleteObjectsOutput, _>(DeleteObjectsError::generic(e)),
+            HeadObject(_) => err::<HeadObjectOutput, _>(HeadObjectError::generic(e)),
+            ListObjectsV2(_) => err::<ListObjectsV2Output, _>(ListObjectsV2Error::generic(e)),
+            PutBucketLifecycleConfiguration(_) => err::<PutBucketLifecycleConfigurationOutput, _>(
+                PutBucketLifecycleConfigurationError::generic(e),
+            ),
+            GetBucketLifecycleConfiguration(_) => err::<GetBucketLifecycleConfigurationOutput, _>(
+                GetBucketLifecycleConfigurationError::generic(e),
+            ),
+            GetBucketLocation(_) => {
+                err::<GetBucketLocationOutput, _>(GetBucketLocationError::generic(e))
+            }
+            GetBucketVersioning(_) => {
+                err::<GetBucketVersioningOutput, _>(GetBucketVersioningError::generic(e))
+            }
+            GetObjectAttributes(_) => {
+                err::<GetObjectAttributesOutput, _>(GetObjectAttributesError::generic(e))
+            }
+            PutObjectLockConfiguration(_) => err::<PutObjectLockConfigurationOutput, _>(
+                PutObjectLockConfigurationError::generic(e),
+            ),
+            GetObjectLockConfiguration(_) => err::<GetObjectLockConfigurationOutput, _>(
+                GetObjectLockConfigurationError::generic(e),
+            ),
+            PutObjectRetention(_) => {
+                err::<PutObjectRetentionOutput, _>(PutObjectRetentionError::generic(e))
+            }
+            PutObjectLegalHold(_) => {
+                err::<PutObjectLegalHoldOutput, _>(PutObjectLegalHoldError::generic(e))
+            }
+            RestoreObject(_) => err::<RestoreObjectOutput, _>(RestoreObjectError::generic(e)),
+            HeadBucket(_) => err::<HeadBucketOutput, _>(HeadBucketError::generic(e)),
+        }
+    }
+}
+
+/// A request processed by [`S3Service`], as recorded in its request log.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RequestRecord {
+    /// The name of the operation, e.g. `PutObject`.
+    pub operation: &'static str,
+    pub bucket: String,
+    /// The object key, or `None` for bucket-level operations.
+    pub key: Option<String>,
 }
 
-#[derive(Debug)]
 pub struct S3Service {
     inner: Mutex<ServiceInner>,
+    /// The clock to stamp `last_modified` of objects.
+    time: TimeHandle,
+    /// The log of processed requests. `None` if logging is disabled.
+    request_log: Mutex<Option<Vec<RequestRecord>>>,
+    /// Rate-based throttling of requests. `None` if disabled.
+    throttle: Mutex<Option<Throttle>>,
+    /// Token bucket throttling of requests.
+    token_buckets: Mutex<TokenBuckets>,
+    /// The transcript being recorded or replayed. `None` if disabled.
+    transcript: Mutex<Option<TranscriptState>>,
+    /// (operation, bucket, key) -> errors to return from the next matching requests
+    faults: Mutex<HashMap<RequestRecord, VecDeque<aws_smithy_types::error::Error>>>,
+    /// The range of delays before processing each request. `None` if disabled.
+    latency: Mutex<Option<Range<Duration>>>,
+    /// (region) -> extra delay of requests from clients in the region
+    region_latency: Mutex<HashMap<String, Duration>>,
+    /// The number of body bytes sent by `GetObject`.
+    downloaded_bytes: Mutex<u64>,
+}
+
+impl std::fmt::Debug for S3Service {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("S3Service")
+            .field("inner", &self.inner)
+            .field("request_log", &self.request_log)
+            .field("throttle", &self.throttle)
+            .field("token_buckets", &self.token_buckets)
+            .field("transcript", &self.transcript)
+            .field("faults", &self.faults)
+            .field("latency", &self.latency)
+            .field("region_latency", &self.region_latency)
+            .field("downloaded_bytes", &self.downloaded_bytes)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for S3Service {
+    fn default() -> Self {
+        S3Service::new()
+    }
 }
 
 impl S3Service {
+    /// Create a service using the clock of the current simulation.
     pub fn new() -> Self {
+        S3Service::with_time(TimeHandle::current())
+    }
+
+    /// Create a service using the given clock.
+    pub fn with_time(time: TimeHandle) -> Self {
         S3Service {
-            inner: Mutex::new(ServiceInner::default()),
+            inner: Default::default(),
+            time,
+            request_log: Default::default(),
+            throttle: Default::default(),
+            token_buckets: Default::default(),
+            transcript: Default::default(),
+            faults: Default::default(),
+            latency: Default::default(),
+            region_latency: Default::default(),
+            downloaded_bytes: Default::default(),
+        }
+    }
+
+    /// Returns the current time of the clock of the service.
+    ///
+    /// All timestamps of the service, such as the last modified time of objects and the
+    /// expiry of restored copies, are derived from this time.
+    pub fn now(&self) -> crate::types::DateTime {
+        self.time.now_time().into()
+    }
+
+    /// Enable recording of processed requests.
+    pub fn enable_request_log(&self) {
+        self.request_log.lock().get_or_insert_with(Vec::new);
+    }
+
+    /// Take the requests recorded since the last call, clearing the log.
+    ///
+    /// Returns an empty list if the request log is not enabled.
+    pub fn take_request_log(&self) -> Vec<RequestRecord> {
+        self.request_log
+            .lock()
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// Start recording a transcript of processed requests and the outcomes of their
+    /// responses, discarding any transcript being recorded or replayed.
+    pub fn start_recording(&self) {
+        *self.transcript.lock() = Some(TranscriptState::Record(Transcript::default()));
+    }
+
+    /// Stop recording and return the transcript.
+    ///
+    /// Returns an empty transcript if not recording.
+    pub fn take_transcript(&self) -> Transcript {
+        match self.transcript.lock().take() {
+            Some(TranscriptState::Record(transcript)) => transcript,
+            _ => Transcript::default(),
+        }
+    }
+
+    /// Start validating processed requests against a recorded transcript.
+    ///
+    /// Each request must match the next entry in operation, bucket and key, and its
+    /// response must have the same outcome. Requests are processed normally either way.
+    pub fn start_replay(&self, transcript: Transcript) {
+        *self.transcript.lock() = Some(TranscriptState::Replay {
+            expected: transcript.entries.into(),
+            mismatches: vec![],
+        });
+    }
+
+    /// Stop replaying and return the differences from the transcript, if any.
+    ///
+    /// Entries of the transcript that are never requested are reported as well.
+    pub fn finish_replay(&self) -> Result<(), Vec<String>> {
+        let Some(TranscriptState::Replay {
+            expected,
+            mut mismatches,
+        }) = self.transcript.lock().take()
+        else {
+            return Ok(());
+        };
+        for entry in expected {
+            mismatches.push(format!("missing request {:?}", entry.request));
+        }
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(mismatches)
+        }
+    }
+
+    /// Record the outcome of a request in the transcript, if enabled.
+    pub(crate) fn transcribe(&self, request: RequestRecord, error: Option<&str>) {
+        if let Some(transcript) = self.transcript.lock().as_mut() {
+            transcript.push(TranscriptEntry {
+                request,
+                error: error.map(String::from),
+            });
+        }
+    }
+
+    /// Throttle requests to a bucket with `SlowDown` errors once more than `max_requests`
+    /// requests are accepted within `window`.
+    ///
+    /// Rejected requests are not counted, so clients recover by backing off.
+    pub fn set_throttle(&self, max_requests: usize, window: Duration) {
+        *self.throttle.lock() = Some(Throttle {
+            max_requests,
+            window,
+            requests: HashMap::new(),
+        });
+    }
+
+    /// Set the capacity of the token bucket of each S3 bucket. Defaults to unlimited.
+    ///
+    /// Each request to a bucket consumes a token from its token bucket, and fails with
+    /// `SlowDown` if there is none left. A token bucket starts full, and is refilled at the
+    /// rate set by [`set_token_refill_rate`](S3Service::set_token_refill_rate).
+    pub fn set_token_capacity(&self, capacity: u32) {
+        self.token_buckets.lock().capacity = Some(capacity.into());
+    }
+
+    /// Set the number of tokens added to each token bucket per second of simulated time.
+    /// Defaults to unlimited.
+    ///
+    /// See [`set_token_capacity`](S3Service::set_token_capacity).
+    pub fn set_token_refill_rate(&self, tokens_per_second: f64) {
+        self.token_buckets.lock().refill_rate = Some(tokens_per_second);
+    }
+
+    /// Returns a `SlowDown` error if the request should be throttled.
+    pub(crate) fn throttle(&self, request: &Request) -> Result<(), aws_smithy_types::error::Error> {
+        let bucket = request.record().bucket;
+        let now = self.time.now_instant();
+        let mut throttle = self.throttle.lock();
+        let accepted = match throttle.as_mut() {
+            Some(throttle) => throttle.accept(bucket.clone(), now),
+            None => true,
+        };
+        if accepted && self.token_buckets.lock().accept(bucket, now) {
+            Ok(())
+        } else {
+            Err(error("SlowDown", "Please reduce your request rate."))
+        }
+    }
+
+    /// Delay each request by a random duration in `latency` of simulated time before
+    /// processing it.
+    ///
+    /// The delays are drawn from the global RNG, so they are reproducible with the seed.
+    pub fn set_latency(&self, latency: Range<Duration>) {
+        *self.latency.lock() = Some(latency);
+    }
+
+    /// Delay each request from clients configured with `region` by `latency` of simulated
+    /// time, in addition to the latency set by [`set_latency`](S3Service::set_latency).
+    ///
+    /// Requests from the region of the service set by [`set_region`](S3Service::set_region)
+    /// are never delayed, so that cross-region clients observe a higher latency than
+    /// same-region ones. Defaults to zero for all regions.
+    pub fn set_region_latency(&self, region: impl Into<String>, latency: Duration) {
+        self.region_latency.lock().insert(region.into(), latency);
+    }
+
+    /// Waits for the latency of a request from a client in `region`.
+    pub(crate) async fn delay(&self, region: Option<&str>) {
+        let latency = self.latency.lock().clone();
+        let mut delay = match latency {
+            Some(latency) if latency.is_empty() => latency.start,
+            Some(latency) => thread_rng().gen_range(latency),
+            None => Duration::ZERO,
+        };
+        let local = self.inner.lock().region.clone();
+        if let Some(region) = region.filter(|r| *r != local.as_deref().unwrap_or("us-east-1")) {
+            delay += (self.region_latency.lock().get(region)).map_or(Duration::ZERO, |d| *d);
+        }
+        if !delay.is_zero() {
+            self.time.sleep(delay).await;
+        }
+    }
+
+    /// Returns the number of body bytes sent to clients by `GetObject`.
+    ///
+    /// Bodies are sent in chunks as clients read them, so a body dropped early is only
+    /// counted up to the chunks requested so far.
+    pub fn downloaded_bytes(&self) -> u64 {
+        *self.downloaded_bytes.lock()
+    }
+
+    /// Counts the body bytes sent by `GetObject`.
+    pub(crate) fn add_downloaded_bytes(&self, len: usize) {
+        *self.downloaded_bytes.lock() += len as u64;
+    }
+
+    /// Returns an `AccessDenied` error if the request expects a bucket owner other than
+    /// the actual one.
+    ///
+    /// Requests without `expected_bucket_owner`, and buckets without an owner, always pass.
+    pub(crate) fn check_bucket_owner(
+        &self,
+        request: &Request,
+    ) -> Result<(), aws_smithy_types::error::Error> {
+        let Some(expected) = request.expected_bucket_owner() else {
+            return Ok(());
+        };
+        let bucket = request.record().bucket;
+        match self.inner.lock().owners.get(&bucket) {
+            Some(owner) if owner != expected => Err(error("AccessDenied", "Access Denied")),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns a `PermanentRedirect` error if the request is sent from another region.
+    ///
+    /// The error carries the endpoint and the region of buckets in its `Endpoint` and
+    /// `Region` fields. Requests without a region, and services without a region set by
+    /// [`set_region`](S3Service::set_region), always pass.
+    pub(crate) fn check_region(
+        &self,
+        region: Option<&str>,
+    ) -> Result<(), aws_smithy_types::error::Error> {
+        let Some(region) = region else {
+            return Ok(());
+        };
+        let inner = self.inner.lock();
+        let Some(expected) = inner.region.as_deref().filter(|r| *r != region) else {
+            return Ok(());
+        };
+        let endpoint = match expected {
+            "us-east-1" => "s3.amazonaws.com".to_string(),
+            _ => format!("s3.{expected}.amazonaws.com"),
+        };
+        Err(aws_smithy_types::error::Error::builder()
+            .code("PermanentRedirect")
+            .message(
+                "The bucket you are attempting to access must be addressed using \
+                 the specified endpoint. Please send all future requests to this endpoint.",
+            )
+            .custom("Endpoint", endpoint)
+            .custom("Region", expected)
+            .build())
+    }
+
+    /// Fail the next request of `operation` to the object with an error of `code`, once.
+    ///
+    /// `key` is `None` for bucket-level operations. Multiple faults registered for the same
+    /// operation and object are returned by subsequent requests in order. Requests rejected
+    /// by region, throttling or bucket owner checks don't consume faults.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operation is unknown.
+    pub fn fail_next(
+        &self,
+        operation: &str,
+        bucket: &str,
+        key: Option<&str>,
+        code: &str,
+        message: &str,
+    ) {
+        let operation = (Request::OPERATIONS.iter())
+            .find(|op| **op == operation)
+            .unwrap_or_else(|| panic!("unknown operation: {operation}"));
+        let record = RequestRecord {
+            operation,
+            bucket: bucket.into(),
+            key: key.map(String::from),
+        };
+        let mut faults = self.faults.lock();
+        faults
+            .entry(record)
+            .or_default()
+            .push_back(error(code, message));
+    }
+
+    /// Returns the next injected error for the request, if any.
+    pub(crate) fn take_fault(
+        &self,
+        record: &RequestRecord,
+    ) -> Result<(), aws_smithy_types::error::Error> {
+        let mut faults = self.faults.lock();
+        let Some(queue) = faults.get_mut(record) else {
+            return Ok(());
+        };
+        let e = queue.pop_front().unwrap();
+        if queue.is_empty() {
+            faults.remove(record);
+        }
+        Err(e)
+    }
+
+    /// Set the account ID that owns the bucket.
+    ///
+    /// Requests to the bucket with a different `expected_bucket_owner` fail with `AccessDenied`.
+    pub fn set_bucket_owner(&self, bucket: impl Into<String>, owner: impl Into<String>) {
+        self.inner.lock().owners.insert(bucket.into(), owner.into());
+    }
+
+    /// Set the region of buckets returned by `GetBucketLocation`.
+    ///
+    /// Defaults to `us-east-1`. Once set, requests from other regions are rejected.
+    pub fn set_region(&self, region: impl Into<String>) {
+        self.inner.lock().region = Some(region.into());
+    }
+
+    /// Set the versioning status of the bucket returned by `GetBucketVersioning`.
+    ///
+    /// NOTE: Object versions are not simulated. This only affects the reported status.
+    pub fn set_bucket_versioning(&self, bucket: impl Into<String>, status: BucketVersioningStatus) {
+        self.inner.lock().versioning.insert(bucket.into(), status);
+    }
+
+    /// Set the time it takes to restore an archived object by `RestoreObject`.
+    ///
+    /// Defaults to zero, so restored objects are available immediately.
+    pub fn set_restore_delay(&self, delay: Duration) {
+        self.inner.lock().restore_delay = delay;
+    }
+
+    /// Set the size limits of objects and parts.
+    pub fn set_size_limits(&self, limits: SizeLimits) {
+        self.inner.lock().limits = limits;
+    }
+
+    /// Set the capacity of the bucket in bytes.
+    ///
+    /// `PutObject` and `CompleteMultipartUpload` fail with `QuotaExceeded` if the usage of
+    /// the bucket would exceed the capacity. The usage includes the completed objects and
+    /// the parts of multipart uploads in progress, until they are completed or aborted.
+    /// No capacity is enforced by default.
+    pub fn set_bucket_quota(&self, bucket: impl Into<String>, capacity: u64) {
+        self.inner.lock().quotas.insert(bucket.into(), capacity);
+    }
+
+    /// Returns the total size in bytes of the completed objects in the bucket.
+    ///
+    /// Returns 0 if the bucket does not exist.
+    pub fn bucket_size(&self, bucket: &str) -> u64 {
+        self.inner.lock().bucket_size(bucket)
+    }
+
+    /// Hide written objects from `GetObject`, `HeadObject` and `ListObjectsV2` for `delay`
+    /// of simulated time after each write, simulating eventual consistency.
+    ///
+    /// Overwritten objects are hidden as well. Defaults to zero, i.e. strong consistency.
+    pub fn set_read_delay(&self, delay: Duration) {
+        self.inner.lock().read_delay = delay;
+    }
+
+    /// Returns the IDs of multipart uploads in progress for the given key.
+    ///
+    /// An object that is not found but has active uploads is being uploaded,
+    /// while one without any never existed or has been deleted.
+    pub fn active_uploads(&self, bucket: &str, key: &str) -> Vec<String> {
+        self.inner.lock().active_uploads(bucket, key)
+    }
+
+    /// Returns the multipart uploads in progress in the bucket, ordered by key and upload ID.
+    ///
+    /// Uploads that are never completed or aborted stay here forever, which is useful to
+    /// test garbage collection of stale uploads.
+    pub fn list_incomplete_uploads(&self, bucket: &str) -> Vec<IncompleteUpload> {
+        self.inner.lock().list_incomplete_uploads(bucket)
+    }
+
+    /// Returns a token granting `GetObject` access to the object until `expires_in` elapses.
+    ///
+    /// This simulates presigned URLs. Use the token with [`get_object_presigned`].
+    ///
+    /// [`get_object_presigned`]: S3Service::get_object_presigned
+    pub fn presign_get(
+        &self,
+        bucket: impl Into<String>,
+        key: impl Into<String>,
+        expires_in: Duration,
+    ) -> String {
+        let expires_at = self.time.now_instant() + expires_in;
+        self.inner
+            .lock()
+            .presign_get(bucket.into(), key.into(), expires_at)
+    }
+
+    /// Get the object granted by a token from [`presign_get`].
+    ///
+    /// Returns `AccessDenied` if the token is invalid or has expired.
+    ///
+    /// [`presign_get`]: S3Service::presign_get
+    pub async fn get_object_presigned(
+        &self,
+        token: &str,
+    ) -> Result<GetObjectOutput, GetObjectError> {
+        let now = self.time.now_instant();
+        let mut inner = self.inner.lock();
+        let (bucket, key, expires_at) = (inner.presigned.get(token).cloned()).ok_or_else(|| {
+            GetObjectError::generic(error("AccessDenied", "Invalid presigned token"))
+        })?;
+        if now >= expires_at {
+            return Err(GetObjectError::generic(error(
+                "AccessDenied",
+                "Request has expired",
+            )));
+        }
+        inner.get_object(bucket, key, None, None, None, None, self.now())
+    }
+
+    pub(crate) fn log_request(&self, request: &Request) {
+        if let Some(log) = self.request_log.lock().as_mut() {
+            log.push(request.record());
         }
     }
 
@@ -47,19 +679,26 @@ impl S3Service {
         &self,
         bucket: String,
         key: String,
+        metadata: ObjectMetadata,
     ) -> Result<CreateMultipartUploadOutput, CreateMultipartUploadError> {
-        self.inner.lock().create_multipart_upload(bucket, key)
+        let now = self.now();
+        self.inner
+            .lock()
+            .create_multipart_upload(bucket, key, metadata, now)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn upload_part(
         &self,
         bucket: String,
         key: String,
         body: Bytes,
-        content_length: i64,
+        content_length: Option<i64>,
+        content_md5: Option<String>,
         part_number: i32,
         upload_id: String,
     ) -> Result<UploadPartOutput, UploadPartError> {
+        check_content_md5(content_md5.as_deref(), &body).map_err(UploadPartError::generic)?;
         self.inner
             .lock()
             .upload_part(bucket, key, body, content_length, part_number, upload_id)
@@ -72,9 +711,10 @@ impl S3Service {
         multipart: crate::model::CompletedMultipartUpload,
         upload_id: String,
     ) -> Result<CompleteMultipartUploadOutput, CompleteMultipartUploadError> {
+        let now = self.now();
         self.inner
             .lock()
-            .complete_multipart_upload(bucket, key, multipart, upload_id)
+            .complete_multipart_upload(bucket, key, multipart, upload_id, now)
     }
 
     pub async fn abort_multipart_upload(
@@ -94,19 +734,38 @@ impl S3Service {
         key: String,
         range: Option<String>,
         part_number: Option<i32>,
+        if_match: Option<String>,
+        if_modified_since: Option<crate::types::DateTime>,
     ) -> Result<GetObjectOutput, GetObjectError> {
-        self.inner
-            .lock()
-            .get_object(bucket, key, range, part_number)
+        let now = self.now();
+        self.inner.lock().get_object(
+            bucket,
+            key,
+            range,
+            part_number,
+            if_match,
+            if_modified_since,
+            now,
+        )
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn put_object(
         &self,
         bucket: String,
         key: String,
         object: Bytes,
+        content_length: Option<i64>,
+        content_md5: Option<String>,
+        if_none_match: Option<String>,
+        metadata: ObjectMetadata,
     ) -> Result<PutObjectOutput, PutObjectError> {
-        self.inner.lock().put_object(bucket, key, object)
+        check_content_length(content_length, &object).map_err(PutObjectError::generic)?;
+        check_content_md5(content_md5.as_deref(), &object).map_err(PutObjectError::generic)?;
+        let now = self.now();
+        self.inner
+            .lock()
+            .put_object(bucket, key, object, if_none_match, metadata, now)
     }
 
     pub async fn delete_object(
@@ -114,7 +773,8 @@ impl S3Service {
         bucket: String,
         key: String,
     ) -> Result<DeleteObjectOutput, DeleteObjectError> {
-        self.inner.lock().delete_object(bucket, key)
+        let now = self.now();
+        self.inner.lock().delete_object(bucket, key, now)
     }
 
     pub async fn delete_objects(
@@ -122,7 +782,8 @@ impl S3Service {
         bucket: String,
         delete: crate::model::Delete,
     ) -> Result<DeleteObjectsOutput, DeleteObjectsError> {
-        self.inner.lock().delete_objects(bucket, delete)
+        let now = self.now();
+        self.inner.lock().delete_objects(bucket, delete, now)
     }
 
     pub async fn head_object(
@@ -130,18 +791,45 @@ impl S3Service {
         bucket: String,
         key: String,
     ) -> Result<HeadObjectOutput, HeadObjectError> {
-        self.inner.lock().head_object(bucket, key)
+        let now = self.now();
+        self.inner.lock().head_object(bucket, key, now)
     }
 
+    pub async fn get_object_attributes(
+        &self,
+        bucket: String,
+        key: String,
+        max_parts: i32,
+        part_number_marker: Option<String>,
+        object_attributes: Vec<ObjectAttributes>,
+    ) -> Result<GetObjectAttributesOutput, GetObjectAttributesError> {
+        self.inner.lock().get_object_attributes(
+            bucket,
+            key,
+            max_parts,
+            part_number_marker,
+            object_attributes,
+        )
+    }
+
+    /// Lists objects in the bucket, in lexicographic order of keys.
     pub async fn list_objects_v2(
         &self,
         bucket: String,
         prefix: Option<String>,
         continuation_token: Option<String>,
+        start_after: Option<String>,
+        max_keys: i32,
     ) -> Result<ListObjectsV2Output, ListObjectsV2Error> {
-        self.inner
-            .lock()
-            .list_objects_v2(bucket, prefix, continuation_token)
+        let now = self.now();
+        self.inner.lock().list_objects_v2(
+            bucket,
+            prefix,
+            continuation_token,
+            start_after,
+            max_keys,
+            now,
+        )
     }
 
     pub async fn get_bucket_lifecycle_configuration(
@@ -168,6 +856,201 @@ impl S3Service {
             expected_bucket_owner,
         )
     }
+
+    pub async fn get_bucket_location(
+        &self,
+        bucket: String,
+        expected_bucket_owner: Option<String>,
+    ) -> Result<GetBucketLocationOutput, GetBucketLocationError> {
+        self.inner
+            .lock()
+            .get_bucket_location(bucket, expected_bucket_owner)
+    }
+
+    pub async fn get_bucket_versioning(
+        &self,
+        bucket: String,
+        expected_bucket_owner: Option<String>,
+    ) -> Result<GetBucketVersioningOutput, GetBucketVersioningError> {
+        self.inner
+            .lock()
+            .get_bucket_versioning(bucket, expected_bucket_owner)
+    }
+
+    pub async fn put_object_lock_configuration(
+        &self,
+        bucket: String,
+        object_lock_configuration: Option<ObjectLockConfiguration>,
+    ) -> Result<PutObjectLockConfigurationOutput, PutObjectLockConfigurationError> {
+        self.inner
+            .lock()
+            .put_object_lock_configuration(bucket, object_lock_configuration)
+    }
+
+    pub async fn get_object_lock_configuration(
+        &self,
+        bucket: String,
+    ) -> Result<GetObjectLockConfigurationOutput, GetObjectLockConfigurationError> {
+        self.inner.lock().get_object_lock_configuration(bucket)
+    }
+
+    pub async fn put_object_retention(
+        &self,
+        bucket: String,
+        key: String,
+        retention: Option<ObjectLockRetention>,
+        bypass_governance_retention: bool,
+    ) -> Result<PutObjectRetentionOutput, PutObjectRetentionError> {
+        let now = self.now();
+        self.inner.lock().put_object_retention(
+            bucket,
+            key,
+            retention,
+            bypass_governance_retention,
+            now,
+        )
+    }
+
+    pub async fn put_object_legal_hold(
+        &self,
+        bucket: String,
+        key: String,
+        legal_hold: Option<ObjectLockLegalHold>,
+    ) -> Result<PutObjectLegalHoldOutput, PutObjectLegalHoldError> {
+        self.inner
+            .lock()
+            .put_object_legal_hold(bucket, key, legal_hold)
+    }
+
+    pub async fn restore_object(
+        &self,
+        bucket: String,
+        key: String,
+        restore_request: Option<RestoreRequest>,
+    ) -> Result<RestoreObjectOutput, RestoreObjectError> {
+        let now = self.now();
+        self.inner
+            .lock()
+            .restore_object(bucket, key, restore_request, now)
+    }
+
+    pub async fn head_bucket(&self, bucket: String) -> Result<HeadBucketOutput, HeadBucketError> {
+        self.inner.lock().head_bucket(bucket)
+    }
+}
+
+/// Size limits of objects and parts. `None` means unlimited.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SizeLimits {
+    /// The maximum size of an object uploaded in a single `PutObject`.
+    /// Larger uploads fail with `EntityTooLarge`.
+    pub max_object_size: Option<u64>,
+    /// The maximum size of a part. Larger parts fail with `EntityTooLarge`.
+    pub max_part_size: Option<u64>,
+    /// The minimum size of all but the last part of a multipart upload.
+    /// Completing an upload with smaller parts fails with `EntityTooSmall`.
+    pub min_part_size: Option<u64>,
+}
+
+impl SizeLimits {
+    /// The limits of real S3: 5 GiB for objects and parts, and 5 MiB for parts except the last.
+    pub fn s3() -> Self {
+        SizeLimits {
+            max_object_size: Some(5 << 30),
+            max_part_size: Some(5 << 30),
+            min_part_size: Some(5 << 20),
+        }
+    }
+}
+
+/// A multipart upload in progress, as returned by [`S3Service::list_incomplete_uploads`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncompleteUpload {
+    pub key: String,
+    pub upload_id: String,
+    /// The number of distinct parts uploaded so far.
+    pub part_count: usize,
+    /// The time of `CreateMultipartUpload`.
+    pub initiated_at: crate::types::DateTime,
+}
+
+/// HTTP headers stored with an object and returned by `GetObject` and `HeadObject`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ObjectMetadata {
+    pub cache_control: Option<String>,
+    pub content_encoding: Option<String>,
+    pub expires: Option<crate::types::DateTime>,
+    /// The storage class of the object. `None` for `STANDARD`.
+    pub storage_class: Option<StorageClass>,
+    /// The server-side encryption of the object. It is only recorded, not performed.
+    pub server_side_encryption: Option<ServerSideEncryption>,
+    /// The ID of the KMS key used by `aws:kms` encryption.
+    pub ssekms_key_id: Option<String>,
+}
+
+impl ObjectMetadata {
+    /// Returns an `InvalidArgument` error if a KMS key ID is given without `aws:kms`
+    /// encryption.
+    fn check_encryption(&self) -> Result<(), aws_smithy_types::error::Error> {
+        match (&self.server_side_encryption, &self.ssekms_key_id) {
+            (Some(ServerSideEncryption::AwsKms), _) | (_, None) => Ok(()),
+            _ => Err(error(
+                "InvalidArgument",
+                "Server Side Encryption with KMS managed key requires \
+                 HTTP header x-amz-server-side-encryption : aws:kms",
+            )),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Throttle {
+    max_requests: usize,
+    window: Duration,
+    /// (bucket) -> arrival time of requests accepted in the window
+    requests: HashMap<String, VecDeque<Instant>>,
+}
+
+impl Throttle {
+    /// Returns whether to accept a request to the bucket at `now`.
+    fn accept(&mut self, bucket: String, now: Instant) -> bool {
+        let requests = self.requests.entry(bucket).or_default();
+        while matches!(requests.front(), Some(t) if *t + self.window <= now) {
+            requests.pop_front();
+        }
+        if requests.len() >= self.max_requests {
+            return false;
+        }
+        requests.push_back(now);
+        true
+    }
+}
+
+#[derive(Debug, Default)]
+struct TokenBuckets {
+    /// The maximum number of tokens. `None` if unlimited.
+    capacity: Option<f64>,
+    /// The number of tokens added per second. `None` if unlimited.
+    refill_rate: Option<f64>,
+    /// (bucket) -> (tokens, last refill time)
+    tokens: HashMap<String, (f64, Instant)>,
+}
+
+impl TokenBuckets {
+    /// Returns whether to accept a request to the bucket at `now`, consuming a token if so.
+    fn accept(&mut self, bucket: String, now: Instant) -> bool {
+        let (Some(capacity), Some(refill_rate)) = (self.capacity, self.refill_rate) else {
+            return true;
+        };
+        let (tokens, last) = self.tokens.entry(bucket).or_insert((capacity, now));
+        *tokens = (*tokens + refill_rate * (now - *last).as_secs_f64()).min(capacity);
+        *last = now;
+        if *tokens < 1.0 {
+            return false;
+        }
+        *tokens -= 1.0;
+        true
+    }
 }
 
 #[derive(Debug, Default)]
@@ -177,6 +1060,53 @@ struct ServiceInner {
 
     /// (bucket) -> LifecycleRules
     lifecycle: BTreeMap<String, Vec<LifecycleRule>>,
+
+    /// (bucket) -> versioning status
+    versioning: BTreeMap<String, BucketVersioningStatus>,
+
+    /// (bucket) -> owner account ID
+    owners: BTreeMap<String, String>,
+
+    /// (bucket) -> object lock configuration
+    object_lock: BTreeMap<String, ObjectLockConfiguration>,
+
+    /// (bucket) -> capacity in bytes
+    quotas: BTreeMap<String, u64>,
+
+    /// The region of buckets. `None` for `us-east-1`.
+    region: Option<String>,
+
+    limits: SizeLimits,
+
+    /// The duration that objects are invisible to reads after written.
+    read_delay: Duration,
+
+    /// The duration of restoring archived objects.
+    restore_delay: Duration,
+
+    /// (token) -> (bucket, key, expiration) of presigned access
+    presigned: HashMap<String, (String, String, Instant)>,
+
+    ids: IdGenerator,
+}
+
+/// Generator of upload IDs and ETags.
+///
+/// It is seeded from the global RNG on creation, so that the generated IDs only depend
+/// on the seed and the order of requests, but not on other random events in the simulation.
+#[derive(Debug)]
+struct IdGenerator(SmallRng);
+
+impl Default for IdGenerator {
+    fn default() -> Self {
+        IdGenerator(SmallRng::seed_from_u64(thread_rng().gen()))
+    }
+}
+
+impl IdGenerator {
+    fn next(&mut self) -> String {
+        self.0.gen::<u32>().to_string()
+    }
 }
 
 #[derive(Debug, Default)]
@@ -188,9 +1118,130 @@ struct Object {
     /// upload_id -> parts
     parts: BTreeMap<String, Vec<ObjectPart>>,
 
+    /// upload_id -> initiation time
+    initiated: BTreeMap<String, crate::types::DateTime>,
+
+    /// upload_id -> metadata of the object to complete
+    upload_metadata: BTreeMap<String, ObjectMetadata>,
+
     last_modified: Option<crate::types::DateTime>,
 
     content_length: i64,
+
+    e_tag: Option<String>,
+
+    /// (part_number, byte range) of the parts this object is assembled from, in order.
+    /// Empty if the object was not uploaded by a multipart upload.
+    part_ranges: Vec<(i32, Range<usize>)>,
+
+    metadata: ObjectMetadata,
+
+    /// The retention period set on the object, if any.
+    retention: Option<ObjectLockRetention>,
+
+    /// Whether a legal hold is placed on the object.
+    legal_hold: bool,
+
+    /// The restoration of the object, if archived and requested.
+    restore: Option<Restore>,
+}
+
+/// The restoration of an archived object.
+#[derive(Debug, Clone, Copy)]
+struct Restore {
+    /// The time when the restored copy becomes available.
+    completed_at: crate::types::DateTime,
+    /// The time when the restored copy expires.
+    expires_at: crate::types::DateTime,
+}
+
+impl Object {
+    /// Returns an `AccessDenied` error if the object can not be deleted or overwritten at `now`,
+    /// because it is under a legal hold or its retention period has not elapsed.
+    fn check_unlocked(
+        &self,
+        now: &crate::types::DateTime,
+    ) -> Result<(), aws_smithy_types::error::Error> {
+        if !self.completed {
+            return Ok(());
+        }
+        if self.legal_hold {
+            return Err(error("AccessDenied", "the object is under a legal hold"));
+        }
+        match self.retain_until() {
+            Some(t) if t.as_nanos() > now.as_nanos() => Err(error(
+                "AccessDenied",
+                format!("the object is under retention until {}s", t.secs()),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns whether the object is archived and must be restored before reads.
+    fn archived(&self) -> bool {
+        matches!(
+            self.metadata.storage_class,
+            Some(StorageClass::Glacier | StorageClass::DeepArchive)
+        )
+    }
+
+    /// Returns the `x-amz-restore` header of the object at `now`.
+    fn restore_header(&self, now: &crate::types::DateTime) -> Option<String> {
+        let restore = self.restore.as_ref()?;
+        if now.as_nanos() < restore.completed_at.as_nanos() {
+            Some(r#"ongoing-request="true""#.into())
+        } else if now.as_nanos() < restore.expires_at.as_nanos() {
+            let expiry = (restore.expires_at.fmt(Format::HttpDate)).unwrap();
+            Some(format!(
+                r#"ongoing-request="false", expiry-date="{expiry}""#
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Returns an `InvalidObjectState` error if the object is archived and no restored
+    /// copy is available at `now`.
+    fn check_restored(
+        &self,
+        now: &crate::types::DateTime,
+    ) -> Result<(), aws_smithy_types::error::Error> {
+        let restored = matches!(self.restore_header(now), Some(h) if h.contains("expiry-date"));
+        if self.archived() && !restored {
+            return Err(error(
+                "InvalidObjectState",
+                "The operation is not valid for the object's storage class",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the end of the retention period of the object.
+    fn retain_until(&self) -> Option<&crate::types::DateTime> {
+        self.retention.as_ref()?.retain_until_date.as_ref()
+    }
+
+    /// Returns the number of bytes stored for the object, including the parts of multipart
+    /// uploads in progress except `except_upload`.
+    fn usage(&self, except_upload: Option<&str>) -> u64 {
+        let body = if self.completed { self.body.len() } else { 0 };
+        let parts = (self.parts.iter())
+            .filter(|(id, _)| Some(id.as_str()) != except_upload)
+            .flat_map(|(_, parts)| parts)
+            .map(|part| part.body.len())
+            .sum::<usize>();
+        (body + parts) as u64
+    }
+
+    /// Returns whether the object is visible to reads at `now`.
+    fn visible(&self, now: &crate::types::DateTime, read_delay: Duration) -> bool {
+        match self.last_modified {
+            Some(t) if !read_delay.is_zero() => {
+                t.as_nanos() + read_delay.as_nanos() as i128 <= now.as_nanos()
+            }
+            _ => true,
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -210,26 +1261,88 @@ impl ServiceInner {
         self.storage.insert(name.to_string(), Default::default());
     }
 
+    fn bucket_size(&self, bucket: &str) -> u64 {
+        let Some(objects) = self.storage.get(bucket) else {
+            return 0;
+        };
+        (objects.values())
+            .filter(|object| object.completed)
+            .map(|object| object.body.len() as u64)
+            .sum()
+    }
+
+    /// Returns the number of bytes that can be written to `key` in the bucket without
+    /// exceeding its quota, replacing the completed object and the parts of `upload_id`.
+    ///
+    /// Returns `None` if the bucket has no quota.
+    fn available_capacity(&self, bucket: &str, key: &str, upload_id: Option<&str>) -> Option<u64> {
+        let capacity = *self.quotas.get(bucket)?;
+        let objects = self.storage.get(bucket)?;
+        let used: u64 = (objects.iter())
+            .map(|(k, object)| match k == key {
+                // the completed object would be replaced
+                true if object.completed => object.usage(upload_id) - object.body.len() as u64,
+                true => object.usage(upload_id),
+                false => object.usage(None),
+            })
+            .sum();
+        Some(capacity.saturating_sub(used))
+    }
+
+    fn active_uploads(&self, bucket: &str, key: &str) -> Vec<String> {
+        self.storage
+            .get(bucket)
+            .and_then(|objects| objects.get(key))
+            .map(|object| object.parts.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn list_incomplete_uploads(&self, bucket: &str) -> Vec<IncompleteUpload> {
+        let Some(objects) = self.storage.get(bucket) else {
+            return vec![];
+        };
+        let mut uploads = vec![];
+        for (key, object) in objects {
+            for (upload_id, parts) in &object.parts {
+                let part_numbers = parts.iter().map(|p| p.part_number);
+                uploads.push(IncompleteUpload {
+                    key: key.clone(),
+                    upload_id: upload_id.clone(),
+                    part_count: part_numbers.collect::<BTreeSet<_>>().len(),
+                    initiated_at: object.initiated[upload_id],
+                });
+            }
+        }
+        uploads
+    }
+
     fn create_multipart_upload(
         &mut self,
         bucket: String,
         key: String,
+        metadata: ObjectMetadata,
+        now: crate::types::DateTime,
     ) -> Result<CreateMultipartUploadOutput, CreateMultipartUploadError> {
         debug!(bucket, key, "create_multipart_upload");
+        (metadata.check_encryption()).map_err(CreateMultipartUploadError::generic)?;
         let object = self
             .storage
             .get_mut(&bucket)
             .ok_or_else(|| CreateMultipartUploadError::unhandled(no_such_bucket(&bucket)))?
-            .entry(key)
+            .entry(key.clone())
             .or_default();
 
         loop {
-            let upload_id = thread_rng().gen::<u32>().to_string();
+            let upload_id = self.ids.next();
             if object.parts.contains_key(&upload_id) {
                 continue;
             } else {
                 object.parts.insert(upload_id.clone(), Default::default());
+                object.initiated.insert(upload_id.clone(), now);
+                object.upload_metadata.insert(upload_id.clone(), metadata);
                 return Ok(CreateMultipartUploadOutput {
+                    bucket: Some(bucket),
+                    key: Some(key),
                     upload_id: Some(upload_id),
                 });
             }
@@ -241,11 +1354,13 @@ impl ServiceInner {
         bucket: String,
         key: String,
         body: Bytes,
-        _content_length: i64,
+        content_length: Option<i64>,
         part_number: i32,
         upload_id: String,
     ) -> Result<UploadPartOutput, UploadPartError> {
         debug!(bucket, key, upload_id, part_number, "upload_part");
+        check_content_length(content_length, &body).map_err(UploadPartError::generic)?;
+        check_max_size(self.limits.max_part_size, &body).map_err(UploadPartError::generic)?;
         let object = self
             .storage
             .get_mut(&bucket)
@@ -258,13 +1373,17 @@ impl ServiceInner {
             .get_mut(&upload_id)
             .ok_or_else(|| UploadPartError::unhandled(no_such_upload(&upload_id)))?;
 
-        let e_tag = thread_rng().gen::<u32>().to_string();
+        let e_tag = self.ids.next();
         let part = ObjectPart {
             part_number,
             body,
             e_tag: e_tag.clone(),
         };
-        parts.push(part);
+        // uploading a part number again overwrites the previous part
+        match parts.iter_mut().find(|p| p.part_number == part_number) {
+            Some(p) => *p = part,
+            None => parts.push(part),
+        }
 
         let e_tag = Some(e_tag);
         Ok(UploadPartOutput { e_tag })
@@ -276,8 +1395,11 @@ impl ServiceInner {
         key: String,
         multipart: crate::model::CompletedMultipartUpload,
         upload_id: String,
+        now: crate::types::DateTime,
     ) -> Result<CompleteMultipartUploadOutput, CompleteMultipartUploadError> {
         debug!(bucket, key, upload_id, "complete_multipart_upload");
+        let retention = self.default_retention(&bucket, &now);
+        let available = self.available_capacity(&bucket, &key, Some(&upload_id));
         let object = self
             .storage
             .get_mut(&bucket)
@@ -290,46 +1412,81 @@ impl ServiceInner {
             .get_mut(&upload_id)
             .ok_or_else(|| CompleteMultipartUploadError::unhandled(no_such_upload(&upload_id)))?;
 
-        if let Some(mut multipart) = multipart.parts {
-            multipart.sort_by_key(|part| part.part_number);
+        if let Some(multipart) = multipart.parts {
+            if multipart
+                .windows(2)
+                .any(|w| w[0].part_number >= w[1].part_number)
+            {
+                return Err(CompleteMultipartUploadError::generic(error(
+                    "InvalidPartOrder",
+                    "the list of parts was not in ascending order",
+                )));
+            }
             let mut selection_idx = vec![];
-            for completed_part in multipart {
-                for (idx, part) in parts.iter().enumerate() {
-                    if part.part_number == completed_part.part_number {
-                        if let Some(e_tag) = &completed_part.e_tag {
-                            if e_tag == &part.e_tag {
-                                selection_idx.push(idx);
-                                break;
-                            }
-                        } else {
-                            selection_idx.push(idx);
-                            break;
-                        }
-                    }
+            for completed_part in &multipart {
+                let idx = parts
+                    .iter()
+                    .position(|part| {
+                        part.part_number == completed_part.part_number
+                            && !matches!(&completed_part.e_tag, Some(e) if e != &part.e_tag)
+                    })
+                    .ok_or_else(|| {
+                        CompleteMultipartUploadError::generic(error(
+                            "InvalidPart",
+                            format!(
+                                "part {} could not be found or its ETag did not match",
+                                completed_part.part_number
+                            ),
+                        ))
+                    })?;
+                selection_idx.push(idx);
+            }
+            // all parts except the last must not be smaller than the minimum size
+            if let Some(min_size) = self.limits.min_part_size {
+                let non_final = &selection_idx[..selection_idx.len().saturating_sub(1)];
+                if let Some(part) = (non_final.iter().map(|idx| &parts[*idx]))
+                    .find(|part| (part.body.len() as u64) < min_size)
+                {
+                    return Err(CompleteMultipartUploadError::generic(error(
+                        "EntityTooSmall",
+                        format!(
+                            "Your proposed upload is smaller than the minimum allowed size: part {}",
+                            part.part_number
+                        ),
+                    )));
                 }
             }
 
-            selection_idx.sort();
-            let mut selection_idx = VecDeque::from(selection_idx);
-            let mut body = vec![];
+            let len = (selection_idx.iter())
+                .map(|idx| parts[*idx].body.len())
+                .sum();
+            check_quota(available, len).map_err(CompleteMultipartUploadError::generic)?;
+            (object.check_unlocked(&now)).map_err(CompleteMultipartUploadError::generic)?;
             let parts = object.parts.remove(&upload_id).unwrap();
-
-            for (idx, part) in parts.into_iter().enumerate() {
-                if let Some(next_idx) = selection_idx.front() {
-                    if *next_idx != idx {
-                        continue;
-                    } else {
-                        body.extend(part.body);
-                        selection_idx.pop_front();
-                    }
-                } else {
-                    break;
-                }
+            object.initiated.remove(&upload_id);
+            let metadata = object
+                .upload_metadata
+                .remove(&upload_id)
+                .unwrap_or_default();
+            let mut body = vec![];
+            object.part_ranges.clear();
+            for idx in &selection_idx {
+                let part = &parts[*idx];
+                let range = body.len()..body.len() + part.body.len();
+                body.extend_from_slice(&part.body);
+                object.part_ranges.push((part.part_number, range));
             }
 
+            // the ETag of a multipart object ends with the number of parts
+            object.e_tag = Some(format!("{}-{}", self.ids.next(), selection_idx.len()));
+            object.content_length = body.len() as i64;
             object.body = body.into();
+            object.metadata = metadata;
+            object.restore = None;
+            object.retention = retention;
+            object.legal_hold = false;
             object.completed = true;
-            object.parts.remove(&upload_id);
+            object.last_modified = Some(now);
 
             Ok(CompleteMultipartUploadOutput {})
         } else {
@@ -359,75 +1516,154 @@ impl ServiceInner {
             .parts
             .remove(&upload_id)
             .ok_or_else(|| AbortMultipartUploadError::unhandled(no_such_upload(&upload_id)))?;
+        object.initiated.remove(&upload_id);
+        object.upload_metadata.remove(&upload_id);
         Ok(AbortMultipartUploadOutput {})
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn get_object(
         &self,
         bucket: String,
         key: String,
         range: Option<String>,
         part_number: Option<i32>,
+        if_match: Option<String>,
+        if_modified_since: Option<crate::types::DateTime>,
+        now: crate::types::DateTime,
     ) -> Result<GetObjectOutput, GetObjectError> {
-        debug!(bucket, key, range, part_number, "get_object");
+        debug!(
+            bucket,
+            key,
+            range,
+            part_number,
+            if_match,
+            ?if_modified_since,
+            "get_object"
+        );
         let object = self
             .storage
             .get(&bucket)
             .ok_or_else(|| GetObjectError::unhandled(no_such_bucket(&bucket)))?
             .get(&key)
             .ok_or_else(|| {
-                GetObjectError::new(GetObjectErrorKind::NoSuchKey(no_such_key(&key)), meta())
+                GetObjectError::new(
+                    GetObjectErrorKind::NoSuchKey(no_such_key(&key)),
+                    meta("NoSuchKey"),
+                )
             })?;
-        if !object.completed {
+        if !object.completed || !object.visible(&now, self.read_delay) {
             return Err(GetObjectError::new(
                 GetObjectErrorKind::NoSuchKey(no_such_key(&key)),
-                meta(),
+                meta("NoSuchKey"),
             ));
         }
-
-        if let Some(range) = range {
-            let invalid_range = || GetObjectError::unhandled(format!("invalid range: {range}"));
-            // https://www.rfc-editor.org/rfc/rfc9110.html#name-range
-            let mut split = range.split('=');
-            let range_unit = split.next().ok_or_else(invalid_range)?;
-
-            if range_unit != "bytes" {
-                return Err(GetObjectError::unhandled(format!(
-                    "unsupported range unit: {range_unit}"
+        object
+            .check_restored(&now)
+            .map_err(GetObjectError::generic)?;
+        // the ETag of a multipart object has a `-N` suffix, which must be matched too
+        if let Some(if_match) = &if_match {
+            let matched = (if_match
+                .split(',')
+                .map(|e_tag| e_tag.trim().trim_matches('"')))
+            .any(|e_tag| e_tag == "*" || Some(e_tag) == object.e_tag.as_deref());
+            if !matched {
+                return Err(GetObjectError::generic(error(
+                    "PreconditionFailed",
+                    "at least one of the pre-conditions you specified did not hold",
                 )));
             }
+        }
+        // the dates in HTTP headers have a precision of seconds
+        if let (Some(since), Some(last_modified)) = (if_modified_since, object.last_modified) {
+            if last_modified.secs() <= since.secs() {
+                return Err(GetObjectError::generic(error(
+                    "NotModified",
+                    "Not Modified",
+                )));
+            }
+        }
 
-            let range_set = split.next().ok_or_else(invalid_range)?;
-
-            let (begin_str, end_str) = range_set.split_once('-').ok_or_else(invalid_range)?;
-            let begin_pos = if begin_str.is_empty() {
-                None
-            } else {
-                Some(begin_str.parse::<usize>().map_err(|_| invalid_range())?)
-            };
-            let end_pos = if end_str.is_empty() {
-                None
-            } else {
-                Some(end_str.parse::<usize>().map_err(|_| invalid_range())?)
-            };
-            let body = match (begin_pos, end_pos) {
-                (Some(begin), Some(end)) => object.body.slice(begin..=end),
-                (Some(begin), None) => object.body.slice(begin..),
-                (None, Some(len)) => object.body.slice(object.body.len() - len..),
-                (None, None) => object.body.slice(..),
+        // an empty range is ignored
+        if let Some(range) = range.filter(|range| !range.is_empty()) {
+            let total = object.body.len();
+            let Some(Range { start: begin, end }) = parse_range(&range, total) else {
+                return Err(GetObjectError::generic(error(
+                    "InvalidRange",
+                    format!("The requested range is not satisfiable: {range}"),
+                )));
             };
+            let body = object.body.slice(begin..end);
 
-            Ok(GetObjectOutput { body: body.into() })
+            Ok(GetObjectOutput {
+                accept_ranges: Some("bytes".into()),
+                content_length: body.len() as i64,
+                content_range: Some(format!("bytes {begin}-{}/{total}", end - 1)),
+                parts_count: 0,
+                cache_control: object.metadata.cache_control.clone(),
+                content_encoding: object.metadata.content_encoding.clone(),
+                expires: object.metadata.expires,
+                restore: object.restore_header(&now),
+                storage_class: object.metadata.storage_class.clone(),
+                server_side_encryption: object.metadata.server_side_encryption.clone(),
+                ssekms_key_id: object.metadata.ssekms_key_id.clone(),
+                last_modified: object.last_modified,
+                e_tag: object.e_tag.clone(),
+                body: body.into(),
+            })
         } else if let Some(part_number) = part_number {
-            if part_number < 0 || part_number as usize >= object.body.len() {
-                return Err(GetObjectError::unhandled(format!(
-                    "invalid part number: {part_number}"
+            let total = object.body.len();
+            // an object uploaded in one piece consists of a single part
+            let (range, parts_count) = if object.part_ranges.is_empty() {
+                ((part_number == 1).then_some(0..total), 1)
+            } else {
+                let range = (object.part_ranges.iter())
+                    .find(|(number, _)| *number == part_number)
+                    .map(|(_, range)| range.clone());
+                (range, object.part_ranges.len())
+            };
+            let Some(range) = range else {
+                return Err(GetObjectError::generic(error(
+                    "InvalidPartNumber",
+                    "The requested partnumber is not satisfiable",
                 )));
             };
-            let _part_number = part_number as usize;
-            todo!("get object by part number");
+            let body = object.body.slice(range.clone());
+            Ok(GetObjectOutput {
+                accept_ranges: Some("bytes".into()),
+                content_length: body.len() as i64,
+                content_range: Some(format!(
+                    "bytes {}-{}/{total}",
+                    range.start,
+                    range.end.saturating_sub(1)
+                )),
+                parts_count: parts_count as i32,
+                cache_control: object.metadata.cache_control.clone(),
+                content_encoding: object.metadata.content_encoding.clone(),
+                expires: object.metadata.expires,
+                restore: object.restore_header(&now),
+                storage_class: object.metadata.storage_class.clone(),
+                server_side_encryption: object.metadata.server_side_encryption.clone(),
+                ssekms_key_id: object.metadata.ssekms_key_id.clone(),
+                last_modified: object.last_modified,
+                e_tag: object.e_tag.clone(),
+                body: body.into(),
+            })
         } else {
             Ok(GetObjectOutput {
+                accept_ranges: Some("bytes".into()),
+                content_length: object.body.len() as i64,
+                content_range: None,
+                parts_count: 0,
+                cache_control: object.metadata.cache_control.clone(),
+                content_encoding: object.metadata.content_encoding.clone(),
+                expires: object.metadata.expires,
+                restore: object.restore_header(&now),
+                storage_class: object.metadata.storage_class.clone(),
+                server_side_encryption: object.metadata.server_side_encryption.clone(),
+                ssekms_key_id: object.metadata.ssekms_key_id.clone(),
+                last_modified: object.last_modified,
+                e_tag: object.e_tag.clone(),
                 body: object.body.clone().into(),
             })
         }
@@ -438,17 +1674,50 @@ impl ServiceInner {
         bucket: String,
         key: String,
         body: Bytes,
+        if_none_match: Option<String>,
+        metadata: ObjectMetadata,
+        now: crate::types::DateTime,
     ) -> Result<PutObjectOutput, PutObjectError> {
-        debug!(bucket, key, len = body.len(), "put_object");
+        debug!(bucket, key, len = body.len(), if_none_match, "put_object");
+        check_max_size(self.limits.max_object_size, &body).map_err(PutObjectError::generic)?;
+        (metadata.check_encryption()).map_err(PutObjectError::generic)?;
+        let retention = self.default_retention(&bucket, &now);
+        let available = self.available_capacity(&bucket, &key, None);
+        if let Some(if_none_match) = &if_none_match {
+            if if_none_match != "*" {
+                return Err(PutObjectError::generic(error(
+                    "NotImplemented",
+                    format!("unsupported If-None-Match: {if_none_match}"),
+                )));
+            }
+        }
         let object = self
             .storage
             .get_mut(&bucket)
             .ok_or_else(|| PutObjectError::unhandled(no_such_bucket(&bucket)))?
             .entry(key)
             .or_default();
+        if if_none_match.is_some() && object.completed {
+            return Err(PutObjectError::generic(error(
+                "PreconditionFailed",
+                "at least one of the pre-conditions you specified did not hold",
+            )));
+        }
+        object
+            .check_unlocked(&now)
+            .map_err(PutObjectError::generic)?;
+        check_quota(available, body.len()).map_err(PutObjectError::generic)?;
 
+        object.content_length = body.len() as i64;
         object.body = body;
+        object.e_tag = Some(self.ids.next());
+        object.part_ranges.clear();
+        object.metadata = metadata;
+        object.restore = None;
+        object.retention = retention;
+        object.legal_hold = false;
         object.completed = true;
+        object.last_modified = Some(now);
 
         Ok(PutObjectOutput {})
     }
@@ -457,6 +1726,7 @@ impl ServiceInner {
         &mut self,
         bucket: String,
         key: String,
+        now: crate::types::DateTime,
     ) -> Result<DeleteObjectOutput, DeleteObjectError> {
         debug!(bucket, key, "delete_object");
         let object = self
@@ -465,15 +1735,12 @@ impl ServiceInner {
             .ok_or_else(|| DeleteObjectError::unhandled(no_such_bucket(&bucket)))?
             .entry(key);
 
-        if let Occupied(mut o) = object {
+        if let Occupied(o) = object {
+            o.get()
+                .check_unlocked(&now)
+                .map_err(DeleteObjectError::generic)?;
             if o.get().completed {
-                if o.get().parts.is_empty() {
-                    o.remove();
-                } else {
-                    let object = o.get_mut();
-                    object.completed = false;
-                    object.body.clear();
-                }
+                delete_completed(o);
             }
         }
         Ok(DeleteObjectOutput {})
@@ -483,6 +1750,7 @@ impl ServiceInner {
         &mut self,
         bucket: String,
         delete: crate::model::Delete,
+        now: crate::types::DateTime,
     ) -> Result<DeleteObjectsOutput, DeleteObjectsError> {
         debug!(bucket, "delete_objects");
         let bucket = self
@@ -491,23 +1759,37 @@ impl ServiceInner {
             .ok_or_else(|| DeleteObjectsError::unhandled(no_such_bucket(&bucket)))?;
 
         let mut output = DeleteObjectsOutput::builder();
+        let quiet = delete.quiet;
         let Some(delete) = delete.objects else {
             return Ok(output.build());
         };
 
         for key in delete.into_iter().flat_map(|i| i.key) {
-            if let Occupied(mut o) = bucket.entry(key.clone()) {
-                if o.get().completed {
-                    if o.get().parts.is_empty() {
-                        o.remove();
-                    } else {
-                        let object = o.get_mut();
-                        object.completed = false;
-                        object.body.clear();
+            match bucket.entry(key.clone()) {
+                Occupied(o) if o.get().completed && o.get().check_unlocked(&now).is_err() => {
+                    let error = crate::model::Error::builder()
+                        .key(key)
+                        .code("AccessDenied")
+                        .message("Access Denied because object protected by object lock.")
+                        .build();
+                    output = output.errors(error);
+                }
+                Occupied(o) if o.get().completed => {
+                    delete_completed(o);
+                    // successful deletions are omitted in quiet mode
+                    if !quiet {
+                        output = output.deleted(DeletedObject::builder().key(key).build());
                     }
                 }
+                _ => {
+                    let error = crate::model::Error::builder()
+                        .key(key)
+                        .code("NoSuchKey")
+                        .message("The specified key does not exist.")
+                        .build();
+                    output = output.errors(error);
+                }
             }
-            output = output.deleted(DeletedObject::builder().key(key).build());
         }
         Ok(output.build())
     }
@@ -516,6 +1798,7 @@ impl ServiceInner {
         &self,
         bucket: String,
         key: String,
+        now: crate::types::DateTime,
     ) -> Result<HeadObjectOutput, HeadObjectError> {
         debug!(bucket, key, "head_object");
         let object = self
@@ -524,13 +1807,16 @@ impl ServiceInner {
             .ok_or_else(|| HeadObjectError::unhandled(no_such_bucket(&bucket)))?
             .get(&key)
             .ok_or_else(|| {
-                HeadObjectError::new(HeadObjectErrorKind::NotFound(not_found(&key)), meta())
+                HeadObjectError::new(
+                    HeadObjectErrorKind::NotFound(not_found(&key)),
+                    meta("NotFound"),
+                )
             })?;
 
-        if !object.completed {
+        if !object.completed || !object.visible(&now, self.read_delay) {
             return Err(HeadObjectError::new(
                 HeadObjectErrorKind::NotFound(not_found(&key)),
-                meta(),
+                meta("NotFound"),
             ));
         }
         let last_modified = object.last_modified;
@@ -538,56 +1824,156 @@ impl ServiceInner {
         Ok(HeadObjectOutput {
             last_modified,
             content_length,
+            cache_control: object.metadata.cache_control.clone(),
+            content_encoding: object.metadata.content_encoding.clone(),
+            expires: object.metadata.expires,
+            restore: object.restore_header(&now),
+            storage_class: object.metadata.storage_class.clone(),
+            server_side_encryption: object.metadata.server_side_encryption.clone(),
+            ssekms_key_id: object.metadata.ssekms_key_id.clone(),
         })
     }
 
+    fn presign_get(&mut self, bucket: String, key: String, expires_at: Instant) -> String {
+        debug!(bucket, key, ?expires_at, "presign_get");
+        loop {
+            let token = self.ids.next();
+            if !self.presigned.contains_key(&token) {
+                self.presigned
+                    .insert(token.clone(), (bucket, key, expires_at));
+                return token;
+            }
+        }
+    }
+
+    fn get_object_attributes(
+        &self,
+        bucket: String,
+        key: String,
+        max_parts: i32,
+        part_number_marker: Option<String>,
+        object_attributes: Vec<ObjectAttributes>,
+    ) -> Result<GetObjectAttributesOutput, GetObjectAttributesError> {
+        debug!(bucket, key, ?object_attributes, "get_object_attributes");
+        let no_such_key = || {
+            GetObjectAttributesError::new(
+                GetObjectAttributesErrorKind::NoSuchKey(no_such_key(&key)),
+                meta("NoSuchKey"),
+            )
+        };
+        let object = self
+            .storage
+            .get(&bucket)
+            .ok_or_else(|| GetObjectAttributesError::unhandled(no_such_bucket(&bucket)))?
+            .get(&key)
+            .filter(|object| object.completed)
+            .ok_or_else(no_such_key)?;
+
+        let mut output =
+            GetObjectAttributesOutput::builder().set_last_modified(object.last_modified);
+        if object_attributes.contains(&ObjectAttributes::Etag) {
+            output = output.set_e_tag(object.e_tag.clone());
+        }
+        if object_attributes.contains(&ObjectAttributes::ObjectSize) {
+            output = output.object_size(object.content_length);
+        }
+        // NOTE: checksums are not simulated, so `Checksum` is never returned.
+        if object_attributes.contains(&ObjectAttributes::ObjectParts)
+            && !object.part_ranges.is_empty()
+        {
+            let max_parts = if max_parts > 0 { max_parts } else { 1000 };
+            let marker = match &part_number_marker {
+                Some(marker) => marker.parse::<i32>().map_err(|_| {
+                    GetObjectAttributesError::generic(error(
+                        "InvalidArgument",
+                        format!("invalid part number marker: {marker}"),
+                    ))
+                })?,
+                None => 0,
+            };
+            let mut remaining = (object.part_ranges.iter()).filter(|(number, _)| *number > marker);
+            let parts = (remaining.by_ref().take(max_parts as usize))
+                .map(|(number, range)| {
+                    crate::model::ObjectPart::builder()
+                        .part_number(*number)
+                        .size(range.len() as i64)
+                        .build()
+                })
+                .collect::<Vec<_>>();
+            let is_truncated = remaining.next().is_some();
+            let object_parts = GetObjectAttributesParts::builder()
+                .total_parts_count(object.part_ranges.len() as i32)
+                .set_part_number_marker(part_number_marker)
+                .set_next_part_number_marker(
+                    (parts.last())
+                        .filter(|_| is_truncated)
+                        .map(|part| part.part_number.to_string()),
+                )
+                .max_parts(max_parts)
+                .is_truncated(is_truncated)
+                .set_parts(Some(parts))
+                .build();
+            output = output.object_parts(object_parts);
+        }
+        Ok(output.build())
+    }
+
     fn list_objects_v2(
         &mut self,
         bucket: String,
         prefix: Option<String>,
-        _continuation_token: Option<String>,
+        continuation_token: Option<String>,
+        start_after: Option<String>,
+        max_keys: i32,
+        now: crate::types::DateTime,
     ) -> Result<ListObjectsV2Output, ListObjectsV2Error> {
-        debug!(bucket, prefix, "list_objects_v2");
+        debug!(
+            bucket,
+            prefix, continuation_token, start_after, max_keys, "list_objects_v2"
+        );
+        let read_delay = self.read_delay;
         let bucket = self.storage.get_mut(&bucket).ok_or_else(move || {
             ListObjectsV2Error::new(
                 ListObjectsV2ErrorKind::NoSuchBucket(no_such_bucket(&bucket)),
-                meta(),
+                meta("NoSuchBucket"),
             )
         })?;
 
-        if let Some(prefix) = prefix {
-            let objects = bucket
-                .iter()
-                .filter(|(key, object)| key.starts_with(&prefix) && object.completed)
-                .map(|(key, object)| crate::model::Object {
-                    key: Some(key.clone()),
-                    last_modified: None,
-                    e_tag: None,
-                    size: object.content_length,
-                })
-                .collect();
-            Ok(ListObjectsV2Output {
-                is_truncated: false,
-                contents: Some(objects),
-                next_continuation_token: None,
-            })
+        let prefix = prefix.unwrap_or_default();
+        // the continuation token is the last key of the previous page
+        let start_after = continuation_token.or(start_after);
+        let start = match &start_after {
+            Some(key) => Bound::Excluded(key.as_str()),
+            None => Bound::Unbounded,
+        };
+        let max_keys = if max_keys > 0 {
+            max_keys as usize
         } else {
-            Ok(ListObjectsV2Output {
-                is_truncated: false,
-                contents: Some(
-                    bucket
-                        .iter()
-                        .map(|(key, object)| crate::model::Object {
-                            key: Some(key.clone()),
-                            last_modified: None,
-                            e_tag: None,
-                            size: object.content_length,
-                        })
-                        .collect(),
-                ),
-                next_continuation_token: None,
+            1000
+        };
+        let mut objects: Vec<_> = bucket
+            .range::<str, _>((start, Bound::Unbounded))
+            .filter(|(key, object)| {
+                key.starts_with(&prefix) && object.completed && object.visible(&now, read_delay)
             })
-        }
+            .take(max_keys + 1)
+            .map(|(key, object)| crate::model::Object {
+                key: Some(key.clone()),
+                last_modified: object.last_modified,
+                e_tag: object.e_tag.clone(),
+                size: object.content_length,
+            })
+            .collect();
+        let is_truncated = objects.len() > max_keys;
+        objects.truncate(max_keys);
+        let next_continuation_token = (objects.last())
+            .and_then(|object| object.key.clone())
+            .filter(|_| is_truncated);
+        Ok(ListObjectsV2Output {
+            is_truncated,
+            contents: Some(objects),
+            next_continuation_token,
+        })
     }
 
     fn get_bucket_lifecycle_configuration(
@@ -621,6 +2007,263 @@ impl ServiceInner {
 
         Ok(PutBucketLifecycleConfigurationOutput {})
     }
+
+    fn get_bucket_location(
+        &self,
+        bucket: String,
+        _expected_bucket_owner: Option<String>,
+    ) -> Result<GetBucketLocationOutput, GetBucketLocationError> {
+        debug!(bucket, "get_bucket_location");
+        if !self.storage.contains_key(&bucket) {
+            return Err(GetBucketLocationError::unhandled(no_such_bucket(&bucket)));
+        }
+        // buckets in us-east-1 have a null location constraint
+        let location_constraint = (self.region.as_deref())
+            .filter(|region| *region != "us-east-1")
+            .map(BucketLocationConstraint::from);
+        Ok(GetBucketLocationOutput {
+            location_constraint,
+        })
+    }
+
+    fn get_bucket_versioning(
+        &self,
+        bucket: String,
+        _expected_bucket_owner: Option<String>,
+    ) -> Result<GetBucketVersioningOutput, GetBucketVersioningError> {
+        debug!(bucket, "get_bucket_versioning");
+        if !self.storage.contains_key(&bucket) {
+            return Err(GetBucketVersioningError::unhandled(no_such_bucket(&bucket)));
+        }
+        // versioning status is absent if it has never been enabled
+        Ok(GetBucketVersioningOutput {
+            status: self.versioning.get(&bucket).cloned(),
+            mfa_delete: None,
+        })
+    }
+
+    /// Returns the default retention of new objects in the bucket at `now`.
+    fn default_retention(
+        &self,
+        bucket: &str,
+        now: &crate::types::DateTime,
+    ) -> Option<ObjectLockRetention> {
+        let config = self.object_lock.get(bucket)?;
+        let default = config.rule.as_ref()?.default_retention.as_ref()?;
+        let days = default.days.max(0) as u64 + default.years.max(0) as u64 * 365;
+        let retain_until = after(now, Duration::from_secs(days * 24 * 60 * 60));
+        Some(
+            ObjectLockRetention::builder()
+                .set_mode(default.mode.clone())
+                .retain_until_date(retain_until)
+                .build(),
+        )
+    }
+
+    fn put_object_lock_configuration(
+        &mut self,
+        bucket: String,
+        config: Option<ObjectLockConfiguration>,
+    ) -> Result<PutObjectLockConfigurationOutput, PutObjectLockConfigurationError> {
+        debug!(bucket, "put_object_lock_configuration");
+        if !self.storage.contains_key(&bucket) {
+            return Err(PutObjectLockConfigurationError::unhandled(no_such_bucket(
+                &bucket,
+            )));
+        }
+        let config = config.unwrap_or_else(|| ObjectLockConfiguration::builder().build());
+        if config.object_lock_enabled != Some(ObjectLockEnabled::Enabled) {
+            return Err(PutObjectLockConfigurationError::generic(error(
+                "MalformedXML",
+                "ObjectLockEnabled must be Enabled",
+            )));
+        }
+        if let Some(default) =
+            (config.rule.as_ref()).and_then(|rule| rule.default_retention.as_ref())
+        {
+            // exactly one of days and years must be positive
+            if default.mode.is_none() || (default.days > 0) == (default.years > 0) {
+                return Err(PutObjectLockConfigurationError::generic(error(
+                    "MalformedXML",
+                    "default retention must specify a mode and either days or years",
+                )));
+            }
+        }
+        self.object_lock.insert(bucket, config);
+        Ok(PutObjectLockConfigurationOutput {})
+    }
+
+    fn get_object_lock_configuration(
+        &self,
+        bucket: String,
+    ) -> Result<GetObjectLockConfigurationOutput, GetObjectLockConfigurationError> {
+        debug!(bucket, "get_object_lock_configuration");
+        if !self.storage.contains_key(&bucket) {
+            return Err(GetObjectLockConfigurationError::unhandled(no_such_bucket(
+                &bucket,
+            )));
+        }
+        let config = self.object_lock.get(&bucket).cloned().ok_or_else(|| {
+            GetObjectLockConfigurationError::generic(error(
+                "ObjectLockConfigurationNotFoundError",
+                "Object Lock configuration does not exist for this bucket",
+            ))
+        })?;
+        Ok(GetObjectLockConfigurationOutput {
+            object_lock_configuration: Some(config),
+        })
+    }
+
+    /// Returns the object in a bucket with object lock enabled.
+    fn locked_object(
+        &mut self,
+        bucket: &str,
+        key: &str,
+    ) -> Result<&mut Object, aws_smithy_types::error::Error> {
+        if !self.storage.contains_key(bucket) {
+            return Err(error("NoSuchBucket", bucket));
+        }
+        if !self.object_lock.contains_key(bucket) {
+            return Err(error(
+                "InvalidRequest",
+                "Bucket is missing Object Lock Configuration",
+            ));
+        }
+        (self.storage.get_mut(bucket).unwrap().get_mut(key))
+            .filter(|object| object.completed)
+            .ok_or_else(|| error("NoSuchKey", key))
+    }
+
+    fn put_object_retention(
+        &mut self,
+        bucket: String,
+        key: String,
+        retention: Option<ObjectLockRetention>,
+        bypass_governance_retention: bool,
+        now: crate::types::DateTime,
+    ) -> Result<PutObjectRetentionOutput, PutObjectRetentionError> {
+        debug!(bucket, key, "put_object_retention");
+        let object =
+            (self.locked_object(&bucket, &key)).map_err(PutObjectRetentionError::generic)?;
+        let retain_until = |r: Option<&ObjectLockRetention>| {
+            r.and_then(|r| r.retain_until_date.as_ref())
+                .map_or(i128::MIN, |t| t.as_nanos())
+        };
+        // an active retention can only be shortened or removed in governance mode with bypass
+        let active = retain_until(object.retention.as_ref()) > now.as_nanos();
+        let shortened = retain_until(retention.as_ref()) < retain_until(object.retention.as_ref());
+        let governance = matches!(
+            &object.retention,
+            Some(r) if r.mode == Some(ObjectLockRetentionMode::Governance)
+        );
+        if active && shortened && !(governance && bypass_governance_retention) {
+            return Err(PutObjectRetentionError::generic(error(
+                "AccessDenied",
+                "the retention period of the object can not be shortened",
+            )));
+        }
+        object.retention = retention;
+        Ok(PutObjectRetentionOutput {})
+    }
+
+    fn put_object_legal_hold(
+        &mut self,
+        bucket: String,
+        key: String,
+        legal_hold: Option<ObjectLockLegalHold>,
+    ) -> Result<PutObjectLegalHoldOutput, PutObjectLegalHoldError> {
+        debug!(bucket, key, "put_object_legal_hold");
+        let object =
+            (self.locked_object(&bucket, &key)).map_err(PutObjectLegalHoldError::generic)?;
+        object.legal_hold = matches!(
+            legal_hold,
+            Some(ObjectLockLegalHold {
+                status: Some(ObjectLockLegalHoldStatus::On)
+            })
+        );
+        Ok(PutObjectLegalHoldOutput {})
+    }
+
+    fn restore_object(
+        &mut self,
+        bucket: String,
+        key: String,
+        restore_request: Option<RestoreRequest>,
+        now: crate::types::DateTime,
+    ) -> Result<RestoreObjectOutput, RestoreObjectError> {
+        debug!(bucket, key, "restore_object");
+        let restore_delay = self.restore_delay;
+        let object = self
+            .storage
+            .get_mut(&bucket)
+            .ok_or_else(|| RestoreObjectError::unhandled(no_such_bucket(&bucket)))?
+            .get_mut(&key)
+            .filter(|object| object.completed)
+            .ok_or_else(|| RestoreObjectError::generic(error("NoSuchKey", &key)))?;
+        if !object.archived() {
+            return Err(RestoreObjectError::generic(error(
+                "InvalidObjectState",
+                "Restore is not allowed for the object's current storage class",
+            )));
+        }
+        let days = restore_request.map_or(0, |r| r.days);
+        if days < 1 {
+            return Err(RestoreObjectError::generic(error(
+                "MalformedXML",
+                "Days must be a positive integer",
+            )));
+        }
+        let lifetime = Duration::from_secs(days as u64 * 24 * 60 * 60);
+        match &mut object.restore {
+            Some(restore) if now.as_nanos() < restore.completed_at.as_nanos() => {
+                return Err(RestoreObjectError::generic(error(
+                    "RestoreAlreadyInProgress",
+                    "Object restore is already in progress",
+                )));
+            }
+            // the expiry of a restored copy is extended
+            Some(restore) if now.as_nanos() < restore.expires_at.as_nanos() => {
+                restore.expires_at = after(&now, lifetime);
+            }
+            _ => {
+                let completed_at = after(&now, restore_delay);
+                object.restore = Some(Restore {
+                    completed_at,
+                    expires_at: after(&completed_at, lifetime),
+                });
+            }
+        }
+        Ok(RestoreObjectOutput {})
+    }
+
+    fn head_bucket(&self, bucket: String) -> Result<HeadBucketOutput, HeadBucketError> {
+        debug!(bucket, "head_bucket");
+        if !self.storage.contains_key(&bucket) {
+            return Err(HeadBucketError::new(
+                HeadBucketErrorKind::NotFound(not_found(&bucket)),
+                meta("NotFound"),
+            ));
+        }
+        Ok(HeadBucketOutput {})
+    }
+}
+
+/// Deletes the completed object of the entry.
+///
+/// Multipart uploads in progress on the key are not affected: the entry is kept as
+/// an incomplete object holding only the uploads, which is invisible to reads.
+fn delete_completed(mut entry: OccupiedEntry<'_, String, Object>) {
+    if entry.get().parts.is_empty() {
+        entry.remove();
+        return;
+    }
+    let object = entry.get_mut();
+    *object = Object {
+        parts: std::mem::take(&mut object.parts),
+        initiated: std::mem::take(&mut object.initiated),
+        upload_metadata: std::mem::take(&mut object.upload_metadata),
+        ..Default::default()
+    };
 }
 
 /// Returns a `NoSuchBucket` error.
@@ -638,12 +2281,114 @@ fn no_such_upload(upload_id: &str) -> NoSuchUpload {
     NoSuchUpload::builder().message(upload_id).build()
 }
 
+/// Returns the time `d` after `t`.
+fn after(t: &crate::types::DateTime, d: Duration) -> crate::types::DateTime {
+    crate::types::DateTime::from_nanos(t.as_nanos() + d.as_nanos() as i128)
+        .expect("timestamp out of range")
+}
+
 /// Returns a `NotFound` error.
 fn not_found(content: &str) -> NotFound {
     NotFound::builder().message(content).build()
 }
 
-/// Returns a meta.
-fn meta() -> aws_smithy_types::error::Error {
-    aws_smithy_types::error::Error::builder().build()
+/// Returns an `EntityTooLarge` error if the body is larger than `max_size`.
+fn check_max_size(
+    max_size: Option<u64>,
+    body: &Bytes,
+) -> Result<(), aws_smithy_types::error::Error> {
+    match max_size {
+        Some(max_size) if body.len() as u64 > max_size => Err(error(
+            "EntityTooLarge",
+            format!(
+                "Your proposed upload exceeds the maximum allowed size: {} > {max_size}",
+                body.len()
+            ),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Returns a `QuotaExceeded` error if `len` bytes exceed the `available` capacity of the bucket.
+fn check_quota(available: Option<u64>, len: usize) -> Result<(), aws_smithy_types::error::Error> {
+    match available {
+        Some(available) if len as u64 > available => Err(error(
+            "QuotaExceeded",
+            format!("Your proposed upload exceeds the quota of the bucket: {len} > {available}"),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Parses the `Range` header of `GetObject` against an object of `total` bytes.
+///
+/// Only a single byte range is supported, in one of the forms
+/// `bytes=<first>-<last>`, `bytes=<first>-` or `bytes=-<suffix length>`.
+/// A last position beyond the end of the object, or a suffix longer than the
+/// object, is truncated to the end of the object.
+///
+/// Returns `None` if the range is malformed or not satisfiable, that is, the first
+/// position is beyond the end of the object or greater than the last position.
+// https://www.rfc-editor.org/rfc/rfc9110.html#name-range
+fn parse_range(range: &str, total: usize) -> Option<Range<usize>> {
+    let (begin, end) = range.strip_prefix("bytes=")?.split_once('-')?;
+    let range = match (begin, end) {
+        ("", suffix) => total.saturating_sub(suffix.parse().ok()?)..total,
+        (begin, "") => begin.parse().ok()?..total,
+        (begin, end) => {
+            begin.parse().ok()?..end.parse::<usize>().ok()?.saturating_add(1).min(total)
+        }
+    };
+    (range.start < range.end).then_some(range)
+}
+
+/// Returns an `IncompleteBody` error if the body length doesn't match the declared one.
+fn check_content_length(
+    content_length: Option<i64>,
+    body: &Bytes,
+) -> Result<(), aws_smithy_types::error::Error> {
+    match content_length {
+        Some(len) if len != body.len() as i64 => Err(error(
+            "IncompleteBody",
+            format!(
+                "content length {len} does not match body length {}",
+                body.len()
+            ),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Checks the body against the base64-encoded MD5 digest in `Content-MD5`.
+fn check_content_md5(
+    content_md5: Option<&str>,
+    body: &Bytes,
+) -> Result<(), aws_smithy_types::error::Error> {
+    let Some(content_md5) = content_md5 else {
+        return Ok(());
+    };
+    let digest = aws_smithy_types::base64::decode(content_md5)
+        .ok()
+        .filter(|digest| digest.len() == 16)
+        .ok_or_else(|| error("InvalidDigest", "the Content-MD5 you specified was invalid"))?;
+    if digest[..] != md5::Md5::digest(body)[..] {
+        return Err(error(
+            "BadDigest",
+            "the Content-MD5 you specified did not match what we received",
+        ));
+    }
+    Ok(())
+}
+
+/// Returns an error with the given code.
+fn error(code: &str, message: impl Into<String>) -> aws_smithy_types::error::Error {
+    aws_smithy_types::error::Error::builder()
+        .code(code)
+        .message(message)
+        .build()
+}
+
+/// Returns a meta with the error code.
+fn meta(code: &str) -> aws_smithy_types::error::Error {
+    aws_smithy_types::error::Error::builder().code(code).build()
 }
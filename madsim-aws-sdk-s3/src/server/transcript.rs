@@ -0,0 +1,110 @@
+//! Transcripts of requests and the outcomes of their responses.
+
+use super::service::{Request, RequestRecord};
+use std::{collections::VecDeque, fmt, str::FromStr};
+
+/// A request and the outcome of its response, as recorded in a [`Transcript`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptEntry {
+    pub request: RequestRecord,
+    /// The error code of the response, or `None` if the request succeeded.
+    ///
+    /// Errors without a code are recorded as `Unhandled`.
+    pub error: Option<String>,
+}
+
+/// A transcript of the requests processed by [`S3Service`], in order.
+///
+/// A transcript is serialized by [`Display`](fmt::Display) one entry per line, in the form
+/// `<operation> <bucket> <key> <outcome>` separated by tabs, where `<key>` is `-` for
+/// bucket-level operations and `<outcome>` is `OK` or the error code. It can be parsed
+/// back by [`FromStr`]. Bucket names and keys must not contain tabs or newlines.
+///
+/// [`S3Service`]: super::S3Service
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Transcript {
+    pub entries: Vec<TranscriptEntry>,
+}
+
+impl fmt::Display for Transcript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            let request = &entry.request;
+            writeln!(
+                f,
+                "{}\t{}\t{}\t{}",
+                request.operation,
+                request.bucket,
+                request.key.as_deref().unwrap_or("-"),
+                entry.error.as_deref().unwrap_or("OK"),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Transcript {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_line = |line: &str| {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let [operation, bucket, key, outcome] = fields[..] else {
+                return Err(format!("invalid transcript entry: {line:?}"));
+            };
+            let operation = (Request::OPERATIONS.iter())
+                .find(|op| **op == operation)
+                .ok_or_else(|| format!("unknown operation: {operation:?}"))?;
+            Ok(TranscriptEntry {
+                request: RequestRecord {
+                    operation,
+                    bucket: bucket.into(),
+                    key: Some(key.into()).filter(|key| key != "-"),
+                },
+                error: Some(outcome.into()).filter(|outcome| outcome != "OK"),
+            })
+        };
+        let entries = (s.lines())
+            .filter(|line| !line.is_empty())
+            .map(parse_line)
+            .collect::<Result<_, _>>()?;
+        Ok(Transcript { entries })
+    }
+}
+
+/// The state of recording or replaying a transcript.
+#[derive(Debug)]
+pub(crate) enum TranscriptState {
+    Record(Transcript),
+    Replay {
+        /// The entries that are not yet matched.
+        expected: VecDeque<TranscriptEntry>,
+        mismatches: Vec<String>,
+    },
+}
+
+impl TranscriptState {
+    /// Records an entry, or validates it against the next expected one.
+    pub fn push(&mut self, entry: TranscriptEntry) {
+        match self {
+            TranscriptState::Record(transcript) => transcript.entries.push(entry),
+            TranscriptState::Replay {
+                expected,
+                mismatches,
+            } => match expected.pop_front() {
+                Some(expected) if expected == entry => {}
+                Some(expected) if expected.request != entry.request => mismatches.push(format!(
+                    "expected request {:?}, got {:?}",
+                    expected.request, entry.request
+                )),
+                Some(expected) => mismatches.push(format!(
+                    "{:?}: expected outcome {}, got {}",
+                    entry.request,
+                    expected.error.as_deref().unwrap_or("OK"),
+                    entry.error.as_deref().unwrap_or("OK"),
+                )),
+                None => mismatches.push(format!("unexpected request {:?}", entry.request)),
+            },
+        }
+    }
+}
@@ -0,0 +1,3114 @@
+#![cfg(madsim)]
+
+use aws_smithy_http::body::SdkBody;
+use bytes::Bytes;
+use futures_core::Stream;
+use madsim::{
+    runtime::{Handle, Runtime},
+    time::{sleep, timeout, Instant, TimeHandle},
+};
+use madsim_aws_sdk_s3::{
+    error::NoSuchBucket,
+    model::{
+        BucketVersioningStatus, CompletedMultipartUpload, CompletedPart, DefaultRetention, Delete,
+        ObjectAttributes, ObjectIdentifier, ObjectLockConfiguration, ObjectLockEnabled,
+        ObjectLockLegalHold, ObjectLockLegalHoldStatus, ObjectLockRetention,
+        ObjectLockRetentionMode, ObjectLockRule, RestoreRequest, ServerSideEncryption,
+        StorageClass,
+    },
+    output::ListObjectsV2Output,
+    server::{RequestRecord, S3Service, SimServer, SizeLimits, Transcript},
+    types::{ByteStream, DateTime, SdkError},
+    Client, Config, Credentials, Endpoint, Region,
+};
+use md5::Digest;
+use std::{future::poll_fn, net::SocketAddr, pin::Pin, time::Duration};
+
+#[madsim::test]
+async fn list_objects_v2_order() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        for key in ["b", "a/3", "c", "a/1", "a", "a/2", "B"] {
+            (client.put_object().bucket("bucket").key(key))
+                .send()
+                .await
+                .unwrap();
+        }
+        // keys are listed in lexicographic (UTF-8 binary) order
+        let keys = list(&client, None).await;
+        assert_eq!(keys, ["B", "a", "a/1", "a/2", "a/3", "b", "c"]);
+        assert!(keys.windows(2).all(|w| w[0] < w[1]));
+        let keys = list(&client, Some("a/")).await;
+        assert_eq!(keys, ["a/1", "a/2", "a/3"]);
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn list_objects_v2_start_after() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        for key in ["a/1", "a/2", "a/3", "a/4", "b/1", "b/2"] {
+            (client.put_object().bucket("bucket").key(key))
+                .send()
+                .await
+                .unwrap();
+        }
+        let keys = |output: &ListObjectsV2Output| -> Vec<String> {
+            (output.contents().unwrap().iter())
+                .map(|object| object.key().unwrap().to_string())
+                .collect()
+        };
+        // keys strictly greater than start_after
+        let output = (client.list_objects_v2().bucket("bucket"))
+            .start_after("a/2")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(keys(&output), ["a/3", "a/4", "b/1", "b/2"]);
+        assert!(!output.is_truncated());
+
+        // composes with prefix
+        let output = (client.list_objects_v2().bucket("bucket"))
+            .prefix("a/")
+            .start_after("a/1")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(keys(&output), ["a/2", "a/3", "a/4"]);
+        // start_after may be a key that does not exist
+        let output = (client.list_objects_v2().bucket("bucket"))
+            .prefix("b/")
+            .start_after("a/9")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(keys(&output), ["b/1", "b/2"]);
+
+        // and with max_keys
+        let output = (client.list_objects_v2().bucket("bucket"))
+            .prefix("a/")
+            .start_after("a/1")
+            .max_keys(2)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(keys(&output), ["a/2", "a/3"]);
+        assert!(output.is_truncated());
+        // resume from the last key
+        let output = (client.list_objects_v2().bucket("bucket"))
+            .prefix("a/")
+            .start_after("a/3")
+            .max_keys(2)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(keys(&output), ["a/4"]);
+        assert!(!output.is_truncated());
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn get_object_if_modified_since() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let body = ByteStream::from_static(b"hello");
+        (client.put_object().bucket("bucket").key("key").body(body))
+            .send()
+            .await
+            .unwrap();
+        let get = || client.get_object().bucket("bucket").key("key");
+        let last_modified = *get().send().await.unwrap().last_modified().unwrap();
+
+        // unchanged since the given time
+        let err = (get().if_modified_since(last_modified).send())
+            .await
+            .unwrap_err();
+        let SdkError::ServiceError { err, .. } = err else {
+            panic!("unexpected error: {err:?}");
+        };
+        assert_eq!(err.code(), Some("NotModified"));
+
+        // modified after the given time
+        let before = DateTime::from_secs(last_modified.secs() - 1);
+        let output = get().if_modified_since(before).send().await.unwrap();
+        let body = output.body.collect().await.unwrap().into_bytes();
+        assert_eq!(&body[..], b"hello");
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn get_object_if_match_multipart() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let output = (client.create_multipart_upload().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap();
+        let upload_id = output.upload_id().unwrap();
+        let mut completed = CompletedMultipartUpload::builder();
+        let mut part_e_tags = vec![];
+        for (part_number, body) in [(1, b"hello"), (2, b"world")] {
+            let output = (client.upload_part().bucket("bucket").key("key"))
+                .upload_id(upload_id)
+                .part_number(part_number)
+                .body(ByteStream::from_static(body))
+                .send()
+                .await
+                .unwrap();
+            let e_tag = output.e_tag().unwrap().to_string();
+            let part = (CompletedPart::builder())
+                .part_number(part_number)
+                .e_tag(&e_tag)
+                .build();
+            completed = completed.parts(part);
+            part_e_tags.push(e_tag);
+        }
+        (client
+            .complete_multipart_upload()
+            .bucket("bucket")
+            .key("key"))
+        .upload_id(upload_id)
+        .multipart_upload(completed.build())
+        .send()
+        .await
+        .unwrap();
+        let get = || client.get_object().bucket("bucket").key("key");
+        let e_tag = get().send().await.unwrap().e_tag().unwrap().to_string();
+        let (prefix, suffix) = e_tag.rsplit_once('-').unwrap();
+        assert_eq!(suffix, "2");
+
+        // the suffixed ETag matches, with or without quotes
+        let output = get().if_match(&e_tag).send().await.unwrap();
+        let body = output.body.collect().await.unwrap().into_bytes();
+        assert_eq!(&body[..], b"helloworld");
+        get().if_match(format!("\"{e_tag}\"")).send().await.unwrap();
+        get()
+            .if_match(format!("other, {e_tag}"))
+            .send()
+            .await
+            .unwrap();
+        get().if_match("*").send().await.unwrap();
+
+        // neither the ETag without suffix nor the ETags of parts match
+        for if_match in [prefix, &part_e_tags[0], &part_e_tags[1]] {
+            let err = get().if_match(if_match).send().await.unwrap_err();
+            assert_eq!(service_error(err).code(), Some("PreconditionFailed"));
+        }
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn content_encoding_round_trip() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        // an (empty) gzip member, stored verbatim
+        let gzip: &[u8] = &[
+            0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x03, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+        (client.put_object().bucket("bucket").key("key"))
+            .body(ByteStream::from_static(gzip))
+            .content_encoding("gzip")
+            .send()
+            .await
+            .unwrap();
+
+        let output = (client.get_object().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(output.content_encoding(), Some("gzip"));
+        let body = output.body.collect().await.unwrap().into_bytes();
+        assert_eq!(&body[..], gzip);
+
+        let output = (client.head_object().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(output.content_encoding(), Some("gzip"));
+        assert_eq!(output.content_length(), gzip.len() as i64);
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn get_object_range() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let body = ByteStream::from_static(b"0123456789");
+        (client.put_object().bucket("bucket").key("key").body(body))
+            .send()
+            .await
+            .unwrap();
+        let get = |range: &str| {
+            let request = client.get_object().bucket("bucket").key("key").range(range);
+            async move {
+                let output = request.send().await?;
+                let content_range = output.content_range().map(String::from);
+                let body = output.body.collect().await.unwrap().into_bytes();
+                Ok::<_, SdkError<_>>((body, content_range))
+            }
+        };
+
+        // an empty range returns the full object
+        let (body, content_range) = get("").await.unwrap();
+        assert_eq!(&body[..], b"0123456789");
+        assert_eq!(content_range, None);
+
+        for (range, expected, content_range) in [
+            ("bytes=2-4", &b"234"[..], "bytes 2-4/10"),
+            ("bytes=7-", b"789", "bytes 7-9/10"),
+            ("bytes=-3", b"789", "bytes 7-9/10"),
+            ("bytes=8-100", b"89", "bytes 8-9/10"),
+            ("bytes=-100", b"0123456789", "bytes 0-9/10"),
+            // the last position does not overflow
+            (
+                "bytes=0-18446744073709551615",
+                b"0123456789",
+                "bytes 0-9/10",
+            ),
+        ] {
+            let (body, actual) = get(range).await.unwrap();
+            assert_eq!(&body[..], expected, "range: {range}");
+            assert_eq!(actual.as_deref(), Some(content_range), "range: {range}");
+        }
+
+        // malformed or not satisfiable
+        for range in [
+            "bytes=",
+            "bytes=abc",
+            "bytes=-",
+            "items=0-1",
+            "bytes=0-1,3-4",
+            "bytes=5-3",
+            "bytes=10-",
+        ] {
+            let err = get(range).await.unwrap_err();
+            let SdkError::ServiceError { err, .. } = err else {
+                panic!("unexpected error: {err:?}");
+            };
+            assert_eq!(err.code(), Some("InvalidRange"), "range: {range}");
+        }
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn last_modified_with_clock_skew() {
+    let handle = Handle::current();
+    let skew = Duration::from_secs(60 * 60);
+    for (name, ip, skew) in [
+        ("server1", [10, 0, 0, 1], Duration::ZERO),
+        ("server2", [10, 0, 0, 2], skew),
+    ] {
+        let server = handle.create_node().name(name).ip(ip.into()).build();
+        TimeHandle::current().set_clock_skew(server.id(), skew);
+        server.spawn(async move {
+            SimServer::builder()
+                .with_bucket("bucket")
+                .serve((ip, 9000).into())
+                .await
+                .unwrap();
+        });
+    }
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.3".parse().unwrap())
+        .build();
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let mut last_modified = vec![];
+        for endpoint in ["http://10.0.0.1:9000", "http://10.0.0.2:9000"] {
+            let client = connect_to(endpoint);
+            let body = ByteStream::from_static(b"hello");
+            (client.put_object().bucket("bucket").key("key").body(body))
+                .send()
+                .await
+                .unwrap();
+            let output = (client.head_object().bucket("bucket").key("key"))
+                .send()
+                .await
+                .unwrap();
+            last_modified.push(*output.last_modified().unwrap());
+        }
+        let diff = last_modified[1].secs() - last_modified[0].secs();
+        assert!((skew.as_secs() as i64..=skew.as_secs() as i64 + 1).contains(&diff));
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn timestamps_follow_simulation_clock() {
+    let service = S3Service::new();
+    service.create_bucket("bucket").await;
+
+    let mut last_modified = vec![];
+    let mut instants = vec![];
+    for key in ["a", "b"] {
+        let now = service.now();
+        instants.push(Instant::now());
+        let body = Bytes::from_static(b"hello");
+        (service.put_object(
+            "bucket".into(),
+            key.into(),
+            body,
+            None,
+            None,
+            None,
+            Default::default(),
+        ))
+        .await
+        .unwrap();
+        let output = (service.head_object("bucket".into(), key.into()))
+            .await
+            .unwrap();
+        assert_eq!(output.last_modified(), Some(&now));
+        last_modified.push(now);
+        sleep(Duration::from_millis(1500)).await;
+    }
+    // the timestamps are as far apart as the instants of the writes
+    let diff = last_modified[1].as_nanos() - last_modified[0].as_nanos();
+    let elapsed = instants[1] - instants[0];
+    assert!(elapsed >= Duration::from_millis(1500));
+    assert_eq!(diff, elapsed.as_nanos() as i128);
+
+    // listed objects have the same timestamps
+    let output = (service.list_objects_v2("bucket".into(), None, None, None, 0))
+        .await
+        .unwrap();
+    let listed: Vec<_> = (output.contents().unwrap().iter())
+        .map(|object| *object.last_modified().unwrap())
+        .collect();
+    assert_eq!(listed, last_modified);
+}
+
+#[madsim::test]
+async fn transcript_replay() {
+    let handle = Handle::current();
+    let mut services = vec![];
+    for (name, ip) in [("server1", [10, 0, 0, 1]), ("server2", [10, 0, 0, 2])] {
+        let server = handle.create_node().name(name).ip(ip.into()).build();
+        let sim_server = SimServer::builder().with_bucket("bucket");
+        services.push(sim_server.service());
+        server.spawn(async move { sim_server.serve((ip, 9000).into()).await.unwrap() });
+    }
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.3".parse().unwrap())
+        .build();
+    sleep(Duration::from_secs(1)).await;
+
+    // records a workload on the first server
+    services[0].start_recording();
+    let task = client.spawn(async move {
+        workload(&connect_to("http://10.0.0.1:9000"), false).await;
+    });
+    task.await.unwrap();
+    let transcript = services[0].take_transcript();
+    let text = transcript.to_string();
+    assert_eq!(
+        text,
+        "PutObject\tbucket\ta\tOK\n\
+         GetObject\tbucket\ta\tOK\n\
+         GetObject\tbucket\tb\tNoSuchKey\n\
+         DeleteObject\tbucket\ta\tOK\n\
+         ListObjectsV2\tbucket\t-\tOK\n"
+    );
+    let transcript: Transcript = text.parse().unwrap();
+
+    // replays the workload on the second server
+    services[1].start_replay(transcript.clone());
+    let task = client.spawn(async move {
+        workload(&connect_to("http://10.0.0.2:9000"), false).await;
+    });
+    task.await.unwrap();
+    assert_eq!(services[1].finish_replay(), Ok(()));
+
+    // a regressed workload is reported
+    let task = client.spawn(async move {
+        let client = connect_to("http://10.0.0.2:9000");
+        let body = ByteStream::from_static(b"hello");
+        (client.put_object().bucket("bucket").key("b").body(body))
+            .send()
+            .await
+            .unwrap();
+    });
+    task.await.unwrap();
+    services[1].start_replay(transcript);
+    let task = client.spawn(async move {
+        workload(&connect_to("http://10.0.0.2:9000"), true).await;
+    });
+    task.await.unwrap();
+    let mismatches = services[1].finish_replay().unwrap_err();
+    assert_eq!(mismatches.len(), 3, "{mismatches:?}");
+    assert!(mismatches[0].contains("expected outcome NoSuchKey, got OK"));
+    assert!(mismatches[1].contains("expected request"));
+    assert!(mismatches[2].contains("missing request"));
+}
+
+#[madsim::test]
+async fn complete_multipart_upload_min_part_size() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        let limits = SizeLimits {
+            min_part_size: Some(5),
+            ..Default::default()
+        };
+        SimServer::builder()
+            .with_bucket("bucket")
+            .with_size_limits(limits)
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        // uploads parts and completes the upload
+        let upload = |parts: &'static [&'static [u8]]| {
+            let client = client.clone();
+            async move {
+                let output = (client.create_multipart_upload().bucket("bucket").key("key"))
+                    .send()
+                    .await
+                    .unwrap();
+                let upload_id = output.upload_id().unwrap();
+                let mut completed = CompletedMultipartUpload::builder();
+                for (i, part) in parts.iter().enumerate() {
+                    let part_number = i as i32 + 1;
+                    let output = (client.upload_part().bucket("bucket").key("key"))
+                        .upload_id(upload_id)
+                        .part_number(part_number)
+                        .body(ByteStream::from_static(part))
+                        .send()
+                        .await
+                        .unwrap();
+                    let part = (CompletedPart::builder())
+                        .part_number(part_number)
+                        .e_tag(output.e_tag().unwrap())
+                        .build();
+                    completed = completed.parts(part);
+                }
+                (client
+                    .complete_multipart_upload()
+                    .bucket("bucket")
+                    .key("key"))
+                .upload_id(upload_id)
+                .multipart_upload(completed.build())
+                .send()
+                .await
+            }
+        };
+
+        // a non-final part is too small
+        let err = upload(&[b"hello", b"abc", b"world"]).await.unwrap_err();
+        let err = service_error(err);
+        assert_eq!(err.code(), Some("EntityTooSmall"));
+        assert!(err.message().unwrap().contains("part 2"));
+
+        // the final part is exempt
+        upload(&[b"hello", b"world", b"!"]).await.unwrap();
+        let output = (client.get_object().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap();
+        let body = output.body.collect().await.unwrap().into_bytes();
+        assert_eq!(&body[..], b"helloworld!");
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn upload_part_overwrite() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let output = (client.create_multipart_upload().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap();
+        let upload_id = output.upload_id().unwrap();
+        let upload_part = |body: &'static [u8]| {
+            (client.upload_part().bucket("bucket").key("key"))
+                .upload_id(upload_id)
+                .part_number(1)
+                .body(ByteStream::from_static(body))
+                .send()
+        };
+        let complete = |e_tag: Option<&str>| {
+            let part = (CompletedPart::builder().part_number(1))
+                .set_e_tag(e_tag.map(String::from))
+                .build();
+            (client
+                .complete_multipart_upload()
+                .bucket("bucket")
+                .key("key"))
+            .upload_id(upload_id)
+            .multipart_upload(CompletedMultipartUpload::builder().parts(part).build())
+            .send()
+        };
+        let first = upload_part(b"hello").await.unwrap();
+        let second = upload_part(b"world").await.unwrap();
+        assert_ne!(first.e_tag(), second.e_tag());
+
+        // the first part is replaced
+        let err = service_error(complete(first.e_tag()).await.unwrap_err());
+        assert_eq!(err.code(), Some("InvalidPart"));
+        complete(None).await.unwrap();
+        let output = (client.get_object().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap();
+        let body = output.body.collect().await.unwrap().into_bytes();
+        assert_eq!(&body[..], b"world");
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn content_md5() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let md5 = |body: &[u8]| aws_smithy_types::base64::encode(md5::Md5::digest(body));
+        let put_object = |body: &'static [u8], content_md5: String| {
+            (client.put_object().bucket("bucket").key("key"))
+                .body(ByteStream::from_static(body))
+                .content_md5(content_md5)
+                .send()
+        };
+        put_object(b"hello", md5(b"hello")).await.unwrap();
+
+        let err = service_error(put_object(b"world", md5(b"hello")).await.unwrap_err());
+        assert_eq!(err.code(), Some("BadDigest"));
+        let err = service_error(put_object(b"world", "invalid".into()).await.unwrap_err());
+        assert_eq!(err.code(), Some("InvalidDigest"));
+        // the object is not overwritten
+        let output = (client.get_object().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap();
+        let body = output.body.collect().await.unwrap().into_bytes();
+        assert_eq!(&body[..], b"hello");
+
+        let output = (client.create_multipart_upload().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap();
+        let upload_part = |body: &'static [u8], content_md5: String| {
+            (client.upload_part().bucket("bucket").key("key"))
+                .upload_id(output.upload_id().unwrap())
+                .part_number(1)
+                .body(ByteStream::from_static(body))
+                .content_md5(content_md5)
+                .send()
+        };
+        upload_part(b"hello", md5(b"hello")).await.unwrap();
+        let err = service_error(upload_part(b"world", md5(b"hello")).await.unwrap_err());
+        assert_eq!(err.code(), Some("BadDigest"));
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn multipart_upload_echoes_bucket_and_key() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let output = (client.create_multipart_upload().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(output.bucket(), Some("bucket"));
+        assert_eq!(output.key(), Some("key"));
+
+        // subsequent requests are built from the output
+        let (bucket, key) = (output.bucket().unwrap(), output.key().unwrap());
+        let upload_id = output.upload_id().unwrap();
+        let part = (client.upload_part().bucket(bucket).key(key))
+            .upload_id(upload_id)
+            .part_number(1)
+            .body(ByteStream::from_static(b"hello"))
+            .send()
+            .await
+            .unwrap();
+        let completed = CompletedPart::builder()
+            .part_number(1)
+            .e_tag(part.e_tag().unwrap())
+            .build();
+        (client.complete_multipart_upload().bucket(bucket).key(key))
+            .upload_id(upload_id)
+            .multipart_upload(CompletedMultipartUpload::builder().parts(completed).build())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(list(&client, None).await, ["key"]);
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn delete_object() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let put = |key: &'static str| {
+            (client.put_object().bucket("bucket").key(key))
+                .body(ByteStream::from_static(b"hello"))
+                .send()
+        };
+        let delete = |key: &'static str| client.delete_object().bucket("bucket").key(key).send();
+        let head = |key: &'static str| client.head_object().bucket("bucket").key(key).send();
+
+        // a completed object
+        put("done").await.unwrap();
+        delete("done").await.unwrap();
+        let err = service_error(head("done").await.unwrap_err());
+        assert!(err.is_not_found());
+
+        // a never-existent key
+        delete("never").await.unwrap();
+        let err = service_error(head("never").await.unwrap_err());
+        assert!(err.is_not_found());
+
+        // a completed object with an upload in progress
+        put("pending").await.unwrap();
+        let output = (client
+            .create_multipart_upload()
+            .bucket("bucket")
+            .key("pending"))
+        .send()
+        .await
+        .unwrap();
+        let upload_id = output.upload_id().unwrap();
+        let part = (client.upload_part().bucket("bucket").key("pending"))
+            .upload_id(upload_id)
+            .part_number(1)
+            .body(ByteStream::from_static(b"world"))
+            .send()
+            .await
+            .unwrap();
+        delete("pending").await.unwrap();
+        let err = service_error(head("pending").await.unwrap_err());
+        assert!(err.is_not_found());
+        assert!(list(&client, None).await.is_empty());
+        // deleting again is a no-op
+        delete("pending").await.unwrap();
+
+        // the upload is not affected
+        let completed = CompletedPart::builder()
+            .part_number(1)
+            .e_tag(part.e_tag().unwrap())
+            .build();
+        (client
+            .complete_multipart_upload()
+            .bucket("bucket")
+            .key("pending"))
+        .upload_id(upload_id)
+        .multipart_upload(CompletedMultipartUpload::builder().parts(completed).build())
+        .send()
+        .await
+        .unwrap();
+        let output = head("pending").await.unwrap();
+        assert_eq!(output.content_length(), 5);
+        let output = (client.get_object().bucket("bucket").key("pending"))
+            .send()
+            .await
+            .unwrap();
+        let body = output.body.collect().await.unwrap().into_bytes();
+        assert_eq!(&body[..], b"world");
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn latency() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .with_latency(Duration::from_secs(1)..Duration::from_secs(2))
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let put = |key: &'static str| {
+            (client.put_object().bucket("bucket").key(key))
+                .body(ByteStream::from_static(b"hello"))
+                .send()
+        };
+        let mut elapsed = vec![];
+        for _ in 0..10 {
+            let t0 = Instant::now();
+            put("key").await.unwrap();
+            let t = t0.elapsed();
+            assert!(t >= Duration::from_secs(1) && t < Duration::from_millis(2100));
+            elapsed.push(t);
+        }
+        elapsed.dedup();
+        assert!(elapsed.len() > 1, "latency should vary between requests");
+
+        // client timeouts fire
+        timeout(Duration::from_millis(500), put("key"))
+            .await
+            .unwrap_err();
+        // parallel requests are delayed concurrently
+        let t0 = Instant::now();
+        let tasks: Vec<_> = ["a", "b"]
+            .map(|key| {
+                let client = client.clone();
+                madsim::task::spawn(async move {
+                    (client.put_object().bucket("bucket").key(key))
+                        .body(ByteStream::from_static(b"hello"))
+                        .send()
+                        .await
+                        .unwrap();
+                })
+            })
+            .into();
+        for task in tasks {
+            task.await.unwrap();
+        }
+        assert!(t0.elapsed() < Duration::from_millis(2100));
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn region_latency() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .with_region_latency("eu-west-1", Duration::from_secs(1))
+            // requests from the region of the service are not delayed
+            .with_region_latency("us-east-1", Duration::from_secs(1))
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let connect_region = |region: &'static str| {
+            let config = Config::builder()
+                .endpoint_resolver(Endpoint::immutable("http://10.0.0.1:9000".parse().unwrap()))
+                .region(Region::new(region))
+                .build();
+            Client::from_conf(config)
+        };
+        let put = |client: Client| async move {
+            let t0 = Instant::now();
+            (client.put_object().bucket("bucket").key("key"))
+                .body(ByteStream::from_static(b"hello"))
+                .send()
+                .await
+                .unwrap();
+            t0.elapsed()
+        };
+        let same = put(connect_region("us-east-1")).await;
+        assert!(same < Duration::from_secs(1));
+        let cross = put(connect_region("eu-west-1")).await;
+        assert!(cross >= Duration::from_secs(1) && cross < Duration::from_millis(1100));
+        assert!(cross > same);
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn server_side_encryption() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        (client.put_object().bucket("bucket").key("kms"))
+            .body(ByteStream::from_static(b"hello"))
+            .server_side_encryption(ServerSideEncryption::AwsKms)
+            .ssekms_key_id("key-1")
+            .send()
+            .await
+            .unwrap();
+        let output = (client.get_object().bucket("bucket").key("kms"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(
+            output.server_side_encryption(),
+            Some(&ServerSideEncryption::AwsKms)
+        );
+        assert_eq!(output.ssekms_key_id(), Some("key-1"));
+        let output = (client.head_object().bucket("bucket").key("kms"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(
+            output.server_side_encryption(),
+            Some(&ServerSideEncryption::AwsKms)
+        );
+        assert_eq!(output.ssekms_key_id(), Some("key-1"));
+
+        // objects are not encrypted by default
+        (client.put_object().bucket("bucket").key("plain"))
+            .body(ByteStream::from_static(b"hello"))
+            .send()
+            .await
+            .unwrap();
+        let output = (client.head_object().bucket("bucket").key("plain"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(output.server_side_encryption(), None);
+        assert_eq!(output.ssekms_key_id(), None);
+
+        // a KMS key requires aws:kms encryption
+        let err = (client.put_object().bucket("bucket").key("invalid"))
+            .body(ByteStream::from_static(b"hello"))
+            .server_side_encryption(ServerSideEncryption::Aes256)
+            .ssekms_key_id("key-1")
+            .send()
+            .await
+            .unwrap_err();
+        assert_eq!(service_error(err).code(), Some("InvalidArgument"));
+
+        // the setting of a multipart upload applies to the completed object
+        let output = (client
+            .create_multipart_upload()
+            .bucket("bucket")
+            .key("multipart"))
+        .server_side_encryption(ServerSideEncryption::Aes256)
+        .send()
+        .await
+        .unwrap();
+        let upload_id = output.upload_id().unwrap();
+        (client.upload_part().bucket("bucket").key("multipart"))
+            .upload_id(upload_id)
+            .part_number(1)
+            .body(ByteStream::from_static(b"hello"))
+            .send()
+            .await
+            .unwrap();
+        let part = CompletedPart::builder().part_number(1).build();
+        (client
+            .complete_multipart_upload()
+            .bucket("bucket")
+            .key("multipart"))
+        .upload_id(upload_id)
+        .multipart_upload(CompletedMultipartUpload::builder().parts(part).build())
+        .send()
+        .await
+        .unwrap();
+        let output = (client.head_object().bucket("bucket").key("multipart"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(
+            output.server_side_encryption(),
+            Some(&ServerSideEncryption::Aes256)
+        );
+        assert_eq!(output.ssekms_key_id(), None);
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn get_object_early_drop() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    let sim_server = SimServer::builder().with_bucket("bucket");
+    let service = sim_server.service();
+    server.spawn(async move { sim_server.serve(addr).await.unwrap() });
+    sleep(Duration::from_secs(1)).await;
+
+    let data: Vec<u8> = (0..1 << 20).map(|i| i as u8).collect();
+    let task = client.spawn(async move {
+        let client = connect();
+        (client.put_object().bucket("bucket").key("large"))
+            .body(ByteStream::from(data.clone()))
+            .send()
+            .await
+            .unwrap();
+
+        // drop the body after reading the first chunk
+        let output = (client.get_object().bucket("bucket").key("large"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(output.content_length(), 1 << 20);
+        let mut body = output.body;
+        let chunk = poll_fn(|cx| Pin::new(&mut body).poll_next(cx)).await;
+        let chunk = chunk.unwrap().unwrap();
+        assert_eq!(chunk, data[..chunk.len()]);
+        drop(body);
+        data
+    });
+    let data = task.await.unwrap();
+    sleep(Duration::from_secs(1)).await;
+    let sent = service.downloaded_bytes();
+    assert!(sent < 1 << 20, "sent {sent} bytes");
+
+    // a fully consumed body is identical to the object
+    let task = client.spawn(async move {
+        let output = (connect().get_object().bucket("bucket").key("large"))
+            .send()
+            .await
+            .unwrap();
+        let body = output.body.collect().await.unwrap().into_bytes();
+        assert_eq!(body, data);
+    });
+    task.await.unwrap();
+    assert_eq!(service.downloaded_bytes(), sent + (1 << 20));
+}
+
+#[madsim::test]
+async fn head_bucket() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .with_bucket_owner("111122223333")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        client.head_bucket().bucket("bucket").send().await.unwrap();
+        (client.head_bucket().bucket("bucket"))
+            .expected_bucket_owner("111122223333")
+            .send()
+            .await
+            .unwrap();
+
+        let err = (client.head_bucket().bucket("unknown").send().await).unwrap_err();
+        let err = service_error(err);
+        assert!(err.is_not_found());
+        assert_eq!(err.code(), Some("NotFound"));
+
+        let err = (client.head_bucket().bucket("bucket"))
+            .expected_bucket_owner("444455556666")
+            .send()
+            .await
+            .unwrap_err();
+        assert_eq!(service_error(err).code(), Some("AccessDenied"));
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn region_redirect() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .with_region("eu-west-1")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let connect_region = |region: Option<&'static str>| {
+            let config = Config::builder()
+                .endpoint_resolver(Endpoint::immutable("http://10.0.0.1:9000".parse().unwrap()))
+                .region(region.map(Region::new))
+                .build();
+            Client::from_conf(config)
+        };
+
+        // the client is configured with another region
+        let err = (connect().put_object().bucket("bucket").key("a"))
+            .body(ByteStream::from_static(b"hello"))
+            .send()
+            .await
+            .unwrap_err();
+        let err = service_error(err);
+        assert_eq!(err.code(), Some("PermanentRedirect"));
+        assert_eq!(
+            err.meta().extra("Endpoint"),
+            Some("s3.eu-west-1.amazonaws.com")
+        );
+        assert_eq!(err.meta().extra("Region"), Some("eu-west-1"));
+
+        // requests from the same region or without a region are served
+        for client in [connect_region(Some("eu-west-1")), connect_region(None)] {
+            (client.put_object().bucket("bucket").key("a"))
+                .body(ByteStream::from_static(b"hello"))
+                .send()
+                .await
+                .unwrap();
+        }
+        assert_eq!(list(&connect_region(None), None).await, ["a"]);
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn fail_next() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    let sim_server = SimServer::builder().with_bucket("bucket");
+    let service = sim_server.service();
+    server.spawn(async move { sim_server.serve(addr).await.unwrap() });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        for key in ["a", "b"] {
+            let body = ByteStream::from_static(b"hello");
+            (client.put_object().bucket("bucket").key(key).body(body))
+                .send()
+                .await
+                .unwrap();
+        }
+        service.fail_next(
+            "GetObject",
+            "bucket",
+            Some("a"),
+            "InternalError",
+            "injected",
+        );
+        service.fail_next("GetObject", "bucket", Some("a"), "SlowDown", "injected");
+        let get = |key| client.get_object().bucket("bucket").key(key).send();
+
+        // other keys are not affected
+        get("b").await.unwrap();
+        // faults are consumed in order, once each
+        let err = service_error(get("a").await.unwrap_err());
+        assert_eq!(err.code(), Some("InternalError"));
+        let err = service_error(get("a").await.unwrap_err());
+        assert_eq!(err.code(), Some("SlowDown"));
+        get("a").await.unwrap();
+
+        // bucket-level operations have no key
+        service.fail_next("ListObjectsV2", "bucket", None, "InternalError", "injected");
+        let err = (client.list_objects_v2().bucket("bucket").send())
+            .await
+            .unwrap_err();
+        assert_eq!(service_error(err).code(), Some("InternalError"));
+        assert_eq!(list(&client, None).await, ["a", "b"]);
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn token_bucket() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    let sim_server = SimServer::builder()
+        .with_bucket("bucket")
+        .with_token_bucket(3, 2.0);
+    let service = sim_server.service();
+    service.create_bucket("other").await;
+    server.spawn(async move { sim_server.serve(addr).await.unwrap() });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let list = |bucket| client.list_objects_v2().bucket(bucket).send();
+
+        // the bucket starts full
+        for _ in 0..3 {
+            list("bucket").await.unwrap();
+        }
+        let err = service_error(list("bucket").await.unwrap_err());
+        assert_eq!(err.code(), Some("SlowDown"));
+        // other buckets have their own tokens
+        list("other").await.unwrap();
+
+        // a client backing off converges as the bucket refills
+        let start = Instant::now();
+        let mut backoff = Duration::from_millis(100);
+        let mut succeeded = 0;
+        while succeeded < 5 {
+            match list("bucket").await {
+                Ok(_) => succeeded += 1,
+                Err(err) => {
+                    assert_eq!(service_error(err).code(), Some("SlowDown"));
+                    sleep(backoff).await;
+                    backoff *= 2;
+                }
+            }
+        }
+        // 5 tokens take at least 2.5s to refill at 2 tokens per second
+        assert!(start.elapsed() >= Duration::from_millis(2500));
+        assert!(start.elapsed() < Duration::from_secs(10));
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn bucket_quota() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    let sim_server = SimServer::builder()
+        .with_bucket("bucket")
+        .with_bucket_quota("bucket", 10);
+    let service = sim_server.service();
+    server.spawn(async move { sim_server.serve(addr).await.unwrap() });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let put = |key: &'static str, body: &'static [u8]| {
+            (client.put_object().bucket("bucket").key(key))
+                .body(ByteStream::from_static(body))
+                .send()
+        };
+
+        // fill the quota
+        put("a", b"hello").await.unwrap();
+        put("b", b"world").await.unwrap();
+        assert_eq!(service.bucket_size("bucket"), 10);
+        let err = service_error(put("c", b"!").await.unwrap_err());
+        assert_eq!(err.code(), Some("QuotaExceeded"));
+        // overwriting replaces the usage of the old object
+        put("b", b"abc").await.unwrap();
+        assert_eq!(service.bucket_size("bucket"), 8);
+
+        // deleting frees the space
+        (client.delete_object().bucket("bucket").key("a"))
+            .send()
+            .await
+            .unwrap();
+        put("c", b"hello").await.unwrap();
+        assert_eq!(service.bucket_size("bucket"), 8);
+
+        // parts of incomplete uploads count toward the usage
+        let output = (client.create_multipart_upload().bucket("bucket").key("d"))
+            .send()
+            .await
+            .unwrap();
+        let upload_id = output.upload_id().unwrap();
+        let output = (client.upload_part().bucket("bucket").key("d"))
+            .upload_id(upload_id)
+            .part_number(1)
+            .body(ByteStream::from_static(b"xy"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(service.bucket_size("bucket"), 8);
+        let err = service_error(put("e", b"!").await.unwrap_err());
+        assert_eq!(err.code(), Some("QuotaExceeded"));
+
+        // completing the upload replaces its parts
+        let part = (CompletedPart::builder())
+            .part_number(1)
+            .e_tag(output.e_tag().unwrap())
+            .build();
+        (client.complete_multipart_upload().bucket("bucket").key("d"))
+            .upload_id(upload_id)
+            .multipart_upload(CompletedMultipartUpload::builder().parts(part).build())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(service.bucket_size("bucket"), 10);
+
+        // aborting an upload frees its parts
+        (client.delete_object().bucket("bucket").key("d"))
+            .send()
+            .await
+            .unwrap();
+        let output = (client.create_multipart_upload().bucket("bucket").key("d"))
+            .send()
+            .await
+            .unwrap();
+        let upload_id = output.upload_id().unwrap();
+        (client.upload_part().bucket("bucket").key("d"))
+            .upload_id(upload_id)
+            .part_number(1)
+            .body(ByteStream::from_static(b"xy"))
+            .send()
+            .await
+            .unwrap();
+        put("e", b"!").await.unwrap_err();
+        (client.abort_multipart_upload().bucket("bucket").key("d"))
+            .upload_id(upload_id)
+            .send()
+            .await
+            .unwrap();
+        put("e", b"!").await.unwrap();
+        assert_eq!(service.bucket_size("bucket"), 9);
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn object_lock() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let day = Duration::from_secs(24 * 60 * 60);
+        let put = |key: &'static str| {
+            let body = ByteStream::from_static(b"hello");
+            client.put_object().bucket("bucket").key(key).body(body)
+        };
+        let delete = |key: &'static str| client.delete_object().bucket("bucket").key(key);
+        put("a").send().await.unwrap();
+
+        // retention requires object lock on the bucket
+        let retention = |mode, until: Duration| {
+            let now = DateTime::from(TimeHandle::current().now_time() + until);
+            (ObjectLockRetention::builder())
+                .mode(mode)
+                .retain_until_date(now)
+                .build()
+        };
+        let err = (client.put_object_retention().bucket("bucket").key("a"))
+            .retention(retention(ObjectLockRetentionMode::Compliance, day))
+            .send()
+            .await
+            .unwrap_err();
+        assert_eq!(service_error(err).code(), Some("InvalidRequest"));
+        let config = (ObjectLockConfiguration::builder())
+            .object_lock_enabled(ObjectLockEnabled::Enabled)
+            .build();
+        (client.put_object_lock_configuration().bucket("bucket"))
+            .object_lock_configuration(config.clone())
+            .send()
+            .await
+            .unwrap();
+        let output = (client.get_object_lock_configuration().bucket("bucket"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(output.object_lock_configuration(), Some(&config));
+
+        // an object under retention can not be deleted, overwritten or released early
+        (client.put_object_retention().bucket("bucket").key("a"))
+            .retention(retention(ObjectLockRetentionMode::Compliance, day))
+            .send()
+            .await
+            .unwrap();
+        let err = delete("a").send().await.unwrap_err();
+        assert_eq!(service_error(err).code(), Some("AccessDenied"));
+        let err = put("a").send().await.unwrap_err();
+        assert_eq!(service_error(err).code(), Some("AccessDenied"));
+        let err = (client.put_object_retention().bucket("bucket").key("a"))
+            .retention(retention(
+                ObjectLockRetentionMode::Compliance,
+                Duration::ZERO,
+            ))
+            .bypass_governance_retention(true)
+            .send()
+            .await
+            .unwrap_err();
+        assert_eq!(service_error(err).code(), Some("AccessDenied"));
+
+        // a legal hold blocks deletion regardless of time
+        put("b").send().await.unwrap();
+        let legal_hold = |status| ObjectLockLegalHold::builder().status(status).build();
+        (client.put_object_legal_hold().bucket("bucket").key("b"))
+            .legal_hold(legal_hold(ObjectLockLegalHoldStatus::On))
+            .send()
+            .await
+            .unwrap();
+
+        // the retention elapses
+        sleep(day).await;
+        delete("a").send().await.unwrap();
+        let err = delete("b").send().await.unwrap_err();
+        assert_eq!(service_error(err).code(), Some("AccessDenied"));
+        (client.put_object_legal_hold().bucket("bucket").key("b"))
+            .legal_hold(legal_hold(ObjectLockLegalHoldStatus::Off))
+            .send()
+            .await
+            .unwrap();
+        delete("b").send().await.unwrap();
+
+        // new objects get the default retention of the bucket
+        let default = (DefaultRetention::builder())
+            .mode(ObjectLockRetentionMode::Governance)
+            .days(1)
+            .build();
+        let config = (ObjectLockConfiguration::builder())
+            .object_lock_enabled(ObjectLockEnabled::Enabled)
+            .rule(ObjectLockRule::builder().default_retention(default).build())
+            .build();
+        (client.put_object_lock_configuration().bucket("bucket"))
+            .object_lock_configuration(config)
+            .send()
+            .await
+            .unwrap();
+        put("c").send().await.unwrap();
+        let objects = (ObjectIdentifier::builder()).key("c").build();
+        let output = (client.delete_objects().bucket("bucket"))
+            .delete(Delete::builder().objects(objects).build())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(output.errors().unwrap()[0].code(), Some("AccessDenied"));
+        // governance retention can be shortened with bypass
+        (client.put_object_retention().bucket("bucket").key("c"))
+            .retention(retention(
+                ObjectLockRetentionMode::Governance,
+                Duration::ZERO,
+            ))
+            .bypass_governance_retention(true)
+            .send()
+            .await
+            .unwrap();
+        delete("c").send().await.unwrap();
+        assert!(list(&client, None).await.is_empty());
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn restore_archived_object() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    let hour = Duration::from_secs(60 * 60);
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .with_restore_delay(4 * hour)
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let body = ByteStream::from_static(b"hello");
+        (client.put_object().bucket("bucket").key("key").body(body))
+            .storage_class(StorageClass::Glacier)
+            .send()
+            .await
+            .unwrap();
+        let get = || client.get_object().bucket("bucket").key("key").send();
+        let head = || client.head_object().bucket("bucket").key("key").send();
+        let restore = || {
+            (client.restore_object().bucket("bucket").key("key"))
+                .restore_request(RestoreRequest::builder().days(1).build())
+                .send()
+        };
+
+        // archived objects can not be read until restored
+        let err = service_error(get().await.unwrap_err());
+        assert_eq!(err.code(), Some("InvalidObjectState"));
+        let output = head().await.unwrap();
+        assert_eq!(output.storage_class(), Some(&StorageClass::Glacier));
+        assert_eq!(output.restore(), None);
+
+        restore().await.unwrap();
+        let output = head().await.unwrap();
+        assert_eq!(output.restore(), Some(r#"ongoing-request="true""#));
+        let err = service_error(get().await.unwrap_err());
+        assert_eq!(err.code(), Some("InvalidObjectState"));
+        let err = service_error(restore().await.unwrap_err());
+        assert_eq!(err.code(), Some("RestoreAlreadyInProgress"));
+
+        // the restore completes
+        sleep(4 * hour).await;
+        let output = get().await.unwrap();
+        assert!(output.restore().unwrap().contains("expiry-date"));
+        let body = output.body.collect().await.unwrap().into_bytes();
+        assert_eq!(&body[..], b"hello");
+
+        // the restored copy expires
+        sleep(24 * hour).await;
+        let err = service_error(get().await.unwrap_err());
+        assert_eq!(err.code(), Some("InvalidObjectState"));
+        assert_eq!(head().await.unwrap().restore(), None);
+
+        // objects in other storage classes can not be restored
+        let body = ByteStream::from_static(b"hello");
+        (client.put_object().bucket("bucket").key("key").body(body))
+            .send()
+            .await
+            .unwrap();
+        let err = service_error(restore().await.unwrap_err());
+        assert_eq!(err.code(), Some("InvalidObjectState"));
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn size_limits() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        let limits = SizeLimits {
+            max_object_size: Some(5),
+            max_part_size: Some(3),
+            ..Default::default()
+        };
+        SimServer::builder()
+            .with_bucket("bucket")
+            .with_size_limits(limits)
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let put = |body: &'static [u8]| {
+            (client.put_object().bucket("bucket").key("key"))
+                .body(ByteStream::from_static(body))
+                .send()
+        };
+        put(b"hello").await.unwrap();
+        let err = service_error(put(b"hello!").await.unwrap_err());
+        assert_eq!(err.code(), Some("EntityTooLarge"));
+
+        let output = (client.create_multipart_upload().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap();
+        let upload_part = |body: &'static [u8]| {
+            (client.upload_part().bucket("bucket").key("key"))
+                .upload_id(output.upload_id().unwrap())
+                .part_number(1)
+                .body(ByteStream::from_static(body))
+                .send()
+        };
+        upload_part(b"abc").await.unwrap();
+        let err = service_error(upload_part(b"abcd").await.unwrap_err());
+        assert_eq!(err.code(), Some("EntityTooLarge"));
+
+        // the rejected object is not stored
+        let output = (client.get_object().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap();
+        let body = output.body.collect().await.unwrap().into_bytes();
+        assert_eq!(body, "hello");
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn request_log() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    let sim_server = SimServer::builder().with_bucket("bucket");
+    let service = sim_server.service();
+    server.spawn(async move { sim_server.serve(addr).await.unwrap() });
+    sleep(Duration::from_secs(1)).await;
+
+    let record = |operation, key: Option<&str>| RequestRecord {
+        operation,
+        bucket: "bucket".into(),
+        key: key.map(String::from),
+    };
+
+    // the log is disabled by default
+    client
+        .spawn(async { workload(&connect(), false).await })
+        .await
+        .unwrap();
+    assert_eq!(service.take_request_log(), []);
+
+    service.enable_request_log();
+    client
+        .spawn(async { workload(&connect(), false).await })
+        .await
+        .unwrap();
+    assert_eq!(
+        service.take_request_log(),
+        [
+            record("PutObject", Some("a")),
+            record("GetObject", Some("a")),
+            record("GetObject", Some("b")),
+            record("DeleteObject", Some("a")),
+            record("ListObjectsV2", None),
+        ]
+    );
+    // the log is cleared on read
+    assert_eq!(service.take_request_log(), []);
+    client
+        .spawn(async { list(&connect(), None).await })
+        .await
+        .unwrap();
+    assert_eq!(service.take_request_log(), [record("ListObjectsV2", None)]);
+}
+
+#[madsim::test]
+async fn active_uploads() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    let sim_server = SimServer::builder().with_bucket("bucket");
+    let service = sim_server.service();
+    server.spawn(async move { sim_server.serve(addr).await.unwrap() });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        // the key never existed
+        assert!(service.active_uploads("bucket", "key").is_empty());
+
+        let output = (client.create_multipart_upload().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap();
+        let upload_id = output.upload_id().unwrap();
+        let output = (client.upload_part().bucket("bucket").key("key"))
+            .upload_id(upload_id)
+            .part_number(1)
+            .body(ByteStream::from_static(b"hello"))
+            .send()
+            .await
+            .unwrap();
+        // the object is being uploaded: not found, but with an active upload
+        let err = (client.head_object().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap_err();
+        assert!(service_error(err).is_not_found());
+        assert_eq!(service.active_uploads("bucket", "key"), [upload_id]);
+        assert!(service.active_uploads("bucket", "other").is_empty());
+
+        let part = (CompletedPart::builder())
+            .part_number(1)
+            .e_tag(output.e_tag().unwrap())
+            .build();
+        (client
+            .complete_multipart_upload()
+            .bucket("bucket")
+            .key("key"))
+        .upload_id(upload_id)
+        .multipart_upload(CompletedMultipartUpload::builder().parts(part).build())
+        .send()
+        .await
+        .unwrap();
+        assert!(service.active_uploads("bucket", "key").is_empty());
+        (client.head_object().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap();
+
+        // aborted uploads are no longer active
+        let output = (client.create_multipart_upload().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap();
+        let upload_id = output.upload_id().unwrap();
+        assert_eq!(service.active_uploads("bucket", "key"), [upload_id]);
+        (client.abort_multipart_upload().bucket("bucket").key("key"))
+            .upload_id(upload_id)
+            .send()
+            .await
+            .unwrap();
+        assert!(service.active_uploads("bucket", "key").is_empty());
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn content_length() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let put = |key: &'static str, content_length: Option<i64>| {
+            (client.put_object().bucket("bucket").key(key))
+                .body(ByteStream::from_static(b"hello"))
+                .set_content_length(content_length)
+                .send()
+        };
+        put("a", Some(5)).await.unwrap();
+        // a truncated or padded body is rejected
+        for content_length in [4, 6] {
+            let err = service_error(put("b", Some(content_length)).await.unwrap_err());
+            assert_eq!(err.code(), Some("IncompleteBody"));
+        }
+        // the true length is recorded if not declared
+        put("b", None).await.unwrap();
+        let output = (client.head_object().bucket("bucket").key("b"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(output.content_length(), 5);
+        assert_eq!(list(&client, None).await, ["a", "b"]);
+
+        let output = (client.create_multipart_upload().bucket("bucket").key("c"))
+            .send()
+            .await
+            .unwrap();
+        let upload_part = |content_length: Option<i64>| {
+            let mut request = (client.upload_part().bucket("bucket").key("c"))
+                .upload_id(output.upload_id().unwrap())
+                .part_number(1)
+                .body(ByteStream::from_static(b"hello"));
+            if let Some(content_length) = content_length {
+                request = request.content_length(content_length);
+            }
+            request.send()
+        };
+        let err = service_error(upload_part(Some(4)).await.unwrap_err());
+        assert_eq!(err.code(), Some("IncompleteBody"));
+        upload_part(Some(5)).await.unwrap();
+        upload_part(None).await.unwrap();
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn complete_multipart_upload_invalid_parts() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let output = (client.create_multipart_upload().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap();
+        let upload_id = output.upload_id().unwrap();
+        let mut e_tags = vec![];
+        for (part_number, body) in [(1, b"ab"), (2, b"cd")] {
+            let output = (client.upload_part().bucket("bucket").key("key"))
+                .upload_id(upload_id)
+                .part_number(part_number)
+                .body(ByteStream::from_static(body))
+                .send()
+                .await
+                .unwrap();
+            e_tags.push(output.e_tag().unwrap().to_string());
+        }
+        let complete = |parts: &[(i32, &str)]| {
+            let mut completed = CompletedMultipartUpload::builder();
+            for (part_number, e_tag) in parts {
+                let part = (CompletedPart::builder())
+                    .part_number(*part_number)
+                    .e_tag(*e_tag)
+                    .build();
+                completed = completed.parts(part);
+            }
+            (client
+                .complete_multipart_upload()
+                .bucket("bucket")
+                .key("key"))
+            .upload_id(upload_id)
+            .multipart_upload(completed.build())
+            .send()
+        };
+        let (e1, e2) = (e_tags[0].as_str(), e_tags[1].as_str());
+
+        for (parts, code) in [
+            // descending
+            (&[(2, e2), (1, e1)][..], "InvalidPartOrder"),
+            // duplicated
+            (&[(1, e1), (1, e1)], "InvalidPartOrder"),
+            // unknown part number
+            (&[(1, e1), (3, e2)], "InvalidPart"),
+            // wrong ETag
+            (&[(1, e2), (2, e2)], "InvalidPart"),
+        ] {
+            let err = service_error(complete(parts).await.unwrap_err());
+            assert_eq!(err.code(), Some(code), "parts: {parts:?}");
+        }
+        // the failed requests leave the upload intact
+        complete(&[(1, e1), (2, e2)]).await.unwrap();
+        let output = (client.get_object().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap();
+        let body = output.body.collect().await.unwrap().into_bytes();
+        assert_eq!(body, "abcd");
+    });
+    task.await.unwrap();
+}
+
+#[test]
+fn ids_are_deterministic() {
+    /// Returns the upload ID and the ETag generated by a multipart upload.
+    async fn ids() -> Vec<String> {
+        let handle = Handle::current();
+        let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+        let server = handle.create_node().name("server").ip(addr.ip()).build();
+        let client = (handle.create_node())
+            .name("client")
+            .ip("10.0.0.2".parse().unwrap())
+            .build();
+
+        server.spawn(async move {
+            SimServer::builder()
+                .with_bucket("bucket")
+                .serve(addr)
+                .await
+                .unwrap();
+        });
+        sleep(Duration::from_secs(1)).await;
+
+        let task = client.spawn(async move {
+            let client = connect();
+            let output = (client.create_multipart_upload().bucket("bucket").key("key"))
+                .send()
+                .await
+                .unwrap();
+            let upload_id = output.upload_id().unwrap();
+            let output = (client.upload_part().bucket("bucket").key("key"))
+                .upload_id(upload_id)
+                .part_number(1)
+                .body(ByteStream::from_static(b"hello"))
+                .send()
+                .await
+                .unwrap();
+            let part = (CompletedPart::builder())
+                .part_number(1)
+                .e_tag(output.e_tag().unwrap())
+                .build();
+            (client
+                .complete_multipart_upload()
+                .bucket("bucket")
+                .key("key"))
+            .upload_id(upload_id)
+            .multipart_upload(CompletedMultipartUpload::builder().parts(part).build())
+            .send()
+            .await
+            .unwrap();
+            let output = (client.get_object().bucket("bucket").key("key"))
+                .send()
+                .await
+                .unwrap();
+            [upload_id, output.e_tag().unwrap()]
+                .map(String::from)
+                .into()
+        });
+        task.await.unwrap()
+    }
+
+    let run = |seed| Runtime::with_seed_and_config(seed, madsim::Config::default()).block_on(ids());
+    assert_eq!(run(1), run(1));
+    assert_ne!(run(1), run(2));
+}
+
+#[madsim::test]
+async fn last_modified_increases() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let mut last_modified = vec![];
+        for _ in 0..3 {
+            (client.put_object().bucket("bucket").key("key"))
+                .body(ByteStream::from_static(b"hello"))
+                .send()
+                .await
+                .unwrap();
+            let output = (client.head_object().bucket("bucket").key("key"))
+                .send()
+                .await
+                .unwrap();
+            last_modified.push(output.last_modified().unwrap().as_nanos());
+            sleep(Duration::from_secs(1)).await;
+        }
+        // each overwrite is stamped at least a second after the previous one
+        for t in last_modified.windows(2) {
+            assert!(t[1] - t[0] >= Duration::from_secs(1).as_nanos() as i128);
+        }
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn put_object_if_none_match() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    // two writers race to create the lock
+    let tasks: Vec<_> = [("client1", "10.0.0.2", "a"), ("client2", "10.0.0.3", "b")]
+        .map(|(name, ip, owner)| {
+            let client = (handle.create_node())
+                .name(name)
+                .ip(ip.parse().unwrap())
+                .build();
+            client.spawn(async move {
+                let result = (connect().put_object().bucket("bucket").key("lock"))
+                    .body(ByteStream::from_static(owner.as_bytes()))
+                    .if_none_match("*")
+                    .send()
+                    .await;
+                (owner, result)
+            })
+        })
+        .into();
+    let mut winners = vec![];
+    for task in tasks {
+        match task.await.unwrap() {
+            (owner, Ok(_)) => winners.push(owner),
+            (_, Err(err)) => assert_eq!(service_error(err).code(), Some("PreconditionFailed")),
+        }
+    }
+    assert_eq!(winners.len(), 1, "exactly one writer wins");
+
+    let client = handle.create_node().ip("10.0.0.4".parse().unwrap()).build();
+    let task = client.spawn(async move {
+        let output = (connect().get_object().bucket("bucket").key("lock"))
+            .send()
+            .await
+            .unwrap();
+        output.body.collect().await.unwrap().into_bytes()
+    });
+    assert_eq!(task.await.unwrap(), winners[0]);
+}
+
+#[madsim::test]
+async fn get_object_range_headers() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let data: Vec<u8> = (0..100).collect();
+        (client.put_object().bucket("bucket").key("key"))
+            .body(ByteStream::from(data.clone()))
+            .send()
+            .await
+            .unwrap();
+
+        let output = (client.get_object().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(output.accept_ranges(), Some("bytes"));
+        assert_eq!(output.content_length(), 100);
+        assert_eq!(output.content_range(), None);
+
+        // resume a download by parsing the content range
+        let mut downloaded = vec![];
+        while downloaded.len() < data.len() {
+            let range = format!("bytes={}-{}", downloaded.len(), downloaded.len() + 29);
+            let output = (client.get_object().bucket("bucket").key("key"))
+                .range(range)
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(output.accept_ranges(), Some("bytes"));
+            let content_range = output.content_range().unwrap();
+            let (range, total) = (content_range.strip_prefix("bytes "))
+                .and_then(|s| s.split_once('/'))
+                .unwrap();
+            let (first, last) = range.split_once('-').unwrap();
+            let (first, last) = (
+                first.parse::<usize>().unwrap(),
+                last.parse::<usize>().unwrap(),
+            );
+            assert_eq!(total, "100");
+            assert_eq!(first, downloaded.len());
+            assert_eq!(output.content_length(), (last - first + 1) as i64);
+            let body = output.body.collect().await.unwrap().into_bytes();
+            assert_eq!(body.len(), last - first + 1);
+            downloaded.extend_from_slice(&body);
+        }
+        assert_eq!(downloaded, data);
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn bucket_location_and_versioning() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    let sim_server = SimServer::builder().with_bucket("bucket");
+    let service = sim_server.service();
+    server.spawn(async move { sim_server.serve(addr).await.unwrap() });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let location = (client.get_bucket_location().bucket("bucket"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(location.location_constraint(), None);
+        let versioning = (client.get_bucket_versioning().bucket("bucket"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(versioning.status(), None);
+
+        service.set_bucket_versioning("bucket", BucketVersioningStatus::Enabled);
+        let versioning = (client.get_bucket_versioning().bucket("bucket"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(versioning.status(), Some(&BucketVersioningStatus::Enabled));
+
+        let err = (client.get_bucket_location().bucket("missing"))
+            .send()
+            .await
+            .unwrap_err();
+        assert!(is_no_such_bucket(&service_error(err)));
+        let err = (client.get_bucket_versioning().bucket("missing"))
+            .send()
+            .await
+            .unwrap_err();
+        assert!(is_no_such_bucket(&service_error(err)));
+
+        service.set_region("eu-west-1");
+        let config = Config::builder()
+            .endpoint_resolver(Endpoint::immutable("http://10.0.0.1:9000".parse().unwrap()))
+            .region(Region::new("eu-west-1"))
+            .build();
+        let location = (Client::from_conf(config).get_bucket_location())
+            .bucket("bucket")
+            .send()
+            .await
+            .unwrap();
+        let location = location.location_constraint().unwrap();
+        assert_eq!(location.as_str(), "eu-west-1");
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn throttle() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    let sim_server = SimServer::builder()
+        .with_bucket("bucket")
+        .with_throttle(3, Duration::from_secs(10));
+    let service = sim_server.service();
+    service.create_bucket("other").await;
+    server.spawn(async move { sim_server.serve(addr).await.unwrap() });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let list = |bucket| client.list_objects_v2().bucket(bucket).send();
+
+        // requests below the threshold always succeed
+        for _ in 0..3 {
+            list("bucket").await.unwrap();
+            sleep(Duration::from_secs(2)).await;
+        }
+        let err = service_error(list("bucket").await.unwrap_err());
+        assert_eq!(err.code(), Some("SlowDown"));
+        // other buckets are throttled separately
+        list("other").await.unwrap();
+
+        // the first request leaves the window after 10s
+        sleep(Duration::from_secs(5)).await;
+        list("bucket").await.unwrap();
+        let err = service_error(list("bucket").await.unwrap_err());
+        assert_eq!(err.code(), Some("SlowDown"));
+
+        // all requests leave the window
+        sleep(Duration::from_secs(10)).await;
+        for _ in 0..3 {
+            list("bucket").await.unwrap();
+        }
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn delete_objects() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let put = |key: &'static str| {
+            (client.put_object().bucket("bucket").key(key))
+                .body(ByteStream::from_static(b"hello"))
+                .send()
+        };
+        // deletes the keys and returns the keys of `Deleted` and `Errors`
+        let delete = |keys: &[&str], quiet: bool| {
+            let mut delete = Delete::builder().quiet(quiet);
+            for key in keys {
+                delete = delete.objects(ObjectIdentifier::builder().key(*key).build());
+            }
+            let request = client
+                .delete_objects()
+                .bucket("bucket")
+                .delete(delete.build());
+            async move {
+                let output = request.send().await.unwrap();
+                let deleted: Vec<_> = (output.deleted().unwrap_or_default().iter())
+                    .map(|object| object.key().unwrap().to_string())
+                    .collect();
+                let errors: Vec<_> = (output.errors().unwrap_or_default().iter())
+                    .map(|error| {
+                        assert_eq!(error.code(), Some("NoSuchKey"));
+                        error.key().unwrap().to_string()
+                    })
+                    .collect();
+                (deleted, errors)
+            }
+        };
+
+        for key in ["a", "b", "c", "d"] {
+            put(key).await.unwrap();
+        }
+        let (deleted, errors) = delete(&["a", "x", "b"], false).await;
+        assert_eq!(deleted, ["a", "b"]);
+        assert_eq!(errors, ["x"]);
+        assert_eq!(list(&client, None).await, ["c", "d"]);
+
+        // successful deletions are omitted in quiet mode
+        let (deleted, errors) = delete(&["c", "y", "a"], true).await;
+        assert!(deleted.is_empty());
+        assert_eq!(errors, ["y", "a"]);
+        assert_eq!(list(&client, None).await, ["d"]);
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn get_object_attributes() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let output = (client.create_multipart_upload().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap();
+        let upload_id = output.upload_id().unwrap();
+        let mut completed = CompletedMultipartUpload::builder();
+        for (part_number, body) in [(1, &b"ab"[..]), (2, b"cde"), (3, b"f")] {
+            let output = (client.upload_part().bucket("bucket").key("key"))
+                .upload_id(upload_id)
+                .part_number(part_number)
+                .body(ByteStream::from_static(body))
+                .send()
+                .await
+                .unwrap();
+            let part = (CompletedPart::builder())
+                .part_number(part_number)
+                .e_tag(output.e_tag().unwrap())
+                .build();
+            completed = completed.parts(part);
+        }
+        // the object is incomplete until the upload is completed
+        let attributes = |key: &str| {
+            (client.get_object_attributes().bucket("bucket").key(key))
+                .object_attributes(ObjectAttributes::Etag)
+                .object_attributes(ObjectAttributes::ObjectSize)
+                .object_attributes(ObjectAttributes::ObjectParts)
+        };
+        let err = attributes("key").send().await.unwrap_err();
+        assert_eq!(service_error(err).code(), Some("NoSuchKey"));
+        (client
+            .complete_multipart_upload()
+            .bucket("bucket")
+            .key("key"))
+        .upload_id(upload_id)
+        .multipart_upload(completed.build())
+        .send()
+        .await
+        .unwrap();
+
+        let output = attributes("key").send().await.unwrap();
+        let e_tag = (client.get_object().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap()
+            .e_tag()
+            .map(String::from);
+        assert_eq!(output.e_tag(), e_tag.as_deref());
+        assert!(output.e_tag().unwrap().ends_with("-3"));
+        assert_eq!(output.object_size(), 6);
+        let parts = output.object_parts().unwrap();
+        assert_eq!(parts.total_parts_count(), 3);
+        assert!(!parts.is_truncated());
+        let sizes: Vec<_> = (parts.parts().unwrap().iter())
+            .map(|part| (part.part_number(), part.size()))
+            .collect();
+        assert_eq!(sizes, [(1, 2), (2, 3), (3, 1)]);
+
+        // list parts page by page
+        let output = attributes("key").max_parts(2).send().await.unwrap();
+        let parts = output.object_parts().unwrap();
+        assert!(parts.is_truncated());
+        assert_eq!(parts.parts().unwrap().len(), 2);
+        assert_eq!(parts.next_part_number_marker(), Some("2"));
+        let output = (attributes("key").max_parts(2))
+            .part_number_marker("2")
+            .send()
+            .await
+            .unwrap();
+        let parts = output.object_parts().unwrap();
+        assert!(!parts.is_truncated());
+        assert_eq!(parts.parts().unwrap()[0].part_number(), 3);
+
+        // objects not uploaded by multipart uploads have no parts
+        (client.put_object().bucket("bucket").key("single"))
+            .body(ByteStream::from_static(b"hello"))
+            .send()
+            .await
+            .unwrap();
+        let output = attributes("single").send().await.unwrap();
+        assert_eq!(output.object_size(), 5);
+        assert!(output.object_parts().is_none());
+
+        let err = attributes("missing").send().await.unwrap_err();
+        assert_eq!(service_error(err).code(), Some("NoSuchKey"));
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn presigned_get_expires() {
+    let service = S3Service::new();
+    service.create_bucket("bucket").await;
+    let body = Bytes::from_static(b"hello");
+    (service.put_object(
+        "bucket".into(),
+        "key".into(),
+        body,
+        None,
+        None,
+        None,
+        Default::default(),
+    ))
+    .await
+    .unwrap();
+
+    let token = service.presign_get("bucket", "key", Duration::from_secs(60));
+    sleep(Duration::from_secs(59)).await;
+    let output = service.get_object_presigned(&token).await.unwrap();
+    let body = output.body.collect().await.unwrap().into_bytes();
+    assert_eq!(body, "hello");
+
+    sleep(Duration::from_secs(1)).await;
+    let err = service.get_object_presigned(&token).await.unwrap_err();
+    assert_eq!(err.code(), Some("AccessDenied"));
+    assert_eq!(err.message(), Some("Request has expired"));
+
+    // a refreshed token grants access again
+    let token = service.presign_get("bucket", "key", Duration::from_secs(60));
+    service.get_object_presigned(&token).await.unwrap();
+    let err = service.get_object_presigned("invalid").await.unwrap_err();
+    assert_eq!(err.code(), Some("AccessDenied"));
+}
+
+#[madsim::test]
+async fn read_delay() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .with_read_delay(Duration::from_secs(5))
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        (client.put_object().bucket("bucket").key("key"))
+            .body(ByteStream::from_static(b"hello"))
+            .send()
+            .await
+            .unwrap();
+
+        // the write is invisible to reads within the delay
+        let err = (client.get_object().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap_err();
+        assert!(service_error(err).is_no_such_key());
+        let err = (client.head_object().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap_err();
+        assert!(service_error(err).is_not_found());
+        assert!(list(&client, None).await.is_empty());
+
+        // a read-after-write retry loop eventually succeeds
+        let start = Instant::now();
+        let output = loop {
+            match (client.get_object().bucket("bucket").key("key"))
+                .send()
+                .await
+            {
+                Ok(output) => break output,
+                Err(err) => assert!(service_error(err).is_no_such_key()),
+            }
+            sleep(Duration::from_millis(500)).await;
+        };
+        assert!(start.elapsed() >= Duration::from_secs(4));
+        let body = output.body.collect().await.unwrap().into_bytes();
+        assert_eq!(body, "hello");
+        (client.head_object().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(list(&client, None).await, ["key"]);
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn get_object_part_number() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let parts: [&'static [u8]; 3] = [b"aaa", b"bbbbb", b"cc"];
+        let output = (client.create_multipart_upload().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap();
+        let upload_id = output.upload_id().unwrap();
+        let mut completed = CompletedMultipartUpload::builder();
+        for (i, body) in parts.iter().enumerate() {
+            let part_number = i as i32 + 1;
+            let output = (client.upload_part().bucket("bucket").key("key"))
+                .upload_id(upload_id)
+                .part_number(part_number)
+                .body(ByteStream::from_static(body))
+                .send()
+                .await
+                .unwrap();
+            let part = (CompletedPart::builder())
+                .part_number(part_number)
+                .e_tag(output.e_tag().unwrap())
+                .build();
+            completed = completed.parts(part);
+        }
+        (client
+            .complete_multipart_upload()
+            .bucket("bucket")
+            .key("key"))
+        .upload_id(upload_id)
+        .multipart_upload(completed.build())
+        .send()
+        .await
+        .unwrap();
+
+        // each part is served with its byte range in the object
+        for (part_number, body, content_range) in [
+            (1, parts[0], "bytes 0-2/10"),
+            (2, parts[1], "bytes 3-7/10"),
+            (3, parts[2], "bytes 8-9/10"),
+        ] {
+            let output = (client.get_object().bucket("bucket").key("key"))
+                .part_number(part_number)
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(output.parts_count(), 3);
+            assert_eq!(output.content_range(), Some(content_range));
+            assert_eq!(output.content_length(), body.len() as i64);
+            let actual = output.body.collect().await.unwrap().into_bytes();
+            assert_eq!(actual, body, "part {part_number}");
+        }
+
+        // an object written by a single put has a single part
+        (client.put_object().bucket("bucket").key("single"))
+            .body(ByteStream::from_static(b"hello"))
+            .send()
+            .await
+            .unwrap();
+        let output = (client.get_object().bucket("bucket").key("single"))
+            .part_number(1)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(output.parts_count(), 1);
+        let body = output.body.collect().await.unwrap().into_bytes();
+        assert_eq!(body, "hello");
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn cache_headers_round_trip() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let expires = DateTime::from_nanos(1_700_000_000_123_456_789).unwrap();
+        (client.put_object().bucket("bucket").key("cached"))
+            .body(ByteStream::from_static(b"hello"))
+            .cache_control("public, max-age=3600")
+            .expires(expires)
+            .send()
+            .await
+            .unwrap();
+        let output = (client.get_object().bucket("bucket").key("cached"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(output.cache_control(), Some("public, max-age=3600"));
+        assert_eq!(output.expires(), Some(&expires));
+        let output = (client.head_object().bucket("bucket").key("cached"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(output.cache_control(), Some("public, max-age=3600"));
+        assert_eq!(output.expires(), Some(&expires));
+
+        // the headers are absent if not set
+        (client.put_object().bucket("bucket").key("plain"))
+            .body(ByteStream::from_static(b"hello"))
+            .send()
+            .await
+            .unwrap();
+        let output = (client.head_object().bucket("bucket").key("plain"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(output.cache_control(), None);
+        assert_eq!(output.expires(), None);
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn list_incomplete_uploads() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    let sim_server = SimServer::builder().with_bucket("bucket");
+    let service = sim_server.service();
+    server.spawn(async move { sim_server.serve(addr).await.unwrap() });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        assert!(service.list_incomplete_uploads("bucket").is_empty());
+
+        let mut uploads = vec![];
+        for key in ["a", "b"] {
+            let now = service.now();
+            let output = (client.create_multipart_upload().bucket("bucket").key(key))
+                .send()
+                .await
+                .unwrap();
+            uploads.push((output.upload_id().unwrap().to_string(), now));
+            sleep(Duration::from_secs(1)).await;
+        }
+        let upload_part = |key: &'static str, upload_id: &str, part_number: i32| {
+            (client.upload_part().bucket("bucket").key(key))
+                .upload_id(upload_id)
+                .part_number(part_number)
+                .body(ByteStream::from_static(b"hello"))
+                .send()
+        };
+        // a re-uploaded part is counted once
+        upload_part("a", &uploads[0].0, 1).await.unwrap();
+        let output = upload_part("a", &uploads[0].0, 1).await.unwrap();
+        upload_part("a", &uploads[0].0, 2).await.unwrap();
+
+        let listed = service.list_incomplete_uploads("bucket");
+        assert_eq!(listed.len(), 2);
+        for (upload, (key, part_count)) in listed.iter().zip([("a", 2), ("b", 0)]) {
+            assert_eq!(upload.key, key);
+            assert_eq!(upload.part_count, part_count);
+        }
+        for (upload, (upload_id, initiated_at)) in listed.iter().zip(&uploads) {
+            assert_eq!(&upload.upload_id, upload_id);
+            // initiated on receipt of the request, after the client sent it
+            assert!(upload.initiated_at.as_nanos() >= initiated_at.as_nanos());
+            assert!(upload.initiated_at.as_nanos() < initiated_at.as_nanos() + 1_000_000_000);
+        }
+        assert!(listed[0].initiated_at.as_nanos() < listed[1].initiated_at.as_nanos());
+
+        // completed and aborted uploads are no longer listed
+        let part = (CompletedPart::builder())
+            .part_number(1)
+            .e_tag(output.e_tag().unwrap())
+            .build();
+        (client.complete_multipart_upload().bucket("bucket").key("a"))
+            .upload_id(&uploads[0].0)
+            .multipart_upload(CompletedMultipartUpload::builder().parts(part).build())
+            .send()
+            .await
+            .unwrap();
+        let listed = service.list_incomplete_uploads("bucket");
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].key, "b");
+        (client.abort_multipart_upload().bucket("bucket").key("b"))
+            .upload_id(&uploads[1].0)
+            .send()
+            .await
+            .unwrap();
+        assert!(service.list_incomplete_uploads("bucket").is_empty());
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn expected_bucket_owner() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .with_bucket_owner("111111111111")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let put = |owner: Option<&str>| {
+            (client.put_object().bucket("bucket").key("key"))
+                .body(ByteStream::from_static(b"hello"))
+                .set_expected_bucket_owner(owner.map(String::from))
+                .send()
+        };
+        // requests without the header are served
+        put(None).await.unwrap();
+        put(Some("111111111111")).await.unwrap();
+        let err = service_error(put(Some("222222222222")).await.unwrap_err());
+        assert_eq!(err.code(), Some("AccessDenied"));
+
+        // object operations
+        (client.get_object().bucket("bucket").key("key"))
+            .expected_bucket_owner("111111111111")
+            .send()
+            .await
+            .unwrap();
+        let err = (client.get_object().bucket("bucket").key("key"))
+            .expected_bucket_owner("222222222222")
+            .send()
+            .await
+            .unwrap_err();
+        assert_eq!(service_error(err).code(), Some("AccessDenied"));
+        let err = (client.delete_object().bucket("bucket").key("key"))
+            .expected_bucket_owner("222222222222")
+            .send()
+            .await
+            .unwrap_err();
+        assert_eq!(service_error(err).code(), Some("AccessDenied"));
+
+        // bucket operations
+        (client.get_bucket_location().bucket("bucket"))
+            .expected_bucket_owner("111111111111")
+            .send()
+            .await
+            .unwrap();
+        let err = (client.get_bucket_location().bucket("bucket"))
+            .expected_bucket_owner("222222222222")
+            .send()
+            .await
+            .unwrap_err();
+        assert_eq!(service_error(err).code(), Some("AccessDenied"));
+        let err = (client.get_bucket_lifecycle_configuration().bucket("bucket"))
+            .expected_bucket_owner("222222222222")
+            .send()
+            .await
+            .unwrap_err();
+        assert_eq!(service_error(err).code(), Some("AccessDenied"));
+
+        // the denied delete has no effect
+        assert_eq!(list(&client, None).await, ["key"]);
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn put_object_stream_error() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        // the stream fails after the first chunk
+        let (mut sender, body) = hyper::Body::channel();
+        madsim::task::spawn(async move {
+            sender
+                .send_data(Bytes::from_static(b"hello"))
+                .await
+                .unwrap();
+            sender.abort();
+        });
+        let err = (client.put_object().bucket("bucket").key("key"))
+            .body(ByteStream::from(SdkBody::from(body)))
+            .send()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SdkError::DispatchFailure(_)), "{err:?}");
+
+        // no object is created
+        sleep(Duration::from_secs(1)).await;
+        let err = (client.head_object().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap_err();
+        assert!(service_error(err).is_not_found());
+        assert!(list(&client, None).await.is_empty());
+    });
+    task.await.unwrap();
+}
+
+#[madsim::test]
+async fn put_object_stream_chunks() {
+    let handle = Handle::current();
+    let addr: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    let server = handle.create_node().name("server").ip(addr.ip()).build();
+    let client = (handle.create_node())
+        .name("client")
+        .ip("10.0.0.2".parse().unwrap())
+        .build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .with_bucket("bucket")
+            .serve(addr)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task = client.spawn(async move {
+        let client = connect();
+        let chunks: Vec<Bytes> = (0..10u8).map(|i| vec![i; 1000].into()).collect();
+        let (mut sender, body) = hyper::Body::channel();
+        let data = chunks.concat();
+        madsim::task::spawn(async move {
+            for chunk in chunks {
+                sender.send_data(chunk).await.unwrap();
+            }
+        });
+        (client.put_object().bucket("bucket").key("key"))
+            .body(ByteStream::from(SdkBody::from(body)))
+            .send()
+            .await
+            .unwrap();
+
+        let output = (client.get_object().bucket("bucket").key("key"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(output.content_length(), data.len() as i64);
+        let body = output.body.collect().await.unwrap().into_bytes();
+        assert_eq!(body, data);
+    });
+    task.await.unwrap();
+}
+
+/// Returns the keys of objects in the bucket.
+async fn list(client: &Client, prefix: Option<&str>) -> Vec<String> {
+    let mut request = client.list_objects_v2().bucket("bucket");
+    if let Some(prefix) = prefix {
+        request = request.prefix(prefix);
+    }
+    let output = request.send().await.unwrap();
+    (output.contents().unwrap().iter())
+        .map(|object| object.key().unwrap().to_string())
+        .collect()
+}
+
+/// Puts, gets, deletes and lists objects.
+///
+/// If `regress`, the object is not deleted.
+async fn workload(client: &Client, regress: bool) {
+    let body = ByteStream::from_static(b"hello");
+    (client.put_object().bucket("bucket").key("a").body(body))
+        .send()
+        .await
+        .unwrap();
+    for key in ["a", "b"] {
+        let _ = client.get_object().bucket("bucket").key(key).send().await;
+    }
+    if !regress {
+        (client.delete_object().bucket("bucket").key("a"))
+            .send()
+            .await
+            .unwrap();
+    }
+    list(client, None).await;
+}
+
+/// Returns the service error of a failed request.
+fn service_error<E: std::fmt::Debug>(err: SdkError<E>) -> E {
+    match err {
+        SdkError::ServiceError { err, .. } => err,
+        err => panic!("unexpected error: {err:?}"),
+    }
+}
+
+/// Returns whether the error is caused by `NoSuchBucket`.
+fn is_no_such_bucket(err: &(dyn std::error::Error + 'static)) -> bool {
+    let mut source = Some(err);
+    while let Some(err) = source {
+        if err.is::<NoSuchBucket>() {
+            return true;
+        }
+        source = err.source();
+    }
+    false
+}
+
+/// Returns a client connected to the server.
+fn connect() -> Client {
+    connect_to("http://10.0.0.1:9000")
+}
+
+/// Returns a client connected to the server at `endpoint`.
+fn connect_to(endpoint: &str) -> Client {
+    let config = Config::builder()
+        .credentials_provider(Credentials::new("access", "secret", None, None, "test"))
+        .endpoint_resolver(Endpoint::immutable(endpoint.parse().unwrap()))
+        .region(Region::new("us-east-1"))
+        .build();
+    Client::from_conf(config)
+}
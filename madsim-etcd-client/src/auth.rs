@@ -0,0 +1,314 @@
+use super::{
+    kv::KeyRange, server::Request, service::prefix_end, Bytes, Error, ResponseHeader, Result, TxnOp,
+};
+use madsim::net::Endpoint;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+
+/// Client for Auth operations.
+#[derive(Clone)]
+pub struct AuthClient {
+    ep: Endpoint,
+    server_addr: SocketAddr,
+}
+
+impl AuthClient {
+    /// Create a new [`AuthClient`].
+    pub(crate) fn new(ep: Endpoint) -> Self {
+        AuthClient {
+            server_addr: ep.peer_addr().unwrap(),
+            ep,
+        }
+    }
+
+    /// Processes an authenticate request.
+    ///
+    /// Once authenticated, all requests from the same [`Client`](super::Client) are
+    /// performed as this user.
+    #[inline]
+    pub async fn authenticate(
+        &mut self,
+        name: String,
+        password: String,
+    ) -> Result<AuthenticateResponse> {
+        let req = Request::Authenticate { name, password };
+        let (tx, mut rx) = self.ep.connect1(self.server_addr).await?;
+        tx.send(Box::new(req)).await?;
+        *rx.recv().await?.downcast().unwrap()
+    }
+}
+
+/// Response for `Authenticate` operation.
+#[derive(Debug, Clone)]
+pub struct AuthenticateResponse {
+    pub(crate) header: ResponseHeader,
+    pub(crate) token: String,
+}
+
+impl AuthenticateResponse {
+    /// Gets response header.
+    #[inline]
+    pub fn header(&self) -> Option<&ResponseHeader> {
+        Some(&self.header)
+    }
+
+    /// An authorized token that can be used in succeeding RPCs.
+    #[inline]
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+}
+
+/// The type of a permission.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(i32)]
+pub enum PermissionType {
+    Read = 0,
+    Write = 1,
+    Readwrite = 2,
+}
+
+/// Role access permission.
+#[derive(Debug, Clone)]
+pub struct Permission {
+    perm_type: PermissionType,
+    key: Bytes,
+    range: KeyRange,
+}
+
+impl Permission {
+    /// Creates a permission with operation type and key
+    #[inline]
+    pub fn new(perm_type: PermissionType, key: impl Into<Vec<u8>>) -> Self {
+        Permission {
+            perm_type,
+            key: key.into().into(),
+            range: KeyRange::Key,
+        }
+    }
+
+    /// Creates a read permission with key
+    #[inline]
+    pub fn read(key: impl Into<Vec<u8>>) -> Self {
+        Permission::new(PermissionType::Read, key)
+    }
+
+    /// Creates a write permission with key
+    #[inline]
+    pub fn write(key: impl Into<Vec<u8>>) -> Self {
+        Permission::new(PermissionType::Write, key)
+    }
+
+    /// Creates a read write permission with key
+    #[inline]
+    pub fn read_write(key: impl Into<Vec<u8>>) -> Self {
+        Permission::new(PermissionType::Readwrite, key)
+    }
+
+    /// Sets range end for the permission
+    #[inline]
+    pub fn with_range_end(mut self, range_end: impl Into<Vec<u8>>) -> Self {
+        self.range = KeyRange::Range(range_end.into().into());
+        self
+    }
+
+    /// Sets the permission with all keys >= key.
+    #[inline]
+    pub fn with_from_key(mut self) -> Self {
+        self.range = KeyRange::FromKey;
+        self
+    }
+
+    /// Sets the permission with all keys prefixed with key.
+    #[inline]
+    pub fn with_prefix(mut self) -> Self {
+        self.range = KeyRange::Prefix;
+        self
+    }
+
+    /// Sets the permission with all keys.
+    #[inline]
+    pub fn with_all_keys(mut self) -> Self {
+        self.range = KeyRange::AllKeys;
+        self
+    }
+
+    /// The key in bytes.
+    #[inline]
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    /// Returns whether this permission allows the operation on the range.
+    fn allows(&self, perm_type: PermissionType, range: &(Bytes, Option<Bytes>)) -> bool {
+        if self.perm_type != perm_type && self.perm_type != PermissionType::Readwrite {
+            return false;
+        }
+        let (start, end) = interval(&self.key, &self.range);
+        start <= range.0
+            && match (&end, &range.1) {
+                (None, _) => true,
+                (Some(_), None) => false,
+                (Some(end), Some(req_end)) => req_end <= end,
+            }
+    }
+}
+
+/// Users and roles of the server.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct AuthStore {
+    enabled: bool,
+    /// name -> (password, roles)
+    users: HashMap<String, (String, Vec<String>)>,
+    /// name -> permissions
+    roles: HashMap<String, Vec<Permission>>,
+}
+
+#[allow(clippy::result_large_err)]
+impl AuthStore {
+    /// The role that has all permissions.
+    const ROOT_ROLE: &'static str = "root";
+
+    pub fn enable(&mut self) {
+        self.enabled = true;
+    }
+
+    pub fn user_add(&mut self, name: String, password: String) {
+        let old = self.users.insert(name, (password, vec![]));
+        assert!(old.is_none(), "user already exists");
+    }
+
+    pub fn user_grant_role(&mut self, name: &str, role: String) {
+        assert!(
+            role == Self::ROOT_ROLE || self.roles.contains_key(&role),
+            "role not found: {role}"
+        );
+        let (_, roles) = self.users.get_mut(name).expect("user not found");
+        roles.push(role);
+    }
+
+    pub fn role_add(&mut self, name: String) {
+        let old = self.roles.insert(name, vec![]);
+        assert!(old.is_none(), "role already exists");
+    }
+
+    pub fn role_grant_permission(&mut self, name: &str, perm: Permission) {
+        let perms = self.roles.get_mut(name).expect("role not found");
+        perms.push(perm);
+    }
+
+    /// Checks the password of the user.
+    pub fn authenticate(&self, name: &str, password: &str) -> Result<()> {
+        if !self.enabled {
+            return Err(Error::GRpcStatus(tonic::Status::new(
+                tonic::Code::FailedPrecondition,
+                "etcdserver: authentication is not enabled",
+            )));
+        }
+        match self.users.get(name) {
+            Some((p, _)) if p == password => Ok(()),
+            _ => Err(Error::GRpcStatus(tonic::Status::new(
+                tonic::Code::InvalidArgument,
+                "etcdserver: authentication failed, invalid user ID or password",
+            ))),
+        }
+    }
+
+    /// Checks whether the user is allowed to perform the request.
+    pub fn check(&self, user: Option<&str>, request: &Request) -> Result<()> {
+        use PermissionType::*;
+
+        if !self.enabled {
+            return Ok(());
+        }
+        let required = match request {
+            Request::Authenticate { .. }
+            | Request::Status
+            | Request::Defragment
+            | Request::MemberList
+            | Request::Dump
+            | Request::LeaseKeyCounts => return Ok(()),
+            Request::Put { key, .. } => vec![(Write, interval(key, &KeyRange::Key))],
+            Request::Get { key, options } => vec![(Read, interval(key, &options.range))],
+            Request::Delete { key, options } => vec![(Write, interval(key, &options.range))],
+            Request::Txn { txn } => {
+                let mut required = vec![];
+                txn_permissions(txn, &mut required);
+                required
+            }
+            // leases are not bound to keys
+            Request::LeaseGrant { .. }
+            | Request::LeaseRevoke { .. }
+            | Request::LeaseKeepAlive { .. }
+            | Request::LeaseTimeToLive { .. }
+            | Request::LeaseLeases => vec![],
+            Request::Watch { key, options } => vec![(Read, interval(key, &options.range))],
+            Request::WatchCancel { .. } => vec![],
+            Request::Campaign { name, .. } => vec![(Write, interval(name, &KeyRange::Prefix))],
+            Request::Proclaim { leader, .. } | Request::Resign { leader } => {
+                vec![(Write, interval(&leader.name, &KeyRange::Prefix))]
+            }
+            Request::Leader { name } | Request::Observe { name } => {
+                vec![(Read, interval(name, &KeyRange::Prefix))]
+            }
+            Request::Snapshot => vec![(Read, interval(&Bytes::new(), &KeyRange::AllKeys))],
+        };
+        let user = user.ok_or_else(|| {
+            Error::GRpcStatus(tonic::Status::new(
+                tonic::Code::InvalidArgument,
+                "etcdserver: user name is empty",
+            ))
+        })?;
+        let (_, roles) = self.users.get(user).expect("user not found");
+        if roles.iter().any(|role| role == Self::ROOT_ROLE) {
+            return Ok(());
+        }
+        let perms: Vec<&Permission> = (roles.iter())
+            .flat_map(|role| self.roles.get(role).into_iter().flatten())
+            .collect();
+        for (perm_type, range) in &required {
+            if !perms.iter().any(|perm| perm.allows(*perm_type, range)) {
+                return Err(Error::GRpcStatus(tonic::Status::new(
+                    tonic::Code::PermissionDenied,
+                    "etcdserver: permission denied",
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Collects the permissions required by a transaction.
+fn txn_permissions(txn: &super::Txn, required: &mut Vec<(PermissionType, (Bytes, Option<Bytes>))>) {
+    use PermissionType::*;
+
+    for cmp in &txn.compare {
+        required.push((Read, interval(&cmp.key, &KeyRange::Key)));
+    }
+    for op in txn.success.iter().chain(&txn.failure) {
+        match op {
+            TxnOp::Get { key, options } => required.push((Read, interval(key, &options.range))),
+            TxnOp::Put { key, .. } => required.push((Write, interval(key, &KeyRange::Key))),
+            TxnOp::Delete { key, options } => required.push((Write, interval(key, &options.range))),
+            TxnOp::Txn { txn } => txn_permissions(txn, required),
+        }
+    }
+}
+
+/// Returns the key range as a half-open interval `[start, end)`.
+///
+/// An `end` of `None` means the range has no upper bound.
+pub(crate) fn interval(key: &Bytes, range: &KeyRange) -> (Bytes, Option<Bytes>) {
+    let next_key = || {
+        let mut end = key.clone();
+        end.push(0);
+        Some(end)
+    };
+    match range {
+        KeyRange::Key => (key.clone(), next_key()),
+        KeyRange::Prefix => (key.clone(), prefix_end(key)),
+        KeyRange::Range(end) => (key.clone(), Some(end.clone().max(key.clone()))),
+        KeyRange::FromKey => (key.clone(), None),
+        KeyRange::AllKeys => (Bytes::new(), None),
+    }
+}
@@ -0,0 +1,123 @@
+use super::{server::Request, ResponseHeader, Result};
+use madsim::net::Endpoint;
+use std::net::SocketAddr;
+
+/// Client for Cluster operations.
+#[derive(Clone)]
+pub struct ClusterClient {
+    ep: Endpoint,
+    server_addr: SocketAddr,
+}
+
+impl ClusterClient {
+    /// Create a new [`ClusterClient`].
+    pub(crate) fn new(ep: Endpoint) -> Self {
+        ClusterClient {
+            server_addr: ep.peer_addr().unwrap(),
+            ep,
+        }
+    }
+
+    /// Lists all the members in the cluster.
+    #[inline]
+    pub async fn member_list(&mut self) -> Result<MemberListResponse> {
+        let req = Request::MemberList;
+        let (tx, mut rx) = self.ep.connect1(self.server_addr).await?;
+        tx.send(Box::new(req)).await?;
+        *rx.recv().await?.downcast().unwrap()
+    }
+}
+
+/// Response for `MemberList` operation.
+#[derive(Debug, Clone)]
+pub struct MemberListResponse {
+    pub(crate) header: ResponseHeader,
+    pub(crate) members: Vec<Member>,
+}
+
+impl MemberListResponse {
+    /// Gets response header.
+    #[inline]
+    pub fn header(&self) -> Option<&ResponseHeader> {
+        Some(&self.header)
+    }
+
+    /// A list of all members associated with the cluster.
+    #[inline]
+    pub fn members(&self) -> &[Member] {
+        &self.members
+    }
+}
+
+/// Cluster member.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+    id: u64,
+    name: String,
+    peer_urls: Vec<String>,
+    client_urls: Vec<String>,
+    is_learner: bool,
+}
+
+impl Member {
+    /// Creates a member with ID and name.
+    ///
+    /// NOTE: This function is only available in the simulator.
+    pub fn new(id: u64, name: impl Into<String>) -> Self {
+        Member {
+            id,
+            name: name.into(),
+            peer_urls: vec![],
+            client_urls: vec![],
+            is_learner: false,
+        }
+    }
+
+    /// Sets the peer URLs.
+    pub fn with_peer_urls(mut self, urls: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.peer_urls = urls.into_iter().map(|url| url.into()).collect();
+        self
+    }
+
+    /// Sets the client URLs.
+    pub fn with_client_urls(mut self, urls: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.client_urls = urls.into_iter().map(|url| url.into()).collect();
+        self
+    }
+
+    /// Marks the member as a raft learner.
+    pub fn with_learner(mut self) -> Self {
+        self.is_learner = true;
+        self
+    }
+
+    /// Member id.
+    #[inline]
+    pub const fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// The human-readable name of the member. If the member is not started, the name will be an empty string.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The list of URLs the member exposes to the cluster for communication.
+    #[inline]
+    pub fn peer_urls(&self) -> &[String] {
+        &self.peer_urls
+    }
+
+    /// The list of URLs the member exposes to clients for communication. If the member is not started, client URLs will be empty.
+    #[inline]
+    pub fn client_urls(&self) -> &[String] {
+        &self.client_urls
+    }
+
+    /// Indicates if the member is raft learner.
+    #[inline]
+    pub const fn is_learner(&self) -> bool {
+        self.is_learner
+    }
+}
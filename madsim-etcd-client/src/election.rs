@@ -75,6 +75,9 @@ impl ElectionClient {
 
     /// Returns a channel that reliably observes ordered leader proposals
     /// as GetResponse values on every current elected leader key.
+    ///
+    /// When the leader is lost by resignation or lease expiry, a response without
+    /// `kv` is sent.
     #[inline]
     pub async fn observe(&mut self, name: impl Into<Vec<u8>>) -> Result<ObserveStream> {
         let req = Request::Observe {
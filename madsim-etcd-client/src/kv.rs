@@ -104,6 +104,8 @@ impl KvClient {
 pub struct PutOptions {
     pub(crate) lease: i64,
     pub(crate) prev_kv: bool,
+    pub(crate) ignore_value: bool,
+    pub(crate) ignore_lease: bool,
 }
 
 impl PutOptions {
@@ -113,6 +115,8 @@ impl PutOptions {
         Self {
             lease: 0,
             prev_kv: false,
+            ignore_value: false,
+            ignore_lease: false,
         }
     }
 
@@ -131,6 +135,22 @@ impl PutOptions {
         self.prev_kv = true;
         self
     }
+
+    /// Updates the key using its current value.
+    /// Returns an error if the key does not exist, or if the value is not empty.
+    #[inline]
+    pub const fn with_ignore_value(mut self) -> Self {
+        self.ignore_value = true;
+        self
+    }
+
+    /// Updates the key using its current lease.
+    /// Returns an error if the key does not exist, or if a lease is given.
+    #[inline]
+    pub const fn with_ignore_lease(mut self) -> Self {
+        self.ignore_lease = true;
+        self
+    }
 }
 
 /// Response for `Put` operation.
@@ -158,7 +178,12 @@ impl PutResponse {
 #[derive(Debug, Default, Clone)]
 pub struct GetOptions {
     pub(crate) revision: i64,
-    pub(crate) prefix: bool,
+    pub(crate) range: KeyRange,
+    pub(crate) limit: i64,
+    pub(crate) sort: Option<(SortTarget, SortOrder)>,
+    pub(crate) count_only: bool,
+    pub(crate) keys_only: bool,
+    pub(crate) serializable: bool,
 }
 
 impl GetOptions {
@@ -167,10 +192,53 @@ impl GetOptions {
     pub const fn new() -> Self {
         GetOptions {
             revision: 0,
-            prefix: true,
+            range: KeyRange::Prefix,
+            limit: 0,
+            sort: None,
+            count_only: false,
+            keys_only: false,
+            serializable: false,
         }
     }
 
+    /// Specifies the range of 'Get'.
+    /// Returns the keys in the range [key, end_key).
+    /// `end_key` must be lexicographically greater than start key.
+    #[inline]
+    pub fn with_range(mut self, end_key: impl Into<Vec<u8>>) -> Self {
+        self.range = KeyRange::Range(end_key.into().into());
+        self
+    }
+
+    /// Gets all keys >= key.
+    #[inline]
+    pub fn with_from_key(mut self) -> Self {
+        self.range = KeyRange::FromKey;
+        self
+    }
+
+    /// Gets all keys prefixed with key.
+    #[inline]
+    pub fn with_prefix(mut self) -> Self {
+        self.range = KeyRange::Prefix;
+        self
+    }
+
+    /// Gets all keys.
+    #[inline]
+    pub fn with_all_keys(mut self) -> Self {
+        self.range = KeyRange::AllKeys;
+        self
+    }
+
+    /// Limits the number of keys returned for the request. When limit is set to 0,
+    /// it is treated as no limit.
+    #[inline]
+    pub const fn with_limit(mut self, limit: i64) -> Self {
+        self.limit = limit;
+        self
+    }
+
     /// The point-in-time of the key-value store to use for the range.
     /// If revision is less or equal to zero, the range is over the newest key-value store.
     /// If the revision has been compacted, ErrCompacted is returned as a response.
@@ -180,12 +248,76 @@ impl GetOptions {
         self
     }
 
-    /// Gets all keys prefixed with key.
+    /// Sets the order for returned sorted results.
+    /// It requires 'with_range' and/or 'with_prefix' to be specified too.
     #[inline]
-    pub fn with_prefix(mut self) -> Self {
-        self.prefix = true;
+    pub fn with_sort(mut self, target: SortTarget, order: SortOrder) -> Self {
+        self.sort = Some((target, order));
         self
     }
+
+    /// Returns only the count of the keys.
+    #[inline]
+    pub const fn with_count_only(mut self) -> Self {
+        self.count_only = true;
+        self
+    }
+
+    /// Gets only the keys, without the values.
+    #[inline]
+    pub const fn with_keys_only(mut self) -> Self {
+        self.keys_only = true;
+        self
+    }
+
+    /// Sets the request to use serializable member-local reads.
+    ///
+    /// In simulation, a serializable read may return stale data from up to the
+    /// [`max_staleness`](crate::SimServer::max_staleness) of the server ago.
+    #[inline]
+    pub const fn with_serializable(mut self) -> Self {
+        self.serializable = true;
+        self
+    }
+}
+
+/// The range of keys an operation applies to, starting from the given key.
+#[derive(Debug, Default, Clone)]
+pub(crate) enum KeyRange {
+    /// Only the given key.
+    #[default]
+    Key,
+    /// All keys prefixed with the given key.
+    Prefix,
+    /// All keys in the range [key, end_key).
+    Range(Bytes),
+    /// All keys >= key.
+    FromKey,
+    /// All keys.
+    AllKeys,
+}
+
+/// The order for returned sorted results.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(i32)]
+pub enum SortOrder {
+    /// Default, no sorting.
+    None = 0,
+    /// Lowest target value first.
+    Ascend = 1,
+    /// Highest target value first.
+    Descend = 2,
+}
+
+/// The key-value field to use for sorting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(i32)]
+pub enum SortTarget {
+    Key = 0,
+    Version = 1,
+    Create = 2,
+    Mod = 3,
+    Value = 4,
 }
 
 /// Response for `Get` operation.
@@ -193,6 +325,8 @@ impl GetOptions {
 pub struct GetResponse {
     pub(crate) header: ResponseHeader,
     pub(crate) kvs: Vec<KeyValue>,
+    pub(crate) more: bool,
+    pub(crate) count: i64,
 }
 
 impl GetResponse {
@@ -208,17 +342,80 @@ impl GetResponse {
     pub fn kvs(&self) -> &[KeyValue] {
         &self.kvs
     }
+
+    /// Indicates if there are more keys to return in the requested range.
+    #[inline]
+    pub const fn more(&self) -> bool {
+        self.more
+    }
+
+    /// The number of keys within the range when requested.
+    #[inline]
+    pub const fn count(&self) -> i64 {
+        self.count
+    }
 }
 
 /// Options for `Delete` operation.
 #[derive(Debug, Default, Clone)]
-pub struct DeleteOptions {}
+pub struct DeleteOptions {
+    pub(crate) range: KeyRange,
+    pub(crate) prev_kv: bool,
+}
+
+impl DeleteOptions {
+    /// Creates a `DeleteOptions`.
+    #[inline]
+    pub const fn new() -> Self {
+        DeleteOptions {
+            range: KeyRange::Key,
+            prev_kv: false,
+        }
+    }
+
+    /// `end_key` is the key following the last key to delete for the range [key, end_key).
+    #[inline]
+    pub fn with_range(mut self, end_key: impl Into<Vec<u8>>) -> Self {
+        self.range = KeyRange::Range(end_key.into().into());
+        self
+    }
+
+    /// Deletes all keys >= key.
+    #[inline]
+    pub fn with_from_key(mut self) -> Self {
+        self.range = KeyRange::FromKey;
+        self
+    }
+
+    /// Deletes all keys prefixed with key.
+    #[inline]
+    pub fn with_prefix(mut self) -> Self {
+        self.range = KeyRange::Prefix;
+        self
+    }
+
+    /// Deletes all keys.
+    #[inline]
+    pub fn with_all_keys(mut self) -> Self {
+        self.range = KeyRange::AllKeys;
+        self
+    }
+
+    /// If `prev_kv` is set, etcd gets the previous key-value pairs before deleting it.
+    /// The previous key-value pairs will be returned in the delete response.
+    #[inline]
+    pub const fn with_prev_key(mut self) -> Self {
+        self.prev_kv = true;
+        self
+    }
+}
 
 /// Response for `Delete` operation.
 #[derive(Debug, Clone)]
 pub struct DeleteResponse {
     pub(crate) header: ResponseHeader,
     pub(crate) deleted: i64,
+    pub(crate) prev_kvs: Vec<KeyValue>,
 }
 
 impl DeleteResponse {
@@ -233,6 +430,12 @@ impl DeleteResponse {
     pub const fn deleted(&self) -> i64 {
         self.deleted
     }
+
+    /// If `prev_kv` is set in the request, the previous key-value pairs will be returned.
+    #[inline]
+    pub fn prev_kvs(&self) -> &[KeyValue] {
+        &self.prev_kvs
+    }
 }
 
 /// Options for `Compact` operation.
@@ -310,8 +513,8 @@ impl Txn {
 #[derive(Debug, Clone)]
 pub struct Compare {
     pub(crate) key: Bytes,
-    pub(crate) value: Bytes,
     pub(crate) op: CompareOp,
+    pub(crate) target: CompareTarget,
 }
 
 ///  Logical comparison operation.
@@ -324,14 +527,53 @@ pub enum CompareOp {
     NotEqual = 3,
 }
 
+/// The field of key-value to compare, with the value to compare against.
+#[derive(Debug, Clone)]
+pub(crate) enum CompareTarget {
+    Version(i64),
+    CreateRevision(i64),
+    ModRevision(i64),
+    Value(Bytes),
+}
+
 impl Compare {
+    /// Compares the version of the given key.
+    #[inline]
+    pub fn version(key: impl Into<Vec<u8>>, cmp: CompareOp, version: i64) -> Self {
+        Compare {
+            key: key.into().into(),
+            op: cmp,
+            target: CompareTarget::Version(version),
+        }
+    }
+
+    /// Compares the creation revision of the given key.
+    #[inline]
+    pub fn create_revision(key: impl Into<Vec<u8>>, cmp: CompareOp, revision: i64) -> Self {
+        Compare {
+            key: key.into().into(),
+            op: cmp,
+            target: CompareTarget::CreateRevision(revision),
+        }
+    }
+
+    /// Compares the last modified revision of the given key.
+    #[inline]
+    pub fn mod_revision(key: impl Into<Vec<u8>>, cmp: CompareOp, revision: i64) -> Self {
+        Compare {
+            key: key.into().into(),
+            op: cmp,
+            target: CompareTarget::ModRevision(revision),
+        }
+    }
+
     /// Compares the value of the given key.
     #[inline]
     pub fn value(key: impl Into<Vec<u8>>, cmp: CompareOp, value: impl Into<Vec<u8>>) -> Self {
         Compare {
             key: key.into().into(),
-            value: value.into().into(),
             op: cmp,
+            target: CompareTarget::Value(value.into().into()),
         }
     }
 }
@@ -399,14 +641,25 @@ impl TxnOp {
 
 impl Display for Compare {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "value({:?}) ", String::from_utf8_lossy(&self.key))?;
+        let target = match &self.target {
+            CompareTarget::Version(_) => "version",
+            CompareTarget::CreateRevision(_) => "create_revision",
+            CompareTarget::ModRevision(_) => "mod_revision",
+            CompareTarget::Value(_) => "value",
+        };
+        write!(f, "{target}({:?}) ", String::from_utf8_lossy(&self.key))?;
         match self.op {
             CompareOp::Equal => write!(f, "=="),
             CompareOp::Greater => write!(f, ">"),
             CompareOp::Less => write!(f, "<"),
             CompareOp::NotEqual => write!(f, "!="),
         }?;
-        write!(f, " {:?}", String::from_utf8_lossy(&self.value))
+        match &self.target {
+            CompareTarget::Version(v)
+            | CompareTarget::CreateRevision(v)
+            | CompareTarget::ModRevision(v) => write!(f, " {v}"),
+            CompareTarget::Value(v) => write!(f, " {:?}", String::from_utf8_lossy(v)),
+        }
     }
 }
 
@@ -478,6 +731,11 @@ impl TxnResponse {
     }
 
     /// Returns responses of transaction operations.
+    ///
+    /// Operations are applied in order, and the responses are in the same order.
+    /// Each operation, including those in nested transactions, sees the effects of
+    /// the previous ones. All writes share the same revision, which is one more than
+    /// the revision before the transaction.
     #[inline]
     pub fn op_responses(&self) -> Vec<TxnOpResponse> {
         self.op_responses.clone()
@@ -492,6 +750,8 @@ pub struct KeyValue {
     pub(crate) lease: i64,
     pub(crate) create_revision: i64,
     pub(crate) modify_revision: i64,
+    #[serde(default)]
+    pub(crate) version: i64,
 }
 
 impl KeyValue {
@@ -524,4 +784,11 @@ impl KeyValue {
     pub const fn mod_revision(&self) -> i64 {
         self.modify_revision
     }
+
+    /// The version of the key. A deletion resets the version to zero and any
+    /// modification of the key increases its version.
+    #[inline]
+    pub const fn version(&self) -> i64 {
+        self.version
+    }
 }
@@ -2,6 +2,7 @@ use super::{server::Request, ResponseHeader, Result};
 use futures_util::stream::{Stream, StreamExt};
 use madsim::net::{Endpoint, Receiver, Sender};
 use std::{
+    collections::BTreeMap,
     net::SocketAddr,
     pin::Pin,
     task::{Context, Poll},
@@ -52,6 +53,9 @@ impl LeaseClient {
 
     /// Keeps the lease alive by streaming keep alive requests from the client
     /// to the server and streaming keep alive responses from the server to the client.
+    ///
+    /// Each request refreshes the TTL of the lease. The response has a TTL of 0 if the
+    /// lease has expired or does not exist.
     #[inline]
     pub async fn keep_alive(&mut self, id: i64) -> Result<(LeaseKeeper, LeaseKeepAliveStream)> {
         let (tx, rx) = self.ep.connect1(self.server_addr).await?;
@@ -86,6 +90,17 @@ impl LeaseClient {
         tx.send(Box::new(req)).await?;
         *rx.recv().await?.downcast().unwrap()
     }
+
+    /// Returns the number of keys attached to each existing lease, by lease ID.
+    ///
+    /// NOTE: This is a simulation-only API for debugging.
+    #[inline]
+    pub async fn key_counts(&mut self) -> Result<BTreeMap<i64, usize>> {
+        let req = Request::LeaseKeyCounts;
+        let (tx, mut rx) = self.ep.connect1(self.server_addr).await?;
+        tx.send(Box::new(req)).await?;
+        *rx.recv().await?.downcast().unwrap()
+    }
 }
 
 /// Options for `Grant` operation.
@@ -1,5 +1,5 @@
 use super::{server::Request, ResponseHeader, Result};
-use madsim::net::Endpoint;
+use madsim::net::{Endpoint, Receiver};
 use std::net::SocketAddr;
 
 /// Client for Maintenance operations.
@@ -26,12 +26,39 @@ impl MaintenanceClient {
         tx.send(Box::new(req)).await?;
         *rx.recv().await?.downcast().unwrap()
     }
+
+    /// Defragments a member's backend database to recover storage space.
+    ///
+    /// In simulation the data is not changed, but the server stalls for the duration set
+    /// by [`SimServer::defrag_duration`](super::SimServer::defrag_duration).
+    #[inline]
+    pub async fn defragment(&mut self) -> Result<DefragmentResponse> {
+        let req = Request::Defragment;
+        let (tx, mut rx) = self.ep.connect1(self.server_addr).await?;
+        tx.send(Box::new(req)).await?;
+        *rx.recv().await?.downcast().unwrap()
+    }
+
+    /// Takes a snapshot of the keyspace.
+    ///
+    /// The snapshot is in the format of [`Client::dump`](super::Client::dump),
+    /// so that it can be loaded by [`SimServer::load`](super::SimServer::load).
+    #[inline]
+    pub async fn snapshot(&mut self) -> Result<SnapshotStreaming> {
+        let req = Request::Snapshot;
+        let (tx, rx) = self.ep.connect1(self.server_addr).await?;
+        tx.send(Box::new(req)).await?;
+        Ok(SnapshotStreaming { rx, done: false })
+    }
 }
 
 /// Response for `Status` operation.
 #[derive(Debug, Clone)]
 pub struct StatusResponse {
     pub(crate) header: ResponseHeader,
+    pub(crate) db_size: i64,
+    pub(crate) leader: u64,
+    pub(crate) raft_term: u64,
 }
 
 impl StatusResponse {
@@ -40,4 +67,93 @@ impl StatusResponse {
     pub fn header(&self) -> Option<&ResponseHeader> {
         Some(&self.header)
     }
+
+    /// The size of the backend database in bytes.
+    ///
+    /// In simulation this is the total size of the keys and values. Since no history
+    /// is kept, compaction does not change the size.
+    #[inline]
+    pub fn db_size(&self) -> i64 {
+        self.db_size
+    }
+
+    /// The member ID which the responding member believes is the current leader.
+    #[inline]
+    pub fn leader(&self) -> u64 {
+        self.leader
+    }
+
+    /// The current raft term of the responding member.
+    #[inline]
+    pub fn raft_term(&self) -> u64 {
+        self.raft_term
+    }
+}
+
+/// Response for `Defragment` operation.
+#[derive(Debug, Clone)]
+pub struct DefragmentResponse {
+    pub(crate) header: ResponseHeader,
+}
+
+impl DefragmentResponse {
+    /// Gets response header.
+    #[inline]
+    pub fn header(&self) -> Option<&ResponseHeader> {
+        Some(&self.header)
+    }
+}
+
+/// Response for `Snapshot` operation.
+#[derive(Debug, Clone)]
+pub struct SnapshotResponse {
+    pub(crate) header: ResponseHeader,
+    pub(crate) remaining_bytes: u64,
+    pub(crate) blob: Vec<u8>,
+}
+
+impl SnapshotResponse {
+    /// Gets response header.
+    #[inline]
+    pub fn header(&self) -> Option<&ResponseHeader> {
+        Some(&self.header)
+    }
+
+    /// The number of bytes remaining to be sent after this chunk.
+    #[inline]
+    pub fn remaining_bytes(&self) -> u64 {
+        self.remaining_bytes
+    }
+
+    /// A chunk of the snapshot.
+    #[inline]
+    pub fn blob(&self) -> &[u8] {
+        &self.blob
+    }
+}
+
+/// Stream of [`SnapshotResponse`] chunks.
+#[derive(Debug)]
+pub struct SnapshotStreaming {
+    rx: Receiver,
+    /// Whether the last chunk has been received.
+    done: bool,
+}
+
+impl SnapshotStreaming {
+    /// Fetches the next message from this stream.
+    ///
+    /// Returns `None` after the last chunk.
+    #[inline]
+    pub async fn message(&mut self) -> Result<Option<SnapshotResponse>> {
+        if self.done {
+            return Ok(None);
+        }
+        let rsp = *(self.rx.recv().await?)
+            .downcast::<Result<SnapshotResponse>>()
+            .unwrap();
+        let rsp = rsp?;
+        self.done = rsp.remaining_bytes == 0;
+        Ok(Some(rsp))
+    }
 }
@@ -1,13 +1,33 @@
-use madsim::net::{Endpoint, Payload};
-use std::{io::Result, net::SocketAddr, sync::Arc};
+use futures_util::future::{select, Either};
+use madsim::{
+    net::{Endpoint, Payload},
+    rand::{thread_rng, Rng},
+};
+use std::{
+    collections::BTreeMap, io::Result, net::SocketAddr, pin::pin, sync::Arc, time::Duration,
+};
 
-use super::{election::*, kv::*, service::EtcdService, Bytes, EventType};
+use super::{
+    auth::*, cluster::*, election::*, kv::*, lease::*, maintenance::DefragmentResponse,
+    maintenance::SnapshotResponse, maintenance::StatusResponse, service::EtcdService, watch::*,
+    Bytes, Error,
+};
 
 /// A simulated etcd server.
 #[derive(Default, Clone)]
 pub struct SimServer {
     timeout_rate: f32,
+    drop_rate: f32,
+    conflict_rate: f32,
+    latency: Duration,
+    jitter: Duration,
+    max_staleness: Duration,
     load: Option<String>,
+    auth: AuthStore,
+    members: Option<Vec<Member>>,
+    raft_term: Option<u64>,
+    leader: Option<u64>,
+    defrag_duration: Duration,
 }
 
 impl SimServer {
@@ -23,23 +43,156 @@ impl SimServer {
         self
     }
 
+    /// Set the rate of dropping the connection after a request is processed.
+    ///
+    /// The request takes effect, but the response is never sent, so the client
+    /// does not know whether it succeeded.
+    pub fn drop_rate(mut self, rate: f32) -> Self {
+        assert!((0.0..=1.0).contains(&rate));
+        self.drop_rate = rate;
+        self
+    }
+
+    /// Set the rate of injecting a conflicting write before a transaction.
+    ///
+    /// Each key compared by its version or modification revision in a transaction is
+    /// rewritten with its current value at this rate, as if by another client, just
+    /// before the transaction is applied. This makes compare-and-swap transactions fail.
+    pub fn conflict_rate(mut self, rate: f32) -> Self {
+        assert!((0.0..=1.0).contains(&rate));
+        self.conflict_rate = rate;
+        self
+    }
+
+    /// Set the latency of requests.
+    ///
+    /// Each request is delayed by `latency` plus a random duration in `[0, jitter]`
+    /// before it is processed.
+    pub fn latency(mut self, latency: Duration, jitter: Duration) -> Self {
+        self.latency = latency;
+        self.jitter = jitter;
+        self
+    }
+
+    /// Set the maximum staleness of serializable reads.
+    ///
+    /// A serializable read observes the key-value store as of a random time in
+    /// `[now - max_staleness, now]`, modeling a lagging follower. Linearizable reads
+    /// always observe the latest data. The default is zero.
+    pub fn max_staleness(mut self, max_staleness: Duration) -> Self {
+        self.max_staleness = max_staleness;
+        self
+    }
+
     /// Load data from dump.
     pub fn load(mut self, data: String) -> Self {
         self.load = Some(data);
         self
     }
 
+    /// Set the members returned by `MemberList`.
+    ///
+    /// By default there is a single member serving at the server address.
+    pub fn members(mut self, members: impl Into<Vec<Member>>) -> Self {
+        self.members = Some(members.into());
+        self
+    }
+
+    /// Set the raft term returned by `Status`.
+    ///
+    /// By default the term is 1.
+    pub fn raft_term(mut self, term: u64) -> Self {
+        self.raft_term = Some(term);
+        self
+    }
+
+    /// Set the ID of the leader returned by `Status`.
+    ///
+    /// By default the leader is the first member.
+    pub fn leader(mut self, id: u64) -> Self {
+        self.leader = Some(id);
+        self
+    }
+
+    /// Set how long the server stalls on `Defragment`.
+    ///
+    /// Requests arriving while defragmenting wait until it finishes. The default is zero.
+    pub fn defrag_duration(mut self, duration: Duration) -> Self {
+        self.defrag_duration = duration;
+        self
+    }
+
+    /// Enable authentication.
+    ///
+    /// Once enabled, clients must authenticate as one of the users added by
+    /// [`user_add`](SimServer::user_add), and are only allowed to access the keys
+    /// granted to their roles. Users with the `root` role can access all keys.
+    pub fn auth_enable(mut self) -> Self {
+        self.auth.enable();
+        self
+    }
+
+    /// Add a user with password.
+    pub fn user_add(mut self, name: impl Into<String>, password: impl Into<String>) -> Self {
+        self.auth.user_add(name.into(), password.into());
+        self
+    }
+
+    /// Grant a role to the user.
+    pub fn user_grant_role(mut self, name: &str, role: impl Into<String>) -> Self {
+        self.auth.user_grant_role(name, role.into());
+        self
+    }
+
+    /// Add a role without any permission.
+    pub fn role_add(mut self, name: impl Into<String>) -> Self {
+        self.auth.role_add(name.into());
+        self
+    }
+
+    /// Grant a permission to the role.
+    pub fn role_grant_permission(mut self, name: &str, perm: Permission) -> Self {
+        self.auth.role_grant_permission(name, perm);
+        self
+    }
+
     /// Consume this [`SimServer`] creating a future that will execute the server.
     pub async fn serve(self, addr: SocketAddr) -> Result<()> {
         let ep = Endpoint::bind(addr).await?;
-        let service = Arc::new(EtcdService::new(self.timeout_rate, self.load));
+        let members = self.members.unwrap_or_else(|| {
+            vec![Member::new(1, "default")
+                .with_peer_urls(["http://localhost:2380"])
+                .with_client_urls([format!("http://{addr}")])]
+        });
+        let leader = (self.leader).unwrap_or_else(|| members.first().map_or(0, |m| m.id()));
+        let service = Arc::new(EtcdService::new(
+            self.timeout_rate,
+            self.conflict_rate,
+            self.latency,
+            self.jitter,
+            self.max_staleness,
+            self.load,
+            self.auth,
+            members,
+            self.raft_term.unwrap_or(1),
+            leader,
+            self.defrag_duration,
+        ));
+        let drop_rate = self.drop_rate as f64;
         loop {
-            let (tx, mut rx, _) = ep.accept1().await?;
+            let (tx, mut rx, addr) = ep.accept1().await?;
             let service = service.clone();
             madsim::task::spawn(async move {
                 while let Ok(request) = rx.recv().await {
                     let request = *request.downcast::<Request>().unwrap();
+                    if let Err(e) = service.check(addr, &request) {
+                        tx.send(request.error_response(e)).await?;
+                        continue;
+                    }
                     let response: Payload = match request {
+                        Request::Authenticate { name, password } => {
+                            Box::new(service.authenticate(addr, name, password).await)
+                        }
                         Request::Put {
                             key,
                             value,
@@ -75,13 +228,15 @@ impl SimServer {
                             }
                             Ok(mut stream) => {
                                 while let Some(event) = stream.recv().await {
-                                    if event.event_type != EventType::Put {
-                                        continue;
-                                    }
+                                    // the leader is lost on delete
+                                    let kv = match event.event_type {
+                                        EventType::Put => Some(event.kv),
+                                        EventType::Delete => None,
+                                    };
                                     let response: super::Result<LeaderResponse> =
                                         Ok(LeaderResponse {
                                             header: service.header(),
-                                            kv: Some(event.kv),
+                                            kv,
                                         });
                                     if tx.send(Box::new(response) as Payload).await.is_err() {
                                         return Ok(());
@@ -91,9 +246,96 @@ impl SimServer {
                             }
                         },
                         Request::Resign { leader } => Box::new(service.resign(leader).await),
+                        Request::Watch { key, options } => {
+                            match service.watch(key, &options).await {
+                                Err(e) => Box::new(super::Result::<WatchResponse>::Err(e)),
+                                Ok((watch_id, events, mut stream)) => {
+                                    /// The maximum number of events in a fragment.
+                                    const FRAGMENT_SIZE: usize = 100;
+                                    let response = |created, canceled, fragment, events| {
+                                        let rsp = WatchResponse {
+                                            header: service.header(),
+                                            watch_id,
+                                            created,
+                                            canceled,
+                                            fragment,
+                                            events,
+                                        };
+                                        Box::new(super::Result::Ok(rsp)) as Payload
+                                    };
+                                    tx.send(response(true, false, false, vec![])).await?;
+                                    // send the events since the start revision
+                                    let mut events: Vec<_> = (events.iter())
+                                        .map(|event| event.to_watch_event(options.prev_kv))
+                                        .collect();
+                                    while events.len() > FRAGMENT_SIZE && options.fragment {
+                                        let rest = events.split_off(FRAGMENT_SIZE);
+                                        let fragment = std::mem::replace(&mut events, rest);
+                                        tx.send(response(false, false, true, fragment)).await?;
+                                    }
+                                    if !events.is_empty() {
+                                        tx.send(response(false, false, false, events)).await?;
+                                    }
+                                    loop {
+                                        let event = match select(
+                                            pin!(stream.recv()),
+                                            pin!(rx.recv()),
+                                        )
+                                        .await
+                                        {
+                                            Either::Left((Some(event), _)) => event,
+                                            // the watcher is canceled
+                                            Either::Left((None, _)) | Either::Right((Ok(_), _)) => {
+                                                break
+                                            }
+                                            Either::Right((Err(_), _)) => return Ok(()),
+                                        };
+                                        let event = event.to_watch_event(options.prev_kv);
+                                        if tx
+                                            .send(response(false, false, false, vec![event]))
+                                            .await
+                                            .is_err()
+                                        {
+                                            return Ok(());
+                                        }
+                                    }
+                                    // the stream ends after the cancellation
+                                    tx.send(response(false, true, false, vec![])).await?;
+                                    return Ok(());
+                                }
+                            }
+                        }
+                        Request::WatchCancel { watch_id } => {
+                            Box::new(super::Result::Ok(WatchResponse {
+                                header: service.header(),
+                                watch_id,
+                                created: false,
+                                canceled: true,
+                                fragment: false,
+                                events: vec![],
+                            }))
+                        }
                         Request::Status => Box::new(service.status().await),
+                        Request::Defragment => Box::new(service.defragment().await),
+                        Request::Snapshot => match service.snapshot().await {
+                            Err(e) => Box::new(super::Result::<SnapshotResponse>::Err(e)),
+                            Ok(mut chunks) => {
+                                // send all chunks but the last one, which is sent as the response
+                                let last = chunks.pop().expect("no chunk");
+                                for chunk in chunks {
+                                    tx.send(Box::new(super::Result::Ok(chunk))).await?;
+                                }
+                                Box::new(super::Result::Ok(last))
+                            }
+                        },
+                        Request::MemberList => Box::new(service.member_list().await),
                         Request::Dump => Box::new(service.dump().await),
+                        Request::LeaseKeyCounts => Box::new(service.lease_key_counts().await),
                     };
+                    if drop_rate > 0.0 && thread_rng().gen_bool(drop_rate) {
+                        tracing::warn!(?addr, "drop connection before response");
+                        return Ok(());
+                    }
                     tx.send(response).await?;
                 }
                 Ok(()) as Result<()>
@@ -154,16 +396,70 @@ pub(crate) enum Request {
         name: Bytes,
     },
     Observe {
-        #[allow(dead_code)]
         name: Bytes,
     },
     Resign {
         leader: LeaderKey,
     },
 
+    // watch API
+    Watch {
+        key: Bytes,
+        options: WatchOptions,
+    },
+    WatchCancel {
+        watch_id: i64,
+    },
+
     // maintenance API
     Status,
+    Defragment,
+    Snapshot,
+
+    // cluster API
+    MemberList,
+
+    // auth API
+    Authenticate {
+        name: String,
+        password: String,
+    },
 
     // internal API
     Dump,
+    LeaseKeyCounts,
+}
+
+impl Request {
+    /// Returns a response of the error for this request.
+    fn error_response(&self, e: Error) -> Payload {
+        type R<T> = super::Result<T>;
+        match self {
+            Request::Put { .. } => Box::new(R::<PutResponse>::Err(e)),
+            Request::Get { .. } => Box::new(R::<GetResponse>::Err(e)),
+            Request::Delete { .. } => Box::new(R::<DeleteResponse>::Err(e)),
+            Request::Txn { .. } => Box::new(R::<TxnResponse>::Err(e)),
+            Request::LeaseGrant { .. } => Box::new(R::<LeaseGrantResponse>::Err(e)),
+            Request::LeaseRevoke { .. } => Box::new(R::<LeaseRevokeResponse>::Err(e)),
+            Request::LeaseKeepAlive { .. } => Box::new(R::<LeaseKeepAliveResponse>::Err(e)),
+            Request::LeaseTimeToLive { .. } => Box::new(R::<LeaseTimeToLiveResponse>::Err(e)),
+            Request::LeaseLeases => Box::new(R::<LeaseLeasesResponse>::Err(e)),
+            Request::Campaign { .. } => Box::new(R::<CampaignResponse>::Err(e)),
+            Request::Proclaim { .. } => Box::new(R::<ProclaimResponse>::Err(e)),
+            Request::Leader { .. } | Request::Observe { .. } => {
+                Box::new(R::<LeaderResponse>::Err(e))
+            }
+            Request::Resign { .. } => Box::new(R::<ResignResponse>::Err(e)),
+            Request::Watch { .. } | Request::WatchCancel { .. } => {
+                Box::new(R::<WatchResponse>::Err(e))
+            }
+            Request::Status => Box::new(R::<StatusResponse>::Err(e)),
+            Request::Defragment => Box::new(R::<DefragmentResponse>::Err(e)),
+            Request::Snapshot => Box::new(R::<SnapshotResponse>::Err(e)),
+            Request::MemberList => Box::new(R::<MemberListResponse>::Err(e)),
+            Request::Authenticate { .. } => Box::new(R::<AuthenticateResponse>::Err(e)),
+            Request::Dump => Box::new(R::<String>::Err(e)),
+            Request::LeaseKeyCounts => Box::new(R::<BTreeMap<i64, usize>>::Err(e)),
+        }
+    }
 }
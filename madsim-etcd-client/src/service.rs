@@ -1,10 +1,13 @@
 use super::*;
 use madsim::rand::{random, thread_rng, Rng};
+use madsim::time::Instant;
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
 use spin::Mutex;
 use std::collections::btree_map::Entry;
-use std::collections::{btree_map::Range, BTreeMap, HashMap, HashSet};
+use std::collections::{btree_map::Range, BTreeMap, HashMap, HashSet, VecDeque};
+use std::net::SocketAddr;
+use std::ops::Bound;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc;
@@ -12,16 +15,43 @@ use tokio::sync::mpsc;
 #[derive(Debug)]
 pub struct EtcdService {
     timeout_rate: f32,
+    conflict_rate: f32,
+    latency: Duration,
+    jitter: Duration,
     inner: Arc<Mutex<ServiceInner>>,
+    auth: AuthStore,
+    members: Vec<Member>,
+    raft_term: u64,
+    /// The ID of the leader member.
+    leader: u64,
+    /// How long `Defragment` stalls the server.
+    defrag_duration: Duration,
+    /// The time until which requests are stalled by defragmentation.
+    paused_until: Mutex<Option<Instant>>,
+    /// Authenticated users of clients.
+    users: Mutex<HashMap<SocketAddr, String>>,
 }
 
 impl EtcdService {
-    pub fn new(timeout_rate: f32, data: Option<String>) -> Self {
-        let inner = Arc::new(Mutex::new(
-            data.map_or_else(ServiceInner::default, |data| {
-                toml::from_str(&data).expect("failed to deserialize dump")
-            }),
-        ));
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        timeout_rate: f32,
+        conflict_rate: f32,
+        latency: Duration,
+        jitter: Duration,
+        max_staleness: Duration,
+        data: Option<String>,
+        auth: AuthStore,
+        members: Vec<Member>,
+        raft_term: u64,
+        leader: u64,
+        defrag_duration: Duration,
+    ) -> Self {
+        let mut inner: ServiceInner = data.map_or_else(Default::default, |data| {
+            toml::from_str(&data).expect("failed to deserialize dump")
+        });
+        inner.watcher.history_duration = max_staleness;
+        let inner = Arc::new(Mutex::new(inner));
         let weak = Arc::downgrade(&inner);
         madsim::task::spawn(async move {
             while let Some(inner) = weak.upgrade() {
@@ -32,10 +62,27 @@ impl EtcdService {
         });
         EtcdService {
             timeout_rate,
+            conflict_rate,
+            latency,
+            jitter,
             inner,
+            auth,
+            members,
+            raft_term,
+            leader,
+            defrag_duration,
+            paused_until: Mutex::new(None),
+            users: Mutex::new(HashMap::new()),
         }
     }
 
+    /// Checks whether the client is allowed to perform the request.
+    pub fn check(&self, addr: SocketAddr, request: &Request) -> Result<()> {
+        let users = self.users.lock();
+        self.auth
+            .check(users.get(&addr).map(|s| s.as_str()), request)
+    }
+
     pub fn header(&self) -> ResponseHeader {
         self.inner.lock().header()
     }
@@ -60,7 +107,17 @@ impl EtcdService {
 
     pub async fn txn(&self, txn: Txn) -> Result<TxnResponse> {
         self.timeout().await?;
-        let rsp = self.inner.lock().txn(txn);
+        let mut inner = self.inner.lock();
+        for cmp in &txn.compare {
+            if matches!(
+                cmp.target,
+                CompareTarget::Version(_) | CompareTarget::ModRevision(_)
+            ) && thread_rng().gen_bool(self.conflict_rate as f64)
+            {
+                inner.inject_conflict(&cmp.key);
+            }
+        }
+        let rsp = inner.txn(txn)?;
         Ok(rsp)
     }
 
@@ -78,7 +135,7 @@ impl EtcdService {
 
     pub async fn lease_keep_alive(&self, id: i64) -> Result<LeaseKeepAliveResponse> {
         self.timeout().await?;
-        let rsp = self.inner.lock().lease_keep_alive(id)?;
+        let rsp = self.inner.lock().lease_keep_alive(id);
         Ok(rsp)
     }
 
@@ -120,6 +177,17 @@ impl EtcdService {
         self.inner.lock().observe(name)
     }
 
+    /// Subscribes to events on the keys. Returns the watch ID, the events since the start
+    /// revision, and the stream of the following events.
+    pub async fn watch(
+        &self,
+        key: Key,
+        options: &WatchOptions,
+    ) -> Result<(i64, Vec<Event>, mpsc::Receiver<Event>)> {
+        self.timeout().await?;
+        Ok(self.inner.lock().watch(key, options))
+    }
+
     pub async fn resign(&self, leader: LeaderKey) -> Result<ResignResponse> {
         self.timeout().await?;
         self.inner.lock().resign(leader)
@@ -127,7 +195,87 @@ impl EtcdService {
 
     pub async fn status(&self) -> Result<StatusResponse> {
         self.timeout().await?;
-        self.inner.lock().status()
+        let inner = self.inner.lock();
+        Ok(StatusResponse {
+            header: inner.header(),
+            db_size: inner.db_size(),
+            leader: self.leader,
+            raft_term: self.raft_term,
+        })
+    }
+
+    /// Stalls the server for the defragmentation duration. The data is not changed.
+    pub async fn defragment(&self) -> Result<DefragmentResponse> {
+        self.timeout().await?;
+        if !self.defrag_duration.is_zero() {
+            let until = Instant::now() + self.defrag_duration;
+            *self.paused_until.lock() = Some(until);
+            madsim::time::sleep_until(until).await;
+        }
+        Ok(DefragmentResponse {
+            header: self.header(),
+        })
+    }
+
+    pub async fn member_list(&self) -> Result<MemberListResponse> {
+        self.timeout().await?;
+        Ok(MemberListResponse {
+            header: self.header(),
+            members: self.members.clone(),
+        })
+    }
+
+    pub async fn authenticate(
+        &self,
+        addr: SocketAddr,
+        name: String,
+        password: String,
+    ) -> Result<AuthenticateResponse> {
+        self.timeout().await?;
+        tracing::trace!(name, "authenticate");
+        self.auth.authenticate(&name, &password)?;
+        let token = format!("{name}.{}", random::<u64>());
+        self.users.lock().insert(addr, name);
+        Ok(AuthenticateResponse {
+            header: self.header(),
+            token,
+        })
+    }
+
+    /// Returns the dump of the keyspace in chunks. There is at least one chunk.
+    pub async fn snapshot(&self) -> Result<Vec<SnapshotResponse>> {
+        /// The size of snapshot chunks.
+        const CHUNK_SIZE: usize = 32 * 1024;
+
+        self.timeout().await?;
+        let header = self.header();
+        let data = self.dump().await?.into_bytes();
+        let mut remaining = data.len();
+        let chunks = (data.chunks(CHUNK_SIZE))
+            .map(|chunk| {
+                remaining -= chunk.len();
+                SnapshotResponse {
+                    header: header.clone(),
+                    remaining_bytes: remaining as u64,
+                    blob: chunk.to_vec(),
+                }
+            })
+            .collect::<Vec<_>>();
+        if chunks.is_empty() {
+            return Ok(vec![SnapshotResponse {
+                header,
+                remaining_bytes: 0,
+                blob: vec![],
+            }]);
+        }
+        Ok(chunks)
+    }
+
+    pub async fn lease_key_counts(&self) -> Result<BTreeMap<i64, usize>> {
+        let inner = self.inner.lock();
+        Ok((inner.lease.iter())
+            .map(|(&id, lease)| (id, lease.keys.len()))
+            .collect())
     }
 
     pub async fn dump(&self) -> Result<String> {
@@ -136,6 +284,15 @@ impl EtcdService {
     }
 
     async fn timeout(&self) -> Result<()> {
+        // wait for the defragmentation to finish
+        let paused_until = *self.paused_until.lock();
+        if let Some(until) = paused_until {
+            madsim::time::sleep_until(until).await;
+        }
+        let latency = self.latency + thread_rng().gen_range(Duration::ZERO..=self.jitter);
+        if !latency.is_zero() {
+            madsim::time::sleep(latency).await;
+        }
         if thread_rng().gen_bool(self.timeout_rate as f64) {
             let t = thread_rng().gen_range(Duration::from_secs(5)..Duration::from_secs(15));
             madsim::time::sleep(t).await;
@@ -158,17 +315,33 @@ struct ServiceInner {
     lease: HashMap<LeaseId, Lease>,
     #[serde(skip)]
     watcher: EventBus,
+    /// The revision of writes in the running transaction.
+    #[serde(skip)]
+    txn_revision: Option<i64>,
 }
 
 #[derive(Debug, Default)]
 struct EventBus {
     list: Vec<(EventPattern, mpsc::Sender<Event>)>,
+    /// The ID of the next watcher.
+    next_watch_id: i64,
+    /// Events published within the last `history_duration`, in order.
+    history: VecDeque<(Instant, Event)>,
+    /// How long events are kept in the history.
+    history_duration: Duration,
+    /// Events of the running transaction, published when it commits.
+    pending: Option<Vec<Event>>,
 }
 
 #[derive(Debug)]
 enum EventPattern {
     Leader(Key),
     Prefix(Key),
+    Watch {
+        start: Key,
+        end: Option<Key>,
+        filters: Vec<WatchFilterType>,
+    },
 }
 
 impl EventPattern {
@@ -178,6 +351,20 @@ impl EventPattern {
                 event.kv.key.starts_with(prefix) && event.event_type == EventType::Delete
             }
             Self::Prefix(prefix) => event.kv.key.starts_with(prefix),
+            Self::Watch {
+                start,
+                end,
+                filters,
+            } => {
+                let filter = match event.event_type {
+                    EventType::Put => WatchFilterType::NoPut,
+                    EventType::Delete => WatchFilterType::NoDelete,
+                };
+                let key = &event.kv.key;
+                key >= start
+                    && !matches!(end, Some(end) if key >= end)
+                    && !filters.contains(&filter)
+            }
         }
     }
 }
@@ -185,23 +372,48 @@ impl EventPattern {
 #[derive(Debug, Clone)]
 pub struct Event {
     pub event_type: EventType,
+    /// The new key-value of a put, or the deleted key-value of a delete.
     pub kv: KeyValue,
+    /// The key-value before a put.
+    pub prev_kv: Option<KeyValue>,
+    /// The revision of the event.
+    pub revision: i64,
 }
 
 impl Event {
     /// Returns a put event.
-    fn put(kv: KeyValue) -> Self {
+    fn put(kv: KeyValue, prev_kv: Option<KeyValue>) -> Self {
         Self {
             event_type: EventType::Put,
+            revision: kv.modify_revision,
             kv,
+            prev_kv,
         }
     }
 
     /// Returns a delete event.
-    fn delete(prev_kv: KeyValue) -> Self {
+    fn delete(prev_kv: KeyValue, revision: i64) -> Self {
         Self {
             event_type: EventType::Delete,
             kv: prev_kv,
+            prev_kv: None,
+            revision,
+        }
+    }
+
+    /// Converts to an event of the watch API.
+    pub fn to_watch_event(&self, prev_kv: bool) -> super::Event {
+        match self.event_type {
+            EventType::Put => super::Event {
+                event_type: EventType::Put,
+                kv: Some(self.kv.clone()),
+                prev_kv: self.prev_kv.clone().filter(|_| prev_kv),
+            },
+            EventType::Delete => super::Event {
+                event_type: EventType::Delete,
+                kv: Some(deleted_kv(self.kv.key.clone(), self.revision)),
+                prev_kv: Some(self.kv.clone()).filter(|_| prev_kv),
+            },
         }
     }
 }
@@ -213,9 +425,23 @@ impl EventBus {
         self.list.push((pattern, tx));
     }
 
-    /// Publish an event.
+    /// Publish an event, or defer it until the running transaction commits.
     fn publish(&mut self, event: Event) {
+        if let Some(pending) = &mut self.pending {
+            pending.push(event);
+            return;
+        }
         tracing::trace!(?event, "new event");
+        if !self.history_duration.is_zero() {
+            let now = Instant::now();
+            while let Some((time, _)) = self.history.front() {
+                if now.duration_since(*time) <= self.history_duration {
+                    break;
+                }
+                self.history.pop_front();
+            }
+            self.history.push_back((now, event.clone()));
+        }
         self.list.retain(|(pattern, tx)| {
             if pattern.is_match(&event) {
                 tx.try_send(event.clone()).is_ok()
@@ -230,7 +456,7 @@ type LeaseId = i64;
 type Key = Bytes;
 type Value = Bytes;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct Lease {
     ttl: i64,
     granted_ttl: i64,
@@ -254,7 +480,15 @@ impl ServiceInner {
         }
     }
 
-    fn put(&mut self, key: Key, value: Value, options: PutOptions) -> Result<PutResponse> {
+    /// Increments the revision for a write and returns it.
+    ///
+    /// All writes in a transaction share the same revision.
+    fn write_revision(&mut self) -> i64 {
+        self.revision = self.txn_revision.unwrap_or(self.revision + 1);
+        self.revision
+    }
+
+    fn put(&mut self, key: Key, mut value: Value, mut options: PutOptions) -> Result<PutResponse> {
         tracing::trace!(
             ?key,
             ?value,
@@ -265,24 +499,30 @@ impl ServiceInner {
             },
             "put"
         );
+        self.check_put(&key, &value, &options)?;
         let prev_value = self.kv.get(&key).cloned();
+        if let Some(prev_value) = &prev_value {
+            if options.ignore_value {
+                value = prev_value.value.clone();
+            }
+            if options.ignore_lease {
+                options.lease = prev_value.lease;
+            }
+        }
         // add key to the new lease
         if options.lease != 0 {
-            let lease = self
-                .lease
-                .get_mut(&options.lease)
-                .ok_or_else(lease_not_found)?;
+            let lease = self.lease.get_mut(&options.lease).expect("no lease");
             lease.keys.insert(key.clone());
         }
         // remove key from the old lease
         if let Some(v) = &prev_value {
-            if v.lease != 0 {
+            if v.lease != 0 && v.lease != options.lease {
                 let lease = self.lease.get_mut(&v.lease).expect("no lease");
                 lease.keys.remove(&key);
             }
         }
         // update main key-value
-        self.revision += 1;
+        self.write_revision();
         let kv = KeyValue {
             key: key.clone(),
             value,
@@ -291,9 +531,10 @@ impl ServiceInner {
                 .as_ref()
                 .map_or(self.revision, |v| v.create_revision),
             modify_revision: self.revision,
+            version: prev_value.as_ref().map_or(1, |v| v.version + 1),
         };
         *self.kv.entry(key).or_default() = kv.clone();
-        self.watcher.publish(Event::put(kv));
+        self.watcher.publish(Event::put(kv, prev_value.clone()));
 
         Ok(PutResponse {
             header: self.header(),
@@ -301,60 +542,190 @@ impl ServiceInner {
         })
     }
 
+    /// Returns the error of a put without applying it.
+    fn check_put(&self, key: &Key, value: &Value, options: &PutOptions) -> Result<()> {
+        if options.ignore_value && !value.is_empty() {
+            return Err(invalid_argument("etcdserver: value is provided"));
+        }
+        if options.ignore_lease && options.lease != 0 {
+            return Err(invalid_argument("etcdserver: lease is provided"));
+        }
+        if (options.ignore_value || options.ignore_lease) && !self.kv.contains_key(key) {
+            return Err(key_not_found());
+        }
+        if options.lease != 0 && !self.lease.contains_key(&options.lease) {
+            return Err(lease_not_found());
+        }
+        Ok(())
+    }
+
     fn get(&mut self, key: Key, options: GetOptions) -> GetResponse {
         tracing::trace!(?key, ?options, "get");
         if options.revision > 0 {
             todo!("get with revision");
         }
-        let kvs = if options.prefix {
-            self.get_prefix_range(key).map(|(_, v)| v.clone()).collect()
-        } else {
-            self.kv.get(&key).cloned().into_iter().collect()
+        let stale_view = options.serializable.then(|| self.stale_view()).flatten();
+        let (kv, revision) = match &stale_view {
+            Some((kv, revision)) => (kv, *revision),
+            None => (&self.kv, self.revision),
         };
+        let header = ResponseHeader { revision };
+        if options.count_only {
+            return GetResponse {
+                header,
+                kvs: vec![],
+                more: false,
+                count: range(kv, &key, &options.range).count() as i64,
+            };
+        }
+        // key-values are in lexicographic key order by default
+        let mut kvs: Vec<KeyValue> = (range(kv, &key, &options.range))
+            .map(|(_, v)| v.clone())
+            .collect();
+        if let Some((target, order)) = options.sort {
+            kvs.sort_by(|a, b| {
+                let ord = match target {
+                    SortTarget::Key => a.key.cmp(&b.key),
+                    SortTarget::Version => a.version.cmp(&b.version),
+                    SortTarget::Create => a.create_revision.cmp(&b.create_revision),
+                    SortTarget::Mod => a.modify_revision.cmp(&b.modify_revision),
+                    SortTarget::Value => a.value.cmp(&b.value),
+                };
+                match order {
+                    SortOrder::None | SortOrder::Ascend => ord,
+                    SortOrder::Descend => ord.reverse(),
+                }
+            });
+        }
+        let count = kvs.len() as i64;
+        let more = options.limit > 0 && count > options.limit;
+        if more {
+            kvs.truncate(options.limit as usize);
+        }
+        if options.keys_only {
+            for kv in &mut kvs {
+                kv.value = Bytes::default();
+            }
+        }
         GetResponse {
-            header: self.header(),
+            header,
             kvs,
+            more,
+            count,
         }
     }
 
-    fn get_prefix_range(&self, key: Key) -> Range<'_, Key, KeyValue> {
-        let mut end = key.clone();
-        *end.last_mut().unwrap() += 1;
-        self.kv.range(key..end)
+    /// Returns the key-values and the revision as seen by a lagging follower.
+    ///
+    /// The follower lags behind by a random duration up to the history duration.
+    /// Returns `None` if it is up to date.
+    fn stale_view(&self) -> Option<(BTreeMap<Key, KeyValue>, i64)> {
+        let history = &self.watcher.history;
+        if history.is_empty() {
+            return None;
+        }
+        let lag = thread_rng().gen_range(Duration::ZERO..=self.watcher.history_duration);
+        let now = Instant::now();
+        let mut kv = self.kv.clone();
+        let mut revision = self.revision;
+        // undo the events that the follower has not applied yet
+        for (_, event) in (history.iter().rev()).take_while(|(time, _)| now - *time < lag) {
+            match (event.event_type, &event.prev_kv) {
+                (EventType::Put, Some(prev_kv)) => kv.insert(prev_kv.key.clone(), prev_kv.clone()),
+                (EventType::Put, None) => kv.remove(&event.kv.key),
+                (EventType::Delete, _) => kv.insert(event.kv.key.clone(), event.kv.clone()),
+            };
+            revision = event.revision - 1;
+        }
+        if revision == self.revision {
+            return None;
+        }
+        tracing::trace!(?lag, revision, "stale view");
+        Some((kv, revision))
     }
 
-    fn delete(&mut self, key: Key, _options: DeleteOptions) -> DeleteResponse {
-        tracing::trace!(?key, "delete");
-        let prev_kv = self.kv.remove(&key);
-        let deleted = prev_kv.is_some() as i64;
-        if let Some(kv) = prev_kv {
-            self.revision += 1;
+    /// Returns the key-values in the range starting from `key`, in lexicographic key order.
+    fn range(&self, key: &Key, range: &KeyRange) -> Range<'_, Key, KeyValue> {
+        self::range(&self.kv, key, range)
+    }
+
+    fn delete(&mut self, key: Key, options: DeleteOptions) -> DeleteResponse {
+        tracing::trace!(?key, ?options, "delete");
+        let keys: Vec<Key> = (self.range(&key, &options.range))
+            .map(|(k, _)| k.clone())
+            .collect();
+        if !keys.is_empty() {
+            self.write_revision();
+        }
+        let mut prev_kvs = Vec::with_capacity(keys.len());
+        for key in keys {
+            let kv = self.kv.remove(&key).expect("no key");
             // remove key from the lease
             if kv.lease != 0 {
                 let lease = self.lease.get_mut(&kv.lease).expect("no lease");
                 lease.keys.remove(&key);
             }
-            self.watcher.publish(Event::delete(kv));
+            self.watcher
+                .publish(Event::delete(kv.clone(), self.revision));
+            prev_kvs.push(kv);
         }
         DeleteResponse {
             header: self.header(),
-            deleted,
+            deleted: prev_kvs.len() as i64,
+            prev_kvs: if options.prev_kv { prev_kvs } else { vec![] },
         }
     }
 
-    fn txn(&mut self, txn: Txn) -> TxnResponse {
+    fn txn(&mut self, txn: Txn) -> Result<TxnResponse> {
         tracing::trace!(%txn, "transaction");
-        let succeeded = txn.compare.iter().all(|cmp| {
-            let value = self.kv.get(&cmp.key).map(|v| &v.value);
-            match cmp.op {
-                CompareOp::Equal => value == Some(&cmp.value),
-                CompareOp::Greater => matches!(value, Some(v) if v > &cmp.value),
-                CompareOp::Less => matches!(value, Some(v) if v < &cmp.value),
-                CompareOp::NotEqual => value != Some(&cmp.value),
+        // nested transactions are part of the outermost one
+        if self.txn_revision.is_some() {
+            return self.apply_txn(txn);
+        }
+        // a failed transaction is rolled back without publishing any events
+        let snapshot = (self.kv.clone(), self.lease.clone(), self.revision);
+        self.txn_revision = Some(self.revision + 1);
+        self.watcher.pending = Some(vec![]);
+        let rsp = self.apply_txn(txn);
+        self.txn_revision = None;
+        let events = self.watcher.pending.take().unwrap();
+        match &rsp {
+            Ok(_) => events.into_iter().for_each(|e| self.watcher.publish(e)),
+            Err(_) => (self.kv, self.lease, self.revision) = snapshot,
+        }
+        rsp
+    }
+
+    /// Returns whether all comparisons of the transaction succeed.
+    fn txn_succeeded(&self, txn: &Txn) -> bool {
+        txn.compare.iter().all(|cmp| {
+            let kv = self.kv.get(&cmp.key);
+            match &cmp.target {
+                CompareTarget::Value(target) => {
+                    let value = kv.map(|v| &v.value);
+                    match cmp.op {
+                        CompareOp::Equal => value == Some(target),
+                        CompareOp::Greater => matches!(value, Some(v) if v > target),
+                        CompareOp::Less => matches!(value, Some(v) if v < target),
+                        CompareOp::NotEqual => value != Some(target),
+                    }
+                }
+                // a missing key has zero version and revisions
+                CompareTarget::Version(target) => {
+                    compare(kv.map_or(0, |v| v.version), cmp.op, *target)
+                }
+                CompareTarget::CreateRevision(target) => {
+                    compare(kv.map_or(0, |v| v.create_revision), cmp.op, *target)
+                }
+                CompareTarget::ModRevision(target) => {
+                    compare(kv.map_or(0, |v| v.modify_revision), cmp.op, *target)
+                }
             }
-        });
+        })
+    }
 
-        let revision = self.revision;
+    fn apply_txn(&mut self, txn: Txn) -> Result<TxnResponse> {
+        let succeeded = self.txn_succeeded(&txn);
         let mut op_responses = vec![];
         for op in if succeeded { txn.success } else { txn.failure } {
             let response = match op {
@@ -363,19 +734,29 @@ impl ServiceInner {
                     key,
                     value,
                     options,
-                } => TxnOpResponse::Put(self.put(key, value, options).expect("put failed in txn")),
+                } => TxnOpResponse::Put(self.put(key, value, options)?),
                 TxnOp::Delete { key, options } => TxnOpResponse::Delete(self.delete(key, options)),
-                TxnOp::Txn { txn } => TxnOpResponse::Txn(self.txn(txn)),
+                TxnOp::Txn { txn } => TxnOpResponse::Txn(self.txn(txn)?),
             };
             op_responses.push(response);
         }
-        self.revision = revision + 1;
-
-        TxnResponse {
+        Ok(TxnResponse {
             header: self.header(),
             succeeded,
             op_responses,
-        }
+        })
+    }
+
+    /// Rewrites the key with its current value, bumping its version and modification revision.
+    fn inject_conflict(&mut self, key: &Key) {
+        let Some(kv) = self.kv.get(key) else {
+            return;
+        };
+        tracing::trace!(?key, "inject conflict");
+        let (value, lease) = (kv.value.clone(), kv.lease);
+        let options = PutOptions::new().with_lease(lease);
+        self.put(key.clone(), value, options)
+            .expect("failed to inject conflict");
     }
 
     fn lease_grant(&mut self, ttl: i64, mut id: i64) -> LeaseGrantResponse {
@@ -402,7 +783,7 @@ impl ServiceInner {
         for key in lease.keys {
             tracing::trace!(?key, "delete");
             let kv = self.kv.remove(&key).expect("no key");
-            self.watcher.publish(Event::delete(kv));
+            self.watcher.publish(Event::delete(kv, self.revision + 1));
         }
         self.revision += 1;
         Ok(LeaseRevokeResponse {
@@ -410,17 +791,24 @@ impl ServiceInner {
         })
     }
 
-    fn lease_keep_alive(&mut self, id: i64) -> Result<LeaseKeepAliveResponse> {
+    fn lease_keep_alive(&mut self, id: i64) -> LeaseKeepAliveResponse {
         tracing::trace!(id, "lease_keep_alive");
-        let lease = self.lease.get_mut(&id).ok_or_else(lease_not_found)?;
+        // like etcd, keeping alive an expired or unknown lease responds with a TTL of 0
+        let Some(lease) = self.lease.get_mut(&id) else {
+            return LeaseKeepAliveResponse {
+                header: self.header(),
+                id,
+                ttl: 0,
+            };
+        };
         let ttl = lease.granted_ttl;
         lease.ttl = ttl;
         self.revision += 1;
-        Ok(LeaseKeepAliveResponse {
+        LeaseKeepAliveResponse {
             header: self.header(),
             id,
             ttl,
-        })
+        }
     }
 
     fn lease_time_to_live(&self, id: i64, keys: bool) -> Result<LeaseTimeToLiveResponse> {
@@ -455,7 +843,7 @@ impl ServiceInner {
                 for key in &lease.keys {
                     tracing::trace!(?key, "delete");
                     let kv = self.kv.remove(key).expect("no key");
-                    self.watcher.publish(Event::delete(kv));
+                    self.watcher.publish(Event::delete(kv, self.revision + 1));
                 }
                 false
             } else {
@@ -473,7 +861,7 @@ impl ServiceInner {
         value: &Value,
         lease: i64,
     ) -> Result<std::result::Result<CampaignResponse, mpsc::Receiver<Event>>> {
-        if self.get_prefix_range(name.clone()).next().is_some() {
+        if self.range(name, &KeyRange::Prefix).next().is_some() {
             // the election name is occupied
             let (tx, rx) = mpsc::channel(1);
             self.watcher
@@ -493,6 +881,7 @@ impl ServiceInner {
             lease,
             create_revision: self.revision,
             modify_revision: self.revision,
+            version: 1,
         };
         self.lease
             .get_mut(&lease)
@@ -500,7 +889,7 @@ impl ServiceInner {
             .keys
             .insert(key.clone());
         self.kv.insert(key.clone(), kv.clone());
-        self.watcher.publish(Event::put(kv));
+        self.watcher.publish(Event::put(kv, None));
         self.revision += 1;
 
         Ok(Ok(CampaignResponse {
@@ -520,9 +909,11 @@ impl ServiceInner {
             Entry::Vacant(_) => return Err(session_expired()),
             Entry::Occupied(mut entry) => {
                 self.revision += 1;
+                let prev_kv = entry.get().clone();
                 entry.get_mut().value = value;
                 entry.get_mut().modify_revision = self.revision;
-                self.watcher.publish(Event::put(entry.get_mut().clone()));
+                entry.get_mut().version += 1;
+                (self.watcher).publish(Event::put(entry.get().clone(), Some(prev_kv)));
             }
         }
         Ok(ProclaimResponse {
@@ -533,7 +924,7 @@ impl ServiceInner {
     fn leader(&self, name: Key) -> Result<LeaderResponse> {
         Ok(LeaderResponse {
             header: self.header(),
-            kv: self.get_prefix_range(name).next().map(|(_, v)| v.clone()),
+            kv: (self.range(&name, &KeyRange::Prefix).next()).map(|(_, v)| v.clone()),
         })
     }
 
@@ -544,21 +935,94 @@ impl ServiceInner {
         Ok(rx)
     }
 
+    fn watch(
+        &mut self,
+        key: Key,
+        options: &WatchOptions,
+    ) -> (i64, Vec<Event>, mpsc::Receiver<Event>) {
+        tracing::trace!(?key, ?options, "watch");
+        let mut events = vec![];
+        if options.start_revision > 0 && !options.filters.contains(&WatchFilterType::NoPut) {
+            events = (self.range(&key, &options.range))
+                .filter(|(_, kv)| kv.modify_revision >= options.start_revision)
+                .map(|(_, kv)| Event::put(kv.clone(), None))
+                .collect();
+            events.sort_by_key(|event| event.revision);
+        }
+        let (start, end) = interval(&key, &options.range);
+        let (tx, rx) = mpsc::channel(100);
+        let pattern = EventPattern::Watch {
+            start,
+            end,
+            filters: options.filters.clone(),
+        };
+        self.watcher.subscribe(pattern, tx);
+        let watch_id = self.watcher.next_watch_id;
+        self.watcher.next_watch_id += 1;
+        (watch_id, events, rx)
+    }
+
     fn resign(&mut self, leader: LeaderKey) -> Result<ResignResponse> {
         tracing::trace!(name = ?leader.name, "resign");
         let kv = self.kv.remove(&leader.key).ok_or_else(session_expired)?;
-        self.watcher.publish(Event::delete(kv));
+        self.watcher.publish(Event::delete(kv, self.revision + 1));
         self.revision += 1;
         Ok(ResignResponse {
             header: self.header(),
         })
     }
 
-    fn status(&mut self) -> Result<StatusResponse> {
-        tracing::trace!("status");
-        Ok(StatusResponse {
-            header: self.header(),
-        })
+    /// Returns the total size of the keys and values in bytes.
+    fn db_size(&self) -> i64 {
+        (self.kv.values())
+            .map(|kv| (kv.key.len() + kv.value.len()) as i64)
+            .sum()
+    }
+}
+
+/// Returns the key-values in the range starting from `key`, in lexicographic key order.
+fn range<'a>(
+    kv: &'a BTreeMap<Key, KeyValue>,
+    key: &Key,
+    range: &KeyRange,
+) -> Range<'a, Key, KeyValue> {
+    let start = Bound::Included(key.clone());
+    let bounds = match range {
+        KeyRange::Key => (start, Bound::Included(key.clone())),
+        KeyRange::Prefix => (
+            start,
+            prefix_end(key).map_or(Bound::Unbounded, Bound::Excluded),
+        ),
+        // an empty range
+        KeyRange::Range(end) if end <= key => (start, Bound::Excluded(key.clone())),
+        KeyRange::Range(end) => (start, Bound::Excluded(end.clone())),
+        KeyRange::FromKey => (start, Bound::Unbounded),
+        KeyRange::AllKeys => (Bound::Unbounded, Bound::Unbounded),
+    };
+    kv.range(bounds)
+}
+
+/// Returns the smallest key greater than all keys with the prefix.
+///
+/// Returns `None` if there is no such key, i.e. the range has no upper bound.
+pub(crate) fn prefix_end(prefix: &[u8]) -> Option<Key> {
+    let mut end = prefix.to_vec();
+    while let Some(last) = end.pop() {
+        if last < 0xff {
+            end.push(last + 1);
+            return Some(end.into());
+        }
+    }
+    None
+}
+
+/// Compares two integers with the operation.
+fn compare(lhs: i64, op: CompareOp, rhs: i64) -> bool {
+    match op {
+        CompareOp::Equal => lhs == rhs,
+        CompareOp::Greater => lhs > rhs,
+        CompareOp::Less => lhs < rhs,
+        CompareOp::NotEqual => lhs != rhs,
     }
 }
 
@@ -569,6 +1033,14 @@ fn lease_not_found() -> Error {
     ))
 }
 
+fn key_not_found() -> Error {
+    invalid_argument("etcdserver: key not found")
+}
+
+fn invalid_argument(message: &'static str) -> Error {
+    Error::GRpcStatus(tonic::Status::new(tonic::Code::InvalidArgument, message))
+}
+
 fn session_expired() -> Error {
     Error::ElectError("session expired".into())
 }
@@ -1,4 +1,6 @@
+mod auth;
 mod bytes;
+mod cluster;
 mod election;
 mod error;
 mod kv;
@@ -12,7 +14,9 @@ use madsim::net::Endpoint;
 use std::net::SocketAddr;
 use std::time::Duration;
 
+pub use self::auth::*;
 pub use self::bytes::Bytes;
+pub use self::cluster::*;
 pub use self::election::*;
 pub use self::error::{Error, Result};
 pub use self::kv::*;
@@ -34,12 +38,16 @@ impl Client {
     /// Connect to `etcd` servers from given `endpoints`.
     pub async fn connect<E: AsRef<str>, S: AsRef<[E]>>(
         endpoints: S,
-        _options: Option<ConnectOptions>,
+        options: Option<ConnectOptions>,
     ) -> Result<Self> {
         let addr = endpoints.as_ref()[0].as_ref();
         let ep = Endpoint::connect(addr).await?;
         let server_addr = ep.peer_addr().unwrap();
-        Ok(Client { ep, server_addr })
+        let client = Client { ep, server_addr };
+        if let Some((name, password)) = options.and_then(|o| o.user) {
+            client.auth_client().authenticate(name, password).await?;
+        }
+        Ok(client)
     }
 
     /// Gets a KV client.
@@ -60,6 +68,24 @@ impl Client {
         ElectionClient::new(self.ep.clone())
     }
 
+    /// Gets an auth client.
+    #[inline]
+    pub fn auth_client(&self) -> AuthClient {
+        AuthClient::new(self.ep.clone())
+    }
+
+    /// Gets a cluster client.
+    #[inline]
+    pub fn cluster_client(&self) -> ClusterClient {
+        ClusterClient::new(self.ep.clone())
+    }
+
+    /// Gets a watch client.
+    #[inline]
+    pub fn watch_client(&self) -> WatchClient {
+        WatchClient::new(self.ep.clone())
+    }
+
     /// Gets a maintenance client.
     #[inline]
     pub fn maintenance_client(&self) -> MaintenanceClient {
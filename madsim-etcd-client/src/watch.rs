@@ -1,3 +1,247 @@
+use super::{kv::KeyRange, server::Request, Bytes, KeyValue, ResponseHeader, Result};
+use futures_util::stream::{Stream, StreamExt};
+use madsim::net::{Endpoint, Receiver, Sender};
+use std::{
+    net::SocketAddr,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Client for Watch operations.
+#[derive(Clone)]
+pub struct WatchClient {
+    ep: Endpoint,
+    server_addr: SocketAddr,
+}
+
+impl WatchClient {
+    /// Create a new [`WatchClient`].
+    pub(crate) fn new(ep: Endpoint) -> Self {
+        WatchClient {
+            server_addr: ep.peer_addr().unwrap(),
+            ep,
+        }
+    }
+
+    /// Watches for events happening or that have happened. Both input and output
+    /// are streams; the input stream is for creating and canceling watcher and the output
+    /// stream sends events. The entire event history can be watched starting from the
+    /// last compaction revision.
+    ///
+    /// NOTE: Only events after the watcher is created are sent, unless a start revision is
+    /// set by [`WatchOptions::with_start_revision`].
+    pub async fn watch(
+        &mut self,
+        key: impl Into<Vec<u8>>,
+        options: Option<WatchOptions>,
+    ) -> Result<(Watcher, WatchStream)> {
+        let req = Request::Watch {
+            key: key.into().into(),
+            options: options.unwrap_or_default(),
+        };
+        let (tx, mut rx) = self.ep.connect1(self.server_addr).await?;
+        tx.send(Box::new(req)).await?;
+        // the first response confirms the creation of the watcher
+        let rsp = (*rx
+            .recv()
+            .await?
+            .downcast::<Result<WatchResponse>>()
+            .unwrap())?;
+        let watcher = Watcher {
+            watch_id: rsp.watch_id,
+            tx,
+        };
+        Ok((watcher, WatchStream { rx }))
+    }
+}
+
+/// Options for `Watch` operation.
+#[derive(Debug, Default, Clone)]
+pub struct WatchOptions {
+    pub(crate) range: KeyRange,
+    pub(crate) filters: Vec<WatchFilterType>,
+    pub(crate) prev_kv: bool,
+    pub(crate) start_revision: i64,
+    pub(crate) fragment: bool,
+}
+
+impl WatchOptions {
+    /// Creates a new `WatchOptions`.
+    #[inline]
+    pub const fn new() -> Self {
+        WatchOptions {
+            range: KeyRange::Key,
+            filters: Vec::new(),
+            prev_kv: false,
+            start_revision: 0,
+            fragment: false,
+        }
+    }
+
+    /// Sets the end of the range [key, end) to watch.
+    #[inline]
+    pub fn with_range(mut self, end: impl Into<Vec<u8>>) -> Self {
+        self.range = KeyRange::Range(end.into().into());
+        self
+    }
+
+    /// Watches all keys >= key.
+    #[inline]
+    pub fn with_from_key(mut self) -> Self {
+        self.range = KeyRange::FromKey;
+        self
+    }
+
+    /// Watches all keys prefixed with key.
+    #[inline]
+    pub fn with_prefix(mut self) -> Self {
+        self.range = KeyRange::Prefix;
+        self
+    }
+
+    /// Watches all keys.
+    #[inline]
+    pub fn with_all_keys(mut self) -> Self {
+        self.range = KeyRange::AllKeys;
+        self
+    }
+
+    /// Filter the events at server side before it sends back to the watcher.
+    #[inline]
+    pub fn with_filters(mut self, filters: impl Into<Vec<WatchFilterType>>) -> Self {
+        self.filters = filters.into();
+        self
+    }
+
+    /// If prev_kv is set, created watcher gets the previous KV before the event happens.
+    /// If the previous KV is already compacted, nothing will be returned.
+    #[inline]
+    pub fn with_prev_key(mut self) -> Self {
+        self.prev_kv = true;
+        self
+    }
+
+    /// Sets the start revision to watch from (inclusive). No start revision is "now".
+    ///
+    /// NOTE: The history is not kept in simulation. The current key-values in range that
+    /// were modified at or after the revision are sent as put events when the watcher is
+    /// created. Deletions before the watcher is created are not sent.
+    #[inline]
+    pub const fn with_start_revision(mut self, revision: i64) -> Self {
+        self.start_revision = revision;
+        self
+    }
+
+    /// Enables splitting large watch responses into fragments.
+    ///
+    /// The events sent when the watcher is created are split into multiple responses.
+    /// All but the last one have [`WatchResponse::fragment`] set.
+    #[inline]
+    pub const fn with_fragment(mut self) -> Self {
+        self.fragment = true;
+        self
+    }
+}
+
+/// Watch filter type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum WatchFilterType {
+    /// Filter out put event.
+    NoPut = 0,
+    /// Filter out delete event.
+    NoDelete = 1,
+}
+
+/// Response for `Watch` operation.
+#[derive(Debug, Clone)]
+pub struct WatchResponse {
+    pub(crate) header: ResponseHeader,
+    pub(crate) watch_id: i64,
+    pub(crate) created: bool,
+    pub(crate) canceled: bool,
+    pub(crate) fragment: bool,
+    pub(crate) events: Vec<Event>,
+}
+
+impl WatchResponse {
+    /// Watch response header.
+    #[inline]
+    pub fn header(&self) -> Option<&ResponseHeader> {
+        Some(&self.header)
+    }
+
+    /// The ID of the watcher that corresponds to the response.
+    #[inline]
+    pub const fn watch_id(&self) -> i64 {
+        self.watch_id
+    }
+
+    /// created is set to true if the response is for a create watch request.
+    /// The client should record the watch_id and expect to receive events for
+    /// the created watcher from the same stream.
+    /// All events sent to the created watcher will attach with the same watch_id.
+    #[inline]
+    pub const fn created(&self) -> bool {
+        self.created
+    }
+
+    /// canceled is set to true if the response is for a cancel watch request.
+    /// No further events will be sent to the canceled watcher.
+    #[inline]
+    pub const fn canceled(&self) -> bool {
+        self.canceled
+    }
+
+    /// fragment is true if a large watch response was split over multiple responses.
+    /// The events of the following responses should be appended until one without
+    /// fragment set is received.
+    #[inline]
+    pub const fn fragment(&self) -> bool {
+        self.fragment
+    }
+
+    /// Events happened on the watched keys.
+    #[inline]
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+}
+
+/// Every change to every key is represented with Event messages.
+#[derive(Debug, Clone)]
+pub struct Event {
+    pub(crate) event_type: EventType,
+    pub(crate) kv: Option<KeyValue>,
+    pub(crate) prev_kv: Option<KeyValue>,
+}
+
+impl Event {
+    /// The kind of event. If type is a PUT, it indicates
+    /// new data has been stored to the key. If type is a DELETE,
+    /// it indicates the key was deleted.
+    #[inline]
+    pub fn event_type(&self) -> EventType {
+        self.event_type
+    }
+
+    /// The KeyValue for the event.
+    /// A PUT event contains current kv pair.
+    /// A PUT event with kv.Version=1 indicates the creation of a key.
+    /// A DELETE/EXPIRE event contains the deleted key with
+    /// its modification revision set to the revision of deletion.
+    #[inline]
+    pub fn kv(&self) -> Option<&KeyValue> {
+        self.kv.as_ref()
+    }
+
+    /// The key-value pair before the event happens.
+    #[inline]
+    pub fn prev_kv(&self) -> Option<&KeyValue> {
+        self.prev_kv.as_ref()
+    }
+}
+
 /// The kind of event.
 #[repr(i32)]
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -6,3 +250,78 @@ pub enum EventType {
     Put = 0,
     Delete = 1,
 }
+
+/// The watching handle.
+///
+/// Dropping the handle also stops the watch.
+#[derive(Debug)]
+pub struct Watcher {
+    watch_id: i64,
+    tx: Sender,
+}
+
+impl Watcher {
+    /// The ID of the watcher.
+    #[inline]
+    pub const fn watch_id(&self) -> i64 {
+        self.watch_id
+    }
+
+    /// Cancels this watcher.
+    ///
+    /// The [`WatchStream`] receives a response with `canceled` set, and then ends.
+    #[inline]
+    pub async fn cancel(&mut self) -> Result<()> {
+        let req = Request::WatchCancel {
+            watch_id: self.watch_id,
+        };
+        self.tx.send(Box::new(req)).await?;
+        Ok(())
+    }
+}
+
+/// The watch response stream.
+#[derive(Debug)]
+pub struct WatchStream {
+    rx: Receiver,
+}
+
+impl WatchStream {
+    /// Fetches the next message from this stream.
+    ///
+    /// Returns `None` after the watcher is canceled.
+    #[inline]
+    pub async fn message(&mut self) -> Result<Option<WatchResponse>> {
+        let Some(payload) = self.rx.next().await else {
+            return Ok(None);
+        };
+        let rsp = *(payload?).downcast::<Result<WatchResponse>>().unwrap();
+        rsp.map(Some)
+    }
+}
+
+impl Stream for WatchStream {
+    type Item = Result<WatchResponse>;
+
+    #[inline]
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match self.rx.poll_next_unpin(cx) {
+            Poll::Ready(Some(Ok(payload))) => {
+                Poll::Ready(Some(*payload.downcast::<Result<WatchResponse>>().unwrap()))
+            }
+            Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(e.into()))),
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Returns the key of a deleted key-value pair as a delete event, with the
+/// modification revision set to the revision of deletion.
+pub(crate) fn deleted_kv(key: Bytes, revision: i64) -> KeyValue {
+    KeyValue {
+        key,
+        modify_revision: revision,
+        ..Default::default()
+    }
+}
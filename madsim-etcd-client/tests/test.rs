@@ -1,8 +1,17 @@
 #![cfg(madsim)]
 
-use madsim::{net::NetSim, runtime::Handle, time::sleep};
-use madsim_etcd_client::{Client, ProclaimOptions, PutOptions, ResignOptions, SimServer};
+use madsim::{
+    net::NetSim,
+    runtime::Handle,
+    time::{sleep, Instant},
+};
+use madsim_etcd_client::{
+    Client, Compare, CompareOp, ConnectOptions, DeleteOptions, Error, EventType, GetOptions,
+    KeyValue, Member, Permission, ProclaimOptions, PutOptions, ResignOptions, SimServer, SortOrder,
+    SortTarget, Txn, TxnOp, TxnOpResponse, WatchFilterType, WatchOptions,
+};
 use std::time::Duration;
+use tonic::Code;
 
 #[madsim::test]
 async fn kv() {
@@ -52,6 +61,361 @@ async fn kv() {
     task1.await.unwrap();
 }
 
+#[madsim::test]
+async fn range() {
+    let handle = Handle::current();
+    let ip1 = "10.0.0.1".parse().unwrap();
+    let ip2 = "10.0.0.2".parse().unwrap();
+    let server = handle.create_node().name("server").ip(ip1).build();
+    let client = handle.create_node().name("client").ip(ip2).build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .serve("10.0.0.1:2379".parse().unwrap())
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task1 = client.spawn(async move {
+        let client = Client::connect(["10.0.0.1:2379"], None).await.unwrap();
+        let mut client = client.kv_client();
+        for key in ["a/3", "a/1", "b", "a/2", "a"] {
+            client.put(key, key.replace('/', ""), None).await.unwrap();
+        }
+        let keys = |kvs: &[KeyValue]| {
+            kvs.iter()
+                .map(|kv| String::from_utf8(kv.key().to_vec()).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        // prefix in key order
+        let opt = GetOptions::new().with_prefix();
+        let resp = client.get("a/", Some(opt)).await.unwrap();
+        assert_eq!(keys(resp.kvs()), ["a/1", "a/2", "a/3"]);
+        assert_eq!(resp.count(), 3);
+        assert!(!resp.more());
+
+        // range with limit
+        let opt = GetOptions::new().with_range("b").with_limit(2);
+        let resp = client.get("a", Some(opt)).await.unwrap();
+        assert_eq!(keys(resp.kvs()), ["a", "a/1"]);
+        assert_eq!(resp.count(), 4);
+        assert!(resp.more());
+
+        // sort by modification revision
+        let opt = GetOptions::new()
+            .with_all_keys()
+            .with_sort(SortTarget::Mod, SortOrder::Descend);
+        let resp = client.get("", Some(opt)).await.unwrap();
+        assert_eq!(keys(resp.kvs()), ["a", "a/2", "b", "a/1", "a/3"]);
+
+        // sort by value with prefix and limit
+        let opt = GetOptions::new()
+            .with_prefix()
+            .with_sort(SortTarget::Value, SortOrder::Descend)
+            .with_limit(2);
+        let resp = client.get("a/", Some(opt)).await.unwrap();
+        assert_eq!(keys(resp.kvs()), ["a/3", "a/2"]);
+        assert_eq!(resp.count(), 3);
+        assert!(resp.more());
+
+        // count only
+        let opt = GetOptions::new()
+            .with_prefix()
+            .with_limit(1)
+            .with_count_only();
+        let resp = client.get("a/", Some(opt)).await.unwrap();
+        assert!(resp.kvs().is_empty());
+        assert_eq!(resp.count(), 3);
+        assert!(!resp.more());
+
+        // keys only, sorted by the omitted values
+        let opt = GetOptions::new()
+            .with_prefix()
+            .with_sort(SortTarget::Value, SortOrder::Descend)
+            .with_limit(2)
+            .with_keys_only();
+        let resp = client.get("a/", Some(opt)).await.unwrap();
+        assert_eq!(keys(resp.kvs()), ["a/3", "a/2"]);
+        assert!(resp.kvs().iter().all(|kv| kv.value().is_empty()));
+        assert_eq!(resp.kvs()[0].create_revision(), 1);
+        assert_eq!(resp.kvs()[1].mod_revision(), 4);
+        assert_eq!(resp.count(), 3);
+        assert!(resp.more());
+
+        // empty range
+        let opt = GetOptions::new().with_range("a");
+        let resp = client.get("b", Some(opt)).await.unwrap();
+        assert!(resp.kvs().is_empty());
+
+        // delete prefix
+        let opt = DeleteOptions::new().with_prefix().with_prev_key();
+        let resp = client.delete("a/", Some(opt)).await.unwrap();
+        assert_eq!(resp.deleted(), 3);
+        assert_eq!(keys(resp.prev_kvs()), ["a/1", "a/2", "a/3"]);
+        let opt = GetOptions::new().with_from_key();
+        let resp = client.get("", Some(opt)).await.unwrap();
+        assert_eq!(keys(resp.kvs()), ["a", "b"]);
+    });
+    task1.await.unwrap();
+}
+
+#[madsim::test]
+async fn delete_prev_kv() {
+    let handle = Handle::current();
+    let ip1 = "10.0.0.1".parse().unwrap();
+    let ip2 = "10.0.0.2".parse().unwrap();
+    let server = handle.create_node().name("server").ip(ip1).build();
+    let client = handle.create_node().name("client").ip(ip2).build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .serve("10.0.0.1:2379".parse().unwrap())
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task1 = client.spawn(async move {
+        let client = Client::connect(["10.0.0.1:2379"], None).await.unwrap();
+        let mut client = client.kv_client();
+        let mut revisions = vec![];
+        for key in ["a/1", "a/2", "b"] {
+            let resp = client.put(key, "v1", None).await.unwrap();
+            revisions.push(resp.header().unwrap().revision());
+        }
+        let resp = client.put("a/2", "v2", None).await.unwrap();
+        let modified = resp.header().unwrap().revision();
+
+        // single key
+        let opt = DeleteOptions::new().with_prev_key();
+        let resp = client.delete("b", Some(opt)).await.unwrap();
+        assert_eq!(resp.deleted(), 1);
+        let kv = &resp.prev_kvs()[0];
+        assert_eq!((kv.key(), kv.value()), (&b"b"[..], &b"v1"[..]));
+        assert_eq!(kv.create_revision(), revisions[2]);
+        assert_eq!(kv.mod_revision(), revisions[2]);
+
+        // prefix
+        let opt = DeleteOptions::new().with_prefix().with_prev_key();
+        let resp = client.delete("a/", Some(opt)).await.unwrap();
+        assert_eq!(resp.deleted(), 2);
+        let kvs = resp.prev_kvs();
+        assert_eq!((kvs[0].key(), kvs[1].key()), (&b"a/1"[..], &b"a/2"[..]));
+        assert_eq!(kvs[0].value(), b"v1");
+        assert_eq!(kvs[0].create_revision(), revisions[0]);
+        assert_eq!(kvs[0].mod_revision(), revisions[0]);
+        assert_eq!(kvs[0].version(), 1);
+        assert_eq!(kvs[1].value(), b"v2");
+        assert_eq!(kvs[1].create_revision(), revisions[1]);
+        assert_eq!(kvs[1].mod_revision(), modified);
+        assert_eq!(kvs[1].version(), 2);
+
+        // not returned without prev_kv
+        client.put("c", "v1", None).await.unwrap();
+        let resp = client.delete("c", None).await.unwrap();
+        assert_eq!(resp.deleted(), 1);
+        assert!(resp.prev_kvs().is_empty());
+    });
+    task1.await.unwrap();
+}
+
+#[madsim::test]
+async fn txn() {
+    let handle = Handle::current();
+    let ip1 = "10.0.0.1".parse().unwrap();
+    let ip2 = "10.0.0.2".parse().unwrap();
+    let server = handle.create_node().name("server").ip(ip1).build();
+    let client = handle.create_node().name("client").ip(ip2).build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .serve("10.0.0.1:2379".parse().unwrap())
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task1 = client.spawn(async move {
+        let client = Client::connect(["10.0.0.1:2379"], None).await.unwrap();
+        let mut client = client.kv_client();
+
+        // create the key if it does not exist
+        let txn = Txn::new()
+            .when([Compare::version("lock", CompareOp::Equal, 0)])
+            .and_then([TxnOp::put("lock", "a", None)])
+            .or_else([TxnOp::get("lock", None)]);
+        let resp = client.txn(txn.clone()).await.unwrap();
+        assert!(resp.succeeded());
+        let resp = client.txn(txn).await.unwrap();
+        assert!(!resp.succeeded());
+        let TxnOpResponse::Get(get) = &resp.op_responses()[0] else {
+            panic!("expect get response");
+        };
+        let kv = get.kvs()[0].clone();
+        assert_eq!(kv.value(), b"a");
+        assert_eq!(kv.version(), 1);
+
+        // compare-and-swap on mod revision
+        let cas = |rev: i64, value: &str| {
+            Txn::new()
+                .when([Compare::mod_revision("lock", CompareOp::Equal, rev)])
+                .and_then([TxnOp::put("lock", value, None)])
+        };
+        let resp = client.txn(cas(kv.mod_revision(), "b")).await.unwrap();
+        assert!(resp.succeeded());
+        assert!(resp.header().unwrap().revision() > kv.mod_revision());
+        let resp = client.txn(cas(kv.mod_revision(), "c")).await.unwrap();
+        assert!(!resp.succeeded());
+
+        let resp = client.get("lock", None).await.unwrap();
+        let kv1 = &resp.kvs()[0];
+        assert_eq!(kv1.value(), b"b");
+        assert_eq!(kv1.version(), 2);
+        assert_eq!(kv1.create_revision(), kv.create_revision());
+
+        // compare create revision and value
+        let txn = Txn::new().when([
+            Compare::create_revision("lock", CompareOp::Equal, kv.create_revision()),
+            Compare::value("lock", CompareOp::Equal, "b"),
+            Compare::version("lock", CompareOp::Greater, 1),
+        ]);
+        assert!(client.txn(txn).await.unwrap().succeeded());
+        let txn = Txn::new().when([Compare::create_revision("lock", CompareOp::Less, 1)]);
+        assert!(!client.txn(txn).await.unwrap().succeeded());
+
+        // nested transactions see the effects of previous operations
+        let revision = client
+            .get("lock", None)
+            .await
+            .unwrap()
+            .header()
+            .unwrap()
+            .revision();
+        let nested = Txn::new()
+            .when([Compare::value("k1", CompareOp::Equal, "1")])
+            .and_then([TxnOp::put("k2", "2", None)])
+            .or_else([TxnOp::put("k2", "0", None)]);
+        let txn = Txn::new().and_then([
+            TxnOp::put("k1", "1", None),
+            TxnOp::txn(nested),
+            TxnOp::get("k", Some(GetOptions::new().with_prefix())),
+        ]);
+        let resp = client.txn(txn).await.unwrap();
+        let ops = resp.op_responses();
+        assert_eq!(ops.len(), 3);
+        let TxnOpResponse::Txn(nested) = &ops[1] else {
+            panic!("expect txn response");
+        };
+        assert!(nested.succeeded());
+        let TxnOpResponse::Get(get) = &ops[2] else {
+            panic!("expect get response");
+        };
+        let values: Vec<_> = get.kvs().iter().map(|kv| kv.value()).collect();
+        assert_eq!(values, [b"1", b"2"]);
+        // all writes share the same revision
+        assert!(get.kvs().iter().all(|kv| kv.mod_revision() == revision + 1));
+        assert_eq!(resp.header().unwrap().revision(), revision + 1);
+
+        // compare-and-swap and fetch the previous values
+        let txn = Txn::new()
+            .when([Compare::value("k1", CompareOp::Equal, "1")])
+            .and_then([
+                TxnOp::put("k1", "10", Some(PutOptions::new().with_prev_key())),
+                TxnOp::put("k3", "3", Some(PutOptions::new().with_prev_key())),
+                TxnOp::delete("k2", Some(DeleteOptions::new().with_prev_key())),
+                TxnOp::put("k4", "4", None),
+            ]);
+        let resp = client.txn(txn).await.unwrap();
+        assert!(resp.succeeded());
+        let revision = resp.header().unwrap().revision();
+        let ops = resp.op_responses();
+        let TxnOpResponse::Put(put) = &ops[0] else {
+            panic!("expect put response");
+        };
+        let prev = put.prev_key().unwrap();
+        assert_eq!(prev.value(), b"1");
+        assert!(prev.mod_revision() < revision);
+        assert_eq!(put.header().unwrap().revision(), revision);
+        let TxnOpResponse::Put(put) = &ops[1] else {
+            panic!("expect put response");
+        };
+        assert!(put.prev_key().is_none());
+        let TxnOpResponse::Delete(delete) = &ops[2] else {
+            panic!("expect delete response");
+        };
+        assert_eq!(delete.prev_kvs()[0].value(), b"2");
+        let TxnOpResponse::Put(put) = &ops[3] else {
+            panic!("expect put response");
+        };
+        assert!(put.prev_key().is_none());
+        let resp = client.get("k1", None).await.unwrap();
+        assert_eq!(resp.kvs()[0].value(), b"10");
+        assert_eq!(resp.kvs()[0].mod_revision(), revision);
+    });
+    task1.await.unwrap();
+}
+
+#[madsim::test]
+async fn cas_conflict() {
+    let handle = Handle::current();
+    let ip1 = "10.0.0.1".parse().unwrap();
+    let ip2 = "10.0.0.2".parse().unwrap();
+    let server = handle.create_node().name("server").ip(ip1).build();
+    let client = handle.create_node().name("client").ip(ip2).build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .conflict_rate(0.5)
+            .serve("10.0.0.1:2379".parse().unwrap())
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task1 = client.spawn(async move {
+        let client = Client::connect(["10.0.0.1:2379"], None).await.unwrap();
+        let mut client = client.kv_client();
+        client.put("counter", "0", None).await.unwrap();
+
+        // increment the counter in a CAS loop
+        let mut retries = 0;
+        for _ in 0..10 {
+            loop {
+                let resp = client.get("counter", None).await.unwrap();
+                let kv = &resp.kvs()[0];
+                let value: u32 = std::str::from_utf8(kv.value()).unwrap().parse().unwrap();
+                let txn = Txn::new()
+                    .when([Compare::mod_revision(
+                        "counter",
+                        CompareOp::Equal,
+                        kv.mod_revision(),
+                    )])
+                    .and_then([TxnOp::put("counter", (value + 1).to_string(), None)]);
+                if client.txn(txn).await.unwrap().succeeded() {
+                    break;
+                }
+                retries += 1;
+            }
+        }
+        assert!(retries > 0);
+
+        // injected conflicts don't change the value
+        let resp = client.get("counter", None).await.unwrap();
+        let kv = &resp.kvs()[0];
+        assert_eq!(kv.value(), b"10");
+        assert_eq!(kv.version(), 11 + retries);
+
+        // comparisons on values are not affected
+        let txn = Txn::new()
+            .when([Compare::value("counter", CompareOp::Equal, "10")])
+            .and_then([TxnOp::put("counter", "11", None)]);
+        assert!(client.txn(txn).await.unwrap().succeeded());
+    });
+    task1.await.unwrap();
+}
+
 #[madsim::test]
 async fn lease() {
     let handle = Handle::current();
@@ -117,6 +481,262 @@ async fn lease() {
     task1.await.unwrap();
 }
 
+#[madsim::test]
+async fn put_ignore_value_and_lease() {
+    let handle = Handle::current();
+    let ip1 = "10.0.0.1".parse().unwrap();
+    let ip2 = "10.0.0.2".parse().unwrap();
+    let server = handle.create_node().name("server").ip(ip1).build();
+    let client = handle.create_node().name("client").ip(ip2).build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .serve("10.0.0.1:2379".parse().unwrap())
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task1 = client.spawn(async move {
+        let client = Client::connect(["10.0.0.1:2379"], None).await.unwrap();
+        let mut kv_client = client.kv_client();
+        let mut lease_client = client.lease_client();
+        let code = |e: Error| match e {
+            Error::GRpcStatus(status) => status.code(),
+            e => panic!("unexpected error: {e}"),
+        };
+        let lease1 = lease_client.grant(60, None).await.unwrap().id();
+        let lease2 = lease_client.grant(60, None).await.unwrap().id();
+        let opt = PutOptions::new().with_lease(lease1);
+        kv_client.put("foo", "bar", Some(opt)).await.unwrap();
+
+        // reassociate the key with a new lease, keeping the value
+        let opt = PutOptions::new().with_lease(lease2).with_ignore_value();
+        kv_client.put("foo", "", Some(opt)).await.unwrap();
+        let resp = kv_client.get("foo", None).await.unwrap();
+        assert_eq!(resp.kvs()[0].value(), b"bar");
+        assert_eq!(resp.kvs()[0].lease(), lease2);
+        // the key is detached from the old lease
+        lease_client.revoke(lease1).await.unwrap();
+        assert_eq!(kv_client.get("foo", None).await.unwrap().count(), 1);
+
+        // update the value, keeping the lease
+        let opt = PutOptions::new().with_ignore_lease();
+        kv_client.put("foo", "baz", Some(opt)).await.unwrap();
+        let resp = kv_client.get("foo", None).await.unwrap();
+        assert_eq!(resp.kvs()[0].value(), b"baz");
+        assert_eq!(resp.kvs()[0].lease(), lease2);
+        // the key is still attached to the lease
+        lease_client.revoke(lease2).await.unwrap();
+        assert_eq!(kv_client.get("foo", None).await.unwrap().count(), 0);
+
+        // the key must exist
+        let opt = PutOptions::new().with_ignore_value();
+        let err = kv_client.put("foo", "", Some(opt)).await.unwrap_err();
+        assert_eq!(code(err), Code::InvalidArgument);
+        let opt = PutOptions::new().with_ignore_lease();
+        let err = kv_client.put("foo", "bar", Some(opt)).await.unwrap_err();
+        assert_eq!(code(err), Code::InvalidArgument);
+        assert_eq!(kv_client.get("foo", None).await.unwrap().count(), 0);
+
+        // the ignored fields must not be given
+        kv_client.put("foo", "bar", None).await.unwrap();
+        let opt = PutOptions::new().with_ignore_value();
+        let err = kv_client.put("foo", "baz", Some(opt)).await.unwrap_err();
+        assert_eq!(code(err), Code::InvalidArgument);
+        let lease = lease_client.grant(60, None).await.unwrap().id();
+        let opt = PutOptions::new().with_lease(lease).with_ignore_lease();
+        let err = kv_client.put("foo", "baz", Some(opt)).await.unwrap_err();
+        assert_eq!(code(err), Code::InvalidArgument);
+
+        // a failed put in a transaction fails the whole transaction without any writes
+        let txn = Txn::new().and_then([
+            TxnOp::put("txn", "1", None),
+            TxnOp::put("missing", "", Some(PutOptions::new().with_ignore_value())),
+        ]);
+        let err = kv_client.txn(txn).await.unwrap_err();
+        assert_eq!(code(err), Code::InvalidArgument);
+        let txn = Txn::new().and_then([TxnOp::txn(Txn::new().and_then([
+            TxnOp::put("txn", "1", None),
+            TxnOp::put("missing", "", Some(PutOptions::new().with_ignore_value())),
+        ]))]);
+        let err = kv_client.txn(txn).await.unwrap_err();
+        assert_eq!(code(err), Code::InvalidArgument);
+        assert_eq!(kv_client.get("txn", None).await.unwrap().count(), 0);
+        // the service keeps serving
+        let txn = Txn::new().and_then([TxnOp::put(
+            "foo",
+            "",
+            Some(PutOptions::new().with_ignore_value()),
+        )]);
+        assert!(kv_client.txn(txn).await.unwrap().succeeded());
+        let resp = kv_client.get("foo", None).await.unwrap();
+        assert_eq!(resp.kvs()[0].value(), b"bar");
+    });
+    task1.await.unwrap();
+}
+
+#[madsim::test]
+async fn txn_rollback() {
+    let handle = Handle::current();
+    let ip1 = "10.0.0.1".parse().unwrap();
+    let ip2 = "10.0.0.2".parse().unwrap();
+    let server = handle.create_node().name("server").ip(ip1).build();
+    let client = handle.create_node().name("client").ip(ip2).build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .serve("10.0.0.1:2379".parse().unwrap())
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task1 = client.spawn(async move {
+        let client = Client::connect(["10.0.0.1:2379"], None).await.unwrap();
+        let mut kv_client = client.kv_client();
+        let mut watch_client = client.watch_client();
+        let rsp = kv_client.put("k", "v", None).await.unwrap();
+        let revision = rsp.header().unwrap().revision();
+        let (_watcher, mut stream) = watch_client.watch("k", None).await.unwrap();
+
+        // the nested put fails on the key deleted earlier in the transaction
+        let txn = Txn::new().and_then([
+            TxnOp::delete("k", None),
+            TxnOp::txn(
+                Txn::new()
+                    .when([Compare::version("k", CompareOp::Equal, 0)])
+                    .and_then([TxnOp::put(
+                        "k",
+                        "",
+                        Some(PutOptions::new().with_ignore_value()),
+                    )]),
+            ),
+        ]);
+        let err = kv_client.txn(txn).await.unwrap_err();
+        let Error::GRpcStatus(status) = err else {
+            panic!("unexpected error: {err}");
+        };
+        assert_eq!(status.code(), Code::InvalidArgument);
+
+        // the delete is rolled back
+        let rsp = kv_client.get("k", None).await.unwrap();
+        assert_eq!(rsp.kvs()[0].value(), b"v");
+        assert_eq!(rsp.kvs()[0].mod_revision(), revision);
+        assert_eq!(rsp.header().unwrap().revision(), revision);
+
+        // and never published to watchers
+        kv_client.put("k", "v2", None).await.unwrap();
+        let rsp = stream.message().await.unwrap().unwrap();
+        assert_eq!(rsp.events().len(), 1);
+        assert_eq!(rsp.events()[0].event_type(), EventType::Put);
+        assert_eq!(rsp.events()[0].kv().unwrap().value(), b"v2");
+    });
+    task1.await.unwrap();
+}
+
+#[madsim::test]
+async fn lease_key_counts() {
+    let handle = Handle::current();
+    let ip1 = "10.0.0.1".parse().unwrap();
+    let ip2 = "10.0.0.2".parse().unwrap();
+    let server = handle.create_node().name("server").ip(ip1).build();
+    let client = handle.create_node().name("client").ip(ip2).build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .serve("10.0.0.1:2379".parse().unwrap())
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task1 = client.spawn(async move {
+        let client = Client::connect(["10.0.0.1:2379"], None).await.unwrap();
+        let mut kv_client = client.kv_client();
+        let mut lease_client = client.lease_client();
+        let a = lease_client.grant(60, None).await.unwrap().id();
+        let b = lease_client.grant(60, None).await.unwrap().id();
+        for key in ["k1", "k2", "k3"] {
+            let opt = PutOptions::new().with_lease(a);
+            kv_client.put(key, "v", Some(opt)).await.unwrap();
+        }
+        let opt = PutOptions::new().with_lease(b);
+        kv_client.put("k4", "v", Some(opt)).await.unwrap();
+        let counts = lease_client.key_counts().await.unwrap();
+        assert_eq!(counts, [(a, 3), (b, 1)].into());
+
+        // keys are detached by overwriting and deleting
+        let opt = PutOptions::new().with_lease(b);
+        kv_client.put("k1", "v", Some(opt)).await.unwrap();
+        kv_client.delete("k2", None).await.unwrap();
+        let counts = lease_client.key_counts().await.unwrap();
+        assert_eq!(counts, [(a, 1), (b, 2)].into());
+        // the standard response is not affected
+        let resp = lease_client.leases().await.unwrap();
+        assert_eq!(resp.leases().len(), 2);
+
+        // revoking a lease deletes exactly its keys
+        lease_client.revoke(a).await.unwrap();
+        let counts = lease_client.key_counts().await.unwrap();
+        assert_eq!(counts, [(b, 2)].into());
+        let resp = kv_client
+            .get("k", Some(GetOptions::new().with_prefix()))
+            .await
+            .unwrap();
+        let keys: Vec<_> = resp.kvs().iter().map(|kv| kv.key()).collect();
+        assert_eq!(keys, [b"k1", b"k4"]);
+    });
+    task1.await.unwrap();
+}
+
+#[madsim::test]
+async fn lease_keep_alive() {
+    let handle = Handle::current();
+    let ip1 = "10.0.0.1".parse().unwrap();
+    let ip2 = "10.0.0.2".parse().unwrap();
+    let server = handle.create_node().name("server").ip(ip1).build();
+    let client = handle.create_node().name("client").ip(ip2).build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .serve("10.0.0.1:2379".parse().unwrap())
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task1 = client.spawn(async move {
+        let client = Client::connect(["10.0.0.1:2379"], None).await.unwrap();
+        let mut kv_client = client.kv_client();
+        let mut lease_client = client.lease_client();
+        let lease = lease_client.grant(5, None).await.unwrap();
+        let opt = PutOptions::new().with_lease(lease.id());
+        kv_client.put("foo", "bar", Some(opt)).await.unwrap();
+
+        // the lease lives far beyond its TTL as long as pings continue
+        let (mut keeper, mut responses) = lease_client.keep_alive(lease.id()).await.unwrap();
+        for _ in 0..30 {
+            sleep(Duration::from_secs(2)).await;
+            keeper.keep_alive().await.unwrap();
+            let resp = responses.message().await.unwrap().unwrap();
+            assert_eq!(resp.id(), lease.id());
+            assert_eq!(resp.ttl(), 5);
+        }
+        let resp = kv_client.get("foo", None).await.unwrap();
+        assert_eq!(resp.kvs().len(), 1);
+
+        // the lease expires once pings stop
+        sleep(Duration::from_secs(10)).await;
+        let resp = kv_client.get("foo", None).await.unwrap();
+        assert!(resp.kvs().is_empty());
+        keeper.keep_alive().await.unwrap();
+        let resp = responses.message().await.unwrap().unwrap();
+        assert_eq!(resp.ttl(), 0);
+    });
+    task1.await.unwrap();
+}
+
 #[madsim::test]
 async fn election() {
     // tracing_subscriber::fmt::init();
@@ -201,8 +821,14 @@ async fn election() {
         assert_eq!(resp.kv().unwrap().value(), b"1");
         let resp = leader_stream.message().await.unwrap().unwrap();
         assert_eq!(resp.kv().unwrap().value(), b"1.1");
+        // leader 1 is lost by revoking the lease
+        let resp = leader_stream.message().await.unwrap().unwrap();
+        assert!(resp.kv().is_none());
         let resp = leader_stream.message().await.unwrap().unwrap();
         assert_eq!(resp.kv().unwrap().value(), b"2");
+        // leader 2 resigns
+        let resp = leader_stream.message().await.unwrap().unwrap();
+        assert!(resp.kv().is_none());
     });
 
     task1.await.unwrap();
@@ -211,7 +837,7 @@ async fn election() {
 }
 
 #[madsim::test]
-async fn maintenance() {
+async fn observe_leader_expired() {
     let handle = Handle::current();
     let ip1 = "10.0.0.1".parse().unwrap();
     let ip2 = "10.0.0.2".parse().unwrap();
@@ -226,17 +852,28 @@ async fn maintenance() {
     });
     sleep(Duration::from_secs(1)).await;
 
-    let task1 = client.spawn(async move {
+    let task = client.spawn(async move {
         let client = Client::connect(["10.0.0.1:2379"], None).await.unwrap();
-        let mut client = client.maintenance_client();
-        // get status
-        client.status().await.unwrap();
+        let mut lease_client = client.lease_client();
+        let mut client = client.election_client();
+        let mut leader_stream = client.observe("leader").await.unwrap();
+
+        let lease = lease_client.grant(10, None).await.unwrap();
+        client.campaign("leader", "1", lease.id()).await.unwrap();
+        let resp = leader_stream.message().await.unwrap().unwrap();
+        assert_eq!(resp.kv().unwrap().value(), b"1");
+
+        // the leader is lost when the lease expires
+        let resp = leader_stream.message().await.unwrap().unwrap();
+        assert!(resp.kv().is_none());
+        let resp = client.leader("leader").await.unwrap();
+        assert!(resp.kv().is_none());
     });
-    task1.await.unwrap();
+    task.await.unwrap();
 }
 
 #[madsim::test]
-async fn load_dump() {
+async fn maintenance() {
     let handle = Handle::current();
     let ip1 = "10.0.0.1".parse().unwrap();
     let ip2 = "10.0.0.2".parse().unwrap();
@@ -251,13 +888,243 @@ async fn load_dump() {
     });
     sleep(Duration::from_secs(1)).await;
 
-    let dump = client
-        .spawn(async move {
-            let mut client = Client::connect(["10.0.0.1:2379"], None).await.unwrap();
-            let lease = client.lease_client().grant(60, None).await.unwrap();
-            let opt = PutOptions::new().with_lease(lease.id());
-            client
-                .kv_client()
+    let task1 = client.spawn(async move {
+        let client = Client::connect(["10.0.0.1:2379"], None).await.unwrap();
+        let mut kv_client = client.kv_client();
+        let mut client = client.maintenance_client();
+        // get status
+        let status = client.status().await.unwrap();
+        assert_eq!(status.db_size(), 0);
+        assert_eq!(status.leader(), 1);
+        assert_eq!(status.raft_term(), 1);
+
+        // the size grows with puts
+        kv_client.put("foo", "bar", None).await.unwrap();
+        kv_client.put("hello", "world", None).await.unwrap();
+        let status = client.status().await.unwrap();
+        assert_eq!(status.db_size(), 16);
+        assert_eq!(status.header().unwrap().revision(), 2);
+
+        // and shrinks with overwrites and deletes
+        kv_client.put("hello", "", None).await.unwrap();
+        kv_client.delete("foo", None).await.unwrap();
+        let status = client.status().await.unwrap();
+        assert_eq!(status.db_size(), 5);
+        assert_eq!(status.header().unwrap().revision(), 4);
+    });
+    task1.await.unwrap();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .members([Member::new(1, "etcd-1"), Member::new(2, "etcd-2")])
+            .raft_term(3)
+            .leader(2)
+            .serve("10.0.0.1:2380".parse().unwrap())
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task1 = client.spawn(async move {
+        let client = Client::connect(["10.0.0.1:2380"], None).await.unwrap();
+        let status = client.maintenance_client().status().await.unwrap();
+        assert_eq!(status.leader(), 2);
+        assert_eq!(status.raft_term(), 3);
+    });
+    task1.await.unwrap();
+}
+
+#[madsim::test]
+async fn defragment() {
+    let handle = Handle::current();
+    let ip1 = "10.0.0.1".parse().unwrap();
+    let ip2 = "10.0.0.2".parse().unwrap();
+    let server = handle.create_node().name("server").ip(ip1).build();
+    let client = handle.create_node().name("client").ip(ip2).build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .serve("10.0.0.1:2379".parse().unwrap())
+            .await
+            .unwrap();
+    });
+    server.spawn(async move {
+        SimServer::builder()
+            .defrag_duration(Duration::from_secs(5))
+            .serve("10.0.0.1:2380".parse().unwrap())
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task1 = client.spawn(async move {
+        // no pause by default
+        let client = Client::connect(["10.0.0.1:2379"], None).await.unwrap();
+        client.kv_client().put("foo", "bar", None).await.unwrap();
+        let t0 = Instant::now();
+        let rsp = client.maintenance_client().defragment().await.unwrap();
+        assert!(t0.elapsed() < Duration::from_secs(1));
+        assert_eq!(rsp.header().unwrap().revision(), 1);
+        let rsp = client.kv_client().get("foo", None).await.unwrap();
+        assert_eq!(rsp.kvs()[0].value(), b"bar");
+
+        // other requests stall while defragmenting
+        let client = Client::connect(["10.0.0.1:2380"], None).await.unwrap();
+        let mut maintenance_client = client.maintenance_client();
+        let t0 = Instant::now();
+        let defrag = madsim::task::spawn(async move {
+            maintenance_client.defragment().await.unwrap();
+            t0.elapsed()
+        });
+        sleep(Duration::from_secs(1)).await;
+        client.kv_client().put("foo", "bar", None).await.unwrap();
+        let put_elapsed = t0.elapsed();
+        let defrag_elapsed = defrag.await.unwrap();
+        assert!(
+            defrag_elapsed >= Duration::from_secs(5),
+            "{defrag_elapsed:?}"
+        );
+        assert!(put_elapsed >= Duration::from_secs(5), "{put_elapsed:?}");
+
+        // requests after defragmentation are not delayed
+        let t0 = Instant::now();
+        client.kv_client().get("foo", None).await.unwrap();
+        assert!(t0.elapsed() < Duration::from_secs(1));
+    });
+    task1.await.unwrap();
+}
+
+#[madsim::test]
+async fn member_list() {
+    let handle = Handle::current();
+    let ip1 = "10.0.0.1".parse().unwrap();
+    let ip2 = "10.0.0.2".parse().unwrap();
+    let server = handle.create_node().name("server").ip(ip1).build();
+    let client = handle.create_node().name("client").ip(ip2).build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .serve("10.0.0.1:2379".parse().unwrap())
+            .await
+            .unwrap();
+    });
+    server.spawn(async move {
+        SimServer::builder()
+            .members([
+                Member::new(1, "etcd-1").with_client_urls(["http://10.0.0.1:2380"]),
+                Member::new(2, "etcd-2").with_client_urls(["http://10.0.0.3:2380"]),
+            ])
+            .serve("10.0.0.1:2380".parse().unwrap())
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task1 = client.spawn(async move {
+        // a single member by default
+        let client = Client::connect(["10.0.0.1:2379"], None).await.unwrap();
+        let resp = client.cluster_client().member_list().await.unwrap();
+        assert_eq!(resp.members().len(), 1);
+        assert_eq!(resp.members()[0].client_urls(), ["http://10.0.0.1:2379"]);
+
+        let client = Client::connect(["10.0.0.1:2380"], None).await.unwrap();
+        let resp = client.cluster_client().member_list().await.unwrap();
+        let names: Vec<_> = resp.members().iter().map(|m| m.name()).collect();
+        assert_eq!(names, ["etcd-1", "etcd-2"]);
+        assert_eq!(resp.members()[1].id(), 2);
+    });
+    task1.await.unwrap();
+}
+
+#[madsim::test]
+async fn auth() {
+    let handle = Handle::current();
+    let ip1 = "10.0.0.1".parse().unwrap();
+    let ip2 = "10.0.0.2".parse().unwrap();
+    let server = handle.create_node().name("server").ip(ip1).build();
+    let client = handle.create_node().name("client").ip(ip2).build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .user_add("root", "rootpw")
+            .user_grant_role("root", "root")
+            .role_add("reader")
+            .role_grant_permission("reader", Permission::read("foo").with_prefix())
+            .user_add("alice", "alicepw")
+            .user_grant_role("alice", "reader")
+            .auth_enable()
+            .serve("10.0.0.1:2379".parse().unwrap())
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task1 = client.spawn(async move {
+        let connect = |name: &str, password: &str| {
+            let options = ConnectOptions::new().with_user(name, password);
+            Client::connect(["10.0.0.1:2379"], Some(options))
+        };
+        let code = |e: Error| match e {
+            Error::GRpcStatus(status) => status.code(),
+            e => panic!("unexpected error: {e}"),
+        };
+        // root can access all keys
+        let mut root = connect("root", "rootpw").await.unwrap().kv_client();
+        root.put("foo/1", "bar", None).await.unwrap();
+        root.put("baz", "bar", None).await.unwrap();
+
+        // reader can only read the prefix
+        let mut alice = connect("alice", "alicepw").await.unwrap().kv_client();
+        let resp = alice.get("foo/1", None).await.unwrap();
+        assert_eq!(resp.kvs()[0].value(), b"bar");
+        let opt = GetOptions::new().with_prefix();
+        alice.get("foo", Some(opt)).await.unwrap();
+        let err = alice.put("foo/1", "gg", None).await.unwrap_err();
+        assert_eq!(code(err), Code::PermissionDenied);
+        let err = alice.get("baz", None).await.unwrap_err();
+        assert_eq!(code(err), Code::PermissionDenied);
+        let opt = GetOptions::new().with_all_keys();
+        let err = alice.get("", Some(opt)).await.unwrap_err();
+        assert_eq!(code(err), Code::PermissionDenied);
+
+        // unauthenticated requests are rejected
+        let mut anonymous = Client::connect(["10.0.0.1:2379"], None)
+            .await
+            .unwrap()
+            .kv_client();
+        let err = anonymous.get("foo/1", None).await.unwrap_err();
+        assert_eq!(code(err), Code::InvalidArgument);
+
+        // wrong password
+        let err = connect("alice", "wrong").await.err().unwrap();
+        assert_eq!(code(err), Code::InvalidArgument);
+    });
+    task1.await.unwrap();
+}
+
+#[madsim::test]
+async fn load_dump() {
+    let handle = Handle::current();
+    let ip1 = "10.0.0.1".parse().unwrap();
+    let ip2 = "10.0.0.2".parse().unwrap();
+    let server = handle.create_node().name("server").ip(ip1).build();
+    let client = handle.create_node().name("client").ip(ip2).build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .serve("10.0.0.1:2379".parse().unwrap())
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let dump = client
+        .spawn(async move {
+            let mut client = Client::connect(["10.0.0.1:2379"], None).await.unwrap();
+            let lease = client.lease_client().grant(60, None).await.unwrap();
+            let opt = PutOptions::new().with_lease(lease.id());
+            client
+                .kv_client()
                 .put("foo", &b"bar\xFF\x01\x02"[..], Some(opt))
                 .await
                 .unwrap();
@@ -285,3 +1152,337 @@ async fn load_dump() {
         .await
         .unwrap();
 }
+
+#[madsim::test]
+async fn snapshot() {
+    let handle = Handle::current();
+    let ip1 = "10.0.0.1".parse().unwrap();
+    let ip2 = "10.0.0.2".parse().unwrap();
+    let server = handle.create_node().name("server").ip(ip1).build();
+    let client = handle.create_node().name("client").ip(ip2).build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .serve("10.0.0.1:2379".parse().unwrap())
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let snapshot = client
+        .spawn(async move {
+            let client = Client::connect(["10.0.0.1:2379"], None).await.unwrap();
+            let mut kv = client.kv_client();
+            for i in 0..100 {
+                kv.put(format!("key{i:03}"), vec![b'x'; 1000], None)
+                    .await
+                    .unwrap();
+            }
+            let mut stream = client.maintenance_client().snapshot().await.unwrap();
+            let mut snapshot = vec![];
+            let mut chunks = 0;
+            while let Some(resp) = stream.message().await.unwrap() {
+                snapshot.extend_from_slice(resp.blob());
+                chunks += 1;
+                if resp.remaining_bytes() == 0 {
+                    break;
+                }
+            }
+            assert!(chunks > 1);
+            assert!(stream.message().await.unwrap().is_none());
+            String::from_utf8(snapshot).unwrap()
+        })
+        .await
+        .unwrap();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .load(snapshot)
+            .serve("10.0.0.1:2380".parse().unwrap())
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    client
+        .spawn(async move {
+            let client = Client::connect(["10.0.0.1:2380"], None).await.unwrap();
+            let options = GetOptions::new().with_prefix();
+            let resp = client.kv_client().get("key", Some(options)).await.unwrap();
+            assert_eq!(resp.count(), 100);
+            assert_eq!(resp.kvs()[99].key(), b"key099");
+            assert_eq!(resp.kvs()[99].value(), [b'x'; 1000]);
+        })
+        .await
+        .unwrap();
+}
+
+#[madsim::test]
+async fn serializable_read() {
+    let handle = Handle::current();
+    let ip1 = "10.0.0.1".parse().unwrap();
+    let ip2 = "10.0.0.2".parse().unwrap();
+    let server = handle.create_node().name("server").ip(ip1).build();
+    let client = handle.create_node().name("client").ip(ip2).build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .max_staleness(Duration::from_secs(10))
+            .serve("10.0.0.1:2379".parse().unwrap())
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task1 = client.spawn(async move {
+        let client = Client::connect(["10.0.0.1:2379"], None).await.unwrap();
+        let mut client = client.kv_client();
+        let rsp = client.put("foo", "1", None).await.unwrap();
+        let rev1 = rsp.header().unwrap().revision();
+        sleep(Duration::from_secs(20)).await;
+        let rsp = client.put("foo", "2", None).await.unwrap();
+        let rev2 = rsp.header().unwrap().revision();
+        client.put("bar", "2", None).await.unwrap();
+
+        let serializable = GetOptions::new().with_prefix().with_serializable();
+        let mut stale = false;
+        for _ in 0..20 {
+            sleep(Duration::from_millis(100)).await;
+            // linearizable reads always see the latest data
+            let rsp = client.get("foo", None).await.unwrap();
+            assert_eq!(rsp.kvs()[0].value(), b"2");
+
+            let rsp = client.get("", Some(serializable.clone())).await.unwrap();
+            let values: Vec<_> = rsp.kvs().iter().map(|kv| kv.value()).collect();
+            match rsp.header().unwrap().revision() {
+                // the latest data
+                r if r == rev2 + 1 => assert_eq!(values, [b"2", b"2"]),
+                // before "bar" is put
+                r if r == rev2 => assert_eq!(values, [b"2"]),
+                // before "foo" is updated
+                r if r == rev1 => {
+                    assert_eq!(values, [b"1"]);
+                    stale = true;
+                }
+                r => panic!("unexpected revision: {r}"),
+            }
+        }
+        assert!(stale, "serializable reads should return stale data");
+
+        // staleness is bounded
+        sleep(Duration::from_secs(10)).await;
+        let rsp = client.get("", Some(serializable)).await.unwrap();
+        assert_eq!(rsp.header().unwrap().revision(), rev2 + 1);
+        assert_eq!(rsp.kvs().len(), 2);
+    });
+    task1.await.unwrap();
+}
+
+#[madsim::test]
+async fn latency() {
+    let handle = Handle::current();
+    let ip1 = "10.0.0.1".parse().unwrap();
+    let ip2 = "10.0.0.2".parse().unwrap();
+    let server = handle.create_node().name("server").ip(ip1).build();
+    let client = handle.create_node().name("client").ip(ip2).build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .latency(Duration::from_secs(1), Duration::from_secs(1))
+            .serve("10.0.0.1:2379".parse().unwrap())
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task1 = client.spawn(async move {
+        let client = Client::connect(["10.0.0.1:2379"], None).await.unwrap();
+        let mut client = client.kv_client();
+        let mut elapsed = vec![];
+        for _ in 0..10 {
+            let t0 = madsim::time::Instant::now();
+            client.put("foo", "bar", None).await.unwrap();
+            let t = t0.elapsed();
+            assert!(t >= Duration::from_secs(1));
+            assert!(t < Duration::from_millis(2100));
+            elapsed.push(t);
+        }
+        elapsed.dedup();
+        assert!(elapsed.len() > 1, "latency should vary between requests");
+    });
+    task1.await.unwrap();
+}
+
+#[madsim::test]
+async fn drop_connection() {
+    let handle = Handle::current();
+    let ip1 = "10.0.0.1".parse().unwrap();
+    let ip2 = "10.0.0.2".parse().unwrap();
+    let server = handle.create_node().name("server").ip(ip1).build();
+    let client = handle.create_node().name("client").ip(ip2).build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .drop_rate(0.5)
+            .serve("10.0.0.1:2379".parse().unwrap())
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task1 = client.spawn(async move {
+        let client = Client::connect(["10.0.0.1:2379"], None).await.unwrap();
+        let mut kv_client = client.kv_client();
+        let mut dropped = 0;
+        for i in 0..20 {
+            if kv_client.put("foo", i.to_string(), None).await.is_ok() {
+                continue;
+            }
+            dropped += 1;
+            // the put takes effect even though the response is lost
+            let resp = loop {
+                if let Ok(resp) = kv_client.get("foo", None).await {
+                    break resp;
+                }
+            };
+            assert_eq!(resp.kvs()[0].value(), i.to_string().as_bytes());
+        }
+        assert!(dropped > 0 && dropped < 20, "{dropped}");
+    });
+    task1.await.unwrap();
+}
+
+#[madsim::test]
+async fn watch() {
+    let handle = Handle::current();
+    let ip1 = "10.0.0.1".parse().unwrap();
+    let ip2 = "10.0.0.2".parse().unwrap();
+    let server = handle.create_node().name("server").ip(ip1).build();
+    let client = handle.create_node().name("client").ip(ip2).build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .serve("10.0.0.1:2379".parse().unwrap())
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task1 = client.spawn(async move {
+        let client = Client::connect(["10.0.0.1:2379"], None).await.unwrap();
+        let mut kv_client = client.kv_client();
+        let mut watch_client = client.watch_client();
+
+        // watch deletes only
+        let opt = WatchOptions::new()
+            .with_prefix()
+            .with_filters([WatchFilterType::NoPut])
+            .with_prev_key();
+        let (mut watcher, mut stream) = watch_client.watch("foo", Some(opt)).await.unwrap();
+        // watch puts with previous values
+        let opt = WatchOptions::new().with_prev_key();
+        let (_watcher2, mut stream2) = watch_client.watch("foo1", Some(opt)).await.unwrap();
+
+        kv_client.put("foo1", "1", None).await.unwrap();
+        kv_client.put("foo1", "2", None).await.unwrap();
+        kv_client.put("bar", "1", None).await.unwrap();
+        let rsp = kv_client.delete("foo1", None).await.unwrap();
+        let revision = rsp.header().unwrap().revision();
+
+        let rsp = stream.message().await.unwrap().unwrap();
+        assert_eq!(rsp.watch_id(), watcher.watch_id());
+        let event = &rsp.events()[0];
+        assert_eq!(event.event_type(), EventType::Delete);
+        assert_eq!(event.kv().unwrap().key(), b"foo1");
+        assert_eq!(event.kv().unwrap().mod_revision(), revision);
+        assert_eq!(event.prev_kv().unwrap().value(), b"2");
+
+        let rsp = stream2.message().await.unwrap().unwrap();
+        let event = &rsp.events()[0];
+        assert_eq!(event.event_type(), EventType::Put);
+        assert_eq!(event.kv().unwrap().value(), b"1");
+        assert!(event.prev_kv().is_none());
+        let rsp = stream2.message().await.unwrap().unwrap();
+        let event = &rsp.events()[0];
+        assert_eq!(event.kv().unwrap().value(), b"2");
+        assert_eq!(event.prev_kv().unwrap().value(), b"1");
+        let rsp = stream2.message().await.unwrap().unwrap();
+        assert_eq!(rsp.events()[0].event_type(), EventType::Delete);
+
+        // cancel the watcher
+        watcher.cancel().await.unwrap();
+        let rsp = stream.message().await.unwrap().unwrap();
+        assert!(rsp.canceled());
+        assert!(stream.message().await.unwrap().is_none());
+    });
+    task1.await.unwrap();
+}
+
+#[madsim::test]
+async fn watch_fragment() {
+    let handle = Handle::current();
+    let ip1 = "10.0.0.1".parse().unwrap();
+    let ip2 = "10.0.0.2".parse().unwrap();
+    let server = handle.create_node().name("server").ip(ip1).build();
+    let client = handle.create_node().name("client").ip(ip2).build();
+
+    server.spawn(async move {
+        SimServer::builder()
+            .serve("10.0.0.1:2379".parse().unwrap())
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let task1 = client.spawn(async move {
+        let client = Client::connect(["10.0.0.1:2379"], None).await.unwrap();
+        let mut kv_client = client.kv_client();
+        let mut watch_client = client.watch_client();
+
+        let rsp = kv_client.put("other", "0", None).await.unwrap();
+        let start = rsp.header().unwrap().revision() + 1;
+        for i in 0..250 {
+            let key = format!("key{i:03}");
+            kv_client.put(key, i.to_string(), None).await.unwrap();
+        }
+
+        // without fragment, all events are sent in a single response
+        let opt = WatchOptions::new().with_prefix().with_start_revision(start);
+        let (_watcher, mut stream) = watch_client.watch("key", Some(opt)).await.unwrap();
+        let rsp = stream.message().await.unwrap().unwrap();
+        assert!(!rsp.fragment());
+        assert_eq!(rsp.events().len(), 250);
+
+        // with fragment, the client reassembles the events from multiple responses
+        let opt = WatchOptions::new()
+            .with_prefix()
+            .with_start_revision(start)
+            .with_fragment();
+        let (_watcher, mut stream) = watch_client.watch("key", Some(opt)).await.unwrap();
+        let mut events = vec![];
+        let mut responses = 0;
+        loop {
+            let rsp = stream.message().await.unwrap().unwrap();
+            responses += 1;
+            events.extend_from_slice(rsp.events());
+            if !rsp.fragment() {
+                break;
+            }
+        }
+        assert!(responses > 1, "{responses}");
+        assert_eq!(events.len(), 250);
+        for (i, event) in events.iter().enumerate() {
+            assert_eq!(event.event_type(), EventType::Put);
+            let kv = event.kv().unwrap();
+            assert_eq!(kv.key(), format!("key{i:03}").as_bytes());
+            assert_eq!(kv.value(), i.to_string().as_bytes());
+        }
+
+        // following events are sent as usual
+        kv_client.put("key000", "new", None).await.unwrap();
+        let rsp = stream.message().await.unwrap().unwrap();
+        assert!(!rsp.fragment());
+        assert_eq!(rsp.events()[0].kv().unwrap().value(), b"new");
+    });
+    task1.await.unwrap();
+}
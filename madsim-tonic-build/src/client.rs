@@ -86,6 +86,24 @@ pub fn generate<T: Service>(
                     self
                 }
 
+                /// Limits the maximum size of a decoded message.
+                ///
+                /// Default: unlimited
+                #[must_use]
+                pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+                    self.inner = self.inner.max_decoding_message_size(limit);
+                    self
+                }
+
+                /// Limits the maximum size of an encoded message.
+                ///
+                /// Default: unlimited
+                #[must_use]
+                pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+                    self.inner = self.inner.max_encoding_message_size(limit);
+                    self
+                }
+
                 #methods
             }
         }
@@ -170,7 +188,7 @@ fn generate_unary<T: Method>(
                tonic::Status::new(tonic::Code::Unknown, format!("Service was not ready: {e}"))
            })?;
            // let codec = #codec_name::default();
-           let codec = ();
+           let codec = tonic::codec::ProstCodec::default();
            let path = http::uri::PathAndQuery::from_static(#path);
            self.inner.unary(request.into_request(), path, codec).await
         }
@@ -197,7 +215,7 @@ fn generate_server_streaming<T: Method>(
                 tonic::Status::new(tonic::Code::Unknown, format!("Service was not ready: {e}"))
             })?;
             // let codec = #codec_name::default();
-            let codec = ();
+            let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(#path);
             self.inner.server_streaming(request.into_request(), path, codec).await
         }
@@ -224,7 +242,7 @@ fn generate_client_streaming<T: Method>(
                 tonic::Status::new(tonic::Code::Unknown, format!("Service was not ready: {e}"))
             })?;
             // let codec = #codec_name::default();
-            let codec = ();
+            let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(#path);
             self.inner.client_streaming(request.into_streaming_request(), path, codec).await
         }
@@ -251,7 +269,7 @@ fn generate_streaming<T: Method>(
                 tonic::Status::new(tonic::Code::Unknown, format!("Service was not ready: {e}"))
             })?;
             // let codec = #codec_name::default();
-            let codec = ();
+            let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(#path);
             self.inner.streaming(request.into_streaming_request(), path, codec).await
         }
@@ -35,7 +35,10 @@ pub fn generate<T: Service>(
         if package.is_empty() { "" } else { "." },
         service.identifier()
     );
-    let transport = generate_transport(&server_service, &server_trait, &path);
+    let method_names = (service.methods().iter())
+        .map(|method| method.identifier().to_string())
+        .collect::<Vec<_>>();
+    let transport = generate_transport(&server_service, &server_trait, &path, &method_names);
     // let mod_attributes = attributes.for_mod(package);
     // let struct_attributes = attributes.for_struct(&path);
 
@@ -79,8 +82,10 @@ pub fn generate<T: Service>(
                 unused_mut,
                 // will trigger if compression is disabled
                 clippy::let_unit_value,
+                clippy::result_large_err,
             )]
             use tonic::codegen::{http::uri::PathAndQuery, futures::{stream::{self, StreamExt}, future::FutureExt}, *};
+            use tonic::codec::Codec as _;
 
             #generated_trait
 
@@ -90,6 +95,8 @@ pub fn generate<T: Service>(
             pub struct #server_service<T: #server_trait, F> {
                 inner: Arc<T>,
                 interceptor: F,
+                max_decoding_message_size: Option<usize>,
+                max_encoding_message_size: Option<usize>,
             }
 
             impl<T: #server_trait> #server_service<T, IdentityInterceptor> {
@@ -101,6 +108,8 @@ pub fn generate<T: Service>(
                     Self {
                         inner,
                         interceptor: Ok,
+                        max_decoding_message_size: None,
+                        max_encoding_message_size: None,
                     }
                 }
             }
@@ -113,10 +122,30 @@ pub fn generate<T: Service>(
                     Self {
                         inner: Arc::new(inner),
                         interceptor,
+                        max_decoding_message_size: None,
+                        max_encoding_message_size: None,
                     }
                 }
 
                 #configure_compression_methods
+
+                /// Limits the maximum size of a decoded message.
+                ///
+                /// Default: unlimited
+                #[must_use]
+                pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+                    self.max_decoding_message_size = Some(limit);
+                    self
+                }
+
+                /// Limits the maximum size of an encoded message.
+                ///
+                /// Default: unlimited
+                #[must_use]
+                pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+                    self.max_encoding_message_size = Some(limit);
+                    self
+                }
             }
 
             impl<T, F> tonic::codegen::Service<(PathAndQuery, tonic::Request<BoxMessageStream>)> for #server_service<T, F>
@@ -134,6 +163,8 @@ pub fn generate<T: Service>(
 
                 fn call(&mut self, (path, request): (PathAndQuery, tonic::Request<BoxMessageStream>)) -> Self::Future {
                     let inner = self.inner.clone();
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
                     let mut request = match request.intercept(&mut self.interceptor) {
                         Ok(r) => r,
                         Err(e) => return Box::pin(async move { Err(e) }),
@@ -156,6 +187,8 @@ pub fn generate<T: Service>(
                     Self {
                         inner: self.inner.clone(),
                         interceptor: self.interceptor.clone(),
+                        max_decoding_message_size: self.max_decoding_message_size,
+                        max_encoding_message_size: self.max_encoding_message_size,
                     }
                 }
             }
@@ -261,6 +294,7 @@ fn generate_transport(
     server_service: &syn::Ident,
     server_trait: &syn::Ident,
     service_name: &str,
+    method_names: &[String],
 ) -> TokenStream {
     let service_name = syn::LitStr::new(service_name, proc_macro2::Span::call_site());
 
@@ -271,6 +305,7 @@ fn generate_transport(
             F: tonic::service::Interceptor,
         {
             const NAME: &'static str = #service_name;
+            const METHODS: &'static [&'static str] = &[#(#method_names),*];
         }
     }
 }
@@ -280,6 +315,7 @@ fn generate_transport(
     _server_service: &syn::Ident,
     _server_trait: &syn::Ident,
     _service_name: &str,
+    _method_names: &[String],
 ) -> TokenStream {
     TokenStream::new()
 }
@@ -358,16 +394,19 @@ fn generate_unary<T: Method>(
     method_ident: Ident,
     _server_trait: Ident,
 ) -> TokenStream {
-    let (request, _) = method.request_response_name(proto_path, compile_well_known_types);
+    let (request, response) = method.request_response_name(proto_path, compile_well_known_types);
 
     quote! {
         let inner = self.inner.clone();
+        let codec = tonic::codec::ProstCodec::<#response, #request>::default();
         Box::pin(async move {
             let request = request.map(|mut stream| {
                 let first = stream.next().now_or_never().unwrap().unwrap();
                 *first.unwrap().downcast::<#request>().unwrap()
             });
+            codec.check_decode(request.get_ref(), max_decoding_message_size)?;
             let response: tonic::Response<_> = (*inner).#method_ident(request).await?;
+            codec.check_encode(response.get_ref(), max_encoding_message_size)?;
             Ok(response.map(|msg| stream::once(async move { Ok(Box::new(msg) as BoxMessage) }).boxed()))
         })
     }
@@ -380,17 +419,22 @@ fn generate_server_streaming<T: Method>(
     method_ident: Ident,
     _server_trait: Ident,
 ) -> TokenStream {
-    let (request, _) = method.request_response_name(proto_path, compile_well_known_types);
+    let (request, response) = method.request_response_name(proto_path, compile_well_known_types);
 
     quote! {
         let inner = self.inner.clone();
+        let codec = tonic::codec::ProstCodec::<#response, #request>::default();
         Box::pin(async move {
             let request = request.map(|mut stream| {
                 let first = stream.next().now_or_never().unwrap().unwrap();
                 *first.unwrap().downcast::<#request>().unwrap()
             });
+            codec.check_decode(request.get_ref(), max_decoding_message_size)?;
             let response: tonic::Response<_> = (*inner).#method_ident(request).await?;
-            Ok(response.map(|stream| stream.map(|res| res.map(|msg| Box::new(msg) as BoxMessage)).boxed()))
+            Ok(response.map(|stream| stream.map(move |res| res.and_then(|msg| {
+                codec.check_encode(&msg, max_encoding_message_size)?;
+                Ok(Box::new(msg) as BoxMessage)
+            })).boxed()))
         })
     }
 }
@@ -402,17 +446,23 @@ fn generate_client_streaming<T: Method>(
     method_ident: Ident,
     _server_trait: Ident,
 ) -> TokenStream {
-    let (request, _) = method.request_response_name(proto_path, compile_well_known_types);
+    let (request, response) = method.request_response_name(proto_path, compile_well_known_types);
 
     quote! {
         let inner = self.inner.clone();
+        let codec = tonic::codec::ProstCodec::<#response, #request>::default();
         Box::pin(async move {
             let request = request.map(|stream| {
                 tonic::Streaming::from_stream(
-                    stream.map(|res| res.map(|msg| *msg.downcast::<#request>().unwrap())).boxed()
+                    stream.map(move |res| res.and_then(|msg| {
+                        let msg = *msg.downcast::<#request>().unwrap();
+                        codec.check_decode(&msg, max_decoding_message_size)?;
+                        Ok(msg)
+                    })).boxed()
                 )
             });
             let response: tonic::Response<_> = (*inner).#method_ident(request).await?;
+            codec.check_encode(response.get_ref(), max_encoding_message_size)?;
             Ok(response.map(|msg| stream::once(async move { Ok(Box::new(msg) as BoxMessage) }).boxed()))
         })
     }
@@ -425,18 +475,26 @@ fn generate_streaming<T: Method>(
     method_ident: Ident,
     _server_trait: Ident,
 ) -> TokenStream {
-    let (request, _) = method.request_response_name(proto_path, compile_well_known_types);
+    let (request, response) = method.request_response_name(proto_path, compile_well_known_types);
 
     quote! {
         let inner = self.inner.clone();
+        let codec = tonic::codec::ProstCodec::<#response, #request>::default();
         Box::pin(async move {
             let request = request.map(|stream| {
                 tonic::Streaming::from_stream(
-                    stream.map(|res| res.map(|msg| *msg.downcast::<#request>().unwrap())).boxed()
+                    stream.map(move |res| res.and_then(|msg| {
+                        let msg = *msg.downcast::<#request>().unwrap();
+                        codec.check_decode(&msg, max_decoding_message_size)?;
+                        Ok(msg)
+                    })).boxed()
                 )
             });
             let response: tonic::Response<_> = (*inner).#method_ident(request).await?;
-            Ok(response.map(|stream| stream.map(|res| res.map(|msg| Box::new(msg) as BoxMessage)).boxed()))
+            Ok(response.map(|stream| stream.map(move |res| res.and_then(|msg| {
+                codec.check_encode(&msg, max_encoding_message_size)?;
+                Ok(Box::new(msg) as BoxMessage)
+            })).boxed()))
         })
     }
 }
@@ -5,6 +5,7 @@ use tonic::codegen::http::uri::PathAndQuery;
 use tracing::instrument;
 
 use crate::{
+    codec::Codec,
     codegen::{BoxMessage, IdentityInterceptor, RequestExt},
     service::Interceptor,
     sim::AppendMetadata,
@@ -15,6 +16,8 @@ use crate::{
 pub struct Grpc<T, F> {
     inner: T,
     interceptor: F,
+    max_decoding_message_size: Option<usize>,
+    max_encoding_message_size: Option<usize>,
 }
 
 impl<T> Grpc<T, IdentityInterceptor> {
@@ -23,10 +26,34 @@ impl<T> Grpc<T, IdentityInterceptor> {
         Grpc {
             inner,
             interceptor: Ok,
+            max_decoding_message_size: None,
+            max_encoding_message_size: None,
         }
     }
 }
 
+impl<T, F> Grpc<T, F> {
+    /// Limits the maximum size of a decoded message.
+    ///
+    /// Responses larger than the limit fail with [`Code::OutOfRange`](crate::Code::OutOfRange).
+    /// Default: unlimited.
+    #[must_use]
+    pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+        self.max_decoding_message_size = Some(limit);
+        self
+    }
+
+    /// Limits the maximum size of an encoded message.
+    ///
+    /// Requests larger than the limit fail with [`Code::OutOfRange`](crate::Code::OutOfRange)
+    /// without being sent. Default: unlimited.
+    #[must_use]
+    pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+        self.max_encoding_message_size = Some(limit);
+        self
+    }
+}
+
 // Message type matrix:
 // |          |                single                  |                   stream                     |
 // |----------|----------------------------------------|----------------------------------------------|
@@ -36,7 +63,12 @@ impl<T> Grpc<T, IdentityInterceptor> {
 impl<F: Interceptor> Grpc<crate::transport::Channel, F> {
     /// Creates a new gRPC client with the provided `GrpcService` and interceptor.
     pub fn with_interceptor(inner: crate::transport::Channel, interceptor: F) -> Self {
-        Grpc { inner, interceptor }
+        Grpc {
+            inner,
+            interceptor,
+            max_decoding_message_size: None,
+            max_encoding_message_size: None,
+        }
     }
 
     /// Check if the inner GrpcService is able to accept a new request.
@@ -50,24 +82,30 @@ impl<F: Interceptor> Grpc<crate::transport::Channel, F> {
         &mut self,
         mut request: Request<M1>,
         path: PathAndQuery,
-        _codec: C,
+        codec: C,
     ) -> Result<Response<M2>, Status>
     where
         M1: Send + Sync + 'static,
         M2: Send + Sync + 'static,
+        C: Codec<M1, M2> + Clone + Send + 'static,
     {
         request.append_metadata();
-        let request = request.intercept(&mut self.interceptor)?.boxed();
+        let request = request.intercept(&mut self.interceptor)?;
+        codec.check_encode(request.get_ref(), self.max_encoding_message_size)?;
+        let request = request.boxed();
         let addr = self.inner.ep.peer_addr().unwrap();
         let (tx, mut rx) = self.inner.ep.connect1(addr).await?;
         // send request
+        self.inner.request_delay().await;
         tx.send(Box::new((path, false, request))).await?;
         // receive response
         let rsp = rx.recv().await?;
+        self.inner.response_delay().await;
         let rsp = *rsp
             .downcast::<Result<Response<BoxMessage>, Status>>()
             .expect("message type mismatch");
         let rsp = rsp?.map(|msg| *msg.downcast().expect("message type mismatch"));
+        codec.check_decode(rsp.get_ref(), self.max_decoding_message_size)?;
         Ok(rsp)
     }
 
@@ -77,101 +115,128 @@ impl<F: Interceptor> Grpc<crate::transport::Channel, F> {
         &mut self,
         mut request: Request<impl Stream<Item = M1> + Send + 'static>,
         path: PathAndQuery,
-        _codec: C,
+        codec: C,
     ) -> Result<Response<M2>, Status>
     where
         M1: Send + Sync + 'static,
         M2: Send + Sync + 'static,
+        C: Codec<M1, M2> + Clone + Send + 'static,
     {
         request.append_metadata();
         let request = request.intercept(&mut self.interceptor)?;
         let addr = self.inner.ep.peer_addr().unwrap();
         let (tx, mut rx) = self.inner.ep.connect1(addr).await?;
         // send requests
-        Self::send_request_stream(request, tx, path, false).await?;
+        self.inner.request_delay().await;
+        let limit = self.max_encoding_message_size;
+        send_request_stream(request, tx, path, false, codec.clone(), limit).await?;
         // receive response
         let rsp = rx.recv().await?;
+        self.inner.response_delay().await;
         let rsp = *rsp
             .downcast::<Result<Response<BoxMessage>, Status>>()
             .expect("message type mismatch");
         let rsp = rsp?.map(|msg| *msg.downcast().expect("message type mismatch"));
+        codec.check_decode(rsp.get_ref(), self.max_decoding_message_size)?;
         Ok(rsp)
     }
 
     /// Send a server side streaming gRPC request.
+    #[allow(clippy::result_large_err)]
     #[instrument(name = "rpc", skip_all, fields(?path))]
     pub async fn server_streaming<M1, M2, C>(
         &mut self,
         mut request: Request<M1>,
         path: PathAndQuery,
-        _codec: C,
+        codec: C,
     ) -> Result<Response<Streaming<M2>>, Status>
     where
         M1: Send + Sync + 'static,
         M2: Send + Sync + 'static,
+        C: Codec<M1, M2> + Clone + Send + 'static,
     {
         request.append_metadata();
-        let request = request.intercept(&mut self.interceptor)?.boxed();
+        let request = request.intercept(&mut self.interceptor)?;
+        codec.check_encode(request.get_ref(), self.max_encoding_message_size)?;
+        let request = request.boxed();
         let addr = self.inner.ep.peer_addr().unwrap();
         let (tx, mut rx) = self.inner.ep.connect1(addr).await?;
         // send request
+        self.inner.request_delay().await;
         tx.send(Box::new((path, true, request))).await?;
         // receive responses
         let res = *(rx.recv().await?)
             .downcast::<Result<Response<()>, Status>>()
             .unwrap();
-        let response = res?.map(move |_| Streaming::new(rx, None));
+        self.inner.response_delay().await;
+        let limit = self.max_decoding_message_size;
+        let check = move |msg: &M2| codec.check_decode(msg, limit);
+        let response = res?.map(move |_| Streaming::new(rx, None, check));
         Ok(response)
     }
 
     /// Send a bi-directional streaming gRPC request.
+    #[allow(clippy::result_large_err)]
     #[instrument(name = "rpc", skip_all, fields(?path))]
     pub async fn streaming<M1, M2, C>(
         &mut self,
         mut request: Request<impl Stream<Item = M1> + Send + 'static>,
         path: PathAndQuery,
-        _codec: C,
+        codec: C,
     ) -> Result<Response<Streaming<M2>>, Status>
     where
         M1: Send + Sync + 'static,
         M2: Send + Sync + 'static,
+        C: Codec<M1, M2> + Clone + Send + 'static,
     {
         request.append_metadata();
         let request = request.intercept(&mut self.interceptor)?;
         let addr = self.inner.ep.peer_addr().unwrap();
         let (tx, mut rx) = self.inner.ep.connect1(addr).await?;
         // send requests in a background task
-        let task = madsim::task::spawn(async move {
-            Self::send_request_stream(request, tx, path, true)
-                .await
-                .unwrap();
-        });
+        self.inner.request_delay().await;
+        let limit = self.max_encoding_message_size;
+        let task = madsim::task::spawn(send_request_stream(
+            request,
+            tx,
+            path,
+            true,
+            codec.clone(),
+            limit,
+        ));
         // receive responses
         let res = *(rx.recv().await?)
             .downcast::<Result<Response<()>, Status>>()
             .unwrap();
-        let response = res?.map(move |_| Streaming::new(rx, Some(task)));
+        self.inner.response_delay().await;
+        let limit = self.max_decoding_message_size;
+        let check = move |msg: &M2| codec.check_decode(msg, limit);
+        let response = res?.map(move |_| Streaming::new(rx, Some(task), check));
         Ok(response)
     }
+}
 
-    async fn send_request_stream<M1>(
-        request: Request<impl Stream<Item = M1> + Send + 'static>,
-        tx: madsim::net::Sender,
-        path: PathAndQuery,
-        server_streaming: bool,
-    ) -> Result<(), Status>
-    where
-        M1: Send + Sync + 'static,
-    {
-        let (metadata, extensions, stream) = request.into_parts();
-        let header = Request::from_parts(metadata, extensions, Box::new(()) as BoxMessage);
-        // send stream start message
-        tx.send(Box::new((path, server_streaming, header))).await?;
-        // send requests
-        pin_mut!(stream);
-        while let Some(item) = stream.next().await {
-            tx.send(Box::new(item)).await?;
-        }
-        Ok(())
+/// Sends the stream start message and then all requests in the stream.
+async fn send_request_stream<M1, M2>(
+    request: Request<impl Stream<Item = M1> + Send + 'static>,
+    tx: madsim::net::Sender,
+    path: PathAndQuery,
+    server_streaming: bool,
+    codec: impl Codec<M1, M2> + Send,
+    max_encoding_message_size: Option<usize>,
+) -> Result<(), Status>
+where
+    M1: Send + Sync + 'static,
+{
+    let (metadata, extensions, stream) = request.into_parts();
+    let header = Request::from_parts(metadata, extensions, Box::new(()) as BoxMessage);
+    // send stream start message
+    tx.send(Box::new((path, server_streaming, header))).await?;
+    // send requests
+    pin_mut!(stream);
+    while let Some(item) = stream.next().await {
+        codec.check_encode(&item, max_encoding_message_size)?;
+        tx.send(Box::new(item)).await?;
     }
+    Ok(())
 }
@@ -1,10 +1,16 @@
-use crate::{codegen::BoxMessage, Status};
+use crate::{
+    codegen::{BoxMessage, Trailers},
+    metadata::MetadataMap,
+    Status,
+};
 use async_stream::try_stream;
-use futures_util::{Stream, StreamExt};
+use futures_util::{select_biased, FutureExt, Stream, StreamExt};
 use madsim::task::JoinHandle;
 use std::{
     fmt,
+    marker::PhantomData,
     pin::Pin,
+    sync::{Arc, Mutex},
     task::{Context, Poll},
 };
 use tonic::codegen::BoxStream;
@@ -12,29 +18,63 @@ use tonic::codegen::BoxStream;
 /// Streaming requests and responses.
 pub struct Streaming<T> {
     stream: BoxStream<T>,
+    trailers: Arc<Mutex<Option<MetadataMap>>>,
 }
 
 impl<T: Send + 'static> Streaming<T> {
     /// Creates a new streaming.
     ///
     /// The elements will be received from the endpoint starting with the given tag.
-    /// If this is a bi-directional streaming RPC, `request_sending_task` is required.
+    /// Each element is validated by `check` before it is yielded.
+    /// If this is a bi-directional streaming RPC, `request_sending_task` is required,
+    /// and its error is yielded if it fails to send requests.
     pub(crate) fn new(
         mut rx: madsim::net::Receiver,
-        request_sending_task: Option<JoinHandle<()>>,
+        request_sending_task: Option<JoinHandle<Result<(), Status>>>,
+        check: impl Fn(&T) -> Result<(), Status> + Send + 'static,
     ) -> Self {
+        let trailers = Arc::new(Mutex::new(None));
+        let trailers0 = trailers.clone();
         Streaming {
             stream: try_stream! {
                 // For bi-directional streaming, we spawn a task to send requests.
                 // This is used to cancel the task when the stream is dropped.
-                let _task = request_sending_task.map(|t| t.cancel_on_drop());
+                let mut task = request_sending_task.map(|t| t.cancel_on_drop());
                 // receive messages
-                while let Ok(msg) = rx.recv().await {
+                loop {
+                    let msg = match &mut task {
+                        Some(t) => select_biased! {
+                            result = t.fuse() => Err(result),
+                            msg = rx.recv().fuse() => Ok(msg),
+                        },
+                        None => Ok(rx.recv().await),
+                    };
+                    let msg = match msg {
+                        Ok(Ok(msg)) => msg,
+                        Ok(Err(_)) => break,
+                        Err(result) => {
+                            // all requests are sent, or failed to be sent
+                            task = None;
+                            result.transpose()?;
+                            continue;
+                        }
+                    };
+                    let msg = match msg.downcast::<Trailers>() {
+                        Ok(t) => {
+                            // trailers are the last message
+                            *trailers0.lock().unwrap() = Some(t.0);
+                            break;
+                        }
+                        Err(msg) => msg,
+                    };
                     let msg = *msg.downcast::<Result<BoxMessage, Status>>().unwrap();
-                    yield *msg?.downcast::<T>().unwrap();
+                    let msg = *msg?.downcast::<T>().unwrap();
+                    check(&msg)?;
+                    yield msg;
                 }
             }
             .boxed(),
+            trailers,
         }
     }
 
@@ -43,7 +83,10 @@ impl<T: Send + 'static> Streaming<T> {
     /// This method is used by macros only. Not a public API.
     #[doc(hidden)]
     pub fn from_stream(stream: BoxStream<T>) -> Self {
-        Streaming { stream }
+        Streaming {
+            stream,
+            trailers: Default::default(),
+        }
     }
 }
 
@@ -52,6 +95,14 @@ impl<T> Streaming<T> {
     pub async fn message(&mut self) -> Result<Option<T>, Status> {
         self.stream.next().await.transpose()
     }
+
+    /// Fetch the trailing metadata.
+    ///
+    /// This will drain the stream of all its messages to receive the trailing metadata.
+    pub async fn trailers(&mut self) -> Result<Option<MetadataMap>, Status> {
+        while self.message().await?.is_some() {}
+        Ok(self.trailers.lock().unwrap().take())
+    }
 }
 
 impl<T> fmt::Debug for Streaming<T> {
@@ -67,3 +118,83 @@ impl<T> Stream for Streaming<T> {
         self.stream.poll_next_unpin(cx)
     }
 }
+
+/// Measures the encoded length of messages to enforce the message size limits.
+///
+/// `T` is the type of messages to encode, and `U` is the type of messages to decode.
+/// Messages are never serialized in simulation.
+#[allow(clippy::result_large_err)]
+pub trait Codec<T, U> {
+    /// Returns the encoded length of a message to send, or `None` if unknown.
+    fn encoded_len(&self, item: &T) -> Option<usize>;
+
+    /// Returns the encoded length of a received message, or `None` if unknown.
+    fn decoded_len(&self, item: &U) -> Option<usize>;
+
+    /// Checks that a message to send does not exceed the limit.
+    fn check_encode(&self, item: &T, limit: Option<usize>) -> Result<(), Status> {
+        check_message_size(self.encoded_len(item), limit)
+    }
+
+    /// Checks that a received message does not exceed the limit.
+    fn check_decode(&self, item: &U, limit: Option<usize>) -> Result<(), Status> {
+        check_message_size(self.decoded_len(item), limit)
+    }
+}
+
+/// The length of messages is unknown, so they are never limited.
+impl<T, U> Codec<T, U> for () {
+    fn encoded_len(&self, _item: &T) -> Option<usize> {
+        None
+    }
+
+    fn decoded_len(&self, _item: &U) -> Option<usize> {
+        None
+    }
+}
+
+/// A [`Codec`] that measures messages by their protobuf encoding.
+pub struct ProstCodec<T, U> {
+    _pd: PhantomData<fn() -> (T, U)>,
+}
+
+impl<T, U> Default for ProstCodec<T, U> {
+    fn default() -> Self {
+        ProstCodec { _pd: PhantomData }
+    }
+}
+
+impl<T, U> Clone for ProstCodec<T, U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, U> Copy for ProstCodec<T, U> {}
+
+impl<T, U> fmt::Debug for ProstCodec<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProstCodec").finish()
+    }
+}
+
+impl<T: prost::Message, U: prost::Message> Codec<T, U> for ProstCodec<T, U> {
+    fn encoded_len(&self, item: &T) -> Option<usize> {
+        Some(item.encoded_len())
+    }
+
+    fn decoded_len(&self, item: &U) -> Option<usize> {
+        Some(item.encoded_len())
+    }
+}
+
+/// Returns an error if the length of a message exceeds the limit.
+#[allow(clippy::result_large_err)]
+fn check_message_size(len: Option<usize>, limit: Option<usize>) -> Result<(), Status> {
+    match (len, limit) {
+        (Some(len), Some(limit)) if len > limit => Err(Status::out_of_range(format!(
+            "Error, message length too large: found {len} bytes, the limit is: {limit} bytes"
+        ))),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,275 @@
+//! A simulated gRPC health checking service.
+//!
+//! Create the service and its reporter with [`health_reporter`], add the service to
+//! the server, and update the status of services through the reporter. Services
+//! whose status has never been set are `SERVING`.
+//!
+//! [`health_reporter`]: self::server::health_reporter
+
+/// An enumeration of the serving status of a service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ServingStatus {
+    /// Unknown status.
+    Unknown,
+    /// The service is serving requests.
+    Serving,
+    /// The service is not serving requests.
+    NotServing,
+}
+
+impl From<ServingStatus> for pb::health_check_response::ServingStatus {
+    fn from(status: ServingStatus) -> Self {
+        match status {
+            ServingStatus::Unknown => Self::Unknown,
+            ServingStatus::Serving => Self::Serving,
+            ServingStatus::NotServing => Self::NotServing,
+        }
+    }
+}
+
+/// Messages and client of `grpc.health.v1`.
+pub mod pb {
+    /// The request of `Check` and `Watch` methods.
+    #[derive(Debug, Default, Clone, PartialEq, Eq)]
+    pub struct HealthCheckRequest {
+        /// The name of the service. An empty name refers to the server as a whole.
+        pub service: String,
+    }
+
+    /// The response of `Check` and `Watch` methods.
+    #[derive(Debug, Default, Clone, PartialEq, Eq)]
+    pub struct HealthCheckResponse {
+        pub status: i32,
+    }
+
+    impl HealthCheckResponse {
+        /// Returns the enum value of `status`, or the default if the value is invalid.
+        pub fn status(&self) -> health_check_response::ServingStatus {
+            use health_check_response::ServingStatus::*;
+            match self.status {
+                1 => Serving,
+                2 => NotServing,
+                3 => ServiceUnknown,
+                _ => Unknown,
+            }
+        }
+    }
+
+    /// Nested message and enum types in `HealthCheckResponse`.
+    pub mod health_check_response {
+        #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+        #[repr(i32)]
+        pub enum ServingStatus {
+            #[default]
+            Unknown = 0,
+            Serving = 1,
+            NotServing = 2,
+            /// Used only by the Watch method.
+            ServiceUnknown = 3,
+        }
+    }
+
+    /// Client of the health service.
+    pub mod health_client {
+        use crate::codegen::*;
+
+        #[derive(Debug, Clone)]
+        pub struct HealthClient<T, F = IdentityInterceptor> {
+            inner: crate::client::Grpc<T, F>,
+        }
+
+        impl HealthClient<crate::transport::Channel> {
+            /// Attempt to create a new client by connecting to a given endpoint.
+            pub async fn connect<D>(dst: D) -> Result<Self, crate::transport::Error>
+            where
+                D: std::convert::TryInto<crate::transport::Endpoint>,
+                D::Error: Into<StdError>,
+            {
+                let conn = crate::transport::Endpoint::new(dst)?.connect().await?;
+                Ok(Self::new(conn))
+            }
+
+            pub fn new(inner: crate::transport::Channel) -> Self {
+                let inner = crate::client::Grpc::new(inner);
+                Self { inner }
+            }
+        }
+
+        impl<F: crate::service::Interceptor> HealthClient<crate::transport::Channel, F> {
+            pub fn with_interceptor(inner: crate::transport::Channel, interceptor: F) -> Self {
+                let inner = crate::client::Grpc::with_interceptor(inner, interceptor);
+                Self { inner }
+            }
+
+            /// Check gets the health of the specified service.
+            pub async fn check(
+                &mut self,
+                request: impl crate::IntoRequest<super::HealthCheckRequest>,
+            ) -> Result<crate::Response<super::HealthCheckResponse>, crate::Status> {
+                let path = http::uri::PathAndQuery::from_static("/grpc.health.v1.Health/Check");
+                self.inner.unary(request.into_request(), path, ()).await
+            }
+
+            /// Performs a watch for the serving status of the requested service.
+            ///
+            /// The server immediately sends back a message indicating the current serving
+            /// status, and then sends a new message whenever the service's serving status
+            /// changes.
+            pub async fn watch(
+                &mut self,
+                request: impl crate::IntoRequest<super::HealthCheckRequest>,
+            ) -> Result<crate::Response<crate::Streaming<super::HealthCheckResponse>>, crate::Status>
+            {
+                let path = http::uri::PathAndQuery::from_static("/grpc.health.v1.Health/Watch");
+                self.inner
+                    .server_streaming(request.into_request(), path, ())
+                    .await
+            }
+        }
+    }
+}
+
+/// The server of the health service.
+pub mod server {
+    use super::{
+        pb::{HealthCheckRequest, HealthCheckResponse},
+        ServingStatus,
+    };
+    use crate::{
+        codegen::{BoxMessage, BoxMessageStream},
+        transport::NamedService,
+        Request, Response, Status,
+    };
+    use async_stream::try_stream;
+    use futures_util::{FutureExt, StreamExt};
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+        task::{Context, Poll},
+    };
+    use tokio::sync::watch;
+    use tonic::codegen::{http::uri::PathAndQuery, BoxFuture, Service};
+
+    /// Service name -> status.
+    type Statuses = Arc<Mutex<HashMap<String, watch::Sender<ServingStatus>>>>;
+
+    /// Creates a [`HealthReporter`] and a linked [`HealthServer`] pair.
+    pub fn health_reporter() -> (HealthReporter, HealthServer) {
+        let statuses = Statuses::default();
+        let reporter = HealthReporter {
+            statuses: statuses.clone(),
+        };
+        (reporter, HealthServer { statuses })
+    }
+
+    /// A handle to update the serving status of services.
+    #[derive(Debug, Clone)]
+    pub struct HealthReporter {
+        statuses: Statuses,
+    }
+
+    impl HealthReporter {
+        /// Sets the status of the service implemented by `S` to `SERVING`.
+        pub async fn set_serving<S: NamedService>(&mut self) {
+            self.set_service_status(S::NAME, ServingStatus::Serving)
+                .await;
+        }
+
+        /// Sets the status of the service implemented by `S` to `NOT_SERVING`.
+        pub async fn set_not_serving<S: NamedService>(&mut self) {
+            self.set_service_status(S::NAME, ServingStatus::NotServing)
+                .await;
+        }
+
+        /// Sets the status of the service with `service_name`.
+        ///
+        /// All watchers of the service are notified if the status changes.
+        pub async fn set_service_status(
+            &mut self,
+            service_name: impl AsRef<str>,
+            status: ServingStatus,
+        ) {
+            let mut statuses = self.statuses.lock().unwrap();
+            let service_name = service_name.as_ref();
+            match statuses.get(service_name) {
+                Some(tx) => {
+                    tx.send_if_modified(|s| std::mem::replace(s, status) != status);
+                }
+                None => {
+                    let (tx, _) = watch::channel(status);
+                    statuses.insert(service_name.to_string(), tx);
+                }
+            }
+        }
+    }
+
+    /// The server of the health service.
+    #[derive(Debug, Clone)]
+    pub struct HealthServer {
+        statuses: Statuses,
+    }
+
+    impl NamedService for HealthServer {
+        const NAME: &'static str = "grpc.health.v1.Health";
+        const METHODS: &'static [&'static str] = &["Check", "Watch"];
+    }
+
+    impl HealthServer {
+        /// Subscribes to the status of the service.
+        fn subscribe(&self, service_name: String) -> watch::Receiver<ServingStatus> {
+            let mut statuses = self.statuses.lock().unwrap();
+            let tx = (statuses.entry(service_name))
+                .or_insert_with(|| watch::channel(ServingStatus::Serving).0);
+            tx.subscribe()
+        }
+    }
+
+    /// Returns a response with the status.
+    fn response(status: ServingStatus) -> BoxMessage {
+        let status = super::pb::health_check_response::ServingStatus::from(status);
+        Box::new(HealthCheckResponse {
+            status: status as i32,
+        })
+    }
+
+    impl Service<(PathAndQuery, Request<BoxMessageStream>)> for HealthServer {
+        type Response = Response<BoxMessageStream>;
+        type Error = Status;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        #[allow(clippy::result_large_err)]
+        fn call(
+            &mut self,
+            (path, request): (PathAndQuery, Request<BoxMessageStream>),
+        ) -> Self::Future {
+            let this = self.clone();
+            Box::pin(async move {
+                let first = request.into_inner().next().now_or_never().unwrap().unwrap();
+                let request = *first?.downcast::<HealthCheckRequest>().unwrap();
+                let mut rx = this.subscribe(request.service);
+                let stream: BoxMessageStream = match path.path() {
+                    "/grpc.health.v1.Health/Check" => {
+                        let status = *rx.borrow();
+                        futures_util::stream::once(async move { Ok(response(status)) }).boxed()
+                    }
+                    "/grpc.health.v1.Health/Watch" => try_stream! {
+                        loop {
+                            let status = *rx.borrow_and_update();
+                            yield response(status);
+                            if rx.changed().await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+                    .boxed(),
+                    _ => return Err(Status::invalid_argument(format!("no path: {path}"))),
+                };
+                Ok(Response::new(stream))
+            })
+        }
+    }
+}
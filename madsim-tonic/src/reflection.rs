@@ -0,0 +1,300 @@
+//! A simulated gRPC reflection service.
+//!
+//! The service is registered by [`Router::add_reflection_service`], and describes
+//! all services of the router. Only listing services and looking up files by
+//! symbol or file name are supported.
+//!
+//! [`Router::add_reflection_service`]: crate::transport::server::Router::add_reflection_service
+
+use self::pb::{
+    server_reflection_request::MessageRequest, server_reflection_response::MessageResponse, *,
+};
+use crate::{
+    codegen::{BoxMessage, BoxMessageStream},
+    transport::NamedService,
+    Code, Request, Response, Status,
+};
+use futures_util::StreamExt;
+use prost::Message;
+use prost_types::{FileDescriptorProto, MethodDescriptorProto, ServiceDescriptorProto};
+use std::{
+    collections::BTreeMap,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tonic::codegen::{http::uri::PathAndQuery, BoxFuture, Service};
+
+/// Messages and client of `grpc.reflection.v1alpha`.
+pub mod pb {
+    /// The message sent by the client when calling `ServerReflectionInfo` method.
+    #[derive(Debug, Default, Clone, PartialEq, Eq)]
+    pub struct ServerReflectionRequest {
+        pub host: String,
+        pub message_request: Option<server_reflection_request::MessageRequest>,
+    }
+
+    /// Nested message and enum types in `ServerReflectionRequest`.
+    pub mod server_reflection_request {
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum MessageRequest {
+            /// Find a proto file by the file name.
+            FileByFilename(String),
+            /// Find the proto file that declares the given fully-qualified symbol name.
+            FileContainingSymbol(String),
+            /// Find the proto file which defines an extension extending the given message type.
+            FileContainingExtension(super::ExtensionRequest),
+            /// Finds the tag numbers used by all known extensions of the given message type.
+            AllExtensionNumbersOfType(String),
+            /// List the full names of registered services.
+            ListServices(String),
+        }
+    }
+
+    /// The type name and extension number sent by the client when requesting
+    /// file_containing_extension.
+    #[derive(Debug, Default, Clone, PartialEq, Eq)]
+    pub struct ExtensionRequest {
+        pub containing_type: String,
+        pub extension_number: i32,
+    }
+
+    /// The message sent by the server to answer `ServerReflectionInfo` method.
+    #[derive(Debug, Default, Clone, PartialEq, Eq)]
+    pub struct ServerReflectionResponse {
+        pub valid_host: String,
+        pub original_request: Option<ServerReflectionRequest>,
+        pub message_response: Option<server_reflection_response::MessageResponse>,
+    }
+
+    /// Nested message and enum types in `ServerReflectionResponse`.
+    pub mod server_reflection_response {
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum MessageResponse {
+            /// The serialized `FileDescriptorProto` messages of the requested files.
+            FileDescriptorResponse(super::FileDescriptorResponse),
+            /// The extension numbers of the requested message type.
+            AllExtensionNumbersResponse(super::ExtensionNumberResponse),
+            /// The services of the server.
+            ListServicesResponse(super::ListServiceResponse),
+            /// The error of the request.
+            ErrorResponse(super::ErrorResponse),
+        }
+    }
+
+    /// Serialized `FileDescriptorProto` messages.
+    #[derive(Debug, Default, Clone, PartialEq, Eq)]
+    pub struct FileDescriptorResponse {
+        pub file_descriptor_proto: Vec<Vec<u8>>,
+    }
+
+    /// A list of extension numbers sent by the server answering
+    /// all_extension_numbers_of_type request.
+    #[derive(Debug, Default, Clone, PartialEq, Eq)]
+    pub struct ExtensionNumberResponse {
+        pub base_type_name: String,
+        pub extension_number: Vec<i32>,
+    }
+
+    /// A list of `ServiceResponse` sent by the server answering list_services request.
+    #[derive(Debug, Default, Clone, PartialEq, Eq)]
+    pub struct ListServiceResponse {
+        pub service: Vec<ServiceResponse>,
+    }
+
+    /// The information of a single service.
+    #[derive(Debug, Default, Clone, PartialEq, Eq)]
+    pub struct ServiceResponse {
+        /// Full name of a registered service.
+        pub name: String,
+    }
+
+    /// The error code and error message sent by the server when an error occurs.
+    #[derive(Debug, Default, Clone, PartialEq, Eq)]
+    pub struct ErrorResponse {
+        /// This field uses the error codes defined in `grpc::StatusCode`.
+        pub error_code: i32,
+        pub error_message: String,
+    }
+
+    /// Client of the reflection service.
+    pub mod server_reflection_client {
+        use crate::codegen::*;
+
+        #[derive(Debug, Clone)]
+        pub struct ServerReflectionClient<T, F = IdentityInterceptor> {
+            inner: crate::client::Grpc<T, F>,
+        }
+
+        impl ServerReflectionClient<crate::transport::Channel> {
+            /// Attempt to create a new client by connecting to a given endpoint.
+            pub async fn connect<D>(dst: D) -> Result<Self, crate::transport::Error>
+            where
+                D: std::convert::TryInto<crate::transport::Endpoint>,
+                D::Error: Into<StdError>,
+            {
+                let conn = crate::transport::Endpoint::new(dst)?.connect().await?;
+                Ok(Self::new(conn))
+            }
+
+            pub fn new(inner: crate::transport::Channel) -> Self {
+                let inner = crate::client::Grpc::new(inner);
+                Self { inner }
+            }
+        }
+
+        impl<F: crate::service::Interceptor> ServerReflectionClient<crate::transport::Channel, F> {
+            pub fn with_interceptor(inner: crate::transport::Channel, interceptor: F) -> Self {
+                let inner = crate::client::Grpc::with_interceptor(inner, interceptor);
+                Self { inner }
+            }
+
+            /// The reflection service is structured as a bidirectional stream, ensuring
+            /// all related requests go to a single server.
+            pub async fn server_reflection_info(
+                &mut self,
+                request: impl crate::IntoStreamingRequest<Message = super::ServerReflectionRequest>,
+            ) -> Result<
+                crate::Response<crate::Streaming<super::ServerReflectionResponse>>,
+                crate::Status,
+            > {
+                let path = http::uri::PathAndQuery::from_static(
+                    "/grpc.reflection.v1alpha.ServerReflection/ServerReflectionInfo",
+                );
+                self.inner
+                    .streaming(request.into_streaming_request(), path, ())
+                    .await
+            }
+        }
+    }
+}
+
+/// The server of the reflection service.
+#[derive(Debug, Clone)]
+pub(crate) struct ServerReflectionServer {
+    /// Service name -> method names.
+    services: Arc<BTreeMap<&'static str, &'static [&'static str]>>,
+}
+
+impl NamedService for ServerReflectionServer {
+    const NAME: &'static str = "grpc.reflection.v1alpha.ServerReflection";
+    const METHODS: &'static [&'static str] = &["ServerReflectionInfo"];
+}
+
+impl ServerReflectionServer {
+    /// Creates a reflection service describing the given services and itself.
+    pub fn new(mut services: BTreeMap<&'static str, &'static [&'static str]>) -> Self {
+        services.insert(Self::NAME, Self::METHODS);
+        ServerReflectionServer {
+            services: Arc::new(services),
+        }
+    }
+
+    /// Answers a reflection request.
+    fn reflect(&self, request: ServerReflectionRequest) -> ServerReflectionResponse {
+        let response = match &request.message_request {
+            Some(MessageRequest::ListServices(_)) => {
+                MessageResponse::ListServicesResponse(ListServiceResponse {
+                    service: (self.services.keys())
+                        .map(|name| ServiceResponse {
+                            name: name.to_string(),
+                        })
+                        .collect(),
+                })
+            }
+            Some(MessageRequest::FileContainingSymbol(symbol)) => {
+                let service = self.services.iter().find(|(name, methods)| {
+                    symbol == *name
+                        || (symbol.strip_prefix(*name))
+                            .and_then(|s| s.strip_prefix('.'))
+                            .filter(|method| methods.contains(method))
+                            .is_some()
+                });
+                match service {
+                    Some((name, methods)) => file_descriptor_response(name, methods),
+                    None => error_response(Code::NotFound, format!("symbol not found: {symbol}")),
+                }
+            }
+            Some(MessageRequest::FileByFilename(filename)) => {
+                let service = (self.services.iter()).find(|(name, _)| file_name(name) == *filename);
+                match service {
+                    Some((name, methods)) => file_descriptor_response(name, methods),
+                    None => error_response(Code::NotFound, format!("file not found: {filename}")),
+                }
+            }
+            Some(_) => error_response(Code::Unimplemented, "unsupported request".into()),
+            None => error_response(Code::InvalidArgument, "empty request".into()),
+        };
+        ServerReflectionResponse {
+            valid_host: request.host.clone(),
+            original_request: Some(request),
+            message_response: Some(response),
+        }
+    }
+}
+
+impl Service<(PathAndQuery, Request<BoxMessageStream>)> for ServerReflectionServer {
+    type Response = Response<BoxMessageStream>;
+    type Error = Status;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn call(&mut self, (path, request): (PathAndQuery, Request<BoxMessageStream>)) -> Self::Future {
+        let this = self.clone();
+        Box::pin(async move {
+            if path.path() != "/grpc.reflection.v1alpha.ServerReflection/ServerReflectionInfo" {
+                return Err(Status::invalid_argument(format!("no path: {path}")));
+            }
+            let stream = (request.into_inner())
+                .map(move |res| {
+                    let request = *res?.downcast::<ServerReflectionRequest>().unwrap();
+                    Ok(Box::new(this.reflect(request)) as BoxMessage)
+                })
+                .boxed();
+            Ok(Response::new(stream))
+        })
+    }
+}
+
+/// Returns the name of the proto file declaring the service.
+fn file_name(service: &str) -> String {
+    format!("{service}.proto")
+}
+
+/// Returns a response with the file declaring the service.
+///
+/// Since the proto files are not available in simulation, the file only contains
+/// the service and the names of its methods.
+fn file_descriptor_response(service: &str, methods: &[&str]) -> MessageResponse {
+    let (package, name) = service.rsplit_once('.').unwrap_or(("", service));
+    let file = FileDescriptorProto {
+        name: Some(file_name(service)),
+        package: Some(package.into()).filter(|p: &String| !p.is_empty()),
+        service: vec![ServiceDescriptorProto {
+            name: Some(name.into()),
+            method: (methods.iter())
+                .map(|method| MethodDescriptorProto {
+                    name: Some(method.to_string()),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }],
+        syntax: Some("proto3".into()),
+        ..Default::default()
+    };
+    MessageResponse::FileDescriptorResponse(FileDescriptorResponse {
+        file_descriptor_proto: vec![file.encode_to_vec()],
+    })
+}
+
+/// Returns an error response.
+fn error_response(code: Code, message: String) -> MessageResponse {
+    MessageResponse::ErrorResponse(ErrorResponse {
+        error_code: code as i32,
+        error_message: message,
+    })
+}
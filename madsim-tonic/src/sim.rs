@@ -13,6 +13,8 @@ macro_rules! include_proto {
 
 pub mod client;
 pub mod codec;
+pub mod health;
+pub mod reflection;
 pub(crate) mod tower;
 pub mod transport;
 
@@ -92,9 +94,14 @@ pub mod codegen {
         }
     }
 
+    /// Trailing metadata of a streaming response.
+    #[derive(Debug, Default, Clone)]
+    pub(crate) struct Trailers(pub MetadataMap);
+
     pub trait ResponseExt<T>: Sized {
         fn into_parts(self) -> (MetadataMap, Extensions, T);
         fn from_parts(metadata: MetadataMap, extensions: Extensions, inner: T) -> Self;
+        fn trailers_mut(&mut self) -> &mut MetadataMap;
     }
 
     impl<T> ResponseExt<T> for Response<T> {
@@ -111,5 +118,16 @@ pub mod codegen {
             *rsp.extensions_mut() = extensions;
             rsp
         }
+
+        /// Get a mutable reference to the trailing metadata.
+        ///
+        /// For streaming responses, the trailers are sent to the client after the stream ends.
+        fn trailers_mut(&mut self) -> &mut MetadataMap {
+            let extensions = self.extensions_mut();
+            if extensions.get::<Trailers>().is_none() {
+                extensions.insert(Trailers::default());
+            }
+            &mut extensions.get_mut::<Trailers>().unwrap().0
+        }
     }
 }
@@ -1,7 +1,10 @@
 //! Client implementation and builder.
 
-use super::Error;
-use std::{fmt, net::SocketAddr, sync::Arc, time::Duration};
+#[cfg(feature = "tls")]
+use super::ClientTlsConfig;
+use super::{tls::ClientHello, Error};
+use madsim::rand::Rng;
+use std::{fmt, net::SocketAddr, ops::Range, sync::Arc, time::Duration};
 use tonic::{
     codegen::{http::HeaderValue, Bytes, StdError},
     transport::Uri,
@@ -12,6 +15,9 @@ use tonic::{
 pub struct Endpoint {
     uri: Uri,
     timeout: Option<Duration>,
+    request_latency: Range<Duration>,
+    response_latency: Range<Duration>,
+    tls: Option<super::tls::ClientTlsConfig>,
 }
 
 impl Endpoint {
@@ -46,6 +52,35 @@ impl Endpoint {
         self
     }
 
+    /// Set the simulated latency applied before each request is sent.
+    ///
+    /// The latency is sampled uniformly from the range. Defaults to zero.
+    pub fn request_latency(mut self, latency: Range<Duration>) -> Self {
+        self.request_latency = latency;
+        self
+    }
+
+    /// Set the simulated latency applied before each response is delivered.
+    ///
+    /// The latency is sampled uniformly from the range. Defaults to zero.
+    pub fn response_latency(mut self, latency: Range<Duration>) -> Self {
+        self.response_latency = latency;
+        self
+    }
+
+    /// Configures TLS for the endpoint.
+    ///
+    /// The simulated handshake fails on connection if the server does not trust
+    /// the client or vice versa. See [`ClientTlsConfig`] for details.
+    #[cfg(feature = "tls")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tls")))]
+    pub fn tls_config(self, tls_config: ClientTlsConfig) -> Result<Self, Error> {
+        Ok(Endpoint {
+            tls: Some(tls_config),
+            ..self
+        })
+    }
+
     /// Create a channel from this config.
     pub async fn connect(&self) -> Result<Channel, Error> {
         let host_port = (self.uri.authority())
@@ -61,9 +96,21 @@ impl Endpoint {
             .map_err(Error::from_source)?;
 
         // handshake
-        ep.connect1(addr).await.map_err(Error::from_source)?;
+        let (tx, mut rx) = ep.connect1(addr).await.map_err(Error::from_source)?;
+        let hello = ClientHello {
+            tls: self.tls.clone(),
+        };
+        tx.send(Box::new(hello)).await.map_err(Error::from_source)?;
+        let ret = *(rx.recv().await.map_err(Error::from_source)?)
+            .downcast::<Result<(), String>>()
+            .expect("invalid type");
+        ret.map_err(Error::from_source)?;
 
-        Ok(Channel { ep: Arc::new(ep) })
+        Ok(Channel {
+            ep: Arc::new(ep),
+            request_latency: self.request_latency.clone(),
+            response_latency: self.response_latency.clone(),
+        })
     }
 
     /// Set a custom user-agent header.
@@ -145,7 +192,13 @@ impl Endpoint {
 
 impl From<Uri> for Endpoint {
     fn from(uri: Uri) -> Self {
-        Self { uri, timeout: None }
+        Self {
+            uri,
+            timeout: None,
+            request_latency: Duration::ZERO..Duration::ZERO,
+            response_latency: Duration::ZERO..Duration::ZERO,
+            tls: None,
+        }
     }
 }
 
@@ -169,6 +222,32 @@ impl TryFrom<String> for Endpoint {
 #[derive(Clone)]
 pub struct Channel {
     pub(crate) ep: Arc<madsim::net::Endpoint>,
+    request_latency: Range<Duration>,
+    response_latency: Range<Duration>,
+}
+
+impl Channel {
+    /// Wait for the simulated latency of a request.
+    pub(crate) async fn request_delay(&self) {
+        delay(&self.request_latency).await;
+    }
+
+    /// Wait for the simulated latency of a response.
+    pub(crate) async fn response_delay(&self) {
+        delay(&self.response_latency).await;
+    }
+}
+
+/// Sleep for a random duration in the range.
+async fn delay(latency: &Range<Duration>) {
+    let duration = if latency.is_empty() {
+        latency.start
+    } else {
+        madsim::rand::thread_rng().gen_range(latency.clone())
+    };
+    if !duration.is_zero() {
+        madsim::time::sleep(duration).await;
+    }
 }
 
 impl fmt::Debug for Channel {
@@ -3,11 +3,15 @@
 pub use self::channel::{Channel, Endpoint};
 pub use self::error::Error;
 pub use self::server::Server;
+#[cfg(feature = "tls")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tls")))]
+pub use self::tls::{Certificate, ClientTlsConfig, Identity, ServerTlsConfig};
 pub use tonic::codegen::http::Uri;
 
 pub mod channel;
 mod error;
 pub mod server;
+mod tls;
 
 /// A trait to provide a static reference to the service's
 /// name. This is used for routing service's within the router.
@@ -16,4 +20,7 @@ pub trait NamedService {
     ///
     /// [here]: https://github.com/grpc/grpc/blob/master/doc/PROTOCOL-HTTP2.md#requests
     const NAME: &'static str;
+
+    /// The names of the methods of the service.
+    const METHODS: &'static [&'static str] = &[];
 }
@@ -1,35 +1,61 @@
 //! Server implementation and builder.
 
-use super::{Error, NamedService};
-use crate::codegen::{BoxMessage, BoxMessageStream, RequestExt, ResponseExt};
+use super::{
+    tls::{ClientHello, ServerTlsConfig},
+    Error, NamedService,
+};
+use crate::codegen::{BoxMessage, BoxMessageStream, RequestExt, ResponseExt, Trailers};
+use crate::reflection::ServerReflectionServer;
 use crate::sim::AppendMetadata;
 use crate::tower::layer::util::{Identity, Stack};
-use crate::{Request, Response, Status};
+use crate::{metadata::MetadataMap, service::Interceptor, Request, Response, Status};
 use async_stream::try_stream;
 use futures_util::{future::poll_fn, select_biased, FutureExt, StreamExt};
-use madsim::net::Endpoint;
+use madsim::{net::Endpoint, rand::Rng};
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap, VecDeque},
     future::{pending, Future},
     marker::PhantomData,
     net::SocketAddr,
+    sync::{Arc, Mutex},
     time::Duration,
 };
+use tokio::sync::Semaphore;
 use tonic::codegen::{http::uri::PathAndQuery, BoxFuture, Service};
-#[cfg(feature = "tls")]
-use tonic::transport::ServerTlsConfig;
 use tracing::*;
 
 /// A default batteries included `transport` server.
+///
+/// If the client of a unary or server streaming call disconnects before the handler
+/// returns, e.g. by dropping the future of the call, the handler future is dropped.
 #[derive(Clone, Debug)]
 pub struct Server<L = Identity> {
+    timeout: Option<Duration>,
+    interceptors: Vec<SharedInterceptor>,
+    tls: Option<ServerTlsConfig>,
     _mark: PhantomData<L>,
 }
 
 #[allow(clippy::derivable_impls)]
 impl Default for Server {
     fn default() -> Self {
-        Self { _mark: PhantomData }
+        Self {
+            timeout: None,
+            interceptors: vec![],
+            tls: None,
+            _mark: PhantomData,
+        }
+    }
+}
+
+/// An interceptor shared by all connections of a server.
+#[derive(Clone)]
+#[allow(clippy::type_complexity)]
+struct SharedInterceptor(Arc<Mutex<dyn FnMut(Request<()>) -> Result<Request<()>, Status> + Send>>);
+
+impl std::fmt::Debug for SharedInterceptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Interceptor")
     }
 }
 
@@ -57,6 +83,11 @@ impl<L> Server<L> {
         let router = Router {
             server: self.clone(),
             services: Default::default(),
+            methods: Default::default(),
+            reflection: false,
+            stream_faults: Default::default(),
+            call_outcomes: Default::default(),
+            concurrency_limits: Default::default(),
         };
         router.add_service(svc)
     }
@@ -64,14 +95,43 @@ impl<L> Server<L> {
     /// Set the Tower Layer all services will be wrapped in.
     pub fn layer<NewLayer>(self, _new_layer: NewLayer) -> Server<Stack<NewLayer, L>> {
         tracing::warn!("layer is unimplemented and ignored");
-        Server { _mark: PhantomData }
+        Server {
+            timeout: self.timeout,
+            interceptors: self.interceptors,
+            tls: self.tls,
+            _mark: PhantomData,
+        }
+    }
+
+    /// Add an interceptor applied to all inbound requests before dispatch.
+    ///
+    /// Interceptors run in the order they are added. If any of them returns an error,
+    /// the remaining ones and the handler are skipped, and the call fails with that status.
+    #[must_use]
+    #[allow(clippy::result_large_err)]
+    pub fn interceptor(mut self, mut interceptor: impl Interceptor + Send + 'static) -> Self {
+        let f = move |request| interceptor.call(request);
+        (self.interceptors).push(SharedInterceptor(Arc::new(Mutex::new(f))));
+        self
+    }
+
+    /// Apply the interceptors to the request in order, stopping at the first error.
+    #[allow(clippy::result_large_err)]
+    fn intercept<T>(&self, mut request: Request<T>) -> Result<Request<T>, Status> {
+        for f in &self.interceptors {
+            request = request.intercept(&mut &mut *f.0.lock().unwrap())?;
+        }
+        Ok(request)
     }
 
     /// Configure TLS for this server.
+    ///
+    /// Clients must then connect with a matching TLS configuration.
+    /// See [`ServerTlsConfig`] for details.
     #[cfg(feature = "tls")]
     #[cfg_attr(docsrs, doc(cfg(feature = "tls")))]
-    pub fn tls_config(self, _tls_config: ServerTlsConfig) -> Result<Self, Error> {
-        // ignore this setting
+    pub fn tls_config(mut self, tls_config: ServerTlsConfig) -> Result<Self, Error> {
+        self.tls = Some(tls_config);
         Ok(self)
     }
 
@@ -84,8 +144,8 @@ impl<L> Server<L> {
 
     /// Set a timeout on for all request handlers.
     #[must_use]
-    pub fn timeout(self, _timeout: Duration) -> Self {
-        // ignore this setting
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
         self
     }
 
@@ -156,7 +216,6 @@ impl<L> Server<L> {
 /// A stack based `Service` router.
 pub struct Router<L = Identity> {
     // TODO: support layers
-    #[allow(dead_code)]
     server: Server<L>,
 
     #[allow(clippy::type_complexity)]
@@ -172,6 +231,12 @@ pub struct Router<L = Identity> {
                 + 'static,
         >,
     >,
+    /// Service name -> method names.
+    methods: BTreeMap<&'static str, &'static [&'static str]>,
+    reflection: bool,
+    stream_faults: HashMap<&'static str, StreamFault>,
+    call_outcomes: HashMap<(&'static str, &'static str), VecDeque<Result<(), Status>>>,
+    concurrency_limits: HashMap<&'static str, Arc<Semaphore>>,
 }
 
 impl<L> Router<L> {
@@ -188,6 +253,45 @@ impl<L> Router<L> {
             + 'static,
     {
         self.services.insert(S::NAME, Box::new(svc));
+        self.methods.insert(S::NAME, S::METHODS);
+        self
+    }
+
+    /// Add the gRPC reflection service describing all services of this router.
+    ///
+    /// See [`reflection`](crate::reflection) for the supported requests.
+    pub fn add_reflection_service(mut self) -> Self {
+        self.reflection = true;
+        self
+    }
+
+    /// Inject faults into the server streaming responses of the service named `service`.
+    pub fn stream_fault(mut self, service: &'static str, fault: StreamFault) -> Self {
+        self.stream_faults.insert(service, fault);
+        self
+    }
+
+    /// Script the outcomes of calls to `method` of the service named `service`.
+    ///
+    /// Each matching call consumes one outcome in order. `Err(status)` fails the call
+    /// with `status` without invoking the handler, while `Ok(())` lets it through.
+    /// Calls are handled normally once the outcomes are exhausted.
+    pub fn call_outcomes(
+        mut self,
+        service: &'static str,
+        method: &'static str,
+        outcomes: impl IntoIterator<Item = Result<(), Status>>,
+    ) -> Self {
+        (self.call_outcomes.entry((service, method)).or_default()).extend(outcomes);
+        self
+    }
+
+    /// Limit the number of in-flight requests to the service named `service`.
+    ///
+    /// A request counts against the limit while its handler is running. Requests
+    /// exceeding the limit are rejected with `RESOURCE_EXHAUSTED`.
+    pub fn max_concurrent_requests(mut self, service: &'static str, limit: usize) -> Self {
+        (self.concurrency_limits).insert(service, Arc::new(Semaphore::new(limit)));
         self
     }
 
@@ -205,6 +309,10 @@ impl<L> Router<L> {
         addr: SocketAddr,
         signal: impl Future<Output = ()>,
     ) -> Result<(), Error> {
+        if self.reflection {
+            let svc = ServerReflectionServer::new(self.methods.clone());
+            self = self.add_service(svc);
+        }
         let ep = Endpoint::bind(addr).await.map_err(Error::from_source)?;
         let mut signal = Box::pin(signal).fuse();
         loop {
@@ -215,7 +323,19 @@ impl<L> Router<L> {
             };
             let msg = match rx.recv().await {
                 Ok(msg) => msg,
-                Err(_) => continue, // maybe handshake or error
+                Err(_) => continue,
+            };
+            // the first message of a channel is the handshake
+            let msg = match msg.downcast::<ClientHello>() {
+                Ok(hello) => {
+                    let ret = hello.handshake(self.server.tls.as_ref());
+                    if let Err(e) = &ret {
+                        debug!(?addr, "handshake failed: {e}");
+                    }
+                    madsim::task::spawn(async move { tx.send(Box::new(ret)).await });
+                    continue;
+                }
+                Err(msg) => msg,
             };
             let (path, server_streaming, mut request) = *msg
                 .downcast::<(PathAndQuery, bool, Request<BoxMessage>)>()
@@ -224,62 +344,137 @@ impl<L> Router<L> {
             debug!(parent: &span, "received");
 
             request.set_remote_addr(addr);
-            let request: Request<BoxMessageStream> = request.map(move |msg| {
-                if msg.downcast_ref::<()>().is_none() {
+            let request = match self.server.intercept(request) {
+                Ok(request) => request,
+                Err(err) => {
+                    debug!(parent: &span, "intercepted {:?}", err.code());
+                    madsim::task::spawn(send_error(tx, server_streaming, err));
+                    continue;
+                }
+            };
+            // the deadline is the smaller one of server timeout and `grpc-timeout` header
+            let timeout = match (self.server.timeout, parse_grpc_timeout(request.metadata())) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
+            // the channel of a single request is closed only when the client disconnects
+            let (request, disconnect): (Request<BoxMessageStream>, _) =
+                if request.get_ref().downcast_ref::<()>().is_none() {
                     // single request
-                    try_stream! { yield msg; }.boxed()
+                    let request = request.map(|msg| try_stream! { yield msg; }.boxed());
+                    (request, Some(rx))
                 } else {
                     // request stream
-                    try_stream! {
-                        while let Ok(msg) = rx.recv().await {
-                            yield msg;
+                    let request = request.map(move |_| {
+                        try_stream! {
+                            while let Ok(msg) = rx.recv().await {
+                                yield msg;
+                            }
                         }
-                    }
-                    .boxed()
-                }
-            });
+                        .boxed()
+                    });
+                    (request, None)
+                };
 
             // call the service in a new spawned task
             let svc_name = path.path().split('/').nth(1).unwrap();
             let Some(svc) = &mut self.services.get_mut(svc_name) else {
                 // return error Unimplemented
-                madsim::task::spawn(async move {
-                    let mut err = Status::unimplemented(format!("service not found: {path}"));
-                    err.metadata_mut().append_metadata();
-                    let msg: BoxMessage = if server_streaming {
-                        Box::new(Err(err) as Result<Response<()>, Status>)
-                    } else {
-                        Box::new(Err(err) as Result<Response<BoxMessage>, Status>)
-                    };
-                    _ = tx.send(msg).await;
-                });
+                let err = Status::unimplemented(format!("service not found: {path}"));
+                madsim::task::spawn(send_error(tx, server_streaming, err));
                 continue;
             };
+            // consume the next scripted outcome of this method
+            let method_name = path.path().split('/').nth(2).unwrap_or_default();
+            let outcome = (self.call_outcomes.iter_mut())
+                .find(|((s, m), _)| *s == svc_name && *m == method_name)
+                .and_then(|(_, outcomes)| outcomes.pop_front());
+            if let Some(Err(err)) = outcome {
+                debug!(parent: &span, "injected {:?}", err.code());
+                madsim::task::spawn(send_error(tx, server_streaming, err));
+                continue;
+            }
+            // reserve a slot for the request until the handler completes
+            let permit = match self.concurrency_limits.get(svc_name) {
+                Some(semaphore) => match semaphore.clone().try_acquire_owned() {
+                    Ok(permit) => Some(permit),
+                    Err(_) => {
+                        debug!(parent: &span, "too many in-flight requests");
+                        let err = Status::resource_exhausted("too many in-flight requests");
+                        madsim::task::spawn(send_error(tx, server_streaming, err));
+                        continue;
+                    }
+                },
+                None => None,
+            };
+            // decide whether to truncate the response stream
+            let truncate_after = (self.stream_faults.get(svc_name))
+                .filter(|fault| madsim::rand::thread_rng().gen_bool(fault.probability))
+                .map(|fault| (fault.after, fault.status.clone()));
             poll_fn(|cx| svc.poll_ready(cx)).await.unwrap();
             let rsp_future = svc.call((path, request));
             madsim::task::spawn(async move {
-                let mut result: Result<Response<BoxMessageStream>, Status> =
-                    rsp_future.instrument(span.clone()).await;
+                let rsp_future = rsp_future.instrument(span.clone());
+                // the handler future is dropped when the client disconnects
+                let rsp_future = async {
+                    let Some(mut rx) = disconnect else {
+                        return rsp_future.await;
+                    };
+                    select_biased! {
+                        result = rsp_future.fuse() => result,
+                        _ = async { while rx.recv().await.is_ok() {} }.fuse() => {
+                            debug!(parent: &span, "client disconnected");
+                            Err(Status::cancelled("client disconnected"))
+                        }
+                    }
+                };
+                let mut result: Result<Response<BoxMessageStream>, Status> = match timeout {
+                    // the handler future is dropped when the deadline expires
+                    Some(timeout) => match madsim::time::timeout(timeout, rsp_future).await {
+                        Ok(result) => result,
+                        Err(_) => Err(Status::deadline_exceeded("Timeout expired")),
+                    },
+                    None => rsp_future.await,
+                };
+                drop(permit);
                 result.append_metadata();
                 if server_streaming {
-                    let (header, stream) = match result {
+                    let (header, stream, trailers) = match result {
                         Ok(response) => {
-                            let (metadata, extensions, stream) = response.into_parts();
+                            let (metadata, mut extensions, stream) = response.into_parts();
+                            let trailers = extensions.remove::<Trailers>();
                             let header = Response::from_parts(metadata, extensions, ());
-                            (Ok(header), Some(stream))
+                            (Ok(header), Some(stream), trailers)
                         }
-                        Err(e) => (Err(e), None),
+                        Err(e) => (Err(e), None, None),
                     };
                     // send the header
                     tx.send(Box::new(header)).await?;
                     // send the stream
-                    let Some(mut stream) = stream else { return Ok::<(), std::io::Error>(()); };
+                    let Some(mut stream) = stream else {
+                        return Ok::<(), std::io::Error>(());
+                    };
                     let mut count = 0;
-                    while let Some(rsp) = stream.next().await {
+                    loop {
+                        if let Some((after, status)) = &truncate_after {
+                            if count == *after {
+                                debug!(parent: &span, "truncated after {count}");
+                                let rsp: Result<BoxMessage, Status> = Err(status.clone());
+                                tx.send(Box::new(rsp)).await?;
+                                return Ok(());
+                            }
+                        }
+                        let Some(rsp) = stream.next().await else {
+                            break;
+                        };
                         // rsp: Result<BoxMessage, Status>
                         tx.send(Box::new(rsp)).await?;
                         count += 1;
                     }
+                    // send the trailers
+                    if let Some(trailers) = trailers {
+                        tx.send(Box::new(trailers)).await?;
+                    }
                     debug!(parent: &span, "completed {count}");
                 } else {
                     let rsp: Result<Response<BoxMessage>, Status> = match result {
@@ -298,3 +493,67 @@ impl<L> Router<L> {
         }
     }
 }
+
+/// Send an error response to the client.
+async fn send_error(tx: madsim::net::Sender, server_streaming: bool, mut err: Status) {
+    err.metadata_mut().append_metadata();
+    let msg: BoxMessage = if server_streaming {
+        Box::new(Err(err) as Result<Response<()>, Status>)
+    } else {
+        Box::new(Err(err) as Result<Response<BoxMessage>, Status>)
+    };
+    _ = tx.send(msg).await;
+}
+
+/// Parse the `grpc-timeout` header.
+///
+/// Returns `None` if the header is missing or invalid.
+fn parse_grpc_timeout(metadata: &MetadataMap) -> Option<Duration> {
+    let s = metadata.get("grpc-timeout")?.to_str().ok()?;
+    if s.is_empty() || s.len() > 9 {
+        return None;
+    }
+    let (value, unit) = s.split_at(s.len() - 1);
+    let value: u64 = value.parse().ok()?;
+    let duration = match unit {
+        "H" => Duration::from_secs(value * 60 * 60),
+        "M" => Duration::from_secs(value * 60),
+        "S" => Duration::from_secs(value),
+        "m" => Duration::from_millis(value),
+        "u" => Duration::from_micros(value),
+        "n" => Duration::from_nanos(value),
+        _ => return None,
+    };
+    Some(duration)
+}
+
+/// A fault injected into server streaming responses.
+///
+/// When triggered, the response stream is terminated with the given [`Status`]
+/// after a number of messages have been sent.
+#[derive(Debug, Clone)]
+pub struct StreamFault {
+    after: usize,
+    status: Status,
+    probability: f64,
+}
+
+impl StreamFault {
+    /// Terminate every response stream with `status` after `after` messages.
+    pub fn new(after: usize, status: Status) -> Self {
+        StreamFault {
+            after,
+            status,
+            probability: 1.0,
+        }
+    }
+
+    /// Set the probability that a response stream is terminated.
+    ///
+    /// Defaults to 1.0.
+    pub fn probability(mut self, probability: f64) -> Self {
+        assert!((0.0..=1.0).contains(&probability), "invalid probability");
+        self.probability = probability;
+        self
+    }
+}
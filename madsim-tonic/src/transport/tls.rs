@@ -0,0 +1,175 @@
+//! Simulated TLS configuration.
+//!
+//! No real cryptography is involved. Certificates are treated as opaque identities:
+//! a certificate is trusted only if it is identical to the configured CA certificate.
+//! The handshake is performed when a channel connects, and fails if the two sides
+//! don't agree on whether to use TLS or don't trust each other's certificates.
+
+// the configuration methods are only exposed with the `tls` feature
+#![cfg_attr(not(feature = "tls"), allow(dead_code))]
+
+/// Represents a X509 certificate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Certificate {
+    pem: Vec<u8>,
+}
+
+impl Certificate {
+    /// Parse a PEM encoded X509 Certificate.
+    pub fn from_pem(pem: impl AsRef<[u8]>) -> Self {
+        let pem = pem.as_ref().into();
+        Self { pem }
+    }
+
+    /// Get a immutable reference to underlying certificate
+    pub fn get_ref(&self) -> &[u8] {
+        self.pem.as_slice()
+    }
+
+    /// Get a mutable reference to underlying certificate
+    pub fn get_mut(&mut self) -> &mut [u8] {
+        self.pem.as_mut()
+    }
+
+    /// Consumes `self`, returning the underlying certificate
+    pub fn into_inner(self) -> Vec<u8> {
+        self.pem
+    }
+}
+
+impl AsRef<[u8]> for Certificate {
+    fn as_ref(&self) -> &[u8] {
+        self.pem.as_ref()
+    }
+}
+
+/// Represents a private key and X509 certificate.
+#[derive(Debug, Clone)]
+pub struct Identity {
+    cert: Certificate,
+    #[allow(dead_code)]
+    key: Vec<u8>,
+}
+
+impl Identity {
+    /// Parse a PEM encoded certificate and private key.
+    ///
+    /// The provided cert must contain at least one PEM encoded certificate.
+    pub fn from_pem(cert: impl AsRef<[u8]>, key: impl AsRef<[u8]>) -> Self {
+        let cert = Certificate::from_pem(cert);
+        let key = key.as_ref().into();
+        Self { cert, key }
+    }
+}
+
+/// Configures TLS settings for servers.
+#[derive(Debug, Clone, Default)]
+pub struct ServerTlsConfig {
+    identity: Option<Identity>,
+    client_ca_root: Option<Certificate>,
+}
+
+impl ServerTlsConfig {
+    /// Creates a new `ServerTlsConfig`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the [`Identity`] of the server.
+    pub fn identity(self, identity: Identity) -> Self {
+        ServerTlsConfig {
+            identity: Some(identity),
+            ..self
+        }
+    }
+
+    /// Sets a certificate against which to validate client TLS certificates.
+    pub fn client_ca_root(self, cert: Certificate) -> Self {
+        ServerTlsConfig {
+            client_ca_root: Some(cert),
+            ..self
+        }
+    }
+}
+
+/// Configures TLS settings for endpoints.
+#[derive(Debug, Clone, Default)]
+pub struct ClientTlsConfig {
+    #[allow(dead_code)]
+    domain: Option<String>,
+    cert: Option<Certificate>,
+    identity: Option<Identity>,
+}
+
+impl ClientTlsConfig {
+    /// Creates a new `ClientTlsConfig`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the domain name against which to verify the server's TLS certificate.
+    ///
+    /// This setting is ignored in simulation.
+    pub fn domain_name(self, domain_name: impl Into<String>) -> Self {
+        ClientTlsConfig {
+            domain: Some(domain_name.into()),
+            ..self
+        }
+    }
+
+    /// Sets the CA Certificate against which to verify the server's TLS certificate.
+    ///
+    /// If not set, the server's certificate is not verified.
+    pub fn ca_certificate(self, ca_certificate: Certificate) -> Self {
+        ClientTlsConfig {
+            cert: Some(ca_certificate),
+            ..self
+        }
+    }
+
+    /// Sets the [`Identity`] to present to the server for client authentication.
+    pub fn identity(self, identity: Identity) -> Self {
+        ClientTlsConfig {
+            identity: Some(identity),
+            ..self
+        }
+    }
+}
+
+/// The first message sent by a client on a new channel.
+#[derive(Debug)]
+pub(crate) struct ClientHello {
+    pub tls: Option<ClientTlsConfig>,
+}
+
+impl ClientHello {
+    /// Performs the handshake with the server's TLS configuration.
+    ///
+    /// Returns the reason of the failure if any.
+    pub fn handshake(&self, server: Option<&ServerTlsConfig>) -> Result<(), String> {
+        let (client, server) = match (&self.tls, server) {
+            (None, None) => return Ok(()),
+            (Some(_), None) => return Err("server does not support TLS".into()),
+            (None, Some(_)) => return Err("server requires TLS".into()),
+            (Some(client), Some(server)) => (client, server),
+        };
+        // verify the server
+        let Some(identity) = &server.identity else {
+            return Err("server has no certificate".into());
+        };
+        if matches!(&client.cert, Some(ca) if *ca != identity.cert) {
+            return Err("invalid peer certificate: UnknownIssuer".into());
+        }
+        // verify the client
+        if let Some(ca) = &server.client_ca_root {
+            match &client.identity {
+                None => return Err("client certificate required".into()),
+                Some(identity) if identity.cert != *ca => {
+                    return Err("invalid client certificate: UnknownIssuer".into())
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
@@ -2,37 +2,41 @@
 
 use spin::{Mutex, RwLock};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt,
     io::{Error, ErrorKind, Result},
     path::{Path, PathBuf},
     sync::Arc,
+    time::SystemTime,
 };
 use tracing::*;
 
 use crate::{
     plugin::{node, simulator, Simulator},
-    rand::GlobalRng,
-    task::NodeId,
+    rand::{GlobalRng, Rng},
+    task::{NodeId, NodeInfo},
     time::TimeHandle,
     Config,
 };
 
 /// File system simulator.
 #[cfg_attr(docsrs, doc(cfg(madsim)))]
-#[derive(Default)]
 pub struct FsSim {
+    rand: GlobalRng,
     handles: Mutex<HashMap<NodeId, FsNodeHandle>>,
 }
 
 impl Simulator for FsSim {
-    fn new(_rand: &GlobalRng, _time: &TimeHandle, _config: &Config) -> Self {
-        Default::default()
+    fn new(rand: &GlobalRng, _time: &TimeHandle, _config: &Config) -> Self {
+        FsSim {
+            rand: rand.clone(),
+            handles: Default::default(),
+        }
     }
 
     fn create_node(&self, id: NodeId) {
         let mut handles = self.handles.lock();
-        handles.insert(id, FsNodeHandle::new());
+        handles.insert(id, FsNodeHandle::new(self.rand.clone()));
     }
 
     fn reset_node(&self, id: NodeId) {
@@ -48,8 +52,101 @@ impl FsSim {
     }
 
     /// Simulate a power failure. All data that does not reach the disk will be lost.
-    pub fn power_fail(&self, _id: NodeId) {
-        // TODO
+    ///
+    /// Creations, renames and removals of files since the last [`sync_dir`] of their
+    /// directories are rolled back. A file created and synced by [`sync_all`](File::sync_all)
+    /// survives.
+    ///
+    /// If torn writes are enabled, a random prefix of the last unsynced write to each file
+    /// may reach the disk. If write reordering is enabled, a random prefix of the unsynced
+    /// writes to each file may reach the disk.
+    ///
+    /// Afterwards reads return the data on disk: bytes written since the last sync read as
+    /// their synced values, or are beyond the end of file. Writes buffered in files opened
+    /// before the failure are discarded.
+    pub fn power_fail(&self, id: NodeId) {
+        debug!(node = %id, "power_fail");
+        let handle = self.get_node(id);
+        let torn_write = *handle.torn_write.lock();
+        let reorder = *handle.reorder_writes.lock();
+        let mut fs = handle.fs.lock();
+        let durable = handle.durable.lock();
+        *handle.power_failures.lock() += 1;
+        // iterate in a deterministic order so that the random choices are reproducible
+        let mut entries = durable.iter().collect::<Vec<_>>();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        let mut visited = HashSet::new();
+        for (_, inode) in entries {
+            // an inode with multiple links fails only once
+            if visited.insert(Arc::as_ptr(inode)) {
+                inode.power_fail(&mut self.rand.clone(), torn_write, reorder);
+            }
+        }
+        *fs = durable.clone();
+    }
+
+    /// Enable or disable torn writes on power failure of the node.
+    ///
+    /// When enabled, the last unsynced write to each file is partially persisted on
+    /// [`power_fail`](FsSim::power_fail), with the split point chosen randomly.
+    pub fn torn_write(&self, id: NodeId, enable: bool) {
+        *self.get_node(id).torn_write.lock() = enable;
+    }
+
+    /// Enable or disable reordering of unsynced writes across files on power failure of the node.
+    ///
+    /// When enabled, each file independently persists a random number of its writes since
+    /// the last [`sync_all`](File::sync_all) on [`power_fail`](FsSim::power_fail). Writes to
+    /// the same file persist in order, but a later write to one file may survive while an
+    /// earlier write to another file is lost. Only `sync_all` orders writes across files.
+    pub fn reorder_writes(&self, id: NodeId, enable: bool) {
+        *self.get_node(id).reorder_writes.lock() = enable;
+    }
+
+    /// Set the probability that a [`sync_all`](File::sync_all) on the node persists corrupted data.
+    ///
+    /// A corrupted sync flips a random bit in the data written since the last sync.
+    /// Readers will observe the corrupted data.
+    pub fn corrupt_on_sync(&self, id: NodeId, probability: f64) {
+        assert!((0.0..=1.0).contains(&probability));
+        *self.get_node(id).corrupt_rate.lock() = probability;
+    }
+
+    /// Set the probability that a [`sync_all`](File::sync_all) on the node fails.
+    ///
+    /// A failed sync returns an error of [`ErrorKind::Other`] without making any data
+    /// durable, so the data written since the last successful sync is still lost on
+    /// [`power_fail`](FsSim::power_fail).
+    pub fn fail_on_sync(&self, id: NodeId, probability: f64) {
+        assert!((0.0..=1.0).contains(&probability));
+        *self.get_node(id).sync_fail_rate.lock() = probability;
+    }
+
+    /// Enable or disable short writes on the node.
+    ///
+    /// When enabled, each [`write_at`](File::write_at) writes only a random non-empty prefix
+    /// of the buffer. [`write_all_at`](File::write_all_at) always writes the entire buffer.
+    pub fn short_writes(&self, id: NodeId, enable: bool) {
+        *self.get_node(id).short_writes.lock() = enable;
+    }
+
+    /// Enable or disable recording of the operations on files of the node.
+    ///
+    /// The recorded operations of a file can be taken by [`take_ops`](FsSim::take_ops).
+    pub fn record_ops(&self, id: NodeId, enable: bool) {
+        *self.get_node(id).record_ops.lock() = enable;
+    }
+
+    /// Take the operations recorded on the file since the last call, clearing the log.
+    pub fn take_ops(&self, id: NodeId, path: impl AsRef<Path>) -> Result<Vec<FileOp>> {
+        let path = path.as_ref();
+        let handle = self.get_node(id);
+        let fs = handle.fs.lock();
+        let inode = fs
+            .get(path)
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, format!("file not found: {path:?}")))?;
+        let ops = std::mem::take(&mut *inode.ops.lock());
+        Ok(ops)
     }
 
     /// Get the size of given file.
@@ -68,15 +165,60 @@ impl FsSim {
 #[derive(Clone)]
 struct FsNodeHandle {
     fs: Arc<Mutex<HashMap<PathBuf, Arc<INode>>>>,
+    /// The namespace that has been synced to disk.
+    durable: Arc<Mutex<HashMap<PathBuf, Arc<INode>>>>,
+    rand: GlobalRng,
+    /// The probability of corrupting data on sync.
+    corrupt_rate: Arc<Mutex<f64>>,
+    /// The probability of failing a sync.
+    sync_fail_rate: Arc<Mutex<f64>>,
+    /// Whether to persist a prefix of the last unsynced write on power failure.
+    torn_write: Arc<Mutex<bool>>,
+    /// Whether to persist a random prefix of the unsynced writes on power failure.
+    reorder_writes: Arc<Mutex<bool>>,
+    /// Whether `write_at` writes only a prefix of the buffer.
+    short_writes: Arc<Mutex<bool>>,
+    /// Whether to record the operations on files.
+    record_ops: Arc<Mutex<bool>>,
+    /// The number of power failures so far.
+    power_failures: Arc<Mutex<u64>>,
 }
 
 impl FsNodeHandle {
-    fn new() -> Self {
+    fn new(rand: GlobalRng) -> Self {
         FsNodeHandle {
             fs: Arc::new(Mutex::new(HashMap::new())),
+            durable: Arc::new(Mutex::new(HashMap::new())),
+            rand,
+            corrupt_rate: Arc::new(Mutex::new(0.0)),
+            sync_fail_rate: Arc::new(Mutex::new(0.0)),
+            torn_write: Arc::new(Mutex::new(false)),
+            reorder_writes: Arc::new(Mutex::new(false)),
+            short_writes: Arc::new(Mutex::new(false)),
+            record_ops: Arc::new(Mutex::new(false)),
+            power_failures: Arc::new(Mutex::new(0)),
         }
     }
 
+    /// Records an operation on the inode if recording is enabled.
+    fn record(&self, inode: &INode, op: FileOp) {
+        if *self.record_ops.lock() {
+            inode.ops.lock().push(op);
+        }
+    }
+
+    /// Returns whether the next sync should corrupt data.
+    fn should_corrupt(&self) -> bool {
+        let rate = *self.corrupt_rate.lock();
+        rate > 0.0 && self.rand.clone().gen_bool(rate)
+    }
+
+    /// Returns whether the next sync should fail.
+    fn should_fail_sync(&self) -> bool {
+        let rate = *self.sync_fail_rate.lock();
+        rate > 0.0 && self.rand.clone().gen_bool(rate)
+    }
+
     fn current() -> Self {
         simulator::<FsSim>().get_node(node())
     }
@@ -85,29 +227,98 @@ impl FsNodeHandle {
         let path = path.as_ref();
         trace!(?path, "open file");
         let fs = self.fs.lock();
+        check_not_dir(&fs, path)?;
         let inode = fs
             .get(path)
             .ok_or_else(|| Error::new(ErrorKind::NotFound, format!("file not found: {path:?}")))?
             .clone();
-        Ok(File {
-            inode,
-            can_write: false,
-        })
+        Ok(File::new(inode, self.clone(), false))
     }
 
     async fn create(&self, path: impl AsRef<Path>) -> Result<File> {
         let path = path.as_ref();
         trace!(?path, "create file");
         let mut fs = self.fs.lock();
+        check_not_dir(&fs, path)?;
         let inode = fs
             .entry(path.into())
-            .and_modify(|inode| inode.truncate())
+            .and_modify(|inode| {
+                inode.truncate();
+                self.record(inode, FileOp::SetLen(0));
+            })
             .or_insert_with(|| Arc::new(INode::new(path)))
             .clone();
-        Ok(File {
-            inode,
-            can_write: true,
-        })
+        Ok(File::new(inode, self.clone(), true))
+    }
+
+    async fn hard_link(&self, original: impl AsRef<Path>, link: impl AsRef<Path>) -> Result<()> {
+        let original = original.as_ref();
+        let link = link.as_ref();
+        trace!(?original, ?link, "hard link");
+        let mut fs = self.fs.lock();
+        let inode = fs
+            .get(original)
+            .ok_or_else(|| {
+                Error::new(ErrorKind::NotFound, format!("file not found: {original:?}"))
+            })?
+            .clone();
+        if fs.contains_key(link) {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                format!("file already exists: {link:?}"),
+            ));
+        }
+        fs.insert(link.into(), inode);
+        Ok(())
+    }
+
+    async fn remove_file(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        trace!(?path, "remove file");
+        // the data is dropped with the last link and open file
+        self.fs
+            .lock()
+            .remove(path)
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, format!("file not found: {path:?}")))?;
+        Ok(())
+    }
+
+    async fn rename(&self, from: impl AsRef<Path>, to: impl AsRef<Path>) -> Result<()> {
+        let from = from.as_ref();
+        let to = to.as_ref();
+        trace!(?from, ?to, "rename");
+        let mut fs = self.fs.lock();
+        let inode = fs
+            .remove(from)
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, format!("file not found: {from:?}")))?;
+        fs.insert(to.into(), inode);
+        Ok(())
+    }
+
+    async fn sync_dir(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        trace!(?path, "sync dir");
+        let dir = normalize_dir(path);
+        let fs = self.fs.lock();
+        let mut durable = self.durable.lock();
+        durable.retain(|p, _| parent_dir(p) != dir || fs.contains_key(p));
+        for (p, inode) in fs.iter() {
+            if parent_dir(p) == dir {
+                durable.insert(p.clone(), inode.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// Makes all names of the inode durable.
+    fn sync_links(&self, inode: &Arc<INode>) {
+        let fs = self.fs.lock();
+        let mut durable = self.durable.lock();
+        for (p, i) in fs.iter() {
+            if Arc::ptr_eq(i, inode) {
+                durable.insert(p.clone(), i.clone());
+            }
+        }
     }
 
     async fn metadata(&self, path: impl AsRef<Path>) -> Result<Metadata> {
@@ -120,9 +331,59 @@ impl FsNodeHandle {
     }
 }
 
+/// Returns the directory containing the path. Files without a directory are in `""`.
+fn parent_dir(path: &Path) -> &Path {
+    path.parent().unwrap_or(Path::new(""))
+}
+
+/// Returns an error if the path names a directory, i.e. the current directory or a
+/// directory containing any file.
+fn check_not_dir(fs: &HashMap<PathBuf, Arc<INode>>, path: &Path) -> Result<()> {
+    let dir = normalize_dir(path);
+    if dir == Path::new("") || fs.keys().any(|p| p != dir && p.starts_with(dir)) {
+        return Err(Error::other(format!("is a directory: {path:?}")));
+    }
+    Ok(())
+}
+
+/// Normalizes a directory path so that `"."` refers to `""`.
+fn normalize_dir(path: &Path) -> &Path {
+    if path == Path::new(".") {
+        Path::new("")
+    } else {
+        path
+    }
+}
+
+/// An operation on a file, as recorded by [`FsSim::record_ops`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileOp {
+    /// Data was written at the offset.
+    ///
+    /// Writes are recorded when they are flushed from the buffer of the [`File`].
+    Write {
+        /// The offset of the write.
+        offset: u64,
+        /// The length of the data.
+        len: u64,
+    },
+    /// The file was truncated or extended to the size.
+    SetLen(u64),
+    /// The file was synced to disk.
+    Sync,
+}
+
 struct INode {
     path: PathBuf,
     data: RwLock<Vec<u8>>,
+    /// Data that has been synced to disk.
+    synced: RwLock<Vec<u8>>,
+    /// Writes since the last sync in order: (offset, data).
+    unsynced: Mutex<Vec<(u64, Vec<u8>)>>,
+    /// The last modification time observed by the node.
+    modified: Mutex<SystemTime>,
+    /// The recorded operations since the last take.
+    ops: Mutex<Vec<FileOp>>,
 }
 
 impl INode {
@@ -130,24 +391,109 @@ impl INode {
         INode {
             path: path.into(),
             data: RwLock::new(Vec::new()),
+            synced: RwLock::new(Vec::new()),
+            unsynced: Mutex::new(Vec::new()),
+            modified: Mutex::new(now()),
+            ops: Mutex::new(Vec::new()),
         }
     }
 
     fn truncate(&self) {
-        self.data.write().clear();
+        self.set_len(0);
+    }
+
+    fn set_len(&self, size: u64) {
+        *self.modified.lock() = now();
+        self.data.write().resize(size as usize, 0);
+        // writes before resizing can no longer be replayed
+        self.unsynced.lock().clear();
+    }
+
+    fn write_at(&self, buf: &[u8], offset: u64) {
+        *self.modified.lock() = now();
+        self.unsynced.lock().push((offset, buf.to_vec()));
+        self.write_data_at(buf, offset);
+    }
+
+    fn write_data_at(&self, buf: &[u8], offset: u64) {
+        let mut data = self.data.write();
+        let offset = offset as usize;
+        // writing past the end of file fills the gap with zeros
+        if data.len() < offset + buf.len() {
+            data.resize(offset + buf.len(), 0);
+        }
+        data[offset..offset + buf.len()].copy_from_slice(buf);
+    }
+
+    fn sync(&self) {
+        *self.synced.write() = self.data.read().clone();
+        self.unsynced.lock().clear();
+    }
+
+    /// Flips a random bit in the data that has changed since the last sync.
+    fn corrupt_unsynced(&self, rng: &mut impl Rng) {
+        let mut data = self.data.write();
+        let synced = self.synced.read();
+        let changed = |i: &usize| synced.get(*i) != Some(&data[*i]);
+        let Some(begin) = (0..data.len()).find(changed) else {
+            return;
+        };
+        let end = (begin..data.len()).rev().find(changed).unwrap() + 1;
+        let i = rng.gen_range(begin..end);
+        drop(synced);
+        data[i] ^= 1 << rng.gen_range(0..8);
+        trace!(path = ?self.path, offset = i, "corrupt data on sync");
+    }
+
+    /// Reverts to the synced data.
+    ///
+    /// If `reorder` is set, a random number of the unsynced writes are persisted in order.
+    /// If `torn_write` is set, a random prefix of the next write is persisted, which is the
+    /// last write when `reorder` is not set.
+    fn power_fail(&self, rng: &mut impl Rng, torn_write: bool, reorder: bool) {
+        *self.data.write() = self.synced.read().clone();
+        let mut unsynced = std::mem::take(&mut *self.unsynced.lock());
+        if reorder {
+            let n = rng.gen_range(0..=unsynced.len());
+            trace!(path = ?self.path, n, total = unsynced.len(), "persist unsynced writes");
+            for (offset, buf) in unsynced.drain(..n) {
+                self.write_data_at(&buf, offset);
+            }
+        } else if !unsynced.is_empty() {
+            unsynced.drain(..unsynced.len() - 1);
+        }
+        if let Some((offset, buf)) = unsynced.first().filter(|_| torn_write) {
+            if !buf.is_empty() {
+                let len = rng.gen_range(0..buf.len());
+                trace!(path = ?self.path, offset, len, "torn write");
+                self.write_data_at(&buf[..len], *offset);
+            }
+        }
+        self.sync();
     }
 
     fn metadata(&self) -> Metadata {
         Metadata {
             len: self.data.read().len() as u64,
+            modified: *self.modified.lock(),
         }
     }
 }
 
 /// A reference to an open file on the filesystem.
+///
+/// Writes are buffered in the file until [`flush`](File::flush) or drop.
 pub struct File {
     inode: Arc<INode>,
+    /// The file system of the node.
+    handle: FsNodeHandle,
     can_write: bool,
+    /// The node that opened this file.
+    node: Arc<NodeInfo>,
+    /// Buffered writes: (offset, data).
+    buffer: Mutex<Vec<(u64, Vec<u8>)>>,
+    /// The number of power failures when the file was opened.
+    power_failures: u64,
 }
 
 impl fmt::Debug for File {
@@ -159,7 +505,21 @@ impl fmt::Debug for File {
 }
 
 impl File {
+    fn new(inode: Arc<INode>, handle: FsNodeHandle, can_write: bool) -> Self {
+        let power_failures = *handle.power_failures.lock();
+        File {
+            inode,
+            handle,
+            can_write,
+            node: crate::context::current_task().node.clone(),
+            buffer: Mutex::new(Vec::new()),
+            power_failures,
+        }
+    }
+
     /// Attempts to open a file in read-only mode.
+    ///
+    /// Returns an error of [`ErrorKind::Other`] if the path is a directory.
     pub async fn open(path: impl AsRef<Path>) -> Result<File> {
         let handle = FsNodeHandle::current();
         handle.open(path).await
@@ -168,6 +528,7 @@ impl File {
     /// Opens a file in write-only mode.
     ///
     /// This function will create a file if it does not exist, and will truncate it if it does.
+    /// Returns an error of [`ErrorKind::Other`] if the path is a directory.
     pub async fn create(path: impl AsRef<Path>) -> Result<File> {
         let handle = FsNodeHandle::current();
         handle.create(path).await
@@ -176,11 +537,39 @@ impl File {
     /// Reads a number of bytes starting from a given offset.
     #[instrument(skip(buf), fields(len = buf.len()))]
     pub async fn read_at(&self, buf: &mut [u8], offset: u64) -> Result<usize> {
+        self.flush_buffer();
         let data = self.inode.data.read();
-        let end = data.len().min(offset as usize + buf.len());
-        let len = end - offset as usize;
-        buf[..len].copy_from_slice(&data[offset as usize..end]);
+        // reading at or past the end of file returns 0 bytes
+        let offset = data.len().min(offset.try_into().unwrap_or(usize::MAX));
+        let end = data.len().min(offset.saturating_add(buf.len()));
+        let len = end - offset;
+        buf[..len].copy_from_slice(&data[offset..end]);
+        // TODO: random delay
+        Ok(len)
+    }
+
+    /// Reads all bytes of the file from the beginning.
+    ///
+    /// Buffered writes of this file are flushed first.
+    #[instrument]
+    pub async fn read_to_end(&self) -> Result<Vec<u8>> {
+        self.flush_buffer();
+        let data = self.inode.data.read().clone();
         // TODO: random delay
+        Ok(data)
+    }
+
+    /// Writes a number of bytes starting from a given offset. Returns the number of bytes written.
+    ///
+    /// If short writes are enabled by [`FsSim::short_writes`], only a random non-empty prefix
+    /// of the buffer is written.
+    #[instrument(skip(buf), fields(len = buf.len()))]
+    pub async fn write_at(&self, buf: &[u8], offset: u64) -> Result<usize> {
+        let mut len = buf.len();
+        if len > 1 && *self.handle.short_writes.lock() {
+            len = self.handle.rand.clone().gen_range(1..=len);
+        }
+        self.write_all_at(&buf[..len], offset).await?;
         Ok(len)
     }
 
@@ -193,30 +582,50 @@ impl File {
                 "the file is read only",
             ));
         }
-        let mut data = self.inode.data.write();
-        let end = data.len().min(offset as usize + buf.len());
-        let len = end - offset as usize;
-        data[offset as usize..end].copy_from_slice(&buf[..len]);
-        if len < buf.len() {
-            data.extend_from_slice(&buf[len..]);
-        }
+        self.buffer.lock().push((offset, buf.to_vec()));
         // TODO: random delay
-        // TODO: simulate buffer, write will not take effect until flush or close
         Ok(())
     }
 
     /// Truncates or extends the underlying file, updating the size of this file to become `size`.
     #[instrument]
     pub async fn set_len(&self, size: u64) -> Result<()> {
-        let mut data = self.inode.data.write();
-        data.resize(size as usize, 0);
+        self.flush_buffer();
+        self.inode.set_len(size);
+        self.handle.record(&self.inode, FileOp::SetLen(size));
         // TODO: random delay
         Ok(())
     }
 
+    /// Flushes buffered writes to the file.
+    ///
+    /// The data is visible to other handles of the file, but not durable until
+    /// [`sync_all`](File::sync_all). It will be lost on power failure.
+    #[instrument]
+    pub async fn flush(&self) -> Result<()> {
+        self.flush_buffer();
+        Ok(())
+    }
+
     /// Attempts to sync all OS-internal metadata to disk.
+    ///
+    /// All data written before this call survives power failure, and so do the names of
+    /// the file. Removals and renames away from the file need a [`sync_dir`].
+    ///
+    /// Returns an error if the sync fails by [`FsSim::fail_on_sync`]. Nothing is made
+    /// durable in this case.
     #[instrument]
     pub async fn sync_all(&self) -> Result<()> {
+        self.flush_buffer();
+        if self.handle.should_fail_sync() {
+            return Err(Error::other(format!("sync failed: {:?}", self.inode.path)));
+        }
+        if self.handle.should_corrupt() {
+            self.inode.corrupt_unsynced(&mut self.handle.rand.clone());
+        }
+        self.inode.sync();
+        self.handle.record(&self.inode, FileOp::Sync);
+        self.handle.sync_links(&self.inode);
         // TODO: random delay
         Ok(())
     }
@@ -224,8 +633,43 @@ impl File {
     /// Queries metadata about the underlying file.
     #[instrument]
     pub async fn metadata(&self) -> Result<Metadata> {
+        self.flush_buffer();
         Ok(self.inode.metadata())
     }
+
+    /// Returns the current length of the file, in bytes.
+    ///
+    /// Buffered writes of this file are flushed first.
+    #[instrument]
+    pub async fn len(&self) -> Result<u64> {
+        self.flush_buffer();
+        Ok(self.inode.data.read().len() as u64)
+    }
+
+    fn flush_buffer(&self) {
+        let mut buffer = self.buffer.lock();
+        // buffered writes are lost on power failure
+        if *self.handle.power_failures.lock() != self.power_failures {
+            buffer.clear();
+            return;
+        }
+        for (offset, buf) in buffer.drain(..) {
+            self.inode.write_at(&buf, offset);
+            let len = buf.len() as u64;
+            self.handle
+                .record(&self.inode, FileOp::Write { offset, len });
+        }
+    }
+}
+
+impl Drop for File {
+    fn drop(&mut self) {
+        // buffered writes are lost if the process is killed
+        if self.node.is_killed() {
+            return;
+        }
+        self.flush_buffer();
+    }
 }
 
 /// Read the entire contents of a file into a bytes vector.
@@ -237,6 +681,48 @@ pub async fn read(path: impl AsRef<Path>) -> Result<Vec<u8>> {
     Ok(data)
 }
 
+/// Writes a slice as the entire contents of a file.
+///
+/// This function will create a file if it does not exist, and will entirely replace its
+/// contents if it does. The contents are not durable until synced.
+pub async fn write(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> Result<()> {
+    let file = File::create(path).await?;
+    file.write_all_at(contents.as_ref(), 0).await?;
+    file.flush().await
+}
+
+/// Creates a new hard link on the filesystem.
+///
+/// The `link` path will be a link pointing to the `original` path. Writes through either
+/// path are visible through the other.
+pub async fn hard_link(original: impl AsRef<Path>, link: impl AsRef<Path>) -> Result<()> {
+    let handle = FsNodeHandle::current();
+    handle.hard_link(original, link).await
+}
+
+/// Removes a file from the filesystem.
+///
+/// The data is not removed until the last link to it is removed.
+pub async fn remove_file(path: impl AsRef<Path>) -> Result<()> {
+    let handle = FsNodeHandle::current();
+    handle.remove_file(path).await
+}
+
+/// Renames a file, replacing the original file if `to` already exists.
+///
+/// The rename is not durable until the directories are synced by [`sync_dir`].
+pub async fn rename(from: impl AsRef<Path>, to: impl AsRef<Path>) -> Result<()> {
+    let handle = FsNodeHandle::current();
+    handle.rename(from, to).await
+}
+
+/// Syncs a directory so that creations, renames and removals of files in it survive
+/// power failure.
+pub async fn sync_dir(path: impl AsRef<Path>) -> Result<()> {
+    let handle = FsNodeHandle::current();
+    handle.sync_dir(path).await
+}
+
 /// Given a path, query the file system to get information about a file, directory, etc.
 pub async fn metadata(path: impl AsRef<Path>) -> Result<Metadata> {
     let handle = FsNodeHandle::current();
@@ -246,6 +732,7 @@ pub async fn metadata(path: impl AsRef<Path>) -> Result<Metadata> {
 /// Metadata information about a file.
 pub struct Metadata {
     len: u64,
+    modified: SystemTime,
 }
 
 impl Metadata {
@@ -254,12 +741,24 @@ impl Metadata {
     pub fn len(&self) -> u64 {
         self.len
     }
+
+    /// Returns the last modification time, as observed by the clock of the node
+    /// that modified the file.
+    pub fn modified(&self) -> Result<SystemTime> {
+        Ok(self.modified)
+    }
+}
+
+/// Returns the current time of the node.
+fn now() -> SystemTime {
+    TimeHandle::try_current().map_or_else(SystemTime::now, |time| time.now_time())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::runtime::Runtime;
+    use std::time::Duration;
 
     #[test]
     fn create_open_read_write() {
@@ -293,4 +792,519 @@ mod tests {
         });
         runtime.block_on(f).unwrap();
     }
+
+    #[test]
+    fn read_to_end_and_write() {
+        let runtime = Runtime::new();
+        let node = runtime.create_node().build();
+        let f = node.spawn(async move {
+            assert_eq!(
+                File::open("file").await.err().unwrap().kind(),
+                ErrorKind::NotFound
+            );
+            write("file", b"hello").await.unwrap();
+            assert_eq!(read("file").await.unwrap(), b"hello");
+
+            // buffered writes of the file are read
+            let file = File::create("file").await.unwrap();
+            assert_eq!(file.read_to_end().await.unwrap(), b"");
+            file.write_all_at(b"world", 0).await.unwrap();
+            assert_eq!(file.read_to_end().await.unwrap(), b"world");
+            drop(file);
+
+            // write should replace the existing contents
+            write("file", b"hi").await.unwrap();
+            let rofile = File::open("file").await.unwrap();
+            assert_eq!(rofile.read_to_end().await.unwrap(), b"hi");
+
+            // writing to a directory should fail
+            write("dir/file", b"").await.unwrap();
+            assert_eq!(
+                write("dir", b"").await.err().unwrap().kind(),
+                ErrorKind::Other
+            );
+        });
+        runtime.block_on(f).unwrap();
+    }
+
+    #[test]
+    fn fail_on_sync() {
+        let runtime = Runtime::new();
+        let node = runtime.create_node().build();
+        let id = node.id();
+        let f = node.spawn(async move {
+            let file = File::create("file").await.unwrap();
+            file.write_all_at(b"hello", 0).await.unwrap();
+            file.sync_all().await.unwrap();
+
+            simulator::<FsSim>().fail_on_sync(id, 1.0);
+            file.write_all_at(b"world", 5).await.unwrap();
+            let err = file.sync_all().await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::Other);
+            // the data is visible but not durable
+            assert_eq!(file.read_to_end().await.unwrap(), b"helloworld");
+            simulator::<FsSim>().power_fail(id);
+            assert_eq!(read("file").await.unwrap(), b"hello");
+
+            simulator::<FsSim>().fail_on_sync(id, 0.0);
+            let file = File::create("file").await.unwrap();
+            file.write_all_at(b"world", 0).await.unwrap();
+            file.sync_all().await.unwrap();
+            simulator::<FsSim>().power_fail(id);
+            assert_eq!(read("file").await.unwrap(), b"world");
+        });
+        runtime.block_on(f).unwrap();
+    }
+
+    #[test]
+    fn len() {
+        let runtime = Runtime::new();
+        let node = runtime.create_node().build();
+        let f = node.spawn(async move {
+            let file = File::create("file").await.unwrap();
+            assert_eq!(file.len().await.unwrap(), 0);
+            file.write_all_at(b"hello", 0).await.unwrap();
+            assert_eq!(file.len().await.unwrap(), 5);
+            // writing past the end extends the file
+            file.write_all_at(b"world", 10).await.unwrap();
+            assert_eq!(file.len().await.unwrap(), 15);
+
+            file.set_len(3).await.unwrap();
+            assert_eq!(file.len().await.unwrap(), 3);
+            file.set_len(8).await.unwrap();
+            assert_eq!(file.len().await.unwrap(), 8);
+
+            // other handles see the same length
+            let rofile = File::open("file").await.unwrap();
+            assert_eq!(rofile.len().await.unwrap(), 8);
+        });
+        runtime.block_on(f).unwrap();
+    }
+
+    #[test]
+    fn record_ops() {
+        let runtime = Runtime::new();
+        let node = runtime.create_node().build();
+        let id = node.id();
+        let f = node.spawn(async move {
+            let file = File::create("file").await.unwrap();
+            file.write_all_at(b"hello", 0).await.unwrap();
+            file.sync_all().await.unwrap();
+            // not recorded until enabled
+            assert_eq!(simulator::<FsSim>().take_ops(id, "file").unwrap(), []);
+
+            simulator::<FsSim>().record_ops(id, true);
+            file.write_all_at(b"world", 5).await.unwrap();
+            file.sync_all().await.unwrap();
+            file.write_all_at(b"!", 10).await.unwrap();
+            file.set_len(8).await.unwrap();
+            drop(file);
+            File::create("file").await.unwrap();
+            assert_eq!(
+                simulator::<FsSim>().take_ops(id, "file").unwrap(),
+                [
+                    FileOp::Write { offset: 5, len: 5 },
+                    FileOp::Sync,
+                    FileOp::Write { offset: 10, len: 1 },
+                    FileOp::SetLen(8),
+                    FileOp::SetLen(0),
+                ]
+            );
+            // the log is cleared on take
+            assert_eq!(simulator::<FsSim>().take_ops(id, "file").unwrap(), []);
+
+            assert_eq!(
+                simulator::<FsSim>()
+                    .take_ops(id, "missing")
+                    .unwrap_err()
+                    .kind(),
+                ErrorKind::NotFound
+            );
+        });
+        runtime.block_on(f).unwrap();
+    }
+
+    #[test]
+    fn read_at_eof() {
+        let runtime = Runtime::new();
+        let node = runtime.create_node().build();
+        let f = node.spawn(async move {
+            let file = File::create("file").await.unwrap();
+            file.write_all_at(b"hello", 0).await.unwrap();
+
+            let mut buf = [0u8; 10];
+            assert_eq!(file.read_at(&mut buf, 5).await.unwrap(), 0);
+            assert_eq!(file.read_at(&mut buf, 100).await.unwrap(), 0);
+            assert_eq!(file.read_at(&mut buf, u64::MAX).await.unwrap(), 0);
+        });
+        runtime.block_on(f).unwrap();
+    }
+
+    #[test]
+    fn modified_time_with_clock_skew() {
+        let runtime = Runtime::new();
+        let node1 = runtime.create_node().build();
+        let node2 = runtime.create_node().build();
+        let f = async move {
+            let skew = Duration::from_secs(60 * 60);
+            TimeHandle::current().set_clock_skew(node2.id(), skew);
+            let write = || async {
+                let file = File::create("file").await.unwrap();
+                file.write_all_at(b"hello", 0).await.unwrap();
+                file.flush().await.unwrap();
+                file.metadata().await.unwrap().modified().unwrap()
+            };
+            let t1 = node1.spawn(write()).await.unwrap();
+            let t2 = node2.spawn(write()).await.unwrap();
+            let diff = t2.duration_since(t1).unwrap();
+            assert!(diff >= skew && diff < skew + Duration::from_secs(1));
+        };
+        runtime.block_on(f);
+    }
+
+    #[test]
+    fn corrupt_on_sync() {
+        let runtime = Runtime::new();
+        let node = runtime.create_node().build();
+        let id = node.id();
+        let f = node.spawn(async move {
+            simulator::<FsSim>().corrupt_on_sync(id, 1.0);
+            let file = File::create("file").await.unwrap();
+            file.write_all_at(b"hello", 0).await.unwrap();
+            file.sync_all().await.unwrap();
+            let data = read("file").await.unwrap();
+            assert_ne!(data, b"hello");
+            // exactly one bit is flipped
+            let diff: u32 = (data.iter().zip(b"hello"))
+                .map(|(a, b)| (a ^ b).count_ones())
+                .sum();
+            assert_eq!(diff, 1);
+
+            // synced data is not corrupted again
+            simulator::<FsSim>().corrupt_on_sync(id, 0.0);
+            file.write_all_at(b" world", 5).await.unwrap();
+            file.sync_all().await.unwrap();
+            simulator::<FsSim>().power_fail(id);
+            assert_eq!(read("file").await.unwrap()[..5], data);
+            assert_eq!(&read("file").await.unwrap()[5..], b" world");
+        });
+        runtime.block_on(f).unwrap();
+    }
+
+    #[test]
+    fn read_across_synced_boundary() {
+        let runtime = Runtime::new();
+        let node = runtime.create_node().build();
+        let id = node.id();
+        let f = node.spawn(async move {
+            let file = File::create("file").await.unwrap();
+            file.write_all_at(b"0123456789", 0).await.unwrap();
+            file.sync_all().await.unwrap();
+            // overwrite the tail and extend the file without syncing
+            file.write_all_at(b"abcdefgh", 6).await.unwrap();
+            let mut buf = [0; 8];
+            assert_eq!(file.read_at(&mut buf, 4).await.unwrap(), 8);
+            assert_eq!(&buf, b"45abcdef");
+
+            // the synced bytes are read with the old values of the unsynced part
+            simulator::<FsSim>().power_fail(id);
+            let mut buf = [0; 8];
+            assert_eq!(file.read_at(&mut buf, 4).await.unwrap(), 6);
+            assert_eq!(&buf[..6], b"456789");
+            let file = File::open("file").await.unwrap();
+            assert_eq!(file.read_at(&mut buf, 4).await.unwrap(), 6);
+            assert_eq!(&buf[..6], b"456789");
+        });
+        runtime.block_on(f).unwrap();
+    }
+
+    #[test]
+    fn read_across_synced_boundary_with_hole() {
+        let runtime = Runtime::new();
+        let node = runtime.create_node().build();
+        let id = node.id();
+        let f = node.spawn(async move {
+            let file = File::create("file").await.unwrap();
+            file.write_all_at(b"hello", 0).await.unwrap();
+            file.set_len(10).await.unwrap();
+            file.sync_all().await.unwrap();
+            file.write_all_at(b"world", 5).await.unwrap();
+            file.flush().await.unwrap();
+
+            // the unsynced part of the range reads as zeros
+            simulator::<FsSim>().power_fail(id);
+            let mut buf = [0xff; 10];
+            assert_eq!(file.read_at(&mut buf, 0).await.unwrap(), 10);
+            assert_eq!(&buf, b"hello\0\0\0\0\0");
+        });
+        runtime.block_on(f).unwrap();
+    }
+
+    #[test]
+    fn buffered_writes_lost_on_power_fail() {
+        let runtime = Runtime::new();
+        let node = runtime.create_node().build();
+        let id = node.id();
+        let f = node.spawn(async move {
+            let file = File::create("file").await.unwrap();
+            file.write_all_at(b"hello", 0).await.unwrap();
+            file.sync_all().await.unwrap();
+            file.write_all_at(b"HELLO world", 0).await.unwrap();
+
+            // the buffered write is not flushed after the power failure
+            simulator::<FsSim>().power_fail(id);
+            let mut buf = [0; 11];
+            assert_eq!(file.read_at(&mut buf, 0).await.unwrap(), 5);
+            assert_eq!(&buf[..5], b"hello");
+            drop(file);
+            assert_eq!(read("file").await.unwrap(), b"hello");
+        });
+        runtime.block_on(f).unwrap();
+    }
+
+    #[test]
+    fn torn_write() {
+        let runtime = Runtime::new();
+        let node = runtime.create_node().build();
+        let id = node.id();
+        let f = node.spawn(async move {
+            simulator::<FsSim>().torn_write(id, true);
+            let file = File::create("file").await.unwrap();
+            file.write_all_at(b"hello", 0).await.unwrap();
+            file.sync_all().await.unwrap();
+            file.write_all_at(b" world", 5).await.unwrap();
+            file.write_all_at(b"0123456789", 11).await.unwrap();
+            drop(file);
+
+            // only a strict prefix of the last write is persisted
+            simulator::<FsSim>().power_fail(id);
+            let data = read("file").await.unwrap();
+            assert!(data.len() < 21);
+            let mut expected = b"hello".to_vec();
+            if data.len() > 5 {
+                expected.resize(11, 0);
+                expected.extend_from_slice(&b"0123456789"[..data.len() - 11]);
+            }
+            assert_eq!(data, expected);
+        });
+        runtime.block_on(f).unwrap();
+    }
+
+    #[test]
+    fn reorder_writes() {
+        fn run(seed: u64) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+            let runtime = Runtime::with_seed_and_config(seed, Config::default());
+            let node = runtime.create_node().build();
+            let id = node.id();
+            let f = node.spawn(async move {
+                simulator::<FsSim>().reorder_writes(id, true);
+                let synced = File::create("synced").await.unwrap();
+                let a = File::create("a").await.unwrap();
+                let b = File::create("b").await.unwrap();
+                super::sync_dir(".").await.unwrap();
+                // b is written before a, but a may survive while b is lost
+                for (i, c) in b"123".iter().enumerate() {
+                    b.write_all_at(&[*c], i as u64).await.unwrap();
+                    b.flush().await.unwrap();
+                    a.write_all_at(&[*c], i as u64).await.unwrap();
+                    a.flush().await.unwrap();
+                }
+                synced.write_all_at(b"123", 0).await.unwrap();
+                synced.sync_all().await.unwrap();
+
+                simulator::<FsSim>().power_fail(id);
+                let synced = read("synced").await.unwrap();
+                (synced, read("a").await.unwrap(), read("b").await.unwrap())
+            });
+            runtime.block_on(f).unwrap()
+        }
+        let mut reordered = false;
+        for seed in 0..50 {
+            let (synced, a, b) = run(seed);
+            assert_eq!(synced, b"123");
+            // writes to the same file persist in order
+            assert!(b"123".starts_with(&a));
+            assert!(b"123".starts_with(&b));
+            assert_eq!(run(seed), (synced, a.clone(), b.clone()));
+            reordered |= a.len() > b.len();
+        }
+        assert!(reordered);
+    }
+
+    #[test]
+    fn hard_link() {
+        let runtime = Runtime::new();
+        let node = runtime.create_node().build();
+        let id = node.id();
+        let f = node.spawn(async move {
+            let file = File::create("a").await.unwrap();
+            file.write_all_at(b"hello", 0).await.unwrap();
+            file.sync_all().await.unwrap();
+            drop(file);
+
+            super::hard_link("a", "b").await.unwrap();
+            assert_eq!(
+                super::hard_link("a", "b").await.err().unwrap().kind(),
+                ErrorKind::AlreadyExists
+            );
+            assert_eq!(
+                super::hard_link("c", "d").await.err().unwrap().kind(),
+                ErrorKind::NotFound
+            );
+
+            // writes through one link are visible through the other
+            let file = File::create("b").await.unwrap();
+            file.write_all_at(b"world", 0).await.unwrap();
+            file.sync_all().await.unwrap();
+            drop(file);
+            assert_eq!(read("a").await.unwrap(), b"world");
+
+            // the data persists until the last link is removed
+            remove_file("a").await.unwrap();
+            assert_eq!(
+                File::open("a").await.err().unwrap().kind(),
+                ErrorKind::NotFound
+            );
+            simulator::<FsSim>().power_fail(id);
+            assert_eq!(read("b").await.unwrap(), b"world");
+            remove_file("b").await.unwrap();
+            assert_eq!(
+                remove_file("b").await.err().unwrap().kind(),
+                ErrorKind::NotFound
+            );
+        });
+        runtime.block_on(f).unwrap();
+    }
+
+    #[test]
+    fn sync_dir() {
+        let runtime = Runtime::new();
+        let node = runtime.create_node().build();
+        let id = node.id();
+        let f = node.spawn(async move {
+            let file = File::create("dir/data").await.unwrap();
+            file.write_all_at(b"old", 0).await.unwrap();
+            file.sync_all().await.unwrap();
+            drop(file);
+
+            // unsynced creation is lost
+            File::create("dir/lost").await.unwrap();
+            simulator::<FsSim>().power_fail(id);
+            assert_eq!(
+                File::open("dir/lost").await.err().unwrap().kind(),
+                ErrorKind::NotFound
+            );
+
+            // unsynced rename is rolled back
+            let file = File::create("dir/tmp").await.unwrap();
+            file.write_all_at(b"new", 0).await.unwrap();
+            file.sync_all().await.unwrap();
+            drop(file);
+            rename("dir/tmp", "dir/data").await.unwrap();
+            assert_eq!(read("dir/data").await.unwrap(), b"new");
+            simulator::<FsSim>().power_fail(id);
+            assert_eq!(read("dir/data").await.unwrap(), b"old");
+            assert_eq!(read("dir/tmp").await.unwrap(), b"new");
+
+            // synced rename is durable
+            rename("dir/tmp", "dir/data").await.unwrap();
+            super::sync_dir("dir").await.unwrap();
+            simulator::<FsSim>().power_fail(id);
+            assert_eq!(read("dir/data").await.unwrap(), b"new");
+            assert_eq!(
+                File::open("dir/tmp").await.err().unwrap().kind(),
+                ErrorKind::NotFound
+            );
+
+            // unsynced removal is rolled back
+            remove_file("dir/data").await.unwrap();
+            simulator::<FsSim>().power_fail(id);
+            assert_eq!(read("dir/data").await.unwrap(), b"new");
+        });
+        runtime.block_on(f).unwrap();
+    }
+
+    #[test]
+    fn short_writes() {
+        let runtime = Runtime::new();
+        let node = runtime.create_node().build();
+        let id = node.id();
+        let f = node.spawn(async move {
+            let data: Vec<u8> = (0..=255).collect();
+            let file = File::create("file").await.unwrap();
+            assert_eq!(file.write_at(&data, 0).await.unwrap(), data.len());
+
+            simulator::<FsSim>().short_writes(id, true);
+            let file = File::create("file").await.unwrap();
+            let mut written = 0;
+            let mut writes = 0;
+            while written < data.len() {
+                let len = file
+                    .write_at(&data[written..], written as u64)
+                    .await
+                    .unwrap();
+                assert!(len > 0);
+                written += len;
+                writes += 1;
+            }
+            assert!(writes > 1, "{writes}");
+            file.flush().await.unwrap();
+            assert_eq!(read("file").await.unwrap(), data);
+
+            // write_all_at is not affected
+            file.write_all_at(&data, 256).await.unwrap();
+            assert_eq!(file.metadata().await.unwrap().len(), 512);
+        });
+        runtime.block_on(f).unwrap();
+    }
+
+    #[test]
+    fn open_dir() {
+        let runtime = Runtime::new();
+        let node = runtime.create_node().build();
+        let f = node.spawn(async move {
+            File::create("dir/sub/data").await.unwrap();
+            for path in ["dir", "dir/sub", "dir/sub/", ".", ""] {
+                let err = File::open(path).await.err().unwrap();
+                assert_eq!(err.kind(), ErrorKind::Other, "{path:?}");
+                assert!(err.to_string().contains("is a directory"), "{err}");
+                let err = File::create(path).await.err().unwrap();
+                assert_eq!(err.kind(), ErrorKind::Other, "{path:?}");
+            }
+            // the directory is not replaced by a file
+            assert_eq!(
+                metadata("dir").await.err().unwrap().kind(),
+                ErrorKind::NotFound
+            );
+            // a file sharing the prefix of the name is not a directory
+            File::create("di").await.unwrap();
+            File::open("di").await.unwrap();
+        });
+        runtime.block_on(f).unwrap();
+    }
+
+    #[test]
+    fn flush_sync_power_fail() {
+        let runtime = Runtime::new();
+        let node = runtime.create_node().build();
+        let id = node.id();
+        let f = node.spawn(async move {
+            let file = File::create("file").await.unwrap();
+            file.write_all_at(b"hello", 0).await.unwrap();
+            // buffered writes are not visible to others
+            assert_eq!(read("file").await.unwrap(), b"");
+            file.flush().await.unwrap();
+            assert_eq!(read("file").await.unwrap(), b"hello");
+
+            file.sync_all().await.unwrap();
+            file.write_all_at(b" world", 5).await.unwrap();
+            drop(file);
+            assert_eq!(read("file").await.unwrap(), b"hello world");
+
+            // flushed but unsynced data is lost
+            simulator::<FsSim>().power_fail(id);
+            assert_eq!(read("file").await.unwrap(), b"hello");
+        });
+        runtime.block_on(f).unwrap();
+    }
 }
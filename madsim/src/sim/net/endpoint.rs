@@ -14,6 +14,8 @@ pub struct Endpoint {
     guard: Arc<BindGuard>,
     socket: Arc<EndpointSocket>,
     pub(super) peer: Arc<Mutex<Option<SocketAddr>>>,
+    /// Outgoing bandwidth limit.
+    bandwidth: Arc<Mutex<Bandwidth>>,
     /// Incoming connections.
     conn_rx: async_channel::Receiver<(PayloadSender, PayloadReceiver, SocketAddr)>,
 }
@@ -31,6 +33,7 @@ impl Endpoint {
             guard,
             socket,
             peer: Arc::new(Mutex::new(None)),
+            bandwidth: Default::default(),
             conn_rx,
         })
     }
@@ -54,6 +57,16 @@ impl Endpoint {
             .ok_or_else(|| io::Error::new(io::ErrorKind::NotConnected, "not connected"))
     }
 
+    /// Limit the outgoing bandwidth of this endpoint to `bytes_per_sec`.
+    ///
+    /// Each message is delayed by its transfer time in addition to the network latency.
+    /// Messages are transferred one by one, so a large message delays the following ones.
+    /// Only byte messages (e.g. sent by [`Endpoint::send_to`]) have a size.
+    /// Set to 0 to remove the limit.
+    pub fn set_bandwidth(&self, bytes_per_sec: u64) {
+        self.bandwidth.lock().bytes_per_sec = bytes_per_sec;
+    }
+
     /// Sends data with tag on the socket to the given address.
     ///
     /// # Example
@@ -117,6 +130,8 @@ impl Endpoint {
     #[cfg_attr(docsrs, doc(cfg(madsim)))]
     pub async fn send_to_raw(&self, dst: SocketAddr, tag: u64, data: Payload) -> io::Result<()> {
         trace!("send: {} -> {dst}, tag={tag}", self.guard.addr);
+        let size = data.downcast_ref::<Vec<u8>>().map_or(0, |data| data.len());
+        let transfer = (self.bandwidth.lock()).transfer(self.guard.net.time.now_instant(), size);
         self.guard
             .net
             .send(
@@ -125,6 +140,7 @@ impl Endpoint {
                 dst,
                 Udp,
                 Box::new((tag, data)),
+                transfer,
             )
             .await?;
         Ok(())
@@ -275,6 +291,28 @@ impl fmt::Debug for Receiver {
     }
 }
 
+/// Bandwidth limit of an endpoint.
+#[derive(Default)]
+struct Bandwidth {
+    /// 0 means unlimited.
+    bytes_per_sec: u64,
+    /// The time when all previous messages have been transferred.
+    idle_at: Option<Instant>,
+}
+
+impl Bandwidth {
+    /// Returns the delay until a message of `size` bytes sent at `now` is transferred.
+    fn transfer(&mut self, now: Instant, size: usize) -> Duration {
+        if self.bytes_per_sec == 0 {
+            return Duration::ZERO;
+        }
+        let start = self.idle_at.map_or(now, |t| t.max(now));
+        let end = start + Duration::from_secs_f64(size as f64 / self.bytes_per_sec as f64);
+        self.idle_at = Some(end);
+        end - now
+    }
+}
+
 struct Message {
     tag: u64,
     data: Payload,
@@ -433,6 +471,145 @@ mod tests {
         runtime.block_on(f).unwrap();
     }
 
+    #[test]
+    fn reorder_duplicate() {
+        let runtime = Runtime::new();
+        let addr1 = "10.0.0.1:1".parse::<SocketAddr>().unwrap();
+        let addr2 = "10.0.0.2:1".parse::<SocketAddr>().unwrap();
+        let node1 = runtime.create_node().ip(addr1.ip()).build();
+        let node2 = runtime.create_node().ip(addr2.ip()).build();
+        let (id1, id2) = (node1.id(), node2.id());
+        let barrier = Arc::new(Barrier::new(2));
+
+        let barrier_ = barrier.clone();
+        node1.spawn(async move {
+            let net = Endpoint::bind(addr1).await.unwrap();
+            barrier_.wait().await;
+
+            for i in 0..100 {
+                net.send_to(addr2, 1, &[i]).await.unwrap();
+                sleep(Duration::from_millis(1)).await;
+            }
+        });
+
+        let f = node2.spawn(async move {
+            let sim = simulator::<NetSim>();
+            sim.set_link_reorder_rate(id1, id2, 0.5);
+            sim.set_link_duplicate_rate(id1, id2, 0.5);
+            let net = Endpoint::bind(addr2).await.unwrap();
+            barrier.wait().await;
+
+            let mut received = vec![];
+            let mut buf = vec![0; 0x10];
+            while let Ok(Ok((len, _))) =
+                timeout(Duration::from_secs(1), net.recv_from(1, &mut buf)).await
+            {
+                assert_eq!(len, 1);
+                received.push(buf[0]);
+            }
+            // every message is received at least once
+            let mut dedup = received.clone();
+            dedup.sort_unstable();
+            dedup.dedup();
+            assert_eq!(dedup, (0..100).collect::<Vec<u8>>());
+            // some messages are duplicated
+            assert!(received.len() > 100);
+            // some messages are reordered
+            assert!(received.windows(2).any(|w| w[0] > w[1]));
+        });
+
+        runtime.block_on(f).unwrap();
+    }
+
+    #[test]
+    fn link_loss() {
+        let runtime = Runtime::new();
+        let addr1 = "10.0.0.1:1".parse::<SocketAddr>().unwrap();
+        let addr2 = "10.0.0.2:1".parse::<SocketAddr>().unwrap();
+        let node1 = runtime.create_node().ip(addr1.ip()).build();
+        let node2 = runtime.create_node().ip(addr2.ip()).build();
+        let (id1, id2) = (node1.id(), node2.id());
+        let barrier = Arc::new(Barrier::new(2));
+
+        let barrier_ = barrier.clone();
+        node2.spawn(async move {
+            let net = Endpoint::bind(addr2).await.unwrap();
+            barrier_.wait().await;
+
+            // echo server
+            let mut buf = vec![0; 0x10];
+            loop {
+                let (len, from) = net.recv_from(1, &mut buf).await.unwrap();
+                net.send_to(from, 1, &buf[..len]).await.unwrap();
+            }
+        });
+
+        let f = node1.spawn(async move {
+            let sim = simulator::<NetSim>();
+            sim.set_link_loss_rate(id1, id2, 0.3);
+            sim.set_link_loss_rate(id2, id1, 0.3);
+            let net = Endpoint::bind(addr1).await.unwrap();
+            barrier.wait().await;
+
+            // request with retries
+            let mut attempts = 0;
+            let mut buf = vec![0; 0x10];
+            for i in 0..50 {
+                loop {
+                    attempts += 1;
+                    net.send_to(addr2, 1, &[i]).await.unwrap();
+                    let recv = timeout(Duration::from_secs(1), net.recv_from(1, &mut buf));
+                    if let Ok(Ok((len, _))) = recv.await {
+                        // ignore stale responses to previous requests
+                        if len == 1 && buf[0] == i {
+                            break;
+                        }
+                    }
+                }
+            }
+            // some messages are lost
+            assert!(attempts > 50);
+        });
+
+        runtime.block_on(f).unwrap();
+    }
+
+    #[test]
+    fn bandwidth() {
+        let runtime = Runtime::new();
+        let addr1 = "10.0.0.1:1".parse::<SocketAddr>().unwrap();
+        let addr2 = "10.0.0.2:1".parse::<SocketAddr>().unwrap();
+        let node1 = runtime.create_node().ip(addr1.ip()).build();
+        let node2 = runtime.create_node().ip(addr2.ip()).build();
+        let barrier = Arc::new(Barrier::new(2));
+
+        let barrier_ = barrier.clone();
+        node1.spawn(async move {
+            let net = Endpoint::bind(addr1).await.unwrap();
+            net.set_bandwidth(1000);
+            barrier_.wait().await;
+
+            net.send_to(addr2, 1, &[0; 2000]).await.unwrap();
+            net.send_to(addr2, 2, &[0; 10]).await.unwrap();
+        });
+
+        let f = node2.spawn(async move {
+            let net = Endpoint::bind(addr2).await.unwrap();
+            barrier.wait().await;
+            let t0 = Instant::now();
+
+            let mut buf = vec![0; 0x1000];
+            // the small message is queued behind the large one
+            net.recv_from(2, &mut buf).await.unwrap();
+            let elapsed = t0.elapsed();
+            assert!(elapsed >= Duration::from_millis(2010), "{elapsed:?}");
+            assert!(elapsed < Duration::from_millis(2100), "{elapsed:?}");
+            net.recv_from(1, &mut buf).await.unwrap();
+        });
+
+        runtime.block_on(f).unwrap();
+    }
+
     #[test]
     #[ignore] // TODO: rethink what happens when network "resets"
     fn reset() {
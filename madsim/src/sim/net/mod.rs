@@ -52,7 +52,7 @@ use crate::{
     plugin,
     rand::{GlobalRng, Rng},
     task::{NodeId, NodeInfo, Spawner},
-    time::{Duration, TimeHandle},
+    time::{Duration, Instant, TimeHandle},
 };
 
 mod addr;
@@ -224,6 +224,31 @@ impl NetSim {
         self.network.lock().clog_link(src, dst);
     }
 
+    /// Set the probability of reordering datagrams on the link from `src` to `dst`.
+    ///
+    /// A reordered datagram is delayed further, so that later datagrams may overtake it.
+    /// Connection-oriented channels are always delivered in order.
+    pub fn set_link_reorder_rate(&self, src: NodeId, dst: NodeId, rate: f64) {
+        self.network.lock().set_link_reorder_rate(src, dst, rate);
+    }
+
+    /// Set the probability of duplicating datagrams on the link from `src` to `dst`.
+    ///
+    /// Only byte messages (e.g. sent by [`Endpoint::send_to`]) can be duplicated.
+    /// Connection-oriented channels are always delivered exactly once.
+    pub fn set_link_duplicate_rate(&self, src: NodeId, dst: NodeId, rate: f64) {
+        self.network.lock().set_link_duplicate_rate(src, dst, rate);
+    }
+
+    /// Set the probability of dropping datagrams on the link from `src` to `dst`.
+    ///
+    /// A dropped datagram is never delivered. This composes with the global
+    /// [`Config::packet_loss_rate`], latency and clogging.
+    /// Connection-oriented channels are never affected.
+    pub fn set_link_loss_rate(&self, src: NodeId, dst: NodeId, rate: f64) {
+        self.network.lock().set_link_loss_rate(src, dst, rate);
+    }
+
     /// Add a DNS record for the cluster.
     pub fn add_dns_record(&self, hostname: &str, ip: IpAddr) {
         self.dns.lock().add(hostname, ip);
@@ -297,6 +322,8 @@ impl NetSim {
     }
 
     /// Send a message to the destination.
+    ///
+    /// The message is delivered after its `transfer` time plus the link latency.
     pub(crate) async fn send(
         &self,
         node: NodeId,
@@ -304,6 +331,7 @@ impl NetSim {
         mut dst: SocketAddr,
         protocol: IpProtocol,
         msg: Payload,
+        transfer: Duration,
     ) -> io::Result<()> {
         self.rand_delay().await?;
         if let Some(hook) = self.hooks_req.lock().get(&node).cloned() {
@@ -317,19 +345,35 @@ impl NetSim {
         {
             dst = addr.parse().expect("invalid socket address");
         }
-        if let Some((ip, dst_node, socket, latency)) =
-            self.network.lock().try_send(node, dst, protocol)
-        {
-            trace!(?latency, "delay");
+        let res = {
+            let mut network = self.network.lock();
+            (network.try_send(node, dst, protocol))
+                .filter(|res| !network.test_loss(node, res.1))
+                .map(|res| {
+                    let disorder = network.test_disorder(node, res.1);
+                    (res, disorder)
+                })
+        };
+        if let Some(((ip, dst_node, socket, latency), (extra, duplicate))) = res {
+            trace!(?latency, ?transfer, ?extra, ?duplicate, "delay");
             let hook = self.hooks_rsp.lock().get(&dst_node).cloned();
-            self.time.add_timer(latency, move || {
-                if let Some(hook) = hook {
+            let deliver = Arc::new(move |msg: Payload| {
+                if let Some(hook) = &hook {
                     if !hook(&msg) {
                         return;
                     }
                 }
                 socket.deliver((ip, port).into(), dst, msg);
             });
+            if let Some(latency) = duplicate {
+                if let Some(msg) = try_clone_payload(&msg) {
+                    let deliver = deliver.clone();
+                    self.time
+                        .add_timer(transfer + latency, move || deliver(msg));
+                }
+            }
+            self.time
+                .add_timer(transfer + latency + extra, move || deliver(msg));
         }
         Ok(())
     }
@@ -404,6 +448,13 @@ impl NetSim {
     }
 }
 
+/// Try to clone a byte message sent by [`Endpoint`].
+fn try_clone_payload(msg: &Payload) -> Option<Payload> {
+    let (tag, data) = msg.downcast_ref::<(u64, Payload)>()?;
+    let data = data.downcast_ref::<Vec<u8>>()?;
+    Some(Box::new((*tag, Box::new(data.clone()) as Payload)))
+}
+
 /// An RAII structure used to release the bound port.
 pub(crate) struct BindGuard {
     net: Arc<NetSim>,
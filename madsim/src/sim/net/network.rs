@@ -31,6 +31,12 @@ pub(crate) struct Network {
     clogged_node_in: HashSet<NodeId>,
     clogged_node_out: HashSet<NodeId>,
     clogged_link: HashSet<(NodeId, NodeId)>,
+    /// Probability of reordering packets on each link.
+    reorder_rate: HashMap<(NodeId, NodeId), f64>,
+    /// Probability of duplicating packets on each link.
+    duplicate_rate: HashMap<(NodeId, NodeId), f64>,
+    /// Probability of dropping packets on each link.
+    loss_rate: HashMap<(NodeId, NodeId), f64>,
 }
 
 /// A node in the network.
@@ -129,6 +135,9 @@ impl Network {
             clogged_node_in: HashSet::new(),
             clogged_node_out: HashSet::new(),
             clogged_link: HashSet::new(),
+            reorder_rate: HashMap::new(),
+            duplicate_rate: HashMap::new(),
+            loss_rate: HashMap::new(),
         }
     }
 
@@ -202,6 +211,30 @@ impl Network {
         self.clogged_link.remove(&(src, dst));
     }
 
+    pub fn set_link_reorder_rate(&mut self, src: NodeId, dst: NodeId, rate: f64) {
+        assert!(self.nodes.contains_key(&src), "node not found");
+        assert!(self.nodes.contains_key(&dst), "node not found");
+        assert!((0.0..=1.0).contains(&rate), "invalid rate: {rate}");
+        debug!(?src, ?dst, rate, "set_link_reorder_rate");
+        self.reorder_rate.insert((src, dst), rate);
+    }
+
+    pub fn set_link_duplicate_rate(&mut self, src: NodeId, dst: NodeId, rate: f64) {
+        assert!(self.nodes.contains_key(&src), "node not found");
+        assert!(self.nodes.contains_key(&dst), "node not found");
+        assert!((0.0..=1.0).contains(&rate), "invalid rate: {rate}");
+        debug!(?src, ?dst, rate, "set_link_duplicate_rate");
+        self.duplicate_rate.insert((src, dst), rate);
+    }
+
+    pub fn set_link_loss_rate(&mut self, src: NodeId, dst: NodeId, rate: f64) {
+        assert!(self.nodes.contains_key(&src), "node not found");
+        assert!(self.nodes.contains_key(&dst), "node not found");
+        assert!((0.0..=1.0).contains(&rate), "invalid rate: {rate}");
+        debug!(?src, ?dst, rate, "set_link_loss_rate");
+        self.loss_rate.insert((src, dst), rate);
+    }
+
     /// Returns whether the link from `src` to `dst` is clogged.
     pub fn link_clogged(&self, src: NodeId, dst: NodeId) -> bool {
         self.clogged_node_out.contains(&src)
@@ -275,6 +308,31 @@ impl Network {
         }
     }
 
+    /// Returns whether a packet on the link from `src` to `dst` should be dropped.
+    pub fn test_loss(&mut self, src: NodeId, dst: NodeId) -> bool {
+        let loss_rate = self.loss_rate.get(&(src, dst)).copied().unwrap_or(0.0);
+        loss_rate > 0.0 && self.rand.gen_bool(loss_rate)
+    }
+
+    /// Returns the extra latency of a reordered packet,
+    /// and the latency of its duplicate if the packet should be duplicated.
+    pub fn test_disorder(&mut self, src: NodeId, dst: NodeId) -> (Duration, Option<Duration>) {
+        let reorder_rate = self.reorder_rate.get(&(src, dst)).copied().unwrap_or(0.0);
+        let duplicate_rate = self.duplicate_rate.get(&(src, dst)).copied().unwrap_or(0.0);
+        // a reordered packet is held back so that later packets may overtake it
+        let extra = if reorder_rate > 0.0 && self.rand.gen_bool(reorder_rate) {
+            self.config.send_latency.end
+        } else {
+            Duration::ZERO
+        };
+        let duplicate = if duplicate_rate > 0.0 && self.rand.gen_bool(duplicate_rate) {
+            Some(self.rand.gen_range(self.config.send_latency.clone()))
+        } else {
+            None
+        };
+        (extra, duplicate)
+    }
+
     /// Resolve destination node from IP address.
     pub fn resolve_dest_node(
         &self,
@@ -3,12 +3,13 @@
 //!
 
 use crate::rand::{GlobalRng, Rng};
+use crate::task::NodeId;
 use futures_util::{select_biased, FutureExt};
 use naive_timer::Timer;
 use spin::Mutex;
 #[doc(no_inline)]
 pub use std::time::{Duration, Instant};
-use std::{future::Future, sync::Arc, time::SystemTime};
+use std::{collections::HashMap, future::Future, sync::Arc, time::SystemTime};
 
 pub mod error;
 mod interval;
@@ -33,6 +34,7 @@ impl TimeRuntime {
         let handle = TimeHandle {
             timer: Arc::new(Mutex::new(Timer::default())),
             clock: Arc::new(Clock::new(base_time)),
+            skews: Default::default(),
         };
         TimeRuntime { handle }
     }
@@ -78,6 +80,8 @@ impl TimeRuntime {
 pub struct TimeHandle {
     timer: Arc<Mutex<Timer>>,
     clock: Arc<Clock>,
+    /// The skew of the system clock on each node.
+    skews: Arc<Mutex<HashMap<NodeId, Duration>>>,
 }
 
 impl TimeHandle {
@@ -97,8 +101,27 @@ impl TimeHandle {
     }
 
     /// Return the current time.
+    ///
+    /// Inside a node, the time is ahead by the clock skew of the node.
     pub fn now_time(&self) -> SystemTime {
-        self.clock.now_time()
+        let now = self.clock.now_time();
+        match crate::context::try_current_task() {
+            Some(task) => now + self.clock_skew(task.node.id),
+            None => now,
+        }
+    }
+
+    /// Set the system clock of the node to be ahead of the global time by `skew`.
+    ///
+    /// The skew only affects [`SystemTime`] observed on the node, while [`Instant`]
+    /// and timers are shared by all nodes.
+    pub fn set_clock_skew(&self, node: NodeId, skew: Duration) {
+        self.skews.lock().insert(node, skew);
+    }
+
+    /// Returns the clock skew of the node.
+    pub fn clock_skew(&self, node: NodeId) -> Duration {
+        self.skews.lock().get(&node).copied().unwrap_or_default()
     }
 
     /// Returns the amount of time elapsed since this handle was created.
@@ -243,4 +266,31 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn clock_skew() {
+        let runtime = Runtime::new();
+        let node1 = runtime.create_node().build();
+        let node2 = runtime.create_node().build();
+
+        let f = async move {
+            TimeHandle::current().set_clock_skew(node2.id(), Duration::from_secs(10));
+            let t1 = node1.spawn(async { SystemTime::now() }).await.unwrap();
+            let t2 = node2.spawn(async { SystemTime::now() }).await.unwrap();
+            let skew = t2.duration_since(t1).unwrap();
+            assert!(skew >= Duration::from_secs(10) && skew < Duration::from_secs(11));
+
+            // the skew is stable on the node
+            let elapsed = node2
+                .spawn(async {
+                    let t0 = SystemTime::now();
+                    sleep(Duration::from_secs(1)).await;
+                    SystemTime::now().duration_since(t0).unwrap()
+                })
+                .await
+                .unwrap();
+            assert!(elapsed >= Duration::from_secs(1) && elapsed < Duration::from_secs(2));
+        };
+        runtime.block_on(f);
+    }
 }
@@ -6,9 +6,15 @@ use std::{
     path::Path,
 };
 
-pub use tokio::fs::{metadata, read};
+pub use tokio::fs::{hard_link, metadata, read, remove_file, rename, write};
 use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
 
+/// Syncs a directory so that creations, renames and removals of files in it survive
+/// power failure.
+pub async fn sync_dir(path: impl AsRef<Path>) -> Result<()> {
+    tokio::fs::File::open(path).await?.sync_all().await
+}
+
 /// A reference to an open file on the filesystem.
 pub struct File {
     inner: tokio::fs::File,
@@ -39,6 +45,22 @@ impl File {
         Ok(len)
     }
 
+    /// Reads all bytes of the file from the beginning.
+    pub async fn read_to_end(&mut self) -> Result<Vec<u8>> {
+        // TODO: make it &self
+        self.inner.seek(SeekFrom::Start(0)).await?;
+        let mut buf = Vec::new();
+        self.inner.read_to_end(&mut buf).await?;
+        Ok(buf)
+    }
+
+    /// Writes a number of bytes starting from a given offset. Returns the number of bytes written.
+    pub async fn write_at(&mut self, buf: &[u8], offset: u64) -> Result<usize> {
+        // TODO: make it &self
+        self.inner.seek(SeekFrom::Start(offset)).await?;
+        self.inner.write(buf).await
+    }
+
     /// Attempts to write an entire buffer starting from a given offset.
     pub async fn write_all_at(&mut self, buf: &[u8], offset: u64) -> Result<()> {
         // TODO: make it &self
@@ -52,6 +74,11 @@ impl File {
         self.inner.set_len(size).await
     }
 
+    /// Flushes buffered writes to the file.
+    pub async fn flush(&mut self) -> Result<()> {
+        self.inner.flush().await
+    }
+
     /// Attempts to sync all OS-internal metadata to disk.
     pub async fn sync_all(&self) -> Result<()> {
         self.inner.sync_all().await
@@ -61,4 +88,9 @@ impl File {
     pub async fn metadata(&self) -> Result<Metadata> {
         self.inner.metadata().await
     }
+
+    /// Returns the current length of the file, in bytes.
+    pub async fn len(&self) -> Result<u64> {
+        Ok(self.inner.metadata().await?.len())
+    }
 }
@@ -46,6 +46,9 @@ impl Greeter for MyGreeter {
         if name == "error" {
             return Err(Status::invalid_argument("error!"));
         }
+        if name == "slow" {
+            sleep(Duration::from_secs(3)).await;
+        }
         let reply = HelloReply {
             message: format!("Hello {}! ({})", name, remote_addr.ip()),
         };
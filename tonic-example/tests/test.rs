@@ -6,13 +6,35 @@ use madsim::{
     net::NetSim,
     rand::{thread_rng, Rng},
     runtime::Handle,
-    time::sleep,
+    time::{sleep, Instant},
 };
+use prost::Message;
 use std::{net::SocketAddr, time::Duration};
-use tonic::transport::{Endpoint, Server};
+use tonic::{
+    codegen::{IdentityInterceptor, ResponseExt},
+    health::{
+        pb::{health_check_response, health_client::HealthClient, HealthCheckRequest},
+        server::health_reporter,
+        ServingStatus,
+    },
+    metadata::MetadataMap,
+    reflection::pb::{
+        server_reflection_client::ServerReflectionClient,
+        server_reflection_request::MessageRequest, server_reflection_response::MessageResponse,
+        ServerReflectionRequest,
+    },
+    transport::{
+        server::StreamFault, Certificate, ClientTlsConfig, Endpoint, Identity, Server,
+        ServerTlsConfig,
+    },
+    Request, Response, Status, Streaming,
+};
 use tonic_example::hello_world::{
-    another_greeter_client::AnotherGreeterClient, another_greeter_server::AnotherGreeterServer,
-    greeter_client::GreeterClient, greeter_server::GreeterServer, HelloRequest,
+    another_greeter_client::AnotherGreeterClient,
+    another_greeter_server::AnotherGreeterServer,
+    greeter_client::GreeterClient,
+    greeter_server::{Greeter, GreeterServer},
+    HelloReply, HelloRequest,
 };
 use tonic_example::MyGreeter;
 
@@ -346,3 +368,1040 @@ async fn interceptor() {
         .await
         .unwrap();
 }
+
+#[madsim::test]
+async fn server_stream_fault() {
+    let handle = Handle::current();
+    let addr0 = "10.0.0.1:50051".parse::<SocketAddr>().unwrap();
+    let ip1 = "10.0.0.2".parse().unwrap();
+    let node0 = handle.create_node().name("server").ip(addr0.ip()).build();
+    node0.spawn(async move {
+        Server::builder()
+            .add_service(GreeterServer::new(MyGreeter::default()))
+            .stream_fault(
+                "helloworld.Greeter",
+                StreamFault::new(1, tonic::Status::unavailable("injected")),
+            )
+            .serve(addr0)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let node1 = handle.create_node().name("client1").ip(ip1).build();
+    node1
+        .spawn(async move {
+            let mut client = GreeterClient::connect("http://10.0.0.1:50051")
+                .await
+                .unwrap();
+            // the stream is truncated after the first message
+            let response = client.lots_of_replies(request()).await.unwrap();
+            let mut stream = response.into_inner();
+            let reply = stream.message().await.unwrap().unwrap();
+            assert_eq!(reply.message, "0: Hello Tonic! (10.0.0.2)");
+            let error = stream.message().await.unwrap_err();
+            assert_eq!(error.code(), tonic::Code::Unavailable);
+            assert!(stream.message().await.unwrap().is_none());
+
+            // unary calls are not affected
+            let response = client.say_hello(request()).await.unwrap();
+            assert_eq!(response.into_inner().message, "Hello Tonic! (10.0.0.2)");
+        })
+        .await
+        .unwrap();
+}
+
+#[madsim::test]
+async fn call_outcomes() {
+    let handle = Handle::current();
+    let addr0 = "10.0.0.1:50051".parse::<SocketAddr>().unwrap();
+    let ip1 = "10.0.0.2".parse().unwrap();
+    let node0 = handle.create_node().name("server").ip(addr0.ip()).build();
+    node0.spawn(async move {
+        Server::builder()
+            .add_service(GreeterServer::new(MyGreeter::default()))
+            .call_outcomes(
+                "helloworld.Greeter",
+                "SayHello",
+                [
+                    Err(tonic::Status::unavailable("injected")),
+                    Err(tonic::Status::unavailable("injected")),
+                    Ok(()),
+                ],
+            )
+            .call_outcomes(
+                "helloworld.Greeter",
+                "LotsOfReplies",
+                [Err(tonic::Status::internal("injected"))],
+            )
+            .serve(addr0)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let node1 = handle.create_node().name("client1").ip(ip1).build();
+    node1
+        .spawn(async move {
+            let mut client = GreeterClient::connect("http://10.0.0.1:50051")
+                .await
+                .unwrap();
+            // retry on unavailable
+            let mut attempts = 0;
+            let response = loop {
+                attempts += 1;
+                match client.say_hello(request()).await {
+                    Err(e) if e.code() == tonic::Code::Unavailable => continue,
+                    result => break result.unwrap(),
+                }
+            };
+            assert_eq!(attempts, 3);
+            assert_eq!(response.into_inner().message, "Hello Tonic! (10.0.0.2)");
+
+            // server streaming
+            let error = client.lots_of_replies(request()).await.unwrap_err();
+            assert_eq!(error.code(), tonic::Code::Internal);
+            assert_eq!(error.message(), "injected");
+
+            // calls are handled normally after the outcomes are exhausted
+            client.say_hello(request()).await.unwrap();
+            client.lots_of_replies(request()).await.unwrap();
+        })
+        .await
+        .unwrap();
+}
+
+#[madsim::test]
+async fn deadline() {
+    let handle = Handle::current();
+    let addr0 = "10.0.0.1:50051".parse::<SocketAddr>().unwrap();
+    let ip1 = "10.0.0.2".parse().unwrap();
+    let node0 = handle.create_node().name("server").ip(addr0.ip()).build();
+    node0.spawn(async move {
+        Server::builder()
+            .add_service(GreeterServer::new(MyGreeter::default()))
+            .serve(addr0)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let node1 = handle.create_node().name("client1").ip(ip1).build();
+    node1
+        .spawn(async move {
+            let mut client = GreeterClient::connect("http://10.0.0.1:50051")
+                .await
+                .unwrap();
+            let slow_request = |timeout| {
+                let mut request = tonic::Request::new(HelloRequest {
+                    name: "slow".into(),
+                });
+                request.set_timeout(timeout);
+                request
+            };
+
+            let t0 = Instant::now();
+            let error = client
+                .say_hello(slow_request(Duration::from_secs(1)))
+                .await
+                .unwrap_err();
+            assert_eq!(error.code(), tonic::Code::DeadlineExceeded);
+            assert!(t0.elapsed() < Duration::from_secs(2));
+
+            let response = client
+                .say_hello(slow_request(Duration::from_secs(10)))
+                .await
+                .unwrap();
+            assert_eq!(response.into_inner().message, "Hello slow! (10.0.0.2)");
+        })
+        .await
+        .unwrap();
+}
+
+#[madsim::test]
+async fn latency() {
+    let handle = Handle::current();
+    let addr0 = "10.0.0.1:50051".parse::<SocketAddr>().unwrap();
+    let ip1 = "10.0.0.2".parse().unwrap();
+    let node0 = handle.create_node().name("server").ip(addr0.ip()).build();
+    node0.spawn(async move {
+        Server::builder()
+            .add_service(GreeterServer::new(MyGreeter::default()))
+            .serve(addr0)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let node1 = handle.create_node().name("client1").ip(ip1).build();
+    node1
+        .spawn(async move {
+            let channel = Endpoint::from_static("http://10.0.0.1:50051")
+                .request_latency(Duration::from_millis(100)..Duration::from_millis(200))
+                .response_latency(Duration::from_millis(300)..Duration::from_millis(300))
+                .connect()
+                .await
+                .unwrap();
+            let mut client = GreeterClient::new(channel);
+            for _ in 0..10 {
+                let t0 = Instant::now();
+                client.say_hello(request()).await.unwrap();
+                let elapsed = t0.elapsed();
+                assert!(elapsed >= Duration::from_millis(400), "{elapsed:?}");
+                assert!(elapsed < Duration::from_millis(550), "{elapsed:?}");
+            }
+        })
+        .await
+        .unwrap();
+}
+
+#[madsim::test]
+async fn streaming_order() {
+    let handle = Handle::current();
+    let addr0 = "10.0.0.1:50051".parse::<SocketAddr>().unwrap();
+    let ip1 = "10.0.0.2".parse().unwrap();
+    let node0 = handle.create_node().name("server").ip(addr0.ip()).build();
+    node0.spawn(async move {
+        Server::builder()
+            .add_service(GreeterServer::new(MyGreeter::default()))
+            .serve(addr0)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let node1 = handle.create_node().name("client1").ip(ip1).build();
+    node1
+        .spawn(async move {
+            // messages must keep their order even if each one has a random latency
+            let channel = Endpoint::from_static("http://10.0.0.1:50051")
+                .request_latency(Duration::from_millis(1)..Duration::from_millis(100))
+                .response_latency(Duration::from_millis(1)..Duration::from_millis(100))
+                .connect()
+                .await
+                .unwrap();
+            let mut client = GreeterClient::new(channel);
+            let names = || {
+                stream! {
+                    for i in 0..100 {
+                        yield HelloRequest { name: i.to_string() };
+                    }
+                }
+            };
+
+            // client stream
+            let response = client.lots_of_greetings(names()).await.unwrap();
+            let expected = (0..100).map(|i| i.to_string()).collect::<Vec<_>>();
+            assert_eq!(
+                response.into_inner().message,
+                format!("Hello {}! (10.0.0.2)", expected.join(" "))
+            );
+
+            // bi-directional stream
+            let response = client.bidi_hello(names()).await.unwrap();
+            let mut stream = response.into_inner();
+            for i in 0..100 {
+                let reply = stream.message().await.unwrap().unwrap();
+                assert_eq!(reply.message, format!("Hello {i}! (10.0.0.2)"));
+            }
+            assert!(stream.message().await.unwrap().is_none());
+        })
+        .await
+        .unwrap();
+}
+
+/// A greeter that attaches trailers to the bi-directional streaming response.
+#[derive(Debug, Default)]
+struct TrailersGreeter(MyGreeter);
+
+#[tonic::async_trait]
+impl Greeter for TrailersGreeter {
+    async fn say_hello(
+        &self,
+        request: Request<HelloRequest>,
+    ) -> Result<Response<HelloReply>, Status> {
+        Greeter::say_hello(&self.0, request).await
+    }
+
+    type LotsOfRepliesStream = <MyGreeter as Greeter>::LotsOfRepliesStream;
+
+    async fn lots_of_replies(
+        &self,
+        request: Request<HelloRequest>,
+    ) -> Result<Response<Self::LotsOfRepliesStream>, Status> {
+        self.0.lots_of_replies(request).await
+    }
+
+    async fn lots_of_greetings(
+        &self,
+        request: Request<Streaming<HelloRequest>>,
+    ) -> Result<Response<HelloReply>, Status> {
+        self.0.lots_of_greetings(request).await
+    }
+
+    type BidiHelloStream = <MyGreeter as Greeter>::BidiHelloStream;
+
+    async fn bidi_hello(
+        &self,
+        request: Request<Streaming<HelloRequest>>,
+    ) -> Result<Response<Self::BidiHelloStream>, Status> {
+        let mut response = self.0.bidi_hello(request).await?;
+        response
+            .trailers_mut()
+            .insert("x-trailer", "bye".parse().unwrap());
+        Ok(response)
+    }
+}
+
+#[madsim::test]
+async fn trailers() {
+    let handle = Handle::current();
+    let addr0 = "10.0.0.1:50051".parse::<SocketAddr>().unwrap();
+    let ip1 = "10.0.0.2".parse().unwrap();
+    let node0 = handle.create_node().name("server").ip(addr0.ip()).build();
+    node0.spawn(async move {
+        Server::builder()
+            .add_service(GreeterServer::new(TrailersGreeter::default()))
+            .serve(addr0)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let node1 = handle.create_node().name("client1").ip(ip1).build();
+    node1
+        .spawn(async move {
+            let mut client = GreeterClient::connect("http://10.0.0.1:50051")
+                .await
+                .unwrap();
+            // trailers are received after all messages
+            let response = client.bidi_hello(hello_stream()).await.unwrap();
+            let mut stream = response.into_inner();
+            let reply = stream.message().await.unwrap().unwrap();
+            assert_eq!(reply.message, "Hello Tonic0! (10.0.0.2)");
+            let trailers = stream.trailers().await.unwrap().unwrap();
+            assert_eq!(trailers.get("x-trailer").unwrap(), "bye");
+            assert!(stream.message().await.unwrap().is_none());
+
+            // the stream ends with an error
+            let response = client.lots_of_replies(request()).await.unwrap();
+            let mut stream = response.into_inner();
+            let error = stream.trailers().await.unwrap_err();
+            assert_eq!(error.code(), tonic::Code::Unknown);
+        })
+        .await
+        .unwrap();
+}
+
+#[madsim::test]
+#[allow(clippy::result_large_err)]
+async fn interceptors() {
+    let handle = Handle::current();
+    let addr0 = "10.0.0.1:50051".parse::<SocketAddr>().unwrap();
+    let ip1 = "10.0.0.2".parse().unwrap();
+    let node0 = handle.create_node().name("server").ip(addr0.ip()).build();
+    node0.spawn(async move {
+        Server::builder()
+            // authentication
+            .interceptor(
+                |request: Request<()>| match request.metadata().get("token") {
+                    Some(token) if token == "secret" => Ok(request),
+                    _ => Err(Status::unauthenticated("invalid token")),
+                },
+            )
+            // record the order of interceptors
+            .interceptor(|mut request: Request<()>| {
+                request.metadata_mut().append("trace", "1".parse().unwrap());
+                Ok(request)
+            })
+            // rate limit
+            .interceptor({
+                let mut count = 0;
+                move |mut request: Request<()>| {
+                    count += 1;
+                    if count > 2 {
+                        return Err(Status::resource_exhausted("rate limited"));
+                    }
+                    request.metadata_mut().append("trace", "2".parse().unwrap());
+                    Ok(request)
+                }
+            })
+            .add_service(GreeterServer::new(TraceGreeter::default()))
+            .serve(addr0)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let node1 = handle.create_node().name("client1").ip(ip1).build();
+    node1
+        .spawn(async move {
+            let mut client = GreeterClient::connect("http://10.0.0.1:50051")
+                .await
+                .unwrap();
+            // rejected by the first interceptor without touching the rest
+            let error = client.say_hello(request()).await.unwrap_err();
+            assert_eq!(error.code(), tonic::Code::Unauthenticated);
+
+            let authed_request = || {
+                let mut request = request();
+                request
+                    .metadata_mut()
+                    .insert("token", "secret".parse().unwrap());
+                request
+            };
+            for _ in 0..2 {
+                let response = client.say_hello(authed_request()).await.unwrap();
+                assert_eq!(response.into_inner().message, "1,2");
+            }
+            let error = client.say_hello(authed_request()).await.unwrap_err();
+            assert_eq!(error.code(), tonic::Code::ResourceExhausted);
+        })
+        .await
+        .unwrap();
+}
+
+/// A greeter that replies with the `trace` metadata of requests.
+#[derive(Debug, Default)]
+struct TraceGreeter(MyGreeter);
+
+#[tonic::async_trait]
+impl Greeter for TraceGreeter {
+    async fn say_hello(
+        &self,
+        request: Request<HelloRequest>,
+    ) -> Result<Response<HelloReply>, Status> {
+        let trace = (request.metadata().get_all("trace").iter())
+            .map(|v| v.to_str().unwrap())
+            .collect::<Vec<_>>();
+        Ok(Response::new(HelloReply {
+            message: trace.join(","),
+        }))
+    }
+
+    type LotsOfRepliesStream = <MyGreeter as Greeter>::LotsOfRepliesStream;
+
+    async fn lots_of_replies(
+        &self,
+        request: Request<HelloRequest>,
+    ) -> Result<Response<Self::LotsOfRepliesStream>, Status> {
+        self.0.lots_of_replies(request).await
+    }
+
+    async fn lots_of_greetings(
+        &self,
+        request: Request<Streaming<HelloRequest>>,
+    ) -> Result<Response<HelloReply>, Status> {
+        self.0.lots_of_greetings(request).await
+    }
+
+    type BidiHelloStream = <MyGreeter as Greeter>::BidiHelloStream;
+
+    async fn bidi_hello(
+        &self,
+        request: Request<Streaming<HelloRequest>>,
+    ) -> Result<Response<Self::BidiHelloStream>, Status> {
+        self.0.bidi_hello(request).await
+    }
+}
+
+/// A greeter that echoes the `x-trace-id` metadata of requests in responses.
+#[derive(Debug, Default)]
+struct EchoGreeter(MyGreeter);
+
+/// Attaches the `x-trace-id` metadata to the request.
+fn with_trace_id<T>(mut request: Request<T>, id: &str) -> Request<T> {
+    (request.metadata_mut()).insert("x-trace-id", id.parse().unwrap());
+    request
+}
+
+/// Copies the `x-trace-id` metadata from the request to the response.
+#[allow(clippy::result_large_err)]
+fn echo_trace_id<T>(
+    request: &MetadataMap,
+    response: Result<Response<T>, Status>,
+) -> Result<Response<T>, Status> {
+    let trace_id = request.get("x-trace-id").cloned();
+    let echo = |metadata: &mut MetadataMap| {
+        if let Some(trace_id) = trace_id {
+            metadata.insert("x-trace-id", trace_id);
+        }
+    };
+    match response {
+        Ok(mut response) => {
+            echo(response.metadata_mut());
+            Ok(response)
+        }
+        Err(mut status) => {
+            echo(status.metadata_mut());
+            Err(status)
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl Greeter for EchoGreeter {
+    async fn say_hello(
+        &self,
+        request: Request<HelloRequest>,
+    ) -> Result<Response<HelloReply>, Status> {
+        let metadata = request.metadata().clone();
+        echo_trace_id(&metadata, self.0.say_hello(request).await)
+    }
+
+    type LotsOfRepliesStream = <MyGreeter as Greeter>::LotsOfRepliesStream;
+
+    async fn lots_of_replies(
+        &self,
+        request: Request<HelloRequest>,
+    ) -> Result<Response<Self::LotsOfRepliesStream>, Status> {
+        let metadata = request.metadata().clone();
+        echo_trace_id(&metadata, self.0.lots_of_replies(request).await)
+    }
+
+    async fn lots_of_greetings(
+        &self,
+        request: Request<Streaming<HelloRequest>>,
+    ) -> Result<Response<HelloReply>, Status> {
+        let metadata = request.metadata().clone();
+        echo_trace_id(&metadata, self.0.lots_of_greetings(request).await)
+    }
+
+    type BidiHelloStream = <MyGreeter as Greeter>::BidiHelloStream;
+
+    async fn bidi_hello(
+        &self,
+        request: Request<Streaming<HelloRequest>>,
+    ) -> Result<Response<Self::BidiHelloStream>, Status> {
+        let metadata = request.metadata().clone();
+        echo_trace_id(&metadata, self.0.bidi_hello(request).await)
+    }
+}
+
+#[madsim::test]
+async fn metadata_echo() {
+    let handle = Handle::current();
+    let addr0 = "10.0.0.1:50051".parse::<SocketAddr>().unwrap();
+    let ip1 = "10.0.0.2".parse().unwrap();
+    let node0 = handle.create_node().name("server").ip(addr0.ip()).build();
+    node0.spawn(async move {
+        Server::builder()
+            .add_service(GreeterServer::new(EchoGreeter::default()))
+            .serve(addr0)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let node1 = handle.create_node().name("client1").ip(ip1).build();
+    node1
+        .spawn(async move {
+            let mut client = GreeterClient::connect("http://10.0.0.1:50051")
+                .await
+                .unwrap();
+
+            let trace_id = |metadata: &MetadataMap| {
+                metadata
+                    .get("x-trace-id")
+                    .map(|v| v.to_str().unwrap().to_string())
+            };
+
+            // unary
+            let response = client
+                .say_hello(with_trace_id(request(), "1"))
+                .await
+                .unwrap();
+            assert_eq!(trace_id(response.metadata()).as_deref(), Some("1"));
+            // error
+            let error_request = Request::new(HelloRequest {
+                name: "error".into(),
+            });
+            let status = client
+                .say_hello(with_trace_id(error_request, "2"))
+                .await
+                .unwrap_err();
+            assert_eq!(trace_id(status.metadata()).as_deref(), Some("2"));
+            // server streaming
+            let response = client
+                .lots_of_replies(with_trace_id(request(), "3"))
+                .await
+                .unwrap();
+            assert_eq!(trace_id(response.metadata()).as_deref(), Some("3"));
+            // client streaming
+            let response = client
+                .lots_of_greetings(with_trace_id(Request::new(hello_stream()), "4"))
+                .await
+                .unwrap();
+            assert_eq!(trace_id(response.metadata()).as_deref(), Some("4"));
+            // bi-directional streaming
+            let response = client
+                .bidi_hello(with_trace_id(Request::new(hello_stream()), "5"))
+                .await
+                .unwrap();
+            assert_eq!(trace_id(response.metadata()).as_deref(), Some("5"));
+            // requests without the metadata get none
+            let response = client.say_hello(request()).await.unwrap();
+            assert_eq!(trace_id(response.metadata()), None);
+        })
+        .await
+        .unwrap();
+}
+
+#[madsim::test]
+async fn reflection() {
+    let handle = Handle::current();
+    let addr0 = "10.0.0.1:50051".parse::<SocketAddr>().unwrap();
+    let ip1 = "10.0.0.2".parse().unwrap();
+    let node0 = handle.create_node().name("server").ip(addr0.ip()).build();
+    node0.spawn(async move {
+        Server::builder()
+            .add_service(GreeterServer::new(MyGreeter::default()))
+            .add_reflection_service()
+            .add_service(AnotherGreeterServer::new(MyGreeter::default()))
+            .serve(addr0)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let node1 = handle.create_node().name("client1").ip(ip1).build();
+    node1
+        .spawn(async move {
+            let mut client = ServerReflectionClient::connect("http://10.0.0.1:50051")
+                .await
+                .unwrap();
+            let requests = [
+                MessageRequest::ListServices(String::new()),
+                MessageRequest::FileContainingSymbol("helloworld.Greeter.SayHello".into()),
+                MessageRequest::FileContainingSymbol("helloworld.Greeter.Unknown".into()),
+            ]
+            .map(|request| ServerReflectionRequest {
+                host: String::new(),
+                message_request: Some(request),
+            });
+            let mut responses = client
+                .server_reflection_info(stream! {
+                    for request in requests {
+                        yield request;
+                    }
+                })
+                .await
+                .unwrap()
+                .into_inner();
+
+            // list services
+            let response = responses.message().await.unwrap().unwrap();
+            let Some(MessageResponse::ListServicesResponse(list)) = response.message_response
+            else {
+                panic!("unexpected response: {response:?}");
+            };
+            let names = list.service.into_iter().map(|s| s.name).collect::<Vec<_>>();
+            assert_eq!(
+                names,
+                [
+                    "grpc.reflection.v1alpha.ServerReflection",
+                    "helloworld.AnotherGreeter",
+                    "helloworld.Greeter",
+                ]
+            );
+
+            // file by symbol
+            let response = responses.message().await.unwrap().unwrap();
+            let Some(MessageResponse::FileDescriptorResponse(file)) = response.message_response
+            else {
+                panic!("unexpected response: {response:?}");
+            };
+            let file =
+                prost_types::FileDescriptorProto::decode(&*file.file_descriptor_proto[0]).unwrap();
+            assert_eq!(file.package(), "helloworld");
+            let service = &file.service[0];
+            assert_eq!(service.name(), "Greeter");
+            let methods = service.method.iter().map(|m| m.name()).collect::<Vec<_>>();
+            assert_eq!(
+                methods,
+                ["SayHello", "LotsOfReplies", "LotsOfGreetings", "BidiHello"]
+            );
+
+            // unknown symbol
+            let response = responses.message().await.unwrap().unwrap();
+            let Some(MessageResponse::ErrorResponse(error)) = response.message_response else {
+                panic!("unexpected response: {response:?}");
+            };
+            assert_eq!(error.error_code, tonic::Code::NotFound as i32);
+        })
+        .await
+        .unwrap();
+}
+
+#[madsim::test]
+async fn health() {
+    let handle = Handle::current();
+    let addr0 = "10.0.0.1:50051".parse::<SocketAddr>().unwrap();
+    let ip1 = "10.0.0.2".parse().unwrap();
+    let node0 = handle.create_node().name("server").ip(addr0.ip()).build();
+    node0.spawn(async move {
+        let (mut reporter, health_service) = health_reporter();
+        madsim::task::spawn(async move {
+            sleep(Duration::from_secs(5)).await;
+            reporter
+                .set_not_serving::<GreeterServer<MyGreeter, IdentityInterceptor>>()
+                .await;
+            sleep(Duration::from_secs(5)).await;
+            reporter
+                .set_service_status("helloworld.Greeter", ServingStatus::Serving)
+                .await;
+        });
+        Server::builder()
+            .add_service(health_service)
+            .add_service(GreeterServer::new(MyGreeter::default()))
+            .serve(addr0)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let node1 = handle.create_node().name("client1").ip(ip1).build();
+    node1
+        .spawn(async move {
+            let mut client = HealthClient::connect("http://10.0.0.1:50051")
+                .await
+                .unwrap();
+            let request = || HealthCheckRequest {
+                service: "helloworld.Greeter".into(),
+            };
+            // serving by default
+            let response = client.check(request()).await.unwrap();
+            assert_eq!(
+                response.into_inner().status(),
+                health_check_response::ServingStatus::Serving
+            );
+
+            // watch the transitions
+            let mut stream = client.watch(request()).await.unwrap().into_inner();
+            let mut statuses = vec![];
+            for _ in 0..3 {
+                let response = stream.message().await.unwrap().unwrap();
+                statuses.push(response.status());
+            }
+            use health_check_response::ServingStatus::*;
+            assert_eq!(statuses, [Serving, NotServing, Serving]);
+        })
+        .await
+        .unwrap();
+}
+
+#[madsim::test]
+async fn tls() {
+    let handle = Handle::current();
+    let ip1 = "10.0.0.2".parse().unwrap();
+    let node0 = handle
+        .create_node()
+        .name("server")
+        .ip([10, 0, 0, 1].into())
+        .build();
+    node0.spawn(async move {
+        let identity = Identity::from_pem("server-cert", "server-key");
+        let tls_config = ServerTlsConfig::new()
+            .identity(identity)
+            .client_ca_root(Certificate::from_pem("client-cert"));
+        Server::builder()
+            .tls_config(tls_config)
+            .unwrap()
+            .add_service(GreeterServer::new(MyGreeter::default()))
+            .serve("10.0.0.1:50051".parse().unwrap())
+            .await
+            .unwrap();
+    });
+    node0.spawn(async move {
+        Server::builder()
+            .add_service(GreeterServer::new(MyGreeter::default()))
+            .serve("10.0.0.1:50052".parse().unwrap())
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let node1 = handle.create_node().name("client1").ip(ip1).build();
+    node1
+        .spawn(async move {
+            let tls_config = |ca: &str| {
+                ClientTlsConfig::new()
+                    .domain_name("example.com")
+                    .ca_certificate(Certificate::from_pem(ca))
+                    .identity(Identity::from_pem("client-cert", "client-key"))
+            };
+            let connect = |addr: &'static str, tls_config: Option<ClientTlsConfig>| async move {
+                let mut endpoint = Endpoint::from_static(addr);
+                if let Some(tls_config) = tls_config {
+                    endpoint = endpoint.tls_config(tls_config).unwrap();
+                }
+                endpoint.connect().await
+            };
+            // matched identities
+            let channel = connect("http://10.0.0.1:50051", Some(tls_config("server-cert")))
+                .await
+                .unwrap();
+            let mut client = GreeterClient::new(channel);
+            client.say_hello(request()).await.unwrap();
+
+            // unexpected server identity
+            connect("http://10.0.0.1:50051", Some(tls_config("other-cert")))
+                .await
+                .unwrap_err();
+            // no client identity
+            let anonymous =
+                ClientTlsConfig::new().ca_certificate(Certificate::from_pem("server-cert"));
+            connect("http://10.0.0.1:50051", Some(anonymous))
+                .await
+                .unwrap_err();
+            // plaintext to TLS server
+            connect("http://10.0.0.1:50051", None).await.unwrap_err();
+            // TLS to plaintext server
+            connect("http://10.0.0.1:50052", Some(tls_config("server-cert")))
+                .await
+                .unwrap_err();
+            // plaintext by default
+            connect("http://10.0.0.1:50052", None).await.unwrap();
+        })
+        .await
+        .unwrap();
+}
+
+#[madsim::test]
+async fn max_concurrent_requests() {
+    let handle = Handle::current();
+    let addr0 = "10.0.0.1:50051".parse::<SocketAddr>().unwrap();
+    let ip1 = "10.0.0.2".parse().unwrap();
+    let node0 = handle.create_node().name("server").ip(addr0.ip()).build();
+    node0.spawn(async move {
+        Server::builder()
+            .add_service(GreeterServer::new(MyGreeter::default()))
+            .add_service(AnotherGreeterServer::new(MyGreeter::default()))
+            .max_concurrent_requests("helloworld.Greeter", 1)
+            .serve(addr0)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let node1 = handle.create_node().name("client1").ip(ip1).build();
+    node1
+        .spawn(async move {
+            let mut client = GreeterClient::connect("http://10.0.0.1:50051")
+                .await
+                .unwrap();
+            let slow = madsim::task::spawn({
+                let mut client = client.clone();
+                async move {
+                    let request = HelloRequest {
+                        name: "slow".into(),
+                    };
+                    client.say_hello(request).await
+                }
+            });
+            sleep(Duration::from_secs(1)).await;
+
+            // rejected while the slow request is in flight
+            let error = client.say_hello(request()).await.unwrap_err();
+            assert_eq!(error.code(), tonic::Code::ResourceExhausted);
+            // other services are not limited
+            let mut another = AnotherGreeterClient::connect("http://10.0.0.1:50051")
+                .await
+                .unwrap();
+            another.say_hello(request()).await.unwrap();
+
+            // released on completion
+            slow.await.unwrap().unwrap();
+            client.say_hello(request()).await.unwrap();
+        })
+        .await
+        .unwrap();
+}
+
+#[madsim::test]
+async fn client_disconnect() {
+    let handle = Handle::current();
+    let addr0 = "10.0.0.1:50051".parse::<SocketAddr>().unwrap();
+    let ip1 = "10.0.0.2".parse().unwrap();
+    let node0 = handle.create_node().name("server").ip(addr0.ip()).build();
+    let greeter = CancelGreeter::default();
+    let state = greeter.state.clone();
+    node0.spawn(async move {
+        Server::builder()
+            .add_service(GreeterServer::new(greeter))
+            .max_concurrent_requests("helloworld.Greeter", 1)
+            .serve(addr0)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let node1 = handle.create_node().name("client1").ip(ip1).build();
+    node1
+        .spawn(async move {
+            let mut client = GreeterClient::connect("http://10.0.0.1:50051")
+                .await
+                .unwrap();
+            // abort the call while the handler is running
+            let call = madsim::task::spawn({
+                let mut client = client.clone();
+                async move { client.say_hello(request()).await }
+            });
+            sleep(Duration::from_secs(1)).await;
+            assert_eq!(*state.lock().unwrap(), ["started"]);
+            call.abort();
+            sleep(Duration::from_secs(1)).await;
+            // the handler future is dropped at its await point
+            assert_eq!(*state.lock().unwrap(), ["started", "dropped"]);
+
+            // the slot of the canceled call is released
+            state.lock().unwrap().clear();
+            let response = client.say_hello(request()).await.unwrap();
+            assert_eq!(response.into_inner().message, "done");
+            assert_eq!(*state.lock().unwrap(), ["started", "completed"]);
+        })
+        .await
+        .unwrap();
+}
+
+/// A greeter that records the progress of slow `say_hello` handlers.
+#[derive(Debug, Default)]
+struct CancelGreeter {
+    inner: MyGreeter,
+    state: std::sync::Arc<std::sync::Mutex<Vec<&'static str>>>,
+}
+
+/// Records `dropped` if the handler is dropped before completion.
+struct CancelGuard {
+    state: std::sync::Arc<std::sync::Mutex<Vec<&'static str>>>,
+    completed: bool,
+}
+
+impl Drop for CancelGuard {
+    fn drop(&mut self) {
+        let event = if self.completed {
+            "completed"
+        } else {
+            "dropped"
+        };
+        self.state.lock().unwrap().push(event);
+    }
+}
+
+#[tonic::async_trait]
+impl Greeter for CancelGreeter {
+    async fn say_hello(
+        &self,
+        _request: Request<HelloRequest>,
+    ) -> Result<Response<HelloReply>, Status> {
+        self.state.lock().unwrap().push("started");
+        let mut guard = CancelGuard {
+            state: self.state.clone(),
+            completed: false,
+        };
+        sleep(Duration::from_secs(3)).await;
+        guard.completed = true;
+        Ok(Response::new(HelloReply {
+            message: "done".into(),
+        }))
+    }
+
+    type LotsOfRepliesStream = <MyGreeter as Greeter>::LotsOfRepliesStream;
+
+    async fn lots_of_replies(
+        &self,
+        request: Request<HelloRequest>,
+    ) -> Result<Response<Self::LotsOfRepliesStream>, Status> {
+        self.inner.lots_of_replies(request).await
+    }
+
+    async fn lots_of_greetings(
+        &self,
+        request: Request<Streaming<HelloRequest>>,
+    ) -> Result<Response<HelloReply>, Status> {
+        self.inner.lots_of_greetings(request).await
+    }
+
+    type BidiHelloStream = <MyGreeter as Greeter>::BidiHelloStream;
+
+    async fn bidi_hello(
+        &self,
+        request: Request<Streaming<HelloRequest>>,
+    ) -> Result<Response<Self::BidiHelloStream>, Status> {
+        self.inner.bidi_hello(request).await
+    }
+}
+
+#[madsim::test]
+async fn max_message_size() {
+    let handle = Handle::current();
+    let addr0 = "10.0.0.1:50051".parse::<SocketAddr>().unwrap();
+    let ip1 = "10.0.0.2".parse().unwrap();
+    let node0 = handle.create_node().name("server").ip(addr0.ip()).build();
+    node0.spawn(async move {
+        let greeter = GreeterServer::new(MyGreeter::default())
+            .max_decoding_message_size(100)
+            .max_encoding_message_size(100);
+        Server::builder()
+            .add_service(greeter)
+            .serve(addr0)
+            .await
+            .unwrap();
+    });
+    sleep(Duration::from_secs(1)).await;
+
+    let node1 = handle.create_node().name("client1").ip(ip1).build();
+    node1
+        .spawn(async move {
+            let large = || HelloRequest {
+                name: "x".repeat(100),
+            };
+            let mut client = GreeterClient::connect("http://10.0.0.1:50051")
+                .await
+                .unwrap();
+            client.say_hello(request()).await.unwrap();
+
+            // the request exceeds the decoding limit of the server
+            let error = client.say_hello(large()).await.unwrap_err();
+            assert_eq!(error.code(), tonic::Code::OutOfRange);
+            let error = (client.lots_of_greetings(stream! { yield large(); }))
+                .await
+                .unwrap_err();
+            assert_eq!(error.code(), tonic::Code::OutOfRange);
+
+            // the response exceeds the encoding limit of the server
+            let name = "x".repeat(90);
+            let error = (client.say_hello(HelloRequest { name: name.clone() }))
+                .await
+                .unwrap_err();
+            assert_eq!(error.code(), tonic::Code::OutOfRange);
+            let mut stream = (client.lots_of_replies(HelloRequest { name }))
+                .await
+                .unwrap()
+                .into_inner();
+            let error = stream.message().await.unwrap_err();
+            assert_eq!(error.code(), tonic::Code::OutOfRange);
+
+            // the request exceeds the encoding limit of the client
+            let mut client = client.max_encoding_message_size(10);
+            let error = client.say_hello(large()).await.unwrap_err();
+            assert_eq!(error.code(), tonic::Code::OutOfRange);
+            let mut stream = (client.bidi_hello(stream! { yield large(); }))
+                .await
+                .unwrap()
+                .into_inner();
+            let error = stream.message().await.unwrap_err();
+            assert_eq!(error.code(), tonic::Code::OutOfRange);
+
+            // the response exceeds the decoding limit of the client
+            let mut client = GreeterClient::connect("http://10.0.0.1:50051")
+                .await
+                .unwrap()
+                .max_decoding_message_size(10);
+            let error = client.say_hello(request()).await.unwrap_err();
+            assert_eq!(error.code(), tonic::Code::OutOfRange);
+            assert!(error.message().contains("the limit is: 10 bytes"));
+        })
+        .await
+        .unwrap();
+}